@@ -1,9 +1,7 @@
 use std::{path::Path, sync::Arc};
 
 use delta_kernel::arrow::array::{Array, RecordBatch};
-use delta_kernel::arrow::compute::{
-    concat_batches, filter_record_batch, lexsort_to_indices, take, SortColumn,
-};
+use delta_kernel::arrow::compute::{concat_batches, filter_record_batch};
 use delta_kernel::arrow::datatypes::{DataType, Schema};
 
 use delta_kernel::object_store::{local::LocalFileSystem, ObjectStore};
@@ -16,6 +14,7 @@ use futures::{stream::TryStreamExt, StreamExt};
 use itertools::Itertools;
 
 use crate::{TestCaseInfo, TestResult};
+use test_utils::sort::sort_record_batch;
 
 pub async fn read_golden(path: &Path, _version: Option<&str>) -> DeltaResult<RecordBatch> {
     let expected_root = path.join("expected").join("latest").join("table_content");
@@ -42,29 +41,6 @@ pub async fn read_golden(path: &Path, _version: Option<&str>) -> DeltaResult<Rec
     Ok(all_data)
 }
 
-pub fn sort_record_batch(batch: RecordBatch) -> DeltaResult<RecordBatch> {
-    // Sort by all columns
-    let mut sort_columns = vec![];
-    for col in batch.columns() {
-        match col.data_type() {
-            DataType::Struct(_) | DataType::List(_) | DataType::Map(_, _) => {
-                // can't sort structs, lists, or maps
-            }
-            _ => sort_columns.push(SortColumn {
-                values: col.clone(),
-                options: None,
-            }),
-        }
-    }
-    let indices = lexsort_to_indices(&sort_columns, None)?;
-    let columns = batch
-        .columns()
-        .iter()
-        .map(|c| take(c, &indices, None).unwrap())
-        .collect();
-    Ok(RecordBatch::try_new(batch.schema(), columns)?)
-}
-
 // Ensure that two schema have the same field names, and dict_is_ordered
 // We ignore:
 //  - data type: This is checked already in `assert_columns_match`
@@ -32,6 +32,7 @@ enum OpType {
   Equal,
   Distinct,
   In,
+  NotIn,
 };
 enum LitType {
   Integer,
@@ -57,6 +58,7 @@ enum VariadicType {
   And,
   Or,
   StructExpression,
+  Coalesce,
 };
 enum UnaryType { Not, IsNull };
 typedef struct {
@@ -178,6 +180,7 @@ DEFINE_BINOP(visit_expr_gt, GreaterThan)
 DEFINE_BINOP(visit_expr_eq, Equal)
 DEFINE_BINOP(visit_expr_distinct, Distinct)
 DEFINE_BINOP(visit_expr_in, In)
+DEFINE_BINOP(visit_expr_not_in, NotIn)
 #undef DEFINE_BINOP
 
 /*************************************************************
@@ -274,6 +277,7 @@ void visit_expr_variadic(void* data,
 DEFINE_VARIADIC(visit_expr_and, And)
 DEFINE_VARIADIC(visit_expr_or, Or)
 DEFINE_VARIADIC(visit_expr_struct_expr, StructExpression)
+DEFINE_VARIADIC(visit_expr_coalesce, Coalesce)
 #undef DEFINE_VARIADIC
 
 void visit_opaque_expr(
@@ -400,12 +404,14 @@ ExpressionItemList construct_expression(SharedExpression* expression) {
     .visit_eq = visit_expr_eq,
     .visit_distinct = visit_expr_distinct,
     .visit_in = visit_expr_in,
+    .visit_not_in = visit_expr_not_in,
     .visit_add = visit_expr_add,
     .visit_minus = visit_expr_minus,
     .visit_multiply = visit_expr_multiply,
     .visit_divide = visit_expr_divide,
     .visit_column = visit_expr_column,
     .visit_struct_expr = visit_expr_struct_expr,
+    .visit_coalesce = visit_expr_coalesce,
     .visit_opaque_pred = visit_opaque_pred,
     .visit_opaque_expr = visit_opaque_expr,
     .visit_unknown = visit_unknown,
@@ -446,12 +452,14 @@ ExpressionItemList construct_predicate(SharedPredicate* predicate) {
     .visit_eq = visit_expr_eq,
     .visit_distinct = visit_expr_distinct,
     .visit_in = visit_expr_in,
+    .visit_not_in = visit_expr_not_in,
     .visit_add = visit_expr_add,
     .visit_minus = visit_expr_minus,
     .visit_multiply = visit_expr_multiply,
     .visit_divide = visit_expr_divide,
     .visit_column = visit_expr_column,
     .visit_struct_expr = visit_expr_struct_expr,
+    .visit_coalesce = visit_expr_coalesce,
     .visit_opaque_pred = visit_opaque_pred,
     .visit_opaque_expr = visit_opaque_expr,
     .visit_unknown = visit_unknown,
@@ -61,6 +61,10 @@ void print_tree_helper(ExpressionItem ref, int depth) {
           printf("In\n");
           break;
         };
+        case NotIn: {
+          printf("NotIn\n");
+          break;
+        };
         case Distinct:
           printf("Distinct\n");
           break;
@@ -80,6 +84,9 @@ void print_tree_helper(ExpressionItem ref, int depth) {
         case StructExpression:
           printf("StructExpression\n");
           break;
+        case Coalesce:
+          printf("Coalesce\n");
+          break;
       }
       print_expression_item_list(var->exprs, depth + 1);
       break;
@@ -182,22 +189,14 @@ void print_tree_helper(ExpressionItem ref, int depth) {
         case Map:
           printf("Map\n");
           struct MapData* map_data = &lit->value.map_data;
+          assert(map_data->keys.len == map_data->vals.len);
           for (size_t i = 0; i < map_data->keys.len; i++) {
             print_n_spaces(depth + 1);
-
-            // Extract key
-            ExpressionItem key = map_data->keys.list[i];
-            assert(key.type == Literal);
-            struct Literal* key_lit = key.ref;
-            assert(key_lit->type == String);
-            // Extract val
-            ExpressionItem val = map_data->vals.list[i];
-            assert(val.type == Literal);
-            struct Literal* val_lit = val.ref;
-            assert(val_lit->type == String);
-
-            // instead of recursing (which forces newlines) we just directly print strings here
-            printf("String(%s): String(%s)\n", key_lit->value.string_data, val_lit->value.string_data);
+            printf("Key\n");
+            print_tree_helper(map_data->keys.list[i], depth + 2);
+            print_n_spaces(depth + 1);
+            printf("Value\n");
+            print_tree_helper(map_data->vals.list[i], depth + 2);
           }
           break;
       }
@@ -56,6 +56,12 @@ pub enum KernelError {
     LiteralExpressionTransformError,
     CheckpointWriteError,
     SchemaError,
+    CommitConflictError,
+    MissingStatsError,
+    TableNotFoundError,
+    EmptyTableError,
+    ChangeDataFeedNotEnabled,
+    CorruptLogError,
 }
 
 impl From<Error> for KernelError {
@@ -119,6 +125,12 @@ impl From<Error> for KernelError {
                 KernelError::LiteralExpressionTransformError
             }
             Error::Schema(_) => KernelError::SchemaError,
+            Error::CommitConflict(_) => KernelError::CommitConflictError,
+            Error::MissingStats(_) => KernelError::MissingStatsError,
+            Error::TableNotFound(_) => KernelError::TableNotFoundError,
+            Error::EmptyTable(_) => KernelError::EmptyTableError,
+            Error::ChangeDataFeedNotEnabled(_) => KernelError::ChangeDataFeedNotEnabled,
+            Error::CorruptLog(_) => KernelError::CorruptLogError,
             _ => KernelError::UnknownError,
         }
     }
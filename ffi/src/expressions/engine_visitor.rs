@@ -6,7 +6,7 @@ use delta_kernel::expressions::{
     ArrayData, BinaryExpression, BinaryExpressionOp, BinaryPredicate, BinaryPredicateOp,
     Expression, JunctionPredicate, JunctionPredicateOp, MapData, OpaqueExpression,
     OpaqueExpressionOpRef, OpaquePredicate, OpaquePredicateOpRef, Predicate, Scalar, StructData,
-    UnaryPredicate, UnaryPredicateOp,
+    UnaryPredicate, UnaryPredicateOp, VariadicExpression, VariadicExpressionOp,
 };
 
 use crate::expressions::{
@@ -19,6 +19,8 @@ type VisitUnaryFn = extern "C" fn(data: *mut c_void, sibling_list_id: usize, chi
 type VisitBinaryFn = extern "C" fn(data: *mut c_void, sibling_list_id: usize, child_list_id: usize);
 type VisitJunctionFn =
     extern "C" fn(data: *mut c_void, sibling_list_id: usize, child_list_id: usize);
+type VisitVariadicFn =
+    extern "C" fn(data: *mut c_void, sibling_list_id: usize, child_list_id: usize);
 
 /// The [`EngineExpressionVisitor`] defines a visitor system to allow engines to build their own
 /// representation of a kernel expression or predicate.
@@ -40,6 +42,7 @@ type VisitJunctionFn =
 ///      - For an array literal, visit each of the elements.
 ///      - For a junction `and` or `or` expression, visit each sub-expression.
 ///      - For a binary operator expression, visit the left and right operands.
+///      - For a variadic expression such as `coalesce`, visit each of its inputs.
 ///      - For a unary `is null` or `not` expression, visit the sub-expression.
 ///  3. When visiting a complex expression, the kernel also passes the "child list" containing
 ///     that element's (already-visited) children.
@@ -147,6 +150,9 @@ pub struct EngineExpressionVisitor {
     /// Visits the `In` binary operator belonging to the list identified by `sibling_list_id`.
     /// The operands will be in a _two_ item list identified by `child_list_id`
     pub visit_in: VisitBinaryFn,
+    /// Visits the `NotIn` binary operator belonging to the list identified by `sibling_list_id`.
+    /// The operands will be in a _two_ item list identified by `child_list_id`
+    pub visit_not_in: VisitBinaryFn,
     /// Visits the `Add` binary operator belonging to the list identified by `sibling_list_id`.
     /// The operands will be in a _two_ item list identified by `child_list_id`
     pub visit_add: VisitBinaryFn,
@@ -166,6 +172,9 @@ pub struct EngineExpressionVisitor {
     /// The sub-expressions of the `StructExpression` are in a list identified by `child_list_id`
     pub visit_struct_expr:
         extern "C" fn(data: *mut c_void, sibling_list_id: usize, child_list_id: usize),
+    /// Visits a `COALESCE` expression belonging to the list identified by `sibling_list_id`.
+    /// The inputs of the `COALESCE` are in a list identified by `child_list_id`
+    pub visit_coalesce: VisitVariadicFn,
     /// Visits the operator (`op`) and children (`child_list_id`) of an opaque expression belonging
     /// to the list identified by `sibling_list_id`.
     pub visit_opaque_expr: extern "C" fn(
@@ -268,7 +277,7 @@ fn visit_expression_array(
     #[allow(deprecated)]
     let elements = array.array_elements();
     let child_list_id = call!(visitor, make_field_list, elements.len());
-    for scalar in elements {
+    for scalar in elements.iter() {
         visit_expression_scalar(visitor, scalar, child_list_id);
     }
     call!(visitor, visit_literal_array, sibling_list_id, child_list_id);
@@ -444,6 +453,12 @@ fn visit_expression_scalar(
                 v.scale()
             )
         }
+        // 256-bit decimals don't fit the two-word `visit_literal_decimal` callback.
+        Scalar::Decimal256(v) => visit_unknown(
+            visitor,
+            sibling_list_id,
+            &format!("Decimal256({})", v.format(false)),
+        ),
         Scalar::Null(_) => call!(visitor, visit_literal_null, sibling_list_id),
         Scalar::Struct(struct_data) => {
             visit_expression_struct_literal(visitor, struct_data, sibling_list_id)
@@ -479,6 +494,16 @@ fn visit_expression_impl(
             };
             visit_fn(visitor.data, sibling_list_id, child_list_id);
         }
+        Expression::Variadic(VariadicExpression { op, exprs }) => {
+            let child_list_id = call!(visitor, make_field_list, exprs.len());
+            for expr in exprs {
+                visit_expression_impl(visitor, expr, child_list_id);
+            }
+            let visit_fn = match op {
+                VariadicExpressionOp::Coalesce => visitor.visit_coalesce,
+            };
+            visit_fn(visitor.data, sibling_list_id, child_list_id);
+        }
         Expression::Opaque(OpaqueExpression { op, exprs }) => {
             visit_expression_opaque(visitor, op, exprs, sibling_list_id)
         }
@@ -516,6 +541,7 @@ fn visit_predicate_impl(
                 BinaryPredicateOp::Equal => visitor.visit_eq,
                 BinaryPredicateOp::Distinct => visitor.visit_distinct,
                 BinaryPredicateOp::In => visitor.visit_in,
+                BinaryPredicateOp::NotIn => visitor.visit_not_in,
             };
             visit_fn(visitor.data, sibling_list_id, child_list_id);
         }
@@ -5,9 +5,11 @@ use crate::{
     ReferenceSet, TryFromStringSlice,
 };
 use delta_kernel::expressions::{
-    BinaryExpressionOp, BinaryPredicateOp, ColumnName, Expression, Predicate, UnaryPredicateOp,
+    ArrayData, BinaryExpressionOp, BinaryPredicateOp, ColumnName, Expression, Predicate, Scalar,
+    UnaryPredicateOp,
 };
-use delta_kernel::DeltaResult;
+use delta_kernel::schema::ArrayType;
+use delta_kernel::{DeltaResult, Error};
 
 pub(crate) enum ExpressionOrPredicate {
     Expression(Expression),
@@ -98,6 +100,80 @@ pub extern "C" fn visit_predicate_and(
     wrap_predicate(state, result)
 }
 
+/// Visit an `IN` predicate, which tests whether the expression identified by `value` is equal to
+/// any of the literals identified by `elements`. The list must contain at least one non-NULL
+/// literal, and its literals must all have the same type (no widening is applied).
+///
+/// The EngineIterator is not thread safe, not reentrant, not owned by callee, not freed by callee.
+///
+/// # Safety
+/// Caller is responsible for passing a valid `allocate_error` function.
+#[no_mangle]
+pub unsafe extern "C" fn visit_predicate_in(
+    state: &mut KernelExpressionVisitorState,
+    value: usize,
+    elements: &mut EngineIterator,
+    allocate_error: AllocateErrorFn,
+) -> ExternResult<usize> {
+    visit_predicate_in_list_impl(state, BinaryPredicateOp::In, value, elements)
+        .into_extern_result(&allocate_error)
+}
+
+/// Visit a `NOT IN` predicate, the inverse of [`visit_predicate_in`], with the same requirements
+/// on `elements`.
+///
+/// The EngineIterator is not thread safe, not reentrant, not owned by callee, not freed by callee.
+///
+/// # Safety
+/// Caller is responsible for passing a valid `allocate_error` function.
+#[no_mangle]
+pub unsafe extern "C" fn visit_predicate_not_in(
+    state: &mut KernelExpressionVisitorState,
+    value: usize,
+    elements: &mut EngineIterator,
+    allocate_error: AllocateErrorFn,
+) -> ExternResult<usize> {
+    visit_predicate_in_list_impl(state, BinaryPredicateOp::NotIn, value, elements)
+        .into_extern_result(&allocate_error)
+}
+
+fn visit_predicate_in_list_impl(
+    state: &mut KernelExpressionVisitorState,
+    op: BinaryPredicateOp,
+    value: usize,
+    elements: &mut EngineIterator,
+) -> DeltaResult<usize> {
+    // Take every element off the state before validating, so none are left behind on error
+    let elements: Vec<_> = elements
+        .map(|id| unwrap_kernel_expression(state, id as usize))
+        .collect();
+    let value = unwrap_kernel_expression(state, value)
+        .ok_or_else(|| Error::generic(format!("Invalid value expression for {op} predicate")))?;
+    let elements = elements
+        .into_iter()
+        .map(|element| match element {
+            Some(Expression::Literal(scalar)) => Ok(scalar),
+            _ => Err(Error::generic(format!(
+                "{op} list elements must be literals"
+            ))),
+        })
+        .collect::<DeltaResult<Vec<_>>>()?;
+    if elements.is_empty() {
+        return Err(Error::generic(format!("{op} list cannot be empty")));
+    }
+    // The list takes the type of its non-NULL elements, which `ArrayData` requires to be identical
+    let element_type = elements
+        .iter()
+        .find(|element| !element.is_null())
+        .map(Scalar::data_type)
+        .ok_or_else(|| Error::generic(format!("{op} list must contain a non-NULL literal")))?;
+    let contains_null = elements.iter().any(Scalar::is_null);
+    let array_type = ArrayType::new(element_type, contains_null);
+    let array = ArrayData::try_new(array_type, elements)?;
+    let pred = Predicate::binary(op, value, Scalar::Array(array));
+    Ok(wrap_predicate(state, pred))
+}
+
 #[no_mangle]
 pub extern "C" fn visit_expression_plus(
     state: &mut KernelExpressionVisitorState,
@@ -321,3 +397,131 @@ pub extern "C" fn visit_expression_literal_bool(
 ) -> usize {
     wrap_expression(state, Expression::literal(value))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::c_void;
+    use std::ptr::NonNull;
+
+    use delta_kernel::expressions::{column_expr, ArrayData, Scalar};
+    use delta_kernel::schema::{ArrayType, DataType};
+    use delta_kernel::Predicate as Pred;
+
+    use super::*;
+    use crate::kernel_string_slice;
+    use crate::tests::{allocate_err, ok_or_panic, recover_error};
+
+    // Iterates over a `std::vec::IntoIter<usize>` of expression ids
+    extern "C" fn next_id(data: NonNull<c_void>) -> *const c_void {
+        let ids = unsafe { data.cast::<std::vec::IntoIter<usize>>().as_mut() };
+        ids.next()
+            .map_or(std::ptr::null(), |id| id as *const c_void)
+    }
+
+    fn visit_in_list(
+        state: &mut KernelExpressionVisitorState,
+        op: BinaryPredicateOp,
+        ids: Vec<usize>,
+    ) -> ExternResult<usize> {
+        let col = "col";
+        let col =
+            unsafe { visit_expression_column(state, kernel_string_slice!(col), allocate_err) };
+        let mut ids = ids.into_iter();
+        let mut elements = EngineIterator {
+            data: NonNull::from(&mut ids).cast(),
+            get_next: next_id,
+        };
+        let visit = match op {
+            BinaryPredicateOp::NotIn => visit_predicate_not_in,
+            _ => visit_predicate_in,
+        };
+        unsafe { visit(state, ok_or_panic(col), &mut elements, allocate_err) }
+    }
+
+    fn visit_in(state: &mut KernelExpressionVisitorState, ids: Vec<usize>) -> ExternResult<usize> {
+        visit_in_list(state, BinaryPredicateOp::In, ids)
+    }
+
+    #[test]
+    fn test_visit_predicate_in() {
+        let mut state = KernelExpressionVisitorState::default();
+        let ids = (1..=3)
+            .map(|i| visit_expression_literal_int(&mut state, i))
+            .collect();
+        let pred = ok_or_panic(visit_in(&mut state, ids));
+        let pred = unwrap_kernel_predicate(&mut state, pred).unwrap();
+
+        let array_type = ArrayType::new(DataType::INTEGER, false);
+        let array = ArrayData::try_new(array_type, [1, 2, 3]).unwrap();
+        let expected = Pred::binary(
+            BinaryPredicateOp::In,
+            column_expr!("col"),
+            Scalar::Array(array),
+        );
+        assert_eq!(pred, expected);
+        assert_eq!(pred.to_string(), "Column(col) IN (1, 2, 3)");
+
+        // NOT IN round-trips to the same operator the engine visitor emits
+        let ids = (1..=3)
+            .map(|i| visit_expression_literal_int(&mut state, i))
+            .collect();
+        let pred = ok_or_panic(visit_in_list(&mut state, BinaryPredicateOp::NotIn, ids));
+        let pred = unwrap_kernel_predicate(&mut state, pred).unwrap();
+        let array_type = ArrayType::new(DataType::INTEGER, false);
+        let array = ArrayData::try_new(array_type, [1, 2, 3]).unwrap();
+        let expected = Pred::binary(
+            BinaryPredicateOp::NotIn,
+            column_expr!("col"),
+            Scalar::Array(array),
+        );
+        assert_eq!(pred, expected);
+    }
+
+    #[test]
+    fn test_visit_predicate_in_with_null() {
+        let mut state = KernelExpressionVisitorState::default();
+        let null_int = Expression::null_literal(DataType::INTEGER);
+        let ids = vec![
+            visit_expression_literal_int(&mut state, 1),
+            wrap_expression(&mut state, null_int),
+        ];
+        let pred = ok_or_panic(visit_in(&mut state, ids));
+        let pred = unwrap_kernel_predicate(&mut state, pred).unwrap();
+
+        let array_type = ArrayType::new(DataType::INTEGER, true);
+        let array = ArrayData::try_new(array_type, [Some(1), None]).unwrap();
+        let expected = Pred::binary(
+            BinaryPredicateOp::In,
+            column_expr!("col"),
+            Scalar::Array(array),
+        );
+        assert_eq!(pred, expected);
+    }
+
+    #[test]
+    fn test_visit_predicate_in_invalid() {
+        let mut state = KernelExpressionVisitorState::default();
+        let mixed = vec![
+            visit_expression_literal_int(&mut state, 1),
+            visit_expression_literal_bool(&mut state, true),
+        ];
+        // elements are not widened to a common type
+        let widenable = vec![
+            visit_expression_literal_int(&mut state, 1),
+            visit_expression_literal_long(&mut state, 2),
+        ];
+        let not_literal = vec![visit_expression_literal_int(&mut state, 1), {
+            let a = visit_expression_literal_int(&mut state, 1);
+            let b = visit_expression_literal_int(&mut state, 2);
+            visit_expression_plus(&mut state, a, b)
+        }];
+        let null_int = Expression::null_literal(DataType::INTEGER);
+        let all_null = vec![wrap_expression(&mut state, null_int)];
+        for ids in [vec![], mixed, widenable, not_literal, all_null] {
+            let ExternResult::Err(err) = visit_in(&mut state, ids) else {
+                panic!("Expected an error");
+            };
+            unsafe { recover_error(err) };
+        }
+    }
+}
@@ -780,7 +780,10 @@ mod tests {
     use crate::error::{EngineError, KernelError};
 
     #[no_mangle]
-    extern "C" fn allocate_err(etype: KernelError, _: KernelStringSlice) -> *mut EngineError {
+    pub(crate) extern "C" fn allocate_err(
+        etype: KernelError,
+        _: KernelStringSlice,
+    ) -> *mut EngineError {
         let boxed = Box::new(EngineError { etype });
         Box::leak(boxed)
     }
@@ -794,7 +797,7 @@ mod tests {
     }
 
     // helper to recover an error from the above
-    unsafe fn recover_error(ptr: *mut EngineError) -> EngineError {
+    pub(crate) unsafe fn recover_error(ptr: *mut EngineError) -> EngineError {
         *Box::from_raw(ptr)
     }
 
@@ -804,7 +807,7 @@ mod tests {
         *unsafe { Box::from_raw(ptr) }
     }
 
-    fn ok_or_panic<T>(result: ExternResult<T>) -> T {
+    pub(crate) fn ok_or_panic<T>(result: ExternResult<T>) -> T {
         match result {
             ExternResult::Ok(t) => t,
             ExternResult::Err(e) => unsafe {
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::ffi::c_void;
 use std::sync::{Arc, Mutex};
 
-use delta_kernel::scan::state::DvInfo;
+use delta_kernel::scan::state::{DvInfo, ScanFile};
 use delta_kernel::scan::{Scan, ScanMetadata};
 use delta_kernel::snapshot::Snapshot;
 use delta_kernel::{DeltaResult, Error, Expression, ExpressionRef};
@@ -469,3 +469,212 @@ pub unsafe extern "C" fn visit_scan_metadata(
         .visit_scan_files(context_wrapper, rust_callback)
         .unwrap();
 }
+
+/// A file that needs to be read for a scan, as passed to a [`CScanFileCallback`]. All of its
+/// references are only valid until the callback returns.
+#[repr(C)]
+pub struct CScanFile<'a> {
+    /// The path to the file
+    pub path: KernelStringSlice,
+    /// The size of the file
+    pub size: i64,
+    /// The stats of the file, or `NULL` if it has no (valid) stats
+    pub stats: Option<&'a Stats>,
+    /// A [`DvInfo`] struct, which allows getting the selection vector for this file
+    pub dv_info: &'a DvInfo,
+    /// An optional expression that, if not `NULL`, _must_ be applied to physical data to convert it
+    /// to the correct logical format. If this is `NULL`, no transform is needed.
+    pub transform: Option<&'a Expression>,
+    /// The partition values of the file, see [`get_from_string_map`]
+    pub partition_map: &'a CStringMap,
+    /// The engine-specific tags of the file's add action (empty if it has none), see
+    /// [`get_from_string_map`]
+    pub tags: &'a CStringMap,
+    /// The `baseRowId` of the file's add action, or `NULL` if it has none
+    pub base_row_id: Option<&'a i64>,
+    /// The `defaultRowCommitVersion` of the file's add action, or `NULL` if it has none
+    pub default_row_commit_version: Option<&'a i64>,
+}
+
+/// This callback will be invoked for each valid file that needs to be read for a scan, with a
+/// [`CScanFile`] describing the file. Unlike [`CScanCallback`], this gives access to all of the
+/// file's metadata, such as its tags and row tracking fields.
+type CScanFileCallback = extern "C" fn(engine_context: NullableCvoid, scan_file: &CScanFile);
+
+// Wrapper function that gets called by the kernel, transforms the scan file to make it ffi-able,
+// and then calls the ffi specified callback
+fn rust_scan_file_callback(context: &mut ScanFileContextWrapper, scan_file: ScanFile) {
+    let transform = scan_file.transform.map(|e| e.as_ref().clone());
+    let partition_map = CStringMap::from(scan_file.partition_values);
+    let tags = CStringMap::from(scan_file.tags);
+    let stats = scan_file.stats.map(|ks| Stats {
+        num_records: ks.num_records,
+    });
+    let path = scan_file.path.as_str();
+    let c_scan_file = CScanFile {
+        path: kernel_string_slice!(path),
+        size: scan_file.size,
+        stats: stats.as_ref(),
+        dv_info: &scan_file.dv_info,
+        transform: transform.as_ref(),
+        partition_map: &partition_map,
+        tags: &tags,
+        base_row_id: scan_file.base_row_id.as_ref(),
+        default_row_commit_version: scan_file.default_row_commit_version.as_ref(),
+    };
+    (context.callback)(context.engine_context, &c_scan_file);
+}
+
+// Wrap up stuff from C so we can pass it through to our callback
+struct ScanFileContextWrapper {
+    engine_context: NullableCvoid,
+    callback: CScanFileCallback,
+}
+
+/// Same as [`visit_scan_metadata`], except that the callback receives each file as a
+/// [`CScanFile`], and any error is returned to the caller instead of panicking. Returns `true`
+/// once every file has been visited.
+///
+/// # Safety
+/// engine is responsible for passing a valid [`SharedScanMetadata`] and [`SharedExternEngine`].
+#[no_mangle]
+pub unsafe extern "C" fn visit_scan_metadata_files(
+    scan_metadata: Handle<SharedScanMetadata>,
+    engine: Handle<SharedExternEngine>,
+    engine_context: NullableCvoid,
+    callback: CScanFileCallback,
+) -> ExternResult<bool> {
+    let scan_metadata = unsafe { scan_metadata.as_ref() };
+    let engine = unsafe { engine.as_ref() };
+    visit_scan_metadata_files_impl(scan_metadata, engine_context, callback)
+        .into_extern_result(&engine)
+}
+
+fn visit_scan_metadata_files_impl(
+    scan_metadata: &ScanMetadata,
+    engine_context: NullableCvoid,
+    callback: CScanFileCallback,
+) -> DeltaResult<bool> {
+    let context_wrapper = ScanFileContextWrapper {
+        engine_context,
+        callback,
+    };
+    scan_metadata.for_each_scan_file(context_wrapper, rust_scan_file_callback)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::NonNull;
+
+    use delta_kernel::engine::default::{executor::tokio::TokioBackgroundExecutor, DefaultEngine};
+    use delta_kernel::object_store::memory::InMemory;
+    use test_utils::{actions_to_string, add_commit, TestAction};
+
+    use super::*;
+    use crate::tests::{allocate_err, ok_or_panic};
+    use crate::{engine_to_handle, free_engine, free_snapshot, snapshot};
+
+    extern "C" fn allocate_string(slice: KernelStringSlice) -> NullableCvoid {
+        let s = unsafe { String::try_from_slice(&slice) }.unwrap();
+        NonNull::new(Box::into_raw(Box::new(s)).cast())
+    }
+
+    // The path, the value of the `tag` tag, and the row tracking fields of a scan file
+    type ScanFileInfo = (String, Option<String>, Option<i64>, Option<i64>);
+
+    // The engine to visit scan files with, and the files visited so far
+    struct ScanFilesContext {
+        engine: Handle<SharedExternEngine>,
+        files: Vec<ScanFileInfo>,
+    }
+
+    extern "C" fn visit_scan_file(engine_context: NullableCvoid, scan_file: &CScanFile) {
+        let context = engine_context.unwrap().as_ptr().cast::<ScanFilesContext>();
+        let path = unsafe { String::try_from_slice(&scan_file.path) }.unwrap();
+        let key = "tag";
+        let tag = unsafe {
+            get_from_string_map(scan_file.tags, kernel_string_slice!(key), allocate_string)
+        };
+        let tag = tag.map(|ptr| *unsafe { Box::from_raw(ptr.as_ptr().cast::<String>()) });
+        let base_row_id = scan_file.base_row_id.copied();
+        let default_row_commit_version = scan_file.default_row_commit_version.copied();
+        let files = unsafe { &mut (*context).files };
+        files.push((path, tag, base_row_id, default_row_commit_version));
+    }
+
+    extern "C" fn visit_metadata(
+        engine_context: NullableCvoid,
+        scan_metadata: Handle<SharedScanMetadata>,
+    ) {
+        let context = engine_context.unwrap().as_ptr().cast::<ScanFilesContext>();
+        unsafe {
+            let engine = (*context).engine.shallow_copy();
+            ok_or_panic(visit_scan_metadata_files(
+                scan_metadata.shallow_copy(),
+                engine,
+                engine_context,
+                visit_scan_file,
+            ));
+            free_scan_metadata(scan_metadata);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visit_scan_metadata_files() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = Arc::new(InMemory::new());
+        let tagged_add = r#"{"add":{"path":"tagged.parquet","partitionValues":{},"size":262,"modificationTime":1587968586000,"dataChange":true,"tags":{"tag":"value"},"baseRowId":5,"defaultRowCommitVersion":0}}"#;
+        let actions = actions_to_string(vec![
+            TestAction::Metadata,
+            TestAction::Add("untagged.parquet".into()),
+        ]);
+        add_commit(storage.as_ref(), 0, format!("{actions}\n{tagged_add}")).await?;
+        let engine = DefaultEngine::new(storage.clone(), Arc::new(TokioBackgroundExecutor::new()));
+        let engine = engine_to_handle(Arc::new(engine), allocate_err);
+        let path = "memory:///";
+
+        let snapshot =
+            unsafe { ok_or_panic(snapshot(kernel_string_slice!(path), engine.shallow_copy())) };
+        let scan =
+            unsafe { ok_or_panic(scan(snapshot.shallow_copy(), engine.shallow_copy(), None)) };
+        let iter = unsafe {
+            ok_or_panic(scan_metadata_iter_init(
+                engine.shallow_copy(),
+                scan.shallow_copy(),
+            ))
+        };
+
+        let mut context = ScanFilesContext {
+            engine: engine.shallow_copy(),
+            files: vec![],
+        };
+        let context_ptr = NonNull::new((&mut context as *mut ScanFilesContext).cast());
+        while unsafe {
+            ok_or_panic(scan_metadata_next(
+                iter.shallow_copy(),
+                context_ptr,
+                visit_metadata,
+            ))
+        } {}
+        let mut files = context.files;
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                (
+                    "tagged.parquet".to_string(),
+                    Some("value".to_string()),
+                    Some(5),
+                    Some(0)
+                ),
+                ("untagged.parquet".to_string(), None, None, None),
+            ]
+        );
+
+        unsafe { free_scan_metadata_iter(iter) }
+        unsafe { free_scan(scan) }
+        unsafe { free_snapshot(snapshot) }
+        unsafe { free_engine(engine) }
+        Ok(())
+    }
+}
@@ -5,15 +5,15 @@ use std::sync::Arc;
 use crate::expressions::{SharedExpression, SharedPredicate};
 use crate::handle::Handle;
 use delta_kernel::expressions::{
-    column_expr, column_pred, ArrayData, BinaryExpressionOp, BinaryPredicateOp, Expression as Expr,
-    MapData, OpaqueExpressionOp, OpaquePredicateOp, Predicate as Pred, Scalar,
+    column_expr, column_pred, scalar, ArrayData, BinaryExpressionOp, BinaryPredicateOp,
+    Expression as Expr, MapData, OpaqueExpressionOp, OpaquePredicateOp, Predicate as Pred, Scalar,
     ScalarExpressionEvaluator, StructData,
 };
 use delta_kernel::kernel_predicates::{
     DirectDataSkippingPredicateEvaluator, DirectPredicateEvaluator,
     IndirectDataSkippingPredicateEvaluator,
 };
-use delta_kernel::schema::{ArrayType, DataType, MapType, StructField, StructType};
+use delta_kernel::schema::{ArrayType, DataType, MapType, StructField};
 use delta_kernel::DeltaResult;
 
 #[derive(Debug, PartialEq)]
@@ -79,7 +79,7 @@ pub unsafe extern "C" fn get_testing_kernel_expression() -> Handle<SharedExpress
         false,
     );
     let array_data =
-        ArrayData::try_new(array_type.clone(), vec![Scalar::Short(5), Scalar::Short(0)]).unwrap();
+        ArrayData::try_new(array_type, vec![Scalar::Short(5), Scalar::Short(0)]).unwrap();
 
     let map_type = MapType::new(DataType::STRING, DataType::STRING, false);
     let map_data = MapData::try_new(
@@ -91,20 +91,26 @@ pub unsafe extern "C" fn get_testing_kernel_expression() -> Handle<SharedExpress
     )
     .unwrap();
 
-    let nested_fields = vec![
-        StructField::not_null("a", DataType::INTEGER),
-        StructField::not_null("b", array_type),
-    ];
-    let nested_values = vec![Scalar::Integer(500), Scalar::Array(array_data.clone())];
-    let nested_struct = StructData::try_new(nested_fields.clone(), nested_values).unwrap();
-    let nested_struct_type = StructType::new(nested_fields);
+    // A map with non-string keys, a null value, and nested array values
+    let values_type = ArrayType::new(DataType::LONG, false);
+    let nested_map_type = MapType::new(DataType::INTEGER, values_type.clone(), true);
+    let nested_map_data = MapData::try_new(
+        nested_map_type,
+        [
+            (
+                Scalar::Integer(1),
+                Scalar::Array(ArrayData::try_new(values_type, [10i64, 20i64]).unwrap()),
+            ),
+            (Scalar::Integer(2), Scalar::Null(DataType::Null)),
+        ],
+    )
+    .unwrap();
 
+    // The nested struct's fields are non-nullable, as inferred from the (non-null) values
+    let nested_struct = scalar!({a: 500, b: [5i16, 0i16]}).unwrap();
     let top_level_struct = StructData::try_new(
-        vec![StructField::nullable(
-            "top",
-            DataType::Struct(Box::new(nested_struct_type)),
-        )],
-        vec![Scalar::Struct(nested_struct)],
+        vec![StructField::nullable("top", nested_struct.data_type())],
+        vec![nested_struct],
     )
     .unwrap();
 
@@ -133,7 +139,9 @@ pub unsafe extern "C" fn get_testing_kernel_expression() -> Handle<SharedExpress
         Scalar::Struct(top_level_struct).into(),
         Scalar::Array(array_data).into(),
         Scalar::Map(map_data).into(),
+        Scalar::Map(nested_map_data).into(),
         Expr::struct_from([Expr::literal(5_i32), Expr::literal(20_i64)]),
+        Expr::coalesce([Expr::null_literal(DataType::INTEGER), Expr::literal(7_i32)]),
         Expr::opaque(
             OpaqueTestOp("foo".to_string()),
             vec![Expr::literal(42), Expr::literal(1.111)],
@@ -181,8 +189,13 @@ pub unsafe extern "C" fn get_testing_kernel_predicate() -> Handle<SharedPredicat
         Pred::not(Pred::binary(
             BinaryPredicateOp::In,
             Expr::literal(10),
-            Scalar::Array(array_data),
+            Scalar::Array(array_data.clone()),
         )),
+        Pred::binary(
+            BinaryPredicateOp::NotIn,
+            Expr::literal(10),
+            Scalar::Array(array_data),
+        ),
         Pred::or_from(vec![
             Pred::eq(Expr::literal(5), Expr::literal(10)),
             Pred::ne(Expr::literal(20), Expr::literal(10)),
@@ -0,0 +1,101 @@
+//! Measures the time needed to evaluate a predicate over many batches of the same (wide) schema,
+//! comparing name-based evaluation against a predicate compiled once with
+//! `CompiledPredicate::try_new`. Both must select the same rows.
+//!
+//! Run with `cargo bench -p delta_kernel --bench compiled_predicate`.
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Instant;
+
+use delta_kernel::arrow::array::{
+    ArrayRef, BooleanArray, Int32Array, RecordBatch, StringArray, StructArray,
+};
+use delta_kernel::arrow::datatypes::{DataType, Field, Fields, Schema};
+use delta_kernel::engine::arrow_conversion::TryIntoKernel as _;
+use delta_kernel::engine::arrow_expression::evaluate_expression::evaluate_predicate;
+use delta_kernel::engine::arrow_expression::CompiledPredicate;
+use delta_kernel::expressions::{column_expr, Expression as Expr, Predicate as Pred};
+use delta_kernel::schema::StructType;
+
+const NUM_BATCHES: usize = 1_000;
+const BATCH_SIZE: usize = 64;
+// Unreferenced columns placed ahead of the referenced ones, as in a wide table.
+const NUM_PADDING_COLUMNS: usize = 1000;
+
+fn measure(name: &str, f: impl FnOnce() -> Vec<BooleanArray>) -> Vec<BooleanArray> {
+    let start = Instant::now();
+    let results = f();
+    let elapsed = start.elapsed();
+    let matched: usize = results.iter().map(|result| result.true_count()).sum();
+    println!(
+        "{name:>10}: {:?}/batch ({matched} of {} rows matched)",
+        elapsed / NUM_BATCHES as u32,
+        NUM_BATCHES * BATCH_SIZE,
+    );
+    results
+}
+
+fn main() {
+    let nested_fields = Fields::from(vec![
+        Field::new("x", DataType::Int32, true),
+        Field::new("s", DataType::Utf8, true),
+    ]);
+    let padding_fields =
+        (0..NUM_PADDING_COLUMNS).map(|i| Field::new(format!("pad{i}"), DataType::Int32, true));
+    let schema = Arc::new(Schema::new(
+        padding_fields
+            .chain([
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+                Field::new("nested", DataType::Struct(nested_fields.clone()), true),
+            ])
+            .collect::<Vec<_>>(),
+    ));
+    let padding: ArrayRef = Arc::new(Int32Array::from(vec![0; BATCH_SIZE]));
+    let batches: Vec<_> = (0..NUM_BATCHES)
+        .map(|batch| {
+            let ints = (0..BATCH_SIZE).map(|i| (batch * BATCH_SIZE + i) as i32 % 100);
+            let a: ArrayRef = Arc::new(Int32Array::from_iter_values(ints.clone()));
+            let b: ArrayRef = Arc::new(StringArray::from_iter_values(ints.clone().map(|i| {
+                if i % 2 == 0 {
+                    "us"
+                } else {
+                    "eu"
+                }
+            })));
+            let x: ArrayRef = Arc::new(Int32Array::from_iter_values(ints.map(|i| 100 - i)));
+            let nested = StructArray::new(nested_fields.clone(), vec![x, b.clone()], None);
+            let columns = std::iter::repeat_n(padding.clone(), NUM_PADDING_COLUMNS)
+                .chain([a, b, Arc::new(nested) as ArrayRef])
+                .collect();
+            RecordBatch::try_new(schema.clone(), columns).unwrap()
+        })
+        .collect();
+
+    let predicate = Pred::and(
+        column_expr!("a").gt(Expr::literal(25)),
+        Pred::or(
+            column_expr!("nested.s").eq(Expr::literal("us")),
+            column_expr!("nested.x").lt(column_expr!("a")),
+        ),
+    );
+    let kernel_schema: StructType = schema.as_ref().try_into_kernel().unwrap();
+    let compiled = CompiledPredicate::try_new(&predicate, &kernel_schema).unwrap();
+
+    let by_name = measure("by name", || {
+        batches
+            .iter()
+            .map(|batch| evaluate_predicate(black_box(&predicate), batch, false).unwrap())
+            .collect()
+    });
+    let by_index = measure("compiled", || {
+        batches
+            .iter()
+            .map(|batch| black_box(&compiled).eval(batch).unwrap())
+            .collect()
+    });
+    assert_eq!(
+        by_name, by_index,
+        "compiled predicate disagrees with by-name evaluation"
+    );
+}
@@ -0,0 +1,67 @@
+//! Measures the heap allocations (and time) needed to evaluate a partition predicate over many
+//! files, comparing a column resolver that owns its column names and values against one that
+//! borrows them.
+//!
+//! Run with `cargo bench -p delta_kernel --bench predicate_evaluation`.
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use delta_kernel::expressions::Scalar;
+use delta_kernel::expressions::{column_expr, ColumnName, Expression as Expr, Predicate as Pred};
+use delta_kernel::kernel_predicates::{DefaultKernelPredicateEvaluator, KernelPredicateEvaluator};
+
+mod common;
+use common::count_allocations;
+
+const NUM_ROWS: usize = 100_000;
+
+fn measure(name: &str, f: impl FnOnce() -> usize) {
+    let (matched, allocations, elapsed) = count_allocations(f);
+    println!(
+        "{name:>8}: {:.2} allocations/row, {:?}/row ({matched} of {NUM_ROWS} rows matched)",
+        allocations as f64 / NUM_ROWS as f64,
+        elapsed / NUM_ROWS as u32,
+    );
+}
+
+fn main() {
+    let predicate = Pred::and(
+        column_expr!("date").gt(Expr::literal(Scalar::Date(17500))),
+        column_expr!("region").eq(Expr::literal("us")),
+    );
+    let rows: Vec<_> = (0..NUM_ROWS)
+        .map(|i| {
+            let region = if i % 2 == 0 { "us" } else { "eu" };
+            [
+                ("date".to_string(), Scalar::Date(17000 + (i % 1000) as i32)),
+                ("region".to_string(), Scalar::from(region)),
+            ]
+        })
+        .collect();
+
+    measure("owned", || {
+        let evaluations = rows.iter().map(|row| {
+            let resolver: HashMap<_, _> = row
+                .iter()
+                .map(|(name, value)| (ColumnName::new([name]), value.clone()))
+                .collect();
+            DefaultKernelPredicateEvaluator::from(resolver).eval(&predicate)
+        });
+        evaluations
+            .filter(|result| black_box(*result) == Some(true))
+            .count()
+    });
+
+    measure("borrowed", || {
+        let evaluations = rows.iter().map(|row| {
+            let resolver: HashMap<_, _> = row
+                .iter()
+                .map(|(name, value)| (name.as_str(), value))
+                .collect();
+            DefaultKernelPredicateEvaluator::from(resolver).eval(&predicate)
+        });
+        evaluations
+            .filter(|result| black_box(*result) == Some(true))
+            .count()
+    });
+}
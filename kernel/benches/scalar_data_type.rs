@@ -0,0 +1,66 @@
+//! Measures the heap allocations (and time) needed to get the data type of struct-heavy and
+//! primitive scalars, comparing [`Scalar::data_type`] (which returns an owned type) against
+//! [`Scalar::data_type_ref`] (which borrows it whenever possible).
+//!
+//! Run with `cargo bench -p delta_kernel --bench scalar_data_type`.
+use std::hint::black_box;
+
+use delta_kernel::expressions::{ArrayData, MapData, Scalar, StructData};
+use delta_kernel::schema::{ArrayType, DataType, MapType, StructField};
+
+mod common;
+use common::count_allocations;
+
+const NUM_CALLS: usize = 100_000;
+
+fn measure(name: &str, mut f: impl FnMut() -> bool) {
+    let (matched, allocations, elapsed) =
+        count_allocations(|| (0..NUM_CALLS).filter(|_| black_box(f())).count());
+    println!(
+        "{name:>18}: {:.2} allocations/call, {:?}/call ({matched} of {NUM_CALLS} calls matched)",
+        allocations as f64 / NUM_CALLS as f64,
+        elapsed / NUM_CALLS as u32,
+    );
+}
+
+fn main() {
+    let array_type = ArrayType::new(DataType::STRING, true);
+    let map_type = MapType::new(DataType::STRING, DataType::LONG, true);
+    let inner = StructData::try_new(
+        vec![
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable("tags", array_type.clone()),
+        ],
+        vec![
+            Scalar::Long(1),
+            Scalar::Array(ArrayData::try_new(array_type, ["a", "b"]).unwrap()),
+        ],
+    )
+    .unwrap();
+    let fields = vec![
+        StructField::nullable("name", DataType::STRING),
+        StructField::nullable("inner", DataType::struct_type(inner.fields().to_vec())),
+        StructField::nullable("counts", map_type.clone()),
+        StructField::nullable("date", DataType::DATE),
+    ];
+    let values = vec![
+        Scalar::from("x"),
+        Scalar::Struct(inner),
+        Scalar::Map(MapData::try_new(map_type, [("k", 1i64)]).unwrap()),
+        Scalar::Date(17500),
+    ];
+    let struct_scalar = Scalar::Struct(StructData::try_new(fields, values).unwrap());
+    let struct_type = struct_scalar.data_type();
+    let primitive = Scalar::Long(42);
+
+    measure("struct owned", || struct_scalar.data_type() == struct_type);
+    measure("struct borrowed", || {
+        *struct_scalar.data_type_ref() == struct_type
+    });
+    measure("primitive owned", || {
+        primitive.data_type() == DataType::LONG
+    });
+    measure("primitive borrowed", || {
+        *primitive.data_type_ref() == DataType::LONG
+    });
+}
@@ -9,10 +9,10 @@ use delta_kernel::actions::{
 };
 use delta_kernel::engine_data::{GetData, RowVisitor, TypedGetData as _};
 use delta_kernel::expressions::ColumnName;
-use delta_kernel::scan::state::{DvInfo, Stats};
+use delta_kernel::scan::state::ScanFile;
 use delta_kernel::scan::ScanBuilder;
 use delta_kernel::schema::{ColumnNamesAndTypes, DataType};
-use delta_kernel::{DeltaResult, Error, ExpressionRef, Snapshot};
+use delta_kernel::{DeltaResult, Error, Snapshot};
 
 use std::collections::HashMap;
 use std::process::ExitCode;
@@ -151,29 +151,29 @@ impl RowVisitor for LogVisitor {
 }
 
 // This is the callback that will be called for each valid scan row
-fn print_scan_file(
-    _: &mut (),
-    path: &str,
-    size: i64,
-    stats: Option<Stats>,
-    dv_info: DvInfo,
-    transform: Option<ExpressionRef>,
-    partition_values: HashMap<String, String>,
-) {
-    let num_record_str = if let Some(s) = stats {
+fn print_scan_file(_: &mut (), scan_file: ScanFile) {
+    let num_record_str = if let Some(s) = scan_file.stats {
         format!("{}", s.num_records)
     } else {
         "[unknown]".to_string()
     };
     println!(
         "Data to process:\n  \
-              Path:\t\t{path}\n  \
-              Size (bytes):\t{size}\n  \
+              Path:\t\t{}\n  \
+              Size (bytes):\t{}\n  \
               Num Records:\t{num_record_str}\n  \
               Has DV?:\t{}\n  \
-              Transform:\t{transform:?}\n  \
-              Part Vals:\t{partition_values:?}",
-        dv_info.has_vector()
+              Transform:\t{:?}\n  \
+              Part Vals:\t{:?}\n  \
+              Tags:\t\t{:?}\n  \
+              Base Row Id:\t{:?}",
+        scan_file.path,
+        scan_file.size,
+        scan_file.dv_info.has_vector(),
+        scan_file.transform,
+        scan_file.partition_values,
+        scan_file.tags,
+        scan_file.base_row_id,
     );
 }
 
@@ -199,7 +199,7 @@ fn try_main() -> DeltaResult<()> {
             let scan_metadata_iter = scan.scan_metadata(&engine)?;
             for res in scan_metadata_iter {
                 let scan_metadata = res?;
-                scan_metadata.visit_scan_files((), print_scan_file)?;
+                scan_metadata.for_each_scan_file((), print_scan_file)?;
             }
         }
         Commands::Actions { oldest_first } => {
@@ -12,6 +12,9 @@ use delta_kernel_derive::ToSchema;
 use crate::utils::require;
 use crate::{DeltaResult, Error, StorageHandler};
 
+/// The magic number that prefixes a serialized deletion vector bitmap (`RoaringBitmapArray` format)
+const DV_MAGIC: u32 = 1681511377;
+
 #[derive(Debug, Clone, PartialEq, Eq, ToSchema)]
 #[cfg_attr(test, derive(serde::Serialize), serde(rename_all = "camelCase"))]
 pub struct DeletionVectorDescriptor {
@@ -109,15 +112,26 @@ impl DeletionVectorDescriptor {
     ) -> DeltaResult<RoaringTreemap> {
         match self.absolute_path(parent)? {
             None => {
-                let byte_slice = z85::decode(&self.path_or_inline_dv)
+                let decoded = z85::decode(&self.path_or_inline_dv)
                     .map_err(|_| Error::deletion_vector("Failed to decode DV"))?;
+                // The encoded bytes are padded to a multiple of 4, so `size_in_bytes` gives the
+                // actual size of the DV
+                let size = usize::try_from(self.size_in_bytes).unwrap_or(usize::MAX);
+                require!(
+                    (4..=decoded.len()).contains(&size),
+                    Error::DeletionVector(format!(
+                        "Invalid inline DV size {size} for {} decoded bytes",
+                        decoded.len()
+                    ))
+                );
+                let byte_slice = &decoded[..size];
                 let magic = slice_to_u32(&byte_slice[0..4], Endian::Little)?;
                 match magic {
-                    1681511377 => RoaringTreemap::deserialize_from(&byte_slice[4..])
+                    DV_MAGIC => RoaringTreemap::deserialize_from(&byte_slice[4..])
                         .map_err(|err| Error::DeletionVector(err.to_string())),
-                    1681511376 => {
-                        todo!("Don't support native serialization in inline bitmaps yet");
-                    }
+                    1681511376 => Err(Error::unsupported(
+                        "Native serialization of inline deletion vectors is not supported",
+                    )),
                     _ => Err(Error::DeletionVector(format!("Invalid magic {magic}"))),
                 }
             }
@@ -153,7 +167,7 @@ impl DeletionVectorDescriptor {
                 );
                 let magic = read_u32(&mut cursor, Endian::Little)?;
                 require!(
-                    magic == 1681511377,
+                    magic == DV_MAGIC,
                     Error::DeletionVector(format!("Invalid magic: {magic}"))
                 );
 
@@ -174,6 +188,51 @@ impl DeletionVectorDescriptor {
         }
     }
 
+    /// Write `treemap` as a new deletion vector file in the table root, and return a descriptor
+    /// referencing it. The file uses the [Deletion Vector File Format], and contains only this
+    /// deletion vector.
+    ///
+    /// [Deletion Vector File Format]: https://github.com/delta-io/delta/blob/master/PROTOCOL.md#deletion-vector-file-storage-format
+    // NOTE: As with reading, the magic and the bitmap are little endian, while the version, size,
+    // and checksum are big endian. The size and checksum both cover the magic and the bitmap.
+    pub fn write(
+        treemap: &RoaringTreemap,
+        storage: Arc<dyn StorageHandler>,
+        table_root: &Url,
+    ) -> DeltaResult<Self> {
+        let mut dv_data = DV_MAGIC.to_le_bytes().to_vec();
+        treemap
+            .serialize_into(&mut dv_data)
+            .map_err(|err| Error::DeletionVector(err.to_string()))?;
+        let size_in_bytes: i32 = dv_data
+            .len()
+            .try_into()
+            .map_err(|_| Error::deletion_vector("Deletion vector is too large"))?;
+        let cardinality = treemap
+            .len()
+            .try_into()
+            .map_err(|_| Error::deletion_vector("Deletion vector cardinality overflows i64"))?;
+
+        let mut file_data = Vec::with_capacity(dv_data.len() + 9);
+        file_data.push(1); // version
+        file_data.extend_from_slice(&(size_in_bytes as u32).to_be_bytes());
+        file_data.extend_from_slice(&dv_data);
+        file_data.extend_from_slice(&crc32fast::hash(&dv_data).to_be_bytes());
+
+        let uuid = uuid::Uuid::new_v4();
+        let descriptor = DeletionVectorDescriptor {
+            storage_type: "u".to_string(),
+            path_or_inline_dv: z85::encode(uuid.as_bytes()),
+            offset: Some(1),
+            size_in_bytes,
+            cardinality,
+        };
+        // NOTE: unwrap is safe because a `u` descriptor always has an absolute path
+        let path = descriptor.absolute_path(table_root)?.unwrap();
+        storage.write_file(&path, file_data.into(), false)?;
+        Ok(descriptor)
+    }
+
     /// Materialize the row indexes of the deletion vector as a `Vec<u64>` in which each element
     /// represents a row index that is deleted from the table.
     pub fn row_indexes(
@@ -225,6 +284,18 @@ pub(crate) fn selection_treemap_to_bools(treemap: RoaringTreemap) -> Vec<bool> {
     treemap_to_bools_with(treemap, true)
 }
 
+/// helper function to convert a boolean vector into a treemap where, for index i, the bit is set if
+/// the vector is false at index i. This is the inverse of `deletion_treemap_to_bools`, and can be
+/// used to build a new deletion vector from a selection vector of kept rows.
+pub fn bools_to_deletion_treemap(bools: &[bool]) -> RoaringTreemap {
+    bools
+        .iter()
+        .enumerate()
+        .filter(|(_, &keep)| !keep)
+        .map(|(index, _)| index as u64)
+        .collect()
+}
+
 /// helper function to generate vectors of bools from treemap. If `set_bit` is `true`, this is
 /// [`selection_treemap_to_bools`]. If `set_bit` is false, this is [`deletion_treemap_to_bools`]
 fn treemap_to_bools_with(treemap: RoaringTreemap, set_bit: bool) -> Vec<bool> {
@@ -378,6 +449,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_inline_read_padded() {
+        // the serialized DV is not a multiple of 4 bytes long, so it is padded before encoding
+        let treemap = RoaringTreemap::from_iter([1, 5, 9]);
+        let mut bytes = DV_MAGIC.to_le_bytes().to_vec();
+        treemap.serialize_into(&mut bytes).unwrap();
+        let size_in_bytes = bytes.len() as i32;
+        assert_ne!(bytes.len() % 4, 0);
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+        let mut inline = DeletionVectorDescriptor {
+            storage_type: "i".to_string(),
+            path_or_inline_dv: z85::encode(&bytes),
+            offset: None,
+            size_in_bytes,
+            cardinality: 3,
+        };
+        let storage = SyncEngine::new().storage_handler();
+        let parent = Url::parse("http://not.used").unwrap();
+        assert_eq!(inline.read(storage.clone(), &parent).unwrap(), treemap);
+
+        // the size cannot exceed the decoded bytes
+        inline.size_in_bytes = bytes.len() as i32 + 1;
+        let err = inline.read(storage, &parent).unwrap_err();
+        assert!(matches!(err, Error::DeletionVector(_)));
+    }
+
     #[test]
     fn test_deletion_vector_read() {
         let path =
@@ -394,6 +491,32 @@ mod tests {
         assert_eq!(found, expected)
     }
 
+    #[test]
+    fn test_deletion_vector_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let parent = url::Url::from_directory_path(dir.path()).unwrap();
+        let sync_engine = SyncEngine::new();
+        let storage = sync_engine.storage_handler();
+
+        let treemap = RoaringTreemap::from_iter([0, 3, 7, u32::MAX as u64 + 5]);
+        let descriptor =
+            DeletionVectorDescriptor::write(&treemap, storage.clone(), &parent).unwrap();
+        assert_eq!(descriptor.storage_type, "u");
+        assert_eq!(descriptor.offset, Some(1));
+        assert_eq!(descriptor.cardinality, 4);
+
+        // the file must exist where the descriptor says, and carry a valid checksum
+        let path = descriptor.absolute_path(&parent).unwrap().unwrap();
+        assert_eq!(path.join(".").unwrap(), parent);
+        let bytes = std::fs::read(path.to_file_path().unwrap()).unwrap();
+        let data_end = bytes.len() - 4;
+        let checksum = u32::from_be_bytes(bytes[data_end..].try_into().unwrap());
+        assert_eq!(checksum, crc32fast::hash(&bytes[5..data_end]));
+
+        let read_back = descriptor.read(storage, &parent).unwrap();
+        assert_eq!(read_back, treemap);
+    }
+
     // this test is ignored by default as it's expensive to allocate such big vecs full of `true`. you can run it via:
     // cargo test actions::deletion_vector::tests::test_dv_to_bools -- --ignored
     #[test]
@@ -440,6 +563,22 @@ mod tests {
         assert_eq!(bools, expected);
     }
 
+    #[test]
+    fn test_bools_to_dv() {
+        let mut rb = RoaringTreemap::new();
+        rb.insert(0);
+        rb.insert(2);
+        rb.insert(7);
+        rb.insert(30854);
+        let bools = super::deletion_treemap_to_bools(rb.clone());
+        assert_eq!(super::bools_to_deletion_treemap(&bools), rb);
+
+        let bools = [true, false, true, true, false];
+        let expected = RoaringTreemap::from_iter([1, 4]);
+        assert_eq!(super::bools_to_deletion_treemap(&bools), expected);
+        assert!(super::bools_to_deletion_treemap(&[]).is_empty());
+    }
+
     #[test]
     fn test_dv_row_indexes() {
         let example = dv_inline();
@@ -10,12 +10,19 @@ use std::sync::{Arc, LazyLock};
 use crate::actions::get_log_domain_metadata_schema;
 use crate::actions::visitors::DomainMetadataVisitor;
 use crate::actions::{DomainMetadata, DOMAIN_METADATA_NAME};
+use crate::expressions::ColumnName;
 use crate::log_replay::ActionsBatch;
 use crate::log_segment::LogSegment;
-use crate::{DeltaResult, Engine, Expression as Expr, PredicateRef, RowVisitor as _};
+use crate::schema::{DataType, StructType};
+use crate::{DeltaResult, Engine, Error, Expression as Expr, PredicateRef, RowVisitor as _};
+
+use serde::Deserialize;
 
 const DOMAIN_METADATA_DOMAIN_FIELD: &str = "domain";
 
+/// The internal domain in which liquid clustering records the table's clustering columns.
+pub(crate) const CLUSTERING_DOMAIN_NAME: &str = "delta.clustering";
+
 pub(crate) type DomainMetadataMap = HashMap<String, DomainMetadata>;
 
 /// Read the latest domain metadata for a given domain and return its `configuration`. This
@@ -35,6 +42,58 @@ pub(crate) fn domain_metadata_configuration(
         .map(|domain_metadata| domain_metadata.configuration))
 }
 
+/// The configuration of the [`CLUSTERING_DOMAIN_NAME`] domain. Each clustering column is stored
+/// as the path of physical field names leading to it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClusteringDomainMetadata {
+    clustering_columns: Vec<Vec<String>>,
+}
+
+/// Read the clustering columns of a table from its `delta.clustering` domain metadata, resolved to
+/// logical column names using `schema`. Returns `None` if the domain is absent (or removed), i.e.
+/// the table is not clustered.
+pub(crate) fn clustering_columns(
+    log_segment: &LogSegment,
+    schema: &StructType,
+    engine: &dyn Engine,
+) -> DeltaResult<Option<Vec<ColumnName>>> {
+    let Some(configuration) =
+        domain_metadata_configuration(log_segment, CLUSTERING_DOMAIN_NAME, engine)?
+    else {
+        return Ok(None);
+    };
+    let clustering: ClusteringDomainMetadata = serde_json::from_str(&configuration)?;
+    let columns = clustering
+        .clustering_columns
+        .iter()
+        .map(|physical_path| to_logical_column(schema, physical_path))
+        .collect::<DeltaResult<_>>()?;
+    Ok(Some(columns))
+}
+
+// Map a path of physical field names to the corresponding logical column of `schema`.
+fn to_logical_column(schema: &StructType, physical_path: &[String]) -> DeltaResult<ColumnName> {
+    let mut current = Some(schema);
+    let mut logical_path = Vec::with_capacity(physical_path.len());
+    for physical_name in physical_path {
+        let field = current
+            .and_then(|s| s.fields().find(|f| f.physical_name() == physical_name))
+            .ok_or_else(|| {
+                Error::generic(format!(
+                    "Clustering column {} not found in table schema",
+                    ColumnName::new(physical_path)
+                ))
+            })?;
+        logical_path.push(field.name().clone());
+        current = match field.data_type() {
+            DataType::Struct(s) => Some(s),
+            _ => None,
+        };
+    }
+    Ok(ColumnName::new(logical_path))
+}
+
 /// Scan the entire log for all domain metadata actions but terminate early if a specific domain
 /// is provided. Note that this returns the latest domain metadata for each domain, accounting for
 /// tombstones (removed=true) - that is, removed domain metadatas will _never_ be returned.
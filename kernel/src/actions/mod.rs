@@ -8,13 +8,17 @@ use std::str::FromStr;
 use std::sync::{Arc, LazyLock};
 
 use self::deletion_vector::DeletionVectorDescriptor;
-use crate::schema::{SchemaRef, StructField, StructType, ToSchema as _};
+use crate::expressions::{ArrayData, MapData, Scalar};
+use crate::path::resolve_file_path;
+use crate::schema::{
+    ArrayType, DataType, MapType, SchemaRef, StructField, StructType, ToSchema as _,
+};
 use crate::table_features::{
     ReaderFeature, WriterFeature, SUPPORTED_READER_FEATURES, SUPPORTED_WRITER_FEATURES,
 };
 use crate::table_properties::TableProperties;
 use crate::utils::require;
-use crate::{DeltaResult, EngineData, Error, FileMeta, RowVisitor as _};
+use crate::{DeltaResult, Engine, EngineData, Error, FileMeta, RowVisitor as _};
 
 use url::Url;
 use visitors::{MetadataVisitor, ProtocolVisitor};
@@ -87,6 +91,13 @@ static LOG_COMMIT_INFO_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
     )]))
 });
 
+static LOG_METADATA_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(StructType::new([StructField::nullable(
+        METADATA_NAME,
+        Metadata::to_schema(),
+    )]))
+});
+
 static LOG_TXN_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
     Arc::new(StructType::new([StructField::nullable(
         SET_TRANSACTION_NAME,
@@ -115,6 +126,10 @@ pub(crate) fn get_log_commit_info_schema() -> &'static SchemaRef {
     &LOG_COMMIT_INFO_SCHEMA
 }
 
+pub(crate) fn get_log_metadata_schema() -> &'static SchemaRef {
+    &LOG_METADATA_SCHEMA
+}
+
 pub(crate) fn get_log_txn_schema() -> &'static SchemaRef {
     &LOG_TXN_SCHEMA
 }
@@ -123,20 +138,40 @@ pub(crate) fn get_log_domain_metadata_schema() -> &'static SchemaRef {
     &LOG_DOMAIN_METADATA_SCHEMA
 }
 
+/// The encoding of the data files of a table, from the `format` field of its `metaData` action.
+/// See [`Snapshot::format`].
+///
+/// [`Snapshot::format`]: crate::Snapshot::format
 #[derive(Debug, Clone, PartialEq, Eq, ToSchema)]
 #[cfg_attr(
     any(test, feature = "internal-api"),
     derive(Serialize, Deserialize),
     serde(rename_all = "camelCase")
 )]
-#[internal_api]
-pub(crate) struct Format {
+pub struct Format {
     /// Name of the encoding for files in this table
     pub(crate) provider: String,
     /// A map containing configuration options for the format
     pub(crate) options: HashMap<String, String>,
 }
 
+impl Format {
+    /// Name of the encoding for files in this table, e.g. `parquet`.
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Configuration options for the format.
+    pub fn options(&self) -> &HashMap<String, String> {
+        &self.options
+    }
+
+    /// Whether the data files of the table are parquet files, the only format kernel can read.
+    pub(crate) fn is_parquet(&self) -> bool {
+        self.provider.eq_ignore_ascii_case("parquet")
+    }
+}
+
 impl Default for Format {
     fn default() -> Self {
         Self {
@@ -193,7 +228,6 @@ impl Metadata {
     }
 
     #[internal_api]
-    #[allow(dead_code)]
     pub(crate) fn created_time(&self) -> Option<i64> {
         self.created_time
     }
@@ -205,7 +239,6 @@ impl Metadata {
     }
 
     #[internal_api]
-    #[allow(dead_code)]
     pub(crate) fn configuration(&self) -> &HashMap<String, String> {
         &self.configuration
     }
@@ -230,6 +263,40 @@ impl Metadata {
     }
 }
 
+// NOTE: We can't derive IntoEngineData because the nested `format` struct and the map/array fields
+// don't convert to a single leaf `Scalar` each.
+impl crate::IntoEngineData for Metadata {
+    fn into_engine_data(
+        self,
+        schema: SchemaRef,
+        engine: &dyn Engine,
+    ) -> DeltaResult<Box<dyn EngineData>> {
+        use crate::EvaluationHandlerExtension as _;
+        // sort the maps so that they are written in a deterministic order
+        let map_type = MapType::new(DataType::STRING, DataType::STRING, false);
+        let to_map = |map: HashMap<String, String>| -> DeltaResult<Scalar> {
+            let map_data = MapData::try_new(map_type.clone(), map.into_iter().sorted())?;
+            Ok(Scalar::Map(map_data))
+        };
+        let partition_columns = ArrayData::try_new(
+            ArrayType::new(DataType::STRING, false),
+            self.partition_columns,
+        )?;
+        let values = [
+            self.id.into(),
+            self.name.into(),
+            self.description.into(),
+            self.format.provider.into(),
+            to_map(self.format.options)?,
+            self.schema_string.into(),
+            Scalar::Array(partition_columns),
+            self.created_time.into(),
+            to_map(self.configuration)?,
+        ];
+        engine.evaluation_handler().create_one(schema, &values)
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[internal_api]
@@ -463,6 +530,14 @@ pub(crate) struct CommitInfo {
     /// Map of arbitrary string key-value pairs that provide additional information about the
     /// operation. This is specified by the engine. For now this is always empty on write.
     pub(crate) operation_parameters: Option<HashMap<String, String>>,
+    /// Map of metrics about the operation (e.g. `numFiles`, `numOutputRows`), with the values
+    /// encoded as strings. This is specified by the engine.
+    pub(crate) operation_metrics: Option<HashMap<String, String>>,
+    /// The isolation level the operation ran at (e.g. `Serializable`), if specified by the engine.
+    pub(crate) isolation_level: Option<String>,
+    /// An arbitrary string the user attached to this commit (e.g. to describe it in the table's
+    /// history). This is specified by the engine.
+    pub(crate) user_metadata: Option<String>,
     /// The version of the delta_kernel crate used to write this commit. The kernel will always
     /// write this field, but it is optional since many tables will not have this field (i.e. any
     /// tables not written by kernel).
@@ -681,7 +756,7 @@ impl Sidecar {
     /// the "_sidecars/" folder and the given sidecar path.
     pub(crate) fn to_filemeta(&self, log_root: &Url) -> DeltaResult<FileMeta> {
         Ok(FileMeta {
-            location: log_root.join("_sidecars/")?.join(&self.path)?,
+            location: resolve_file_path(&log_root.join("_sidecars/")?, &self.path)?,
             last_modified: self.modification_time,
             size: self.size_in_bytes.try_into().map_err(|_| {
                 Error::generic(format!(
@@ -943,6 +1018,12 @@ mod tests {
                     "operationParameters",
                     MapType::new(DataType::STRING, DataType::STRING, false),
                 ),
+                StructField::nullable(
+                    "operationMetrics",
+                    MapType::new(DataType::STRING, DataType::STRING, false),
+                ),
+                StructField::nullable("isolationLevel", DataType::STRING),
+                StructField::nullable("userMetadata", DataType::STRING),
                 StructField::nullable("kernelVersion", DataType::STRING),
                 StructField::nullable(
                     "engineCommitInfo",
@@ -190,12 +190,12 @@ impl RemoveVisitor {
         let extended_file_metadata: Option<bool> =
             getters[3].get_opt(row_index, "remove.extendedFileMetadata")?;
 
+        // The extended file metadata (partition values, size and tags) is optional: older writers
+        // (and writers that don't set `extendedFileMetadata`) may omit any of it.
         let partition_values: Option<HashMap<_, _>> =
             getters[4].get_opt(row_index, "remove.partitionValues")?;
-
         let size: Option<i64> = getters[5].get_opt(row_index, "remove.size")?;
-
-        // TODO(nick) tags are skipped in getters[6]
+        let tags: Option<HashMap<_, _>> = getters[6].get_opt(row_index, "remove.tags")?;
 
         let deletion_vector = visit_deletion_vector_at(row_index, &getters[7..])?;
 
@@ -210,7 +210,7 @@ impl RemoveVisitor {
             extended_file_metadata,
             partition_values,
             size,
-            tags: None,
+            tags,
             deletion_vector,
             base_row_id,
             default_row_commit_version,
@@ -554,7 +554,8 @@ pub(crate) fn visit_metadata_at<'a>(
     let description: Option<String> = getters[2].get_opt(row_index, "metadata.description")?;
     // get format out of primitives
     let format_provider: String = getters[3].get(row_index, "metadata.format.provider")?;
-    // options for format is always empty, so skip getters[4]
+    let format_options: Option<HashMap<_, _>> =
+        getters[4].get_opt(row_index, "metadata.format.options")?;
     let schema_string: String = getters[5].get(row_index, "metadata.schema_string")?;
     let partition_columns: Vec<_> = getters[6].get(row_index, "metadata.partition_list")?;
     let created_time: Option<i64> = getters[7].get_opt(row_index, "metadata.created_time")?;
@@ -568,7 +569,7 @@ pub(crate) fn visit_metadata_at<'a>(
         description,
         format: Format {
             provider: format_provider,
-            options: HashMap::new(),
+            options: format_options.unwrap_or_default(),
         },
         schema_string,
         partition_columns,
@@ -862,6 +863,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_remove_with_and_without_extended_metadata() {
+        let json_strings: StringArray = vec![
+            r#"{"remove":{"path":"part-00000.snappy.parquet","deletionTimestamp":1670892998135,"dataChange":true,"extendedFileMetadata":true,"partitionValues":{"c1":"4"},"size":452,"tags":{"INSERTION_TIME":"1670892998135000"}}}"#,
+            r#"{"remove":{"path":"part-00001.snappy.parquet","deletionTimestamp":1670892998135,"dataChange":true}}"#,
+            r#"{"remove":{"path":"part-00002.snappy.parquet","dataChange":false,"extendedFileMetadata":false}}"#,
+        ]
+        .into();
+        let batch = parse_json_batch(json_strings);
+        let mut remove_visitor = RemoveVisitor::default();
+        remove_visitor.visit_rows_of(batch.as_ref()).unwrap();
+        let extended = Remove {
+            path: "part-00000.snappy.parquet".into(),
+            deletion_timestamp: Some(1670892998135),
+            data_change: true,
+            extended_file_metadata: Some(true),
+            partition_values: Some(HashMap::from([("c1".to_string(), "4".to_string())])),
+            size: Some(452),
+            tags: Some(HashMap::from([(
+                "INSERTION_TIME".to_string(),
+                "1670892998135000".to_string(),
+            )])),
+            ..Default::default()
+        };
+        let minimal = Remove {
+            path: "part-00001.snappy.parquet".into(),
+            deletion_timestamp: Some(1670892998135),
+            data_change: true,
+            ..Default::default()
+        };
+        let no_extended = Remove {
+            path: "part-00002.snappy.parquet".into(),
+            extended_file_metadata: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(remove_visitor.removes, [extended, minimal, no_extended]);
+    }
+
     #[test]
     fn test_parse_txn() {
         let json_strings: StringArray = vec![
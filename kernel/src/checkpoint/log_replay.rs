@@ -11,7 +11,10 @@
 //!    and metadata actions.
 //! 2. **Txn Actions**: Keeps exactly one `txn` action for each unique app ID, always selecting
 //!    the latest one encountered.
-//! 3. **File Actions**: Resolves file actions to produce the latest state of the table, keeping
+//! 3. **Domain Metadata Actions**: Keeps exactly one `domainMetadata` action for each unique
+//!    domain, always selecting the latest one encountered, and omits domains whose latest action
+//!    is a removal. Only applies to tables which support the `domainMetadata` writer feature.
+//! 4. **File Actions**: Resolves file actions to produce the latest state of the table, keeping
 //!    the most recent valid add actions and unexpired remove actions (tombstones) that are newer
 //!    than `minimum_file_retention_timestamp`.
 //!
@@ -56,6 +59,9 @@ pub(crate) struct CheckpointLogReplayProcessor {
     seen_metadata: bool,
     /// Set of transaction app IDs that have been processed to avoid duplicates.
     seen_txns: HashSet<String>,
+    /// Set of domains that have been processed to avoid duplicates. This is `None` if the table
+    /// does not support domain metadata, in which case domain metadata actions are not visited.
+    seen_domains: Option<HashSet<String>>,
     /// Minimum timestamp for file retention, used for filtering expired tombstones.
     minimum_file_retention_timestamp: i64,
     /// Transaction expiration timestamp for filtering old transactions
@@ -109,6 +115,7 @@ impl LogReplayProcessor for CheckpointLogReplayProcessor {
             self.seen_metadata,
             &mut self.seen_txns,
             self.txn_expiration_timestamp,
+            self.seen_domains.as_mut(),
         );
         visitor.visit_rows_of(actions.as_ref())?;
 
@@ -138,12 +145,14 @@ impl CheckpointLogReplayProcessor {
     pub(crate) fn new(
         minimum_file_retention_timestamp: i64,
         txn_expiration_timestamp: Option<i64>,
+        is_domain_metadata_supported: bool,
     ) -> Self {
         Self {
             seen_file_keys: Default::default(),
             seen_protocol: false,
             seen_metadata: false,
             seen_txns: Default::default(),
+            seen_domains: is_domain_metadata_supported.then(Default::default),
             minimum_file_retention_timestamp,
             txn_expiration_timestamp,
         }
@@ -173,6 +182,8 @@ impl CheckpointLogReplayProcessor {
 /// - Keeps only the first protocol action (newest version)
 /// - Keeps only the first metadata action (most recent table metadata)
 /// - Keeps only the first txn action for each unique app ID
+/// - Keeps only the first domainMetadata action for each unique domain, unless that action removes
+///   the domain, in which case no action is kept for the domain
 ///
 /// # Excluded Actions
 /// - CommitInfo, CDC, and CheckpointMetadata actions should not appear in the action
@@ -185,7 +196,7 @@ impl CheckpointLogReplayProcessor {
 ///
 /// # Memory Usage
 /// This struct has O(N + M) memory usage where:
-/// - N = number of txn actions with unique appIds
+/// - N = number of txn actions with unique appIds and domainMetadata actions with unique domains
 /// - M = number of file actions with unique (path, dvId) pairs
 ///
 /// The resulting filtered set of actions are the actions which should be written to a
@@ -214,6 +225,9 @@ pub(crate) struct CheckpointVisitor<'seen> {
     seen_txns: &'seen mut HashSet<String>,
     /// Transaction expiration timestamp for filtering old transactions
     txn_expiration_timestamp: Option<i64>,
+    // Set of domains to deduplicate domainMetadata actions by domain. If `None`, the domainMetadata
+    // columns are not visited.
+    seen_domains: Option<&'seen mut HashSet<String>>,
 }
 
 #[allow(unused)]
@@ -225,6 +239,8 @@ impl CheckpointVisitor<'_> {
     const REMOVE_PATH_INDEX: usize = 4; // Position of "remove.path" in getters
     const REMOVE_DELETION_TIMESTAMP_INDEX: usize = 5; // Position of "remove.deletionTimestamp" in getters
     const REMOVE_DV_START_INDEX: usize = 6; // Start position of remove deletion vector columns
+    const DOMAIN_METADATA_DOMAIN_INDEX: usize = 13; // Position of "domainMetadata.domain" in getters
+    const DOMAIN_METADATA_REMOVED_INDEX: usize = 14; // Position of "domainMetadata.removed" in getters
 
     // These are the column names used to access the data in the getters
     const REMOVE_DELETION_TIMESTAMP: &'static str = "remove.deletionTimestamp";
@@ -241,6 +257,7 @@ impl CheckpointVisitor<'_> {
         seen_metadata: bool,
         seen_txns: &'seen mut HashSet<String>,
         txn_expiration_timestamp: Option<i64>,
+        seen_domains: Option<&'seen mut HashSet<String>>,
     ) -> CheckpointVisitor<'seen> {
         CheckpointVisitor {
             deduplicator: FileActionDeduplicator::new(
@@ -259,6 +276,7 @@ impl CheckpointVisitor<'_> {
             seen_metadata,
             seen_txns,
             txn_expiration_timestamp,
+            seen_domains,
         }
     }
 
@@ -404,13 +422,44 @@ impl CheckpointVisitor<'_> {
         Ok(true)
     }
 
+    /// Processes a potential domainMetadata action to determine if it should be included in the
+    /// checkpoint.
+    ///
+    /// Returns Ok(true) if the row contains the newest domainMetadata action for its domain, and
+    /// that action does not remove the domain.
+    /// Returns Ok(false) if the row doesn't contain a domainMetadata action, is a duplicate, or
+    /// removes its domain.
+    /// Returns Err(...) if there was an error processing the action.
+    fn check_domain_metadata_action<'a>(
+        &mut self,
+        i: usize,
+        getters: &[&'a dyn GetData<'a>],
+    ) -> DeltaResult<bool> {
+        let Some(seen_domains) = self.seen_domains.as_deref_mut() else {
+            return Ok(false); // Domain metadata columns are not visited
+        };
+        let Some(domain) =
+            getters[Self::DOMAIN_METADATA_DOMAIN_INDEX].get_str(i, "domainMetadata.domain")?
+        else {
+            return Ok(false); // Not a domainMetadata action
+        };
+        let removed: bool =
+            getters[Self::DOMAIN_METADATA_REMOVED_INDEX].get(i, "domainMetadata.removed")?;
+
+        // The domain is recorded even if the action removes it, so that older actions for the
+        // same domain are also omitted.
+        let is_new_domain = seen_domains.insert(domain.to_string());
+        Ok(is_new_domain && !removed)
+    }
+
     /// Determines if a row in the batch should be included in the checkpoint.
     ///
     /// This method checks each action type in sequence, short-circuiting as soon as a valid action is found.
     /// Actions are checked in order of expected frequency of occurrence to optimize performance:
     /// 1. File actions (most frequent)
     /// 2. Txn actions
-    /// 3. Protocol & Metadata actions (least frequent)
+    /// 3. Domain metadata actions
+    /// 4. Protocol & Metadata actions (least frequent)
     ///
     /// Returns Ok(true) if the row should be included in the checkpoint.
     /// Returns Ok(false) if the row should be skipped.
@@ -424,6 +473,7 @@ impl CheckpointVisitor<'_> {
         // the rest will not be evaluated.
         let is_valid = self.check_file_action(i, getters)?
             || self.check_txn_action(i, getters)?
+            || self.check_domain_metadata_action(i, getters)?
             || self.check_protocol_action(i, getters[10])?
             || self.check_metadata_action(i, getters[9])?;
 
@@ -443,11 +493,26 @@ impl RowVisitor for CheckpointVisitor<'_> {
         // 3. METADATA
         // 4. PROTOCOL
         // 5. TXN
+        // 6. DOMAIN METADATA (only if domain metadata is supported)
         static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
+            let (types, names) = checkpoint_columns().into_iter().unzip();
+            (names, types).into()
+        });
+        static NAMES_AND_TYPES_WITH_DOMAIN_METADATA: LazyLock<ColumnNamesAndTypes> =
+            LazyLock::new(|| {
+                let mut types_and_names = checkpoint_columns();
+                types_and_names.extend([
+                    (DataType::STRING, column_name!("domainMetadata.domain")),
+                    (DataType::BOOLEAN, column_name!("domainMetadata.removed")),
+                ]);
+                let (types, names) = types_and_names.into_iter().unzip();
+                (names, types).into()
+            });
+        fn checkpoint_columns() -> Vec<(DataType, ColumnName)> {
             const STRING: DataType = DataType::STRING;
             const INTEGER: DataType = DataType::INTEGER;
             const LONG: DataType = DataType::LONG;
-            let types_and_names = vec![
+            vec![
                 // File action columns
                 (STRING, column_name!("add.path")),
                 (STRING, column_name!("add.deletionVector.storageType")),
@@ -463,16 +528,19 @@ impl RowVisitor for CheckpointVisitor<'_> {
                 (INTEGER, column_name!("protocol.minReaderVersion")),
                 (STRING, column_name!("txn.appId")),
                 (LONG, column_name!("txn.lastUpdated")),
-            ];
-            let (types, names) = types_and_names.into_iter().unzip();
-            (names, types).into()
-        });
-        NAMES_AND_TYPES.as_ref()
+            ]
+        }
+        if self.seen_domains.is_some() {
+            NAMES_AND_TYPES_WITH_DOMAIN_METADATA.as_ref()
+        } else {
+            NAMES_AND_TYPES.as_ref()
+        }
     }
 
     fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
+        let expected_getters = if self.seen_domains.is_some() { 15 } else { 13 };
         require!(
-            getters.len() == 13,
+            getters.len() == expected_getters,
             Error::InternalError(format!(
                 "Wrong number of visitor getters: {}",
                 getters.len()
@@ -507,7 +575,7 @@ mod tests {
     fn run_checkpoint_test(
         input_batches: Vec<ActionsBatch>,
     ) -> DeltaResult<(Vec<FilteredEngineData>, i64, i64)> {
-        let processed_batches: Vec<_> = CheckpointLogReplayProcessor::new(0, None)
+        let processed_batches: Vec<_> = CheckpointLogReplayProcessor::new(0, None, true)
             .process_actions_iter(input_batches.into_iter().map(Ok))
             .try_collect()?;
         let total_count: i64 = processed_batches.iter().map(|b| b.actions_count).sum();
@@ -533,6 +601,7 @@ mod tests {
             false,
             &mut seen_txns,
             None,
+            None,
         );
 
         visitor.visit_rows_of(data.as_ref())?;
@@ -588,6 +657,7 @@ mod tests {
             false,
             &mut seen_txns,
             None,
+            None,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -619,6 +689,7 @@ mod tests {
             false,
             &mut seen_txns,
             None,
+            None,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -658,6 +729,7 @@ mod tests {
             false,
             &mut seen_txns,
             None,
+            None,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -693,6 +765,7 @@ mod tests {
             true,           // The visitor has already seen a metadata action
             &mut seen_txns, // Pre-populated transaction
             None,
+            None,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -731,6 +804,7 @@ mod tests {
             false,
             &mut seen_txns,
             None,
+            None,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -785,6 +859,37 @@ mod tests {
         Ok(())
     }
 
+    /// This test ensures that the processor keeps only the newest domainMetadata action for each
+    /// domain across multiple batches, and omits domains whose newest action removes them.
+    #[test]
+    fn test_checkpoint_actions_iter_domain_metadata_actions() -> DeltaResult<()> {
+        // Batch 1: newest actions for each domain
+        let batch1 = vec![
+            r#"{"domainMetadata":{"domain":"domain1","configuration":"{\"v\":2}","removed":false}}"#,
+            r#"{"domainMetadata":{"domain":"domain2","configuration":"{\"v\":2}","removed":true}}"#,
+        ];
+
+        // Batch 2: older actions for the same domains, and a new domain
+        let batch2 = vec![
+            // Older versions of already seen domains should be skipped
+            r#"{"domainMetadata":{"domain":"domain1","configuration":"{\"v\":1}","removed":false}}"#,
+            r#"{"domainMetadata":{"domain":"domain2","configuration":"{\"v\":1}","removed":false}}"#,
+            // Unique domain should be included
+            r#"{"domainMetadata":{"domain":"domain3","configuration":"{\"v\":1}","removed":false}}"#,
+        ];
+
+        let input_batches = vec![create_batch(batch1)?, create_batch(batch2)?];
+        let (results, actions_count, add_actions) = run_checkpoint_test(input_batches)?;
+
+        assert_eq!(results.len(), 2, "Expected two batches in results");
+        assert_eq!(results[0].selection_vector, vec![true, false]);
+        assert_eq!(results[1].selection_vector, vec![false, false, true]);
+        assert_eq!(actions_count, 2);
+        assert_eq!(add_actions, 0);
+
+        Ok(())
+    }
+
     /// This test ensures that the processor correctly deduplicates and filters
     /// file actions (add, remove) across multiple batches.
     #[test]
@@ -885,6 +990,7 @@ mod tests {
             false,
             &mut seen_txns,
             Some(1000), // expiration timestamp
+            None,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -923,7 +1029,7 @@ mod tests {
         let input_batches = vec![create_batch(batch1)?, create_batch(batch2)?];
 
         // Create processor with txn expiration timestamp
-        let processor = CheckpointLogReplayProcessor::new(0, Some(1000));
+        let processor = CheckpointLogReplayProcessor::new(0, Some(1000), false);
         let results: Vec<_> = processor
             .process_actions_iter(input_batches.into_iter().map(Ok))
             .try_collect()?;
@@ -13,6 +13,11 @@
 //! For more information on the V1/V2 specifications, see the following protocol section:
 //! <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#checkpoint-specs>
 //!
+//! ## Checkpoint Schema
+//! Checkpoints always contain the `add`, `remove`, `metaData`, `protocol`, `txn` and `sidecar`
+//! actions. If the table supports the `domainMetadata` writer feature, checkpoints also contain
+//! the latest `domainMetadata` action for each domain that has not been removed.
+//!
 //! ## Architecture
 //!
 //! - [`CheckpointWriter`] - Core component that manages the checkpoint creation workflow
@@ -22,7 +27,8 @@
 //!
 //! The following steps outline the process of creating a checkpoint:
 //!
-//! 1. Create a [`CheckpointWriter`] using [`Snapshot::checkpoint`]
+//! 1. Create a [`CheckpointWriter`] using [`Snapshot::checkpoint`] (or, after a commit, using
+//!    [`Snapshot::maybe_checkpoint`] to only checkpoint every `delta.checkpointInterval` commits)
 //! 2. Get the checkpoint path from [`CheckpointWriter::checkpoint_path`]
 //! 2. Get the checkpoint data from [`CheckpointWriter::checkpoint_data`]
 //! 3. Write the data to the path in object storage (engine-specific)
@@ -85,8 +91,9 @@ use std::sync::{Arc, LazyLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::actions::{
-    Add, Metadata, Protocol, Remove, SetTransaction, Sidecar, ADD_NAME, CHECKPOINT_METADATA_NAME,
-    METADATA_NAME, PROTOCOL_NAME, REMOVE_NAME, SET_TRANSACTION_NAME, SIDECAR_NAME,
+    Add, DomainMetadata, Metadata, Protocol, Remove, SetTransaction, Sidecar, ADD_NAME,
+    CHECKPOINT_METADATA_NAME, DOMAIN_METADATA_NAME, METADATA_NAME, PROTOCOL_NAME, REMOVE_NAME,
+    SET_TRANSACTION_NAME, SIDECAR_NAME,
 };
 use crate::engine_data::FilteredEngineData;
 use crate::expressions::Scalar;
@@ -111,6 +118,10 @@ const HOURS_PER_DAY: u64 = 24;
 /// This is set to 7 days, which is the default in delta-spark.
 const DEFAULT_RETENTION_SECS: u64 = 7 * HOURS_PER_DAY * MINUTES_PER_HOUR * SECONDS_PER_MINUTE;
 
+/// The default number of commits between checkpoints, used by [`Snapshot::maybe_checkpoint`] if the
+/// table does not set the `delta.checkpointInterval` property. This matches delta-spark.
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 10;
+
 /// Schema of the `_last_checkpoint` file
 /// We cannot use `LastCheckpointInfo::to_schema()` as it would include the 'checkpoint_schema'
 /// field, which is only known at runtime.
@@ -125,17 +136,43 @@ static LAST_CHECKPOINT_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
     .into()
 });
 
-/// Schema for extracting relevant actions from log files for checkpoint creation
-static CHECKPOINT_ACTIONS_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
-    Arc::new(StructType::new([
+/// Schema for extracting relevant actions from log files for checkpoint creation, for tables that
+/// do not support the `domainMetadata` writer feature.
+static CHECKPOINT_ACTIONS_SCHEMA: LazyLock<SchemaRef> =
+    LazyLock::new(|| Arc::new(StructType::new(checkpoint_action_fields())));
+
+/// Schema for extracting relevant actions from log files for checkpoint creation, for tables that
+/// support the `domainMetadata` writer feature.
+static CHECKPOINT_ACTIONS_WITH_DOMAIN_METADATA_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(StructType::new(checkpoint_action_fields().chain([
+        StructField::nullable(DOMAIN_METADATA_NAME, DomainMetadata::to_schema()),
+    ])))
+});
+
+/// The action fields present in every checkpoint. The `sidecar` field is always required, since
+/// reading file actions from a V2 checkpoint requires resolving its sidecar files.
+fn checkpoint_action_fields() -> impl Iterator<Item = StructField> {
+    [
         StructField::nullable(ADD_NAME, Add::to_schema()),
         StructField::nullable(REMOVE_NAME, Remove::to_schema()),
         StructField::nullable(METADATA_NAME, Metadata::to_schema()),
         StructField::nullable(PROTOCOL_NAME, Protocol::to_schema()),
         StructField::nullable(SET_TRANSACTION_NAME, SetTransaction::to_schema()),
         StructField::nullable(SIDECAR_NAME, Sidecar::to_schema()),
-    ]))
-});
+    ]
+    .into_iter()
+}
+
+/// Returns the schema used to read actions from the log for checkpoint creation, which is also the
+/// schema of the written checkpoint. The `domainMetadata` action is only included when the table
+/// supports the `domainMetadata` writer feature, as it can't appear in the log otherwise.
+fn checkpoint_actions_schema(is_domain_metadata_supported: bool) -> SchemaRef {
+    if is_domain_metadata_supported {
+        CHECKPOINT_ACTIONS_WITH_DOMAIN_METADATA_SCHEMA.clone()
+    } else {
+        CHECKPOINT_ACTIONS_SCHEMA.clone()
+    }
+}
 
 // Schema of the [`CheckpointMetadata`] action that is included in V2 checkpoints
 // We cannot use `CheckpointMetadata::to_schema()` as it would include the 'tags' field which
@@ -257,22 +294,21 @@ impl CheckpointWriter {
     //    (i.e., if `v2Checkpoints` feature is supported by table)
     // 5. Generates the appropriate checkpoint path
     pub fn checkpoint_data(&self, engine: &dyn Engine) -> DeltaResult<CheckpointDataIterator> {
-        let is_v2_checkpoints_supported = self
-            .snapshot
-            .table_configuration()
-            .is_v2_checkpoint_write_supported();
+        let table_configuration = self.snapshot.table_configuration();
+        let is_v2_checkpoints_supported = table_configuration.is_v2_checkpoint_write_supported();
+        let is_domain_metadata_supported = table_configuration.is_domain_metadata_supported();
 
-        let actions = self.snapshot.log_segment().read_actions(
-            engine,
-            CHECKPOINT_ACTIONS_SCHEMA.clone(),
-            CHECKPOINT_ACTIONS_SCHEMA.clone(),
-            None,
-        )?;
+        let schema = checkpoint_actions_schema(is_domain_metadata_supported);
+        let actions =
+            self.snapshot
+                .log_segment()
+                .read_actions(engine, schema.clone(), schema, None)?;
 
         // Create iterator over actions for checkpoint data
         let checkpoint_data = CheckpointLogReplayProcessor::new(
             self.deleted_file_retention_timestamp()?,
             self.get_transaction_expiration_timestamp()?,
+            is_domain_metadata_supported,
         )
         .process_actions_iter(actions);
 
@@ -2,12 +2,14 @@ use std::{sync::Arc, time::Duration};
 
 use super::DEFAULT_RETENTION_SECS;
 use crate::actions::{Add, Metadata, Protocol, Remove};
-use crate::arrow::array::{ArrayRef, StructArray};
+use crate::arrow::array::{ArrayRef, BooleanArray, StructArray};
+use crate::arrow::compute::filter_record_batch;
 use crate::arrow::datatypes::{DataType, Schema};
 use crate::checkpoint::{create_last_checkpoint_data, deleted_file_retention_timestamp_with_time};
 use crate::engine::arrow_data::ArrowEngineData;
 use crate::engine::default::{executor::tokio::TokioBackgroundExecutor, DefaultEngine};
 use crate::object_store::{memory::InMemory, path::Path, ObjectStore};
+use crate::parquet::arrow::ArrowWriter;
 use crate::utils::test_utils::Action;
 use crate::{DeltaResult, FileMeta, Snapshot};
 
@@ -16,6 +18,7 @@ use arrow_55::{
     datatypes::Field,
 };
 
+use serde::Serialize;
 use serde_json::{from_slice, json, Value};
 use test_utils::delta_path_for_version;
 use url::Url;
@@ -170,7 +173,7 @@ fn new_in_memory_store() -> (Arc<InMemory>, Url) {
 /// This function formats the provided filename into the _delta_log directory.
 fn write_commit_to_store(
     store: &Arc<InMemory>,
-    actions: Vec<Action>,
+    actions: Vec<impl Serialize>,
     version: u64,
 ) -> DeltaResult<()> {
     let json_lines: Vec<String> = actions
@@ -505,3 +508,104 @@ fn test_v2_checkpoint_supported_table() -> DeltaResult<()> {
 
     Ok(())
 }
+
+/// Tests the `checkpoint()` API with a table that supports domainMetadata, writing the checkpoint
+/// data to storage and verifying that domain metadata and txn actions are read back from it.
+#[test]
+fn test_checkpoint_preserves_domain_metadata_and_txns() -> DeltaResult<()> {
+    let (store, _) = new_in_memory_store();
+    let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+
+    // 1st commit: protocol with the domainMetadata writer feature, metadata, and an add action
+    let protocol = Protocol::try_new(
+        3,
+        7,
+        Some(Vec::<String>::new()),
+        Some(vec!["domainMetadata"]),
+    )?;
+    write_commit_to_store(
+        &store,
+        vec![
+            Action::Protocol(protocol),
+            create_metadata_action(),
+            create_add_action("fake_path_1"),
+        ],
+        0,
+    )?;
+
+    // 2nd commit: domain metadata for two domains & a txn action
+    write_commit_to_store(
+        &store,
+        vec![
+            json!({"domainMetadata": {"domain": "app.a", "configuration": "v1", "removed": false}}),
+            json!({"domainMetadata": {"domain": "app.b", "configuration": "v1", "removed": false}}),
+            json!({"txn": {"appId": "app-1", "version": 1}}),
+        ],
+        1,
+    )?;
+
+    // 3rd commit: updates `app.a`, removes `app.b`, and updates the txn version
+    write_commit_to_store(
+        &store,
+        vec![
+            json!({"domainMetadata": {"domain": "app.a", "configuration": "v2", "removed": false}}),
+            json!({"domainMetadata": {"domain": "app.b", "configuration": "v1", "removed": true}}),
+            json!({"txn": {"appId": "app-1", "version": 2}}),
+        ],
+        2,
+    )?;
+
+    let table_root = Url::parse("memory:///")?;
+    let snapshot = Arc::new(Snapshot::try_new(table_root.clone(), &engine, None)?);
+    let writer = snapshot.checkpoint()?;
+    let checkpoint_path = writer.checkpoint_path()?;
+    let mut data_iter = writer.checkpoint_data(&engine)?;
+
+    // The newest batch keeps `app.a` and the txn action, but not the removed `app.b`.
+    let batch = data_iter.next().unwrap()?;
+    assert_eq!(batch.selection_vector, [true, false, true]);
+
+    // Write the selected checkpoint actions to storage as parquet
+    let record_batches = std::iter::once(Ok(batch))
+        .chain(data_iter.by_ref())
+        .map(|batch| {
+            let batch = batch?;
+            let data = ArrowEngineData::try_from_engine_data(batch.data)?;
+            let selection_vector = BooleanArray::from(batch.selection_vector);
+            Ok(filter_record_batch(data.record_batch(), &selection_vector)?)
+        })
+        .collect::<DeltaResult<Vec<_>>>()?;
+    let mut buffer = vec![];
+    let mut parquet_writer = ArrowWriter::try_new(&mut buffer, record_batches[0].schema(), None)?;
+    for record_batch in &record_batches {
+        parquet_writer.write(record_batch)?;
+    }
+    parquet_writer.close()?;
+    let size = buffer.len() as u64;
+    let rt = tokio::runtime::Runtime::new().expect("create tokio runtime");
+    rt.block_on(store.put(&Path::from_url_path(checkpoint_path.path())?, buffer.into()))?;
+
+    let metadata = FileMeta {
+        location: checkpoint_path,
+        last_modified: 0,
+        size,
+    };
+    writer.finalize(&engine, &metadata, data_iter)?;
+
+    // Remove the commits so that the new snapshot can only be built from the checkpoint
+    for version in 0..=2 {
+        let commit_path = format!("_delta_log/{}", delta_path_for_version(version, "json"));
+        rt.block_on(store.delete(&Path::from(commit_path)))?;
+    }
+
+    let snapshot = Arc::new(Snapshot::try_new(table_root, &engine, None)?);
+    assert_eq!(snapshot.version(), 2);
+    assert_eq!(
+        snapshot.get_domain_metadata("app.a", &engine)?,
+        Some("v2".to_string())
+    );
+    assert_eq!(snapshot.get_domain_metadata("app.b", &engine)?, None);
+    assert_eq!(snapshot.get_app_id_version("app-1", &engine)?, Some(2));
+
+    Ok(())
+}
@@ -11,7 +11,8 @@ use itertools::Itertools;
 
 use crate::error::Error;
 use crate::schema::{
-    ArrayType, DataType, MapType, MetadataValue, PrimitiveType, StructField, StructType,
+    ArrayType, DataType, DecimalType, MapType, MetadataValue, PrimitiveType, StructField,
+    StructType,
 };
 
 pub(crate) const LIST_ARRAY_ROOT: &str = "element";
@@ -131,6 +132,12 @@ impl TryFromKernel<&DataType> for ArrowDataType {
                     PrimitiveType::Double => Ok(ArrowDataType::Float64),
                     PrimitiveType::Boolean => Ok(ArrowDataType::Boolean),
                     PrimitiveType::Binary => Ok(ArrowDataType::Binary),
+                    PrimitiveType::Decimal(dtype) if dtype.requires_256_bits() => {
+                        Ok(ArrowDataType::Decimal256(
+                            dtype.precision(),
+                            dtype.scale() as i8, // 0..=76
+                        ))
+                    }
                     PrimitiveType::Decimal(dtype) => Ok(ArrowDataType::Decimal128(
                         dtype.precision(),
                         dtype.scale() as i8, // 0..=38
@@ -161,6 +168,7 @@ impl TryFromKernel<&DataType> for ArrowDataType {
                 Arc::new(m.as_ref().try_into_arrow()?),
                 false,
             )),
+            DataType::Null => Ok(ArrowDataType::Null),
         }
     }
 }
@@ -214,15 +222,23 @@ impl TryFromArrow<&ArrowDataType> for DataType {
             ArrowDataType::FixedSizeBinary(_) => Ok(DataType::BINARY),
             ArrowDataType::LargeBinary => Ok(DataType::BINARY),
             ArrowDataType::BinaryView => Ok(DataType::BINARY),
-            ArrowDataType::Decimal128(p, s) => {
+            ArrowDataType::Decimal128(p, s) | ArrowDataType::Decimal256(p, s) => {
                 if *s < 0 {
                     return Err(ArrowError::from_external_error(
                         Error::invalid_decimal("Negative scales are not supported in Delta").into(),
                     ));
                 };
-                DataType::decimal(*p, *s as u8)
+                // Table schemas never declare decimals wider than 38 digits, so a `Decimal256`
+                // of greater precision can only hold intermediate values (e.g. a wide literal).
+                let dtype = match arrow_datatype {
+                    ArrowDataType::Decimal256(..) => DecimalType::try_new_wide(*p, *s as u8),
+                    _ => DecimalType::try_new(*p, *s as u8),
+                };
+                dtype
+                    .map(DataType::from)
                     .map_err(|e| ArrowError::from_external_error(e.into()))
             }
+            ArrowDataType::Null => Ok(DataType::Null),
             ArrowDataType::Date32 => Ok(DataType::DATE),
             ArrowDataType::Date64 => Ok(DataType::DATE),
             ArrowDataType::Timestamp(TimeUnit::Microsecond, None) => Ok(DataType::TIMESTAMP_NTZ),
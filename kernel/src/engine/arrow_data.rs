@@ -5,8 +5,10 @@ use crate::{DeltaResult, Error};
 use crate::arrow::array::cast::AsArray;
 use crate::arrow::array::types::{Int32Type, Int64Type};
 use crate::arrow::array::{
-    Array, ArrayRef, GenericListArray, MapArray, OffsetSizeTrait, RecordBatch, StructArray,
+    Array, ArrayRef, BooleanArray, GenericListArray, MapArray, OffsetSizeTrait, RecordBatch,
+    StructArray,
 };
+use crate::arrow::compute::filter_record_batch;
 use crate::arrow::datatypes::{DataType as ArrowDataType, FieldRef};
 use tracing::debug;
 
@@ -152,6 +154,33 @@ impl EngineData for ArrowEngineData {
         self.data.num_rows()
     }
 
+    fn slice(&self, offset: usize, length: usize) -> DeltaResult<Box<dyn EngineData>> {
+        if offset.saturating_add(length) > self.len() {
+            return Err(Error::generic(format!(
+                "Cannot slice {length} rows at offset {offset} from data with {} rows",
+                self.len()
+            )));
+        }
+        Ok(Box::new(ArrowEngineData::new(
+            self.data.slice(offset, length),
+        )))
+    }
+
+    fn filter(&self, selection_vector: &[bool]) -> DeltaResult<Box<dyn EngineData>> {
+        if selection_vector.len() > self.len() {
+            return Err(Error::generic(format!(
+                "Selection vector has {} rows, but the data only has {} rows",
+                selection_vector.len(),
+                self.len()
+            )));
+        }
+        // missing entries in the selection vector are implicitly true
+        let mut mask = selection_vector.to_vec();
+        mask.resize(self.len(), true);
+        let filtered = filter_record_batch(&self.data, &BooleanArray::from(mask))?;
+        Ok(Box::new(ArrowEngineData::new(filtered)))
+    }
+
     fn visit_rows(
         &self,
         leaf_columns: &[ColumnName],
@@ -294,13 +323,42 @@ impl ArrowEngineData {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use crate::actions::{get_log_schema, Metadata, Protocol};
-    use crate::arrow::array::StringArray;
+    use crate::arrow::array::{AsArray as _, Int32Array, RecordBatch, StringArray};
+    use crate::arrow::datatypes::Int32Type;
+    use crate::engine::arrow_data::ArrowEngineData;
     use crate::engine::sync::SyncEngine;
+    use crate::engine_data::EngineData as _;
     use crate::table_features::{ReaderFeature, WriterFeature};
     use crate::utils::test_utils::string_array_to_engine_data;
     use crate::{DeltaResult, Engine as _};
 
+    #[test]
+    fn test_filter() -> DeltaResult<()> {
+        let values = Int32Array::from(vec![0, 1, 2, 3, 4]);
+        let batch = RecordBatch::try_from_iter([("value", Arc::new(values) as _)])?;
+        let data = ArrowEngineData::new(batch);
+        let filter = |selection_vector: &[bool]| -> DeltaResult<Vec<i32>> {
+            let filtered = ArrowEngineData::try_from_engine_data(data.filter(selection_vector)?)?;
+            let column = filtered
+                .record_batch()
+                .column(0)
+                .as_primitive::<Int32Type>();
+            Ok(column.values().to_vec())
+        };
+
+        assert_eq!(filter(&[true, false, true, false, true])?, [0, 2, 4]);
+        assert_eq!(filter(&[false; 5])?, [0; 0]);
+        // missing entries are implicitly selected
+        assert_eq!(filter(&[false, false])?, [2, 3, 4]);
+        assert_eq!(filter(&[])?, [0, 1, 2, 3, 4]);
+        // a selection vector longer than the data is an error
+        assert!(filter(&[true; 6]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_md_extract() -> DeltaResult<()> {
         let engine = SyncEngine::new();
@@ -0,0 +1,173 @@
+//! Predicates whose column references are resolved to field indices ahead of time, so that
+//! evaluating them repeatedly over batches of the same schema does not need to look up columns by
+//! name each time.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use itertools::Itertools as _;
+
+use crate::arrow::array::{ArrayRef, BooleanArray, RecordBatch};
+use crate::arrow::datatypes::{DataType as ArrowDataType, Schema as ArrowSchema};
+use crate::engine::arrow_conversion::TryFromKernel as _;
+use crate::error::{DeltaResult, Error};
+use crate::expressions::transforms::ExpressionTransform;
+use crate::expressions::{ColumnName, OpaqueExpression, OpaquePredicate, Predicate};
+use crate::schema::{DataType, StructType};
+
+use super::evaluate_expression::{evaluate_predicate_over, extract_column_by_index, ColumnSource};
+
+/// A [`Predicate`] compiled against a schema. Every column it references is resolved once, at
+/// compile time, to the indices of its (nested) field, and [`CompiledPredicate::eval`] fetches
+/// those columns by index and evaluates the predicate directly over them. The result is the same
+/// as evaluating the original predicate over the batch.
+#[derive(Debug, Clone)]
+pub struct CompiledPredicate {
+    // The predicate, with each column reference replaced by the position of the column in
+    // `columns`. Opaque predicates and expressions evaluate over the batch, so they keep their
+    // original column references.
+    predicate: Predicate,
+    // The field indices of the path of each column the predicate references.
+    columns: Vec<Vec<usize>>,
+    // The schema the predicate was compiled against, which every evaluated batch must have.
+    schema: Arc<ArrowSchema>,
+}
+
+impl CompiledPredicate {
+    /// Resolves the columns referenced by `predicate` against `schema`, so it can be evaluated
+    /// repeatedly over arrow batches that have that schema. Returns an error if the predicate
+    /// references a column that does not exist in `schema`.
+    pub fn try_new(predicate: &Predicate, schema: &StructType) -> DeltaResult<Self> {
+        let mut resolver = ResolveColumns::default();
+        for name in predicate.references() {
+            let path = resolve_column_path(schema, name)?;
+            resolver.slots.insert(name.clone(), resolver.columns.len());
+            resolver.columns.push(path);
+        }
+        let predicate = resolver
+            .transform_pred(predicate)
+            .ok_or_else(|| Error::internal_error("Failed to resolve predicate columns"))?
+            .into_owned();
+        Ok(Self {
+            predicate,
+            columns: resolver.columns,
+            schema: Arc::new(ArrowSchema::try_from_kernel(schema)?),
+        })
+    }
+
+    /// Evaluates the predicate over an arrow `batch`. Returns an error if the batch does not have
+    /// the schema the predicate was compiled against.
+    pub fn eval(&self, batch: &RecordBatch) -> DeltaResult<BooleanArray> {
+        self.check_schema(batch)?;
+        let columns = self
+            .columns
+            .iter()
+            .map(|path| extract_column_by_index(batch, path))
+            .collect::<DeltaResult<_>>()?;
+        let input = ResolvedColumns { batch, columns };
+        evaluate_predicate_over(&self.predicate, &input, false)
+    }
+
+    // Checks that each field of the batch has the name and type of the corresponding field of the
+    // compiled schema. Dictionary-encoded columns match their value type, as they are decoded when
+    // extracted.
+    fn check_schema(&self, batch: &RecordBatch) -> DeltaResult<()> {
+        let batch_schema = batch.schema_ref();
+        if Arc::ptr_eq(batch_schema, &self.schema) {
+            return Ok(());
+        }
+        fn value_type(data_type: &ArrowDataType) -> &ArrowDataType {
+            match data_type {
+                ArrowDataType::Dictionary(_, value_type) => value_type,
+                data_type => data_type,
+            }
+        }
+        let (expected, actual) = (self.schema.fields(), batch_schema.fields());
+        let matches = expected.len() == actual.len()
+            && expected.iter().zip(actual).all(|(expected, actual)| {
+                expected.name() == actual.name()
+                    && value_type(expected.data_type()).equals_datatype(actual.data_type())
+            });
+        if !matches {
+            return Err(Error::schema(format!(
+                "Batch schema {batch_schema:?} does not match the schema the predicate was \
+                compiled against: {:?}",
+                self.schema
+            )));
+        }
+        Ok(())
+    }
+}
+
+// Resolves a (nested) column name to the indices of the fields along its path in `schema`.
+fn resolve_column_path(schema: &StructType, name: &ColumnName) -> DeltaResult<Vec<usize>> {
+    let mut path = Vec::with_capacity(name.len());
+    let mut fields = Some(schema);
+    for field_name in name.iter() {
+        let (index, field) = fields
+            .and_then(|fields| fields.index_of(field_name).zip(fields.field(field_name)))
+            .ok_or_else(|| Error::missing_column(name))?;
+        fields = match field.data_type() {
+            DataType::Struct(fields) => Some(fields),
+            _ => None,
+        };
+        path.push(index);
+    }
+    Ok(path)
+}
+
+// Replaces each column reference by its position among the resolved columns. The children of
+// opaque predicates and expressions are left untouched.
+#[derive(Default)]
+struct ResolveColumns {
+    slots: HashMap<ColumnName, usize>,
+    columns: Vec<Vec<usize>>,
+}
+
+impl<'a> ExpressionTransform<'a> for ResolveColumns {
+    fn transform_expr_column(&mut self, name: &'a ColumnName) -> Option<Cow<'a, ColumnName>> {
+        let slot = self.slots.get(name)?;
+        Some(Cow::Owned(ColumnName::new([slot.to_string()])))
+    }
+
+    fn transform_pred_opaque(
+        &mut self,
+        pred: &'a OpaquePredicate,
+    ) -> Option<Cow<'a, OpaquePredicate>> {
+        Some(Cow::Borrowed(pred))
+    }
+
+    fn transform_expr_opaque(
+        &mut self,
+        expr: &'a OpaqueExpression,
+    ) -> Option<Cow<'a, OpaqueExpression>> {
+        Some(Cow::Borrowed(expr))
+    }
+}
+
+// The columns of a batch referenced by a compiled predicate, in the order of its `columns`.
+struct ResolvedColumns<'a> {
+    batch: &'a RecordBatch,
+    columns: Vec<ArrayRef>,
+}
+
+impl ColumnSource for ResolvedColumns<'_> {
+    fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    // Compiled column references are single-element names holding the column's position.
+    fn column(&self, name: &[String]) -> DeltaResult<ArrayRef> {
+        name.iter()
+            .exactly_one()
+            .ok()
+            .and_then(|slot| slot.parse::<usize>().ok())
+            .and_then(|slot| self.columns.get(slot))
+            .cloned()
+            .ok_or_else(|| Error::internal_error(format!("Column {name:?} was not compiled")))
+    }
+
+    fn batch(&self) -> &RecordBatch {
+        self.batch
+    }
+}
@@ -1,31 +1,61 @@
 //! Expression handling based on arrow-rs compute kernels.
 use crate::arrow::array::types::*;
 use crate::arrow::array::{
-    Array, ArrayRef, AsArray, BooleanArray, Datum, RecordBatch, StructArray,
+    make_array, Array, ArrayRef, AsArray, BooleanArray, Datum, RecordBatch, Scalar as ArrowScalar,
+    StructArray,
 };
+use crate::arrow::buffer::NullBuffer;
 use crate::arrow::compute::kernels::cmp::{distinct, eq, gt, gt_eq, lt, lt_eq, neq, not_distinct};
 use crate::arrow::compute::kernels::comparison::in_list_utf8;
 use crate::arrow::compute::kernels::numeric::{add, div, mul, sub};
-use crate::arrow::compute::{and_kleene, is_not_null, is_null, not, or_kleene};
+use crate::arrow::compute::kernels::zip::zip;
+use crate::arrow::compute::{and_kleene, cast, is_not_null, is_null, not, or_kleene};
 use crate::arrow::datatypes::{
     DataType as ArrowDataType, Field as ArrowField, IntervalUnit, TimeUnit,
 };
 use crate::arrow::error::ArrowError;
+use crate::engine::arrow_conversion::{TryFromArrow as _, TryFromKernel as _};
 use crate::engine::arrow_expression::opaque::{
     ArrowOpaqueExpressionOpAdaptor, ArrowOpaquePredicateOpAdaptor,
 };
 use crate::engine::arrow_utils::prim_array_cmp;
 use crate::error::{DeltaResult, Error};
 use crate::expressions::{
-    BinaryExpression, BinaryExpressionOp, BinaryPredicate, BinaryPredicateOp, Expression,
-    JunctionPredicate, JunctionPredicateOp, OpaqueExpression, OpaquePredicate, Predicate, Scalar,
-    UnaryPredicate, UnaryPredicateOp,
+    widest_type, ArrayData, BinaryExpression, BinaryExpressionOp, BinaryPredicate,
+    BinaryPredicateOp, Expression, JunctionPredicate, JunctionPredicateOp, OpaqueExpression,
+    OpaquePredicate, Predicate, Scalar, UnaryPredicate, UnaryPredicateOp, VariadicExpression,
+    VariadicExpressionOp,
 };
 use crate::schema::DataType;
 use itertools::Itertools;
 use std::borrow::Cow;
 use std::sync::Arc;
 
+// The input an expression is evaluated over: a record batch whose columns are looked up by name, or
+// the columns of a [`CompiledPredicate`], which were fetched by index.
+//
+// [`CompiledPredicate`]: super::CompiledPredicate
+pub(crate) trait ColumnSource {
+    fn num_rows(&self) -> usize;
+    fn column(&self, name: &[String]) -> DeltaResult<ArrayRef>;
+    // The batch that opaque expressions and predicates evaluate over.
+    fn batch(&self) -> &RecordBatch;
+}
+
+impl ColumnSource for RecordBatch {
+    fn num_rows(&self) -> usize {
+        self.num_rows()
+    }
+
+    fn column(&self, name: &[String]) -> DeltaResult<ArrayRef> {
+        extract_column(self, name)
+    }
+
+    fn batch(&self) -> &RecordBatch {
+        self
+    }
+}
+
 trait ProvidesColumnByName {
     fn column_by_name(&self, name: &str) -> Option<&ArrayRef>;
 }
@@ -57,24 +87,90 @@ impl ProvidesColumnByName for StructArray {
 // }
 // ```
 // The path ["b", "d", "f"] would retrieve the int64 column while ["a", "b"] would produce an error.
+//
+// A dictionary-encoded column is decoded to (a plain array of) its value type, since kernel data
+// types have no notion of dictionary encoding and not every compute kernel supports it.
 fn extract_column(mut parent: &dyn ProvidesColumnByName, col: &[String]) -> DeltaResult<ArrayRef> {
     let mut field_names = col.iter();
     let Some(mut field_name) = field_names.next() else {
         return Err(ArrowError::SchemaError("Empty column path".to_string()))?;
     };
+    // The combined nulls of all enclosing structs: a field of a NULL struct is itself NULL.
+    let mut parent_nulls: Option<NullBuffer> = None;
     loop {
         let child = parent
             .column_by_name(field_name)
             .ok_or_else(|| ArrowError::SchemaError(format!("No such field: {field_name}")))?;
         field_name = match field_names.next() {
             Some(name) => name,
-            None => return Ok(child.clone()),
+            None => {
+                let child = with_parent_nulls(child, parent_nulls.as_ref())?;
+                return match child.data_type() {
+                    ArrowDataType::Dictionary(_, value_type) => Ok(cast(&child, value_type)?),
+                    _ => Ok(child),
+                };
+            }
         };
-        parent = child
+        let child = child
             .as_any()
             .downcast_ref::<StructArray>()
             .ok_or_else(|| ArrowError::SchemaError(format!("Not a struct: {field_name}")))?;
+        parent_nulls = NullBuffer::union(parent_nulls.as_ref(), child.nulls());
+        parent = child;
+    }
+}
+
+// Like [`extract_column`], but the nested column path is given as a list of field indices instead of
+// field names, as resolved by [`CompiledPredicate::try_new`]. Returns Err if the path is invalid.
+//
+// [`CompiledPredicate::try_new`]: super::CompiledPredicate::try_new
+pub(crate) fn extract_column_by_index(
+    batch: &RecordBatch,
+    path: &[usize],
+) -> DeltaResult<ArrayRef> {
+    let (&first, rest) = path
+        .split_first()
+        .ok_or_else(|| ArrowError::SchemaError("Empty column path".to_string()))?;
+    let mut child = batch
+        .columns()
+        .get(first)
+        .ok_or_else(|| ArrowError::SchemaError(format!("No field at index {first}")))?;
+    let mut parent_nulls: Option<NullBuffer> = None;
+    for &index in rest {
+        let parent = child
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(|| ArrowError::SchemaError(format!("Not a struct at index {index}")))?;
+        parent_nulls = NullBuffer::union(parent_nulls.as_ref(), parent.nulls());
+        child = parent
+            .columns()
+            .get(index)
+            .ok_or_else(|| ArrowError::SchemaError(format!("No field at index {index}")))?;
+    }
+    let child = with_parent_nulls(child, parent_nulls.as_ref())?;
+    match child.data_type() {
+        ArrowDataType::Dictionary(_, value_type) => Ok(cast(&child, value_type)?),
+        _ => Ok(child),
+    }
+}
+
+// Applies the nulls of the enclosing structs (if any) to a column extracted from them. The column
+// is only rebuilt if some parent null is not already a null of the column, which is rarely the case
+// (e.g. readers typically null out the fields of a null struct).
+fn with_parent_nulls(array: &ArrayRef, parent_nulls: Option<&NullBuffer>) -> DeltaResult<ArrayRef> {
+    let Some(parent_nulls) = parent_nulls.filter(|nulls| nulls.null_count() > 0) else {
+        return Ok(array.clone());
+    };
+    if array.data_type() == &ArrowDataType::Null {
+        return Ok(array.clone());
     }
+    // The union has at least the array's nulls, so it has no more iff the array covers the parent's
+    let nulls = NullBuffer::union(Some(parent_nulls), array.nulls());
+    if nulls.as_ref().map(NullBuffer::null_count) == Some(array.null_count()) {
+        return Ok(array.clone());
+    }
+    let data = array.to_data().into_builder().nulls(nulls).build()?;
+    Ok(make_array(data))
 }
 
 /// Evaluates a kernel expression over a record batch
@@ -82,17 +178,28 @@ pub fn evaluate_expression(
     expression: &Expression,
     batch: &RecordBatch,
     result_type: Option<&DataType>,
+) -> DeltaResult<ArrayRef> {
+    evaluate_expression_over(expression, batch, result_type)
+}
+
+// Like [`evaluate_expression`], but over any [`ColumnSource`].
+pub(crate) fn evaluate_expression_over<C: ColumnSource>(
+    expression: &Expression,
+    input: &C,
+    result_type: Option<&DataType>,
 ) -> DeltaResult<ArrayRef> {
     use BinaryExpressionOp::*;
     use Expression::*;
     match (expression, result_type) {
-        (Literal(scalar), _) => Ok(scalar.to_array(batch.num_rows())?),
-        (Column(name), _) => extract_column(batch, name),
+        (Literal(scalar), _) => Ok(scalar.to_array(input.num_rows())?),
+        (Column(name), _) => input.column(name),
         (Struct(fields), Some(DataType::Struct(output_schema))) => {
             let columns = fields
                 .iter()
                 .zip(output_schema.fields())
-                .map(|(expr, field)| evaluate_expression(expr, batch, Some(field.data_type())));
+                .map(|(expr, field)| {
+                    evaluate_expression_over(expr, input, Some(field.data_type()))
+                });
             let output_cols: Vec<ArrayRef> = columns.try_collect()?;
             let output_fields: Vec<ArrowField> = output_cols
                 .iter()
@@ -112,15 +219,15 @@ pub fn evaluate_expression(
             "Data type is required to evaluate struct expressions",
         )),
         (Predicate(pred), None | Some(&DataType::BOOLEAN)) => {
-            let result = evaluate_predicate(pred, batch, false)?;
+            let result = evaluate_predicate_over(pred, input, false)?;
             Ok(Arc::new(result))
         }
         (Predicate(_), Some(data_type)) => Err(Error::generic(format!(
             "Predicate evaluation produces boolean output, but caller expects {data_type:?}"
         ))),
         (Binary(BinaryExpression { op, left, right }), _) => {
-            let left_arr = evaluate_expression(left.as_ref(), batch, None)?;
-            let right_arr = evaluate_expression(right.as_ref(), batch, None)?;
+            let left_arr = evaluate_expression_over(left.as_ref(), input, None)?;
+            let right_arr = evaluate_expression_over(right.as_ref(), input, None)?;
 
             type Operation = fn(&dyn Datum, &dyn Datum) -> Result<ArrayRef, ArrowError>;
             let eval: Operation = match op {
@@ -132,12 +239,36 @@ pub fn evaluate_expression(
 
             Ok(eval(&left_arr, &right_arr)?)
         }
+        (
+            Variadic(VariadicExpression {
+                op: VariadicExpressionOp::Coalesce,
+                exprs,
+            }),
+            _,
+        ) => {
+            // Fill the nulls of the result so far from each input in turn. Inputs are only
+            // evaluated while some row is still null.
+            let mut arrays = exprs
+                .iter()
+                .map(|expr| evaluate_expression_over(expr, input, result_type));
+            let Some(first) = arrays.next() else {
+                return Err(Error::invalid_expression(
+                    "COALESCE requires at least one input",
+                ));
+            };
+            arrays.try_fold(first?, |result, next| {
+                if result.null_count() == 0 {
+                    return Ok(result);
+                }
+                Ok(zip(&is_not_null(&result)?, &result, &next?)?)
+            })
+        }
         (Opaque(OpaqueExpression { op, exprs }), _) => {
             match op
                 .any_ref()
                 .downcast_ref::<ArrowOpaqueExpressionOpAdaptor>()
             {
-                Some(op) => op.eval_expr(exprs, batch, result_type),
+                Some(op) => op.eval_expr(exprs, input.batch(), result_type),
                 None => Err(Error::unsupported(format!(
                     "Unsupported opaque expression: {op:?}"
                 ))),
@@ -147,11 +278,48 @@ pub fn evaluate_expression(
     }
 }
 
+// Evaluates `values IN (list...)` with SQL NULL semantics, widening the values and the list
+// elements to a common type first if needed. See [`Predicate::is_in`].
+fn eval_in_list(values: ArrayRef, list: &ArrayData) -> DeltaResult<BooleanArray> {
+    let values_type = DataType::try_from_arrow(values.data_type())?;
+    let element_type = list.array_type().element_type();
+    let Some(ty) = widest_type(&values_type, element_type) else {
+        return Err(Error::invalid_expression(format!(
+            "Cannot compare {values_type} values with an IN list of {element_type}"
+        )));
+    };
+    let values = match ty == values_type {
+        true => values,
+        false => cast(&values, &ArrowDataType::try_from_kernel(&ty)?)?,
+    };
+    list.elements().try_fold(
+        BooleanArray::from(vec![false; values.len()]),
+        |result, element| {
+            let Some(element) = element.try_widen_to(&ty) else {
+                return Err(Error::internal_error(format!(
+                    "Failed to widen {element} to {ty}"
+                )));
+            };
+            let matches = eq(&values, &ArrowScalar::new(element.to_array(1)?))?;
+            Ok(or_kleene(&result, &matches)?)
+        },
+    )
+}
+
 /// Evaluates a (possibly inverted) kernel predicate over a record batch
 pub fn evaluate_predicate(
     predicate: &Predicate,
     batch: &RecordBatch,
     inverted: bool,
+) -> DeltaResult<BooleanArray> {
+    evaluate_predicate_over(predicate, batch, inverted)
+}
+
+// Like [`evaluate_predicate`], but over any [`ColumnSource`].
+pub(crate) fn evaluate_predicate_over<C: ColumnSource>(
+    predicate: &Predicate,
+    input: &C,
+    inverted: bool,
 ) -> DeltaResult<BooleanArray> {
     use BinaryPredicateOp::*;
     use Predicate::*;
@@ -167,15 +335,15 @@ pub fn evaluate_predicate(
             // Grr -- there's no way to cast an `Arc<dyn Array>` back to its native type, so we
             // can't use `Arc::into_inner` here and must clone instead. At least the inner `Buffer`
             // instances are still cheaply clonable.
-            let arr = evaluate_expression(expr, batch, Some(&DataType::BOOLEAN))?;
+            let arr = evaluate_expression_over(expr, input, Some(&DataType::BOOLEAN))?;
             match arr.as_any().downcast_ref::<BooleanArray>() {
                 Some(arr) => Ok(maybe_inverted(Cow::Borrowed(arr))?),
                 None => Err(Error::generic("expected boolean array")),
             }
         }
-        Not(pred) => evaluate_predicate(pred, batch, !inverted),
+        Not(pred) => evaluate_predicate_over(pred, input, !inverted),
         Unary(UnaryPredicate { op, expr }) => {
-            let arr = evaluate_expression(expr.as_ref(), batch, None)?;
+            let arr = evaluate_expression_over(expr.as_ref(), input, None)?;
             let eval_op_fn = match (op, inverted) {
                 (UnaryPredicateOp::IsNull, false) => is_null,
                 (UnaryPredicateOp::IsNull, true) => is_not_null,
@@ -190,8 +358,8 @@ pub fn evaluate_predicate(
             // TODO: Factor out as a stand-alone function instead of a closure?
             let eval_in = || match (left, right) {
                 (Expression::Literal(_), Expression::Column(_)) => {
-                    let left = evaluate_expression(left, batch, None)?;
-                    let right = evaluate_expression(right, batch, None)?;
+                    let left = evaluate_expression_over(left, input, None)?;
+                    let right = evaluate_expression_over(right, input, None)?;
                     if let Some(string_arr) = left.as_string_opt::<i32>() {
                         if let Some(list_arr) = right.as_list_opt::<i32>() {
                             let result = in_list_utf8(string_arr, list_arr)?;
@@ -235,10 +403,39 @@ pub fn evaluate_predicate(
                     }
                 }
                 (Expression::Literal(lit), Expression::Literal(Scalar::Array(ad))) => {
-                    #[allow(deprecated)]
-                    let exists = ad.array_elements().contains(lit);
+                    // Like a column probe, the literal and the elements compare as their widest type
+                    let (lit_type, element_type) =
+                        (lit.data_type(), ad.array_type().element_type());
+                    let Some(ty) = widest_type(&lit_type, element_type) else {
+                        return Err(Error::invalid_expression(format!(
+                            "Cannot compare {lit_type} values with an IN list of {element_type}"
+                        )));
+                    };
+                    let widen = |value: &Scalar| {
+                        value.try_widen_to(&ty).ok_or_else(|| {
+                            Error::internal_error(format!("Failed to widen {value} to {ty}"))
+                        })
+                    };
+                    let lit = widen(lit)?;
+                    let elements: Vec<_> = ad.elements().map(|e| widen(&e)).try_collect()?;
+
+                    // SQL semantics: TRUE if any element matches. Otherwise NULL if the probe or any
+                    // element is NULL (the comparison is unknown), and FALSE if not.
+                    let exists = if lit.is_null() {
+                        None
+                    } else if elements.contains(&lit) {
+                        Some(true)
+                    } else if elements.iter().any(Scalar::is_null) {
+                        None
+                    } else {
+                        Some(false)
+                    };
                     Ok(BooleanArray::from(vec![exists]))
                 }
+                (_, Expression::Literal(Scalar::Array(ad))) => {
+                    let left = evaluate_expression_over(left, input, None)?;
+                    eval_in_list(left, ad)
+                }
                 (l, r) => Err(Error::invalid_expression(format!(
                     "Invalid right value for (NOT) IN comparison, left is: {l} right is: {r}"
                 ))),
@@ -254,10 +451,13 @@ pub fn evaluate_predicate(
                 (Distinct, false) => distinct,
                 (Distinct, true) => not_distinct,
                 (In, _) => return Ok(maybe_inverted(Cow::Owned(eval_in()?))?),
+                // NOT IN is the (NULL-preserving) inverse of IN, so invert unless already inverted
+                (NotIn, false) => return Ok(not(&eval_in()?)?),
+                (NotIn, true) => return eval_in(),
             };
 
-            let left = evaluate_expression(left, batch, None)?;
-            let right = evaluate_expression(right, batch, None)?;
+            let left = evaluate_expression_over(left, input, None)?;
+            let right = evaluate_expression_over(right, input, None)?;
             Ok(eval_fn(&left, &right)?)
         }
         Junction(JunctionPredicate { op, preds }) => {
@@ -275,13 +475,13 @@ pub fn evaluate_predicate(
             };
             preds
                 .iter()
-                .map(|pred| evaluate_predicate(pred, batch, inverted))
+                .map(|pred| evaluate_predicate_over(pred, input, inverted))
                 .reduce(|l, r| Ok(reducer(&l?, &r?)?))
-                .unwrap_or_else(|| Ok(BooleanArray::from(vec![default; batch.num_rows()])))
+                .unwrap_or_else(|| Ok(BooleanArray::from(vec![default; input.num_rows()])))
         }
         Opaque(OpaquePredicate { op, exprs }) => {
             match op.any_ref().downcast_ref::<ArrowOpaquePredicateOpAdaptor>() {
-                Some(op) => op.eval_pred(exprs, batch, inverted),
+                Some(op) => op.eval_pred(exprs, input.batch(), inverted),
                 None => Err(Error::unsupported(format!(
                     "Unsupported opaque predicate: {op:?}"
                 ))),
@@ -1,15 +1,23 @@
 //! Expression handling based on arrow-rs compute kernels.
 use std::sync::Arc;
 
-use crate::arrow::array::{self, ArrayBuilder, ArrayRef, RecordBatch};
+use crate::arrow::array::{
+    self, Array, ArrayBuilder, ArrayRef, AsArray as _, BooleanArray, RecordBatch,
+};
+use crate::arrow::compute::{cast_with_options, prep_null_mask_filter, CastOptions};
 use crate::arrow::datatypes::{
-    DataType as ArrowDataType, Field as ArrowField, Schema as ArrowSchema,
+    i256, DataType as ArrowDataType, Date32Type, Decimal128Type, Decimal256Type,
+    Field as ArrowField, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    Schema as ArrowSchema, TimestampMicrosecondType,
 };
 
-use super::arrow_conversion::{TryFromKernel as _, TryIntoArrow as _};
+use super::arrow_conversion::{TryFromArrow as _, TryFromKernel as _, TryIntoArrow as _};
 use crate::engine::arrow_data::ArrowEngineData;
 use crate::error::{DeltaResult, Error};
-use crate::expressions::{Expression, Predicate, Scalar};
+use crate::expressions::{
+    ArrayData, DecimalData, DecimalData256, Expression, MapData, Predicate, Scalar, StructData,
+    I256,
+};
 use crate::schema::{DataType, PrimitiveType, SchemaRef};
 use crate::utils::require;
 use crate::{EngineData, EvaluationHandler, ExpressionEvaluator, PredicateEvaluator};
@@ -21,9 +29,12 @@ use apply_schema::{apply_schema, apply_schema_to};
 use evaluate_expression::{evaluate_expression, evaluate_predicate};
 
 mod apply_schema;
+mod compiled;
 pub mod evaluate_expression;
 pub mod opaque;
 
+pub use compiled::CompiledPredicate;
+
 #[cfg(test)]
 mod tests;
 
@@ -72,6 +83,119 @@ impl Scalar {
         Ok(builder.finish())
     }
 
+    /// Convert scalar to a single-element arrow array. See also [`Scalar::from_arrow_array`].
+    pub fn to_arrow_array(&self) -> DeltaResult<ArrayRef> {
+        self.to_array(1)
+    }
+
+    /// Convert the element at `index` of an arrow array to a scalar of the corresponding kernel
+    /// type. Any arrow type that maps to a kernel type is accepted (e.g. `LargeUtf8`, `UInt32` or
+    /// dictionary-encoded arrays), including lists, maps and structs of such types.
+    pub fn from_arrow_array(array: &dyn Array, index: usize) -> DeltaResult<Scalar> {
+        require!(
+            index < array.len(),
+            Error::generic(format!(
+                "Index {index} out of bounds for array of length {}",
+                array.len()
+            ))
+        );
+        let data_type = DataType::try_from_arrow(array.data_type())?;
+        // NOTE: A `NullArray` has no validity buffer, so `is_null` would report its rows as valid.
+        if data_type == DataType::Null || array.is_null(index) {
+            return Ok(Scalar::Null(data_type));
+        }
+        let invalid_array = || {
+            Error::generic(format!(
+                "Unsupported arrow array type {}",
+                array.data_type()
+            ))
+        };
+        let scalar = match data_type {
+            DataType::Struct(ref stype) => {
+                let array = array.as_struct_opt().ok_or_else(invalid_array)?;
+                let values = array
+                    .columns()
+                    .iter()
+                    .map(|column| Self::from_arrow_array(column, index))
+                    .try_collect()?;
+                Scalar::Struct(StructData::try_new(
+                    stype.fields().cloned().collect(),
+                    values,
+                )?)
+            }
+            DataType::Array(ref atype) => {
+                let values = match array.data_type() {
+                    ArrowDataType::List(_) => array.as_list::<i32>().value(index),
+                    ArrowDataType::LargeList(_) => array.as_list::<i64>().value(index),
+                    ArrowDataType::FixedSizeList(..) => array.as_fixed_size_list().value(index),
+                    _ => return Err(invalid_array()),
+                };
+                let elements: Vec<_> = (0..values.len())
+                    .map(|i| Self::from_arrow_array(&values, i))
+                    .try_collect()?;
+                Scalar::Array(ArrayData::try_new(atype.as_ref().clone(), elements)?)
+            }
+            DataType::Map(ref mtype) => {
+                let entries = array.as_map_opt().ok_or_else(invalid_array)?.value(index);
+                let (keys, values) = (entries.column(0), entries.column(1));
+                let pairs: Vec<_> = (0..entries.len())
+                    .map(|i| {
+                        let key = Self::from_arrow_array(keys, i)?;
+                        Ok::<_, Error>((key, Self::from_arrow_array(values, i)?))
+                    })
+                    .try_collect()?;
+                Scalar::Map(MapData::try_new(mtype.as_ref().clone(), pairs)?)
+            }
+            DataType::Primitive(ref ptype) => {
+                // Cast to the canonical arrow type of the kernel type, so we only need to handle
+                // one arrow array type per primitive. Unsafe casts error out instead of silently
+                // producing NULL (e.g. a `UInt64` value that overflows `Int64`).
+                let arrow_type = ArrowDataType::try_from_kernel(&data_type)?;
+                let options = CastOptions {
+                    safe: false,
+                    ..Default::default()
+                };
+                let array = cast_with_options(&array.slice(index, 1), &arrow_type, &options)?;
+                macro_rules! value_as {
+                    ($t:ty) => {
+                        array.as_primitive::<$t>().value(0)
+                    };
+                }
+                match ptype {
+                    PrimitiveType::String => {
+                        Scalar::String(array.as_string::<i32>().value(0).into())
+                    }
+                    PrimitiveType::Long => Scalar::Long(value_as!(Int64Type)),
+                    PrimitiveType::Integer => Scalar::Integer(value_as!(Int32Type)),
+                    PrimitiveType::Short => Scalar::Short(value_as!(Int16Type)),
+                    PrimitiveType::Byte => Scalar::Byte(value_as!(Int8Type)),
+                    PrimitiveType::Float => Scalar::Float(value_as!(Float32Type)),
+                    PrimitiveType::Double => Scalar::Double(value_as!(Float64Type)),
+                    PrimitiveType::Boolean => Scalar::Boolean(array.as_boolean().value(0)),
+                    PrimitiveType::Binary => {
+                        Scalar::Binary(array.as_binary::<i32>().value(0).into())
+                    }
+                    PrimitiveType::Date => Scalar::Date(value_as!(Date32Type)),
+                    PrimitiveType::Timestamp => {
+                        Scalar::Timestamp(value_as!(TimestampMicrosecondType))
+                    }
+                    PrimitiveType::TimestampNtz => {
+                        Scalar::TimestampNtz(value_as!(TimestampMicrosecondType))
+                    }
+                    PrimitiveType::Decimal(dtype) if dtype.requires_256_bits() => {
+                        let bits = I256::from_le_bytes(value_as!(Decimal256Type).to_le_bytes());
+                        Scalar::Decimal256(DecimalData256::try_new(bits, *dtype)?)
+                    }
+                    PrimitiveType::Decimal(dtype) => {
+                        Scalar::Decimal(DecimalData::try_new(value_as!(Decimal128Type), *dtype)?)
+                    }
+                }
+            }
+            DataType::Null => unreachable!("NULL-typed arrays were handled above"),
+        };
+        Ok(scalar)
+    }
+
     // Arrow uses composable "builders" to assemble arrays one row at a time. Each concrete `Array`
     // type has a corresponding concrete `ArrayBuilder` type. For primitive types, the builder just
     // needs to `append` one value per row. For complex types, the builder needs to recursively
@@ -130,6 +254,10 @@ impl Scalar {
             Binary(val) => append_val_as!(array::BinaryBuilder, val),
             // precision and scale were already set at builder construction time
             Decimal(val) => append_val_as!(array::Decimal128Builder, val.bits()),
+            Decimal256(val) => append_val_as!(
+                array::Decimal256Builder,
+                i256::from_le_bytes(val.bits().to_le_bytes())
+            ),
             Struct(data) => {
                 let builder = builder_as!(array::StructBuilder);
                 require!(
@@ -159,8 +287,7 @@ impl Scalar {
             Array(data) => {
                 let builder = builder_as!(array::ListBuilder<Box<dyn ArrayBuilder>>);
                 for _ in 0..num_rows {
-                    #[allow(deprecated)]
-                    for value in data.array_elements() {
+                    for value in data.elements() {
                         value.append_to(builder.values(), 1)?;
                     }
                     builder.append(true);
@@ -220,6 +347,9 @@ impl Scalar {
             }
             DataType::DATE => append_null_as!(array::Date32Builder),
             DataType::BINARY => append_null_as!(array::BinaryBuilder),
+            DataType::Primitive(PrimitiveType::Decimal(dtype)) if dtype.requires_256_bits() => {
+                append_null_as!(array::Decimal256Builder)
+            }
             DataType::Primitive(PrimitiveType::Decimal(_)) => {
                 append_null_as!(array::Decimal128Builder)
             }
@@ -261,6 +391,7 @@ impl Scalar {
                     builder.append(false)?;
                 }
             }
+            DataType::Null => append_null_as!(array::NullBuilder),
         }
         Ok(())
     }
@@ -288,10 +419,7 @@ impl EvaluationHandler for ArrowEvaluationHandler {
         schema: SchemaRef,
         predicate: Predicate,
     ) -> Arc<dyn PredicateEvaluator> {
-        Arc::new(DefaultPredicateEvaluator {
-            input_schema: schema,
-            predicate,
-        })
+        Arc::new(DefaultPredicateEvaluator::new(schema, predicate))
     }
 
     /// Create a single-row array with all-null leaf values. Note that if a nested struct is
@@ -351,6 +479,30 @@ pub struct DefaultPredicateEvaluator {
     predicate: Predicate,
 }
 
+impl DefaultPredicateEvaluator {
+    /// Create a new evaluator for `predicate` over batches with the given `input_schema`.
+    pub fn new(input_schema: SchemaRef, predicate: Predicate) -> Self {
+        Self {
+            input_schema,
+            predicate,
+        }
+    }
+
+    /// Evaluates the predicate over an arrow `batch` to a boolean mask, e.g. to filter rows after
+    /// a scan with [`filter_record_batch`]. Unlike [`PredicateEvaluator::evaluate`], the mask has
+    /// no nulls: rows for which the predicate evaluates to NULL are `false`, as in a SQL `WHERE`
+    /// clause.
+    ///
+    /// [`filter_record_batch`]: crate::arrow::compute::filter_record_batch
+    pub fn evaluate_mask(&self, batch: &RecordBatch) -> DeltaResult<BooleanArray> {
+        let result = evaluate_predicate(&self.predicate, batch, false)?;
+        match result.null_count() {
+            0 => Ok(result),
+            _ => Ok(prep_null_mask_filter(&result)),
+        }
+    }
+}
+
 impl PredicateEvaluator for DefaultPredicateEvaluator {
     fn evaluate(&self, batch: &dyn EngineData) -> DeltaResult<Box<dyn EngineData>> {
         debug!("Arrow evaluator evaluating: {:#?}", self.predicate);
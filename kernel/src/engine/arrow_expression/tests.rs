@@ -1,14 +1,17 @@
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::arrow::array::{
-    create_array, Array, ArrayRef, BooleanArray, GenericStringArray, Int32Array, Int32Builder,
-    ListArray, MapArray, MapBuilder, MapFieldNames, StringBuilder, StructArray,
+    create_array, Array, ArrayRef, BooleanArray, DictionaryArray, GenericStringArray, Int32Array,
+    Int32Builder, Int64Array, Int64Builder, ListArray, MapArray, MapBuilder, MapFieldNames,
+    StringArray, StringBuilder, StructArray,
 };
 use crate::arrow::buffer::{OffsetBuffer, ScalarBuffer};
 use crate::arrow::compute::kernels::cmp::{gt_eq, lt};
-use crate::arrow::datatypes::{DataType, Field, Fields, Schema};
+use crate::arrow::compute::not;
+use crate::arrow::datatypes::{DataType, Field, Fields, Int32Type, Schema};
 
 use super::*;
+use crate::engine::arrow_conversion::TryIntoKernel as _;
 use crate::engine::arrow_expression::opaque::{
     ArrowOpaqueExpression as _, ArrowOpaqueExpressionOp, ArrowOpaquePredicate as _,
     ArrowOpaquePredicateOp,
@@ -114,6 +117,187 @@ fn test_literal_type_array() {
     assert_eq!(result, in_expected);
 }
 
+#[test]
+fn test_not_in_literal_array() {
+    let field = Arc::new(Field::new("item", DataType::Int32, true));
+    let schema = Schema::new([field.clone()]);
+    let batch = RecordBatch::new_empty(Arc::new(schema));
+
+    let array = |values: Vec<Scalar>| {
+        let array_type = ArrayType::new(KernelDataType::INTEGER, true);
+        Scalar::Array(ArrayData::try_new(array_type, values).unwrap())
+    };
+    let not_in = |probe: Scalar, values| {
+        Pred::binary(
+            BinaryPredicateOp::NotIn,
+            Expr::literal(probe),
+            array(values),
+        )
+    };
+    let null = || Scalar::Null(KernelDataType::INTEGER);
+
+    // (probe, list, expected NOT IN result)
+    let test_cases = [
+        // a probe not in the list is TRUE, and a probe in the list is FALSE
+        (Scalar::Integer(5), vec![1.into(), 2.into()], Some(true)),
+        (Scalar::Integer(1), vec![1.into(), 2.into()], Some(false)),
+        // a probe that doesn't match any non-null element might match the NULL element
+        (Scalar::Integer(5), vec![1.into(), null()], None),
+        // ... but a matching probe is FALSE regardless of NULL elements
+        (Scalar::Integer(1), vec![1.into(), null()], Some(false)),
+        // a NULL probe is NULL
+        (null(), vec![1.into(), 2.into()], None),
+    ];
+    for (probe, values, expected) in test_cases {
+        let pred = not_in(probe, values);
+        let result = evaluate_predicate(&pred, &batch, false).unwrap();
+        assert_eq!(result, BooleanArray::from(vec![expected]), "{pred}");
+
+        // inverting NOT IN produces IN, which is NULL whenever NOT IN is
+        let result = evaluate_predicate(&pred, &batch, true).unwrap();
+        let expected = expected.map(|b| !b);
+        assert_eq!(result, BooleanArray::from(vec![expected]), "NOT({pred})");
+    }
+}
+
+#[test]
+fn test_in_literal_array_with_widening() {
+    let field = Arc::new(Field::new("item", DataType::Int32, true));
+    let schema = Schema::new([field.clone()]);
+    let batch = RecordBatch::new_empty(Arc::new(schema));
+
+    let in_list = |probe: Scalar, values: Vec<Scalar>| {
+        let array_type = ArrayType::new(KernelDataType::INTEGER, true);
+        let array = ArrayData::try_new(array_type, values).unwrap();
+        Pred::binary(
+            BinaryPredicateOp::In,
+            Expr::literal(probe),
+            Scalar::Array(array),
+        )
+    };
+    let null = || Scalar::Null(KernelDataType::INTEGER);
+
+    // A LONG probe compared against a list of INTEGER values, (probe, list, expected IN result)
+    let test_cases = [
+        (Scalar::Long(1), vec![1.into(), 2.into()], Some(true)),
+        (Scalar::Long(1), vec![1.into(), null()], Some(true)),
+        (Scalar::Long(5), vec![1.into(), 2.into()], Some(false)),
+        (Scalar::Long(5), vec![1.into(), null()], None),
+        // a DOUBLE probe widens the INTEGER elements instead
+        (Scalar::Double(2.0), vec![1.into(), 2.into()], Some(true)),
+    ];
+    for (probe, values, expected) in test_cases {
+        let pred = in_list(probe, values);
+        let result = evaluate_predicate(&pred, &batch, false).unwrap();
+        assert_eq!(result, BooleanArray::from(vec![expected]), "{pred}");
+    }
+
+    let pred = in_list(Scalar::from("a"), vec![1.into()]);
+    assert!(evaluate_predicate(&pred, &batch, false).is_err());
+}
+
+#[test]
+fn test_not_in_list_column() {
+    let values = Int32Array::from(vec![Some(1), Some(4), None]);
+    let schema = Schema::new(vec![Field::new("x", DataType::Int32, true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(values)]).unwrap();
+
+    let not_in = Pred::not_in(column_expr!("x"), [Scalar::Integer(1), Scalar::Integer(2)]).unwrap();
+    let result = evaluate_predicate(&not_in, &batch, false).unwrap();
+    assert_eq!(
+        result,
+        BooleanArray::from(vec![Some(false), Some(true), None])
+    );
+    let result = evaluate_predicate(&not_in, &batch, true).unwrap();
+    assert_eq!(
+        result,
+        BooleanArray::from(vec![Some(true), Some(false), None])
+    );
+
+    // with a NULL element, values not in the list are NULL rather than TRUE
+    let values = [Scalar::Integer(1), Scalar::Null(KernelDataType::INTEGER)];
+    let not_in = Pred::not_in(column_expr!("x"), values).unwrap();
+    let result = evaluate_predicate(&not_in, &batch, false).unwrap();
+    assert_eq!(result, BooleanArray::from(vec![Some(false), None, None]));
+    let result = evaluate_predicate(&not_in, &batch, true).unwrap();
+    assert_eq!(result, BooleanArray::from(vec![Some(true), None, None]));
+}
+
+#[test]
+fn test_in_list_with_widening() {
+    let values = Int64Array::from(vec![Some(1), Some(4), None]);
+    let schema = Schema::new(vec![Field::new("x", DataType::Int64, true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(values)]).unwrap();
+
+    // `x` is a LONG column, compared against a list of INTEGER values
+    let in_op = Pred::is_in(
+        column_expr!("x"),
+        [Scalar::Integer(1), Scalar::Integer(2), Scalar::Integer(3)],
+    )
+    .unwrap();
+    let result = evaluate_predicate(&in_op, &batch, false).unwrap();
+    assert_eq!(
+        result,
+        BooleanArray::from(vec![Some(true), Some(false), None])
+    );
+    let result = evaluate_predicate(&in_op, &batch, true).unwrap();
+    assert_eq!(
+        result,
+        BooleanArray::from(vec![Some(false), Some(true), None])
+    );
+
+    // A NULL in the list means a non-matching value produces NULL instead of FALSE
+    let in_op = Pred::is_in(
+        column_expr!("x"),
+        [Scalar::Integer(1), Scalar::Null(KernelDataType::INTEGER)],
+    )
+    .unwrap();
+    let result = evaluate_predicate(&in_op, &batch, false).unwrap();
+    assert_eq!(result, BooleanArray::from(vec![Some(true), None, None]));
+
+    // The column is widened instead when the list is wider than the column
+    let in_op = Pred::is_in(column_expr!("x"), [Scalar::Double(4.0)]).unwrap();
+    let result = evaluate_predicate(&in_op, &batch, false).unwrap();
+    assert_eq!(
+        result,
+        BooleanArray::from(vec![Some(false), Some(true), None])
+    );
+
+    let in_op = Pred::is_in(column_expr!("x"), [Scalar::from("a")]).unwrap();
+    assert!(evaluate_predicate(&in_op, &batch, false).is_err());
+}
+
+#[test]
+fn test_not_in_list_with_null() {
+    let values = Int64Array::from(vec![Some(1), Some(2), None]);
+    let schema = Schema::new(vec![Field::new("x", DataType::Int64, true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(values)]).unwrap();
+
+    let list = [Scalar::Integer(1), Scalar::Null(KernelDataType::INTEGER)];
+    let not_in_op = Pred::not_in(column_expr!("x"), list.clone()).unwrap();
+    let not_of_in_op = Pred::not(Pred::is_in(column_expr!("x"), list).unwrap());
+
+    // `x NOT IN (1, NULL)` is never TRUE, and neither is `NOT(x IN (1, NULL))`
+    let expected = BooleanArray::from(vec![Some(false), None, None]);
+    let result = evaluate_predicate(&not_in_op, &batch, false).unwrap();
+    assert_eq!(result, expected);
+    let result = evaluate_predicate(&not_of_in_op, &batch, false).unwrap();
+    assert_eq!(result, expected);
+
+    // Inverting NOT IN produces IN
+    let expected = BooleanArray::from(vec![Some(true), None, None]);
+    let result = evaluate_predicate(&not_in_op, &batch, true).unwrap();
+    assert_eq!(result, expected);
+
+    // Without a NULL in the list, only a NULL input produces NULL
+    let not_in_op = Pred::not_in(column_expr!("x"), [Scalar::Integer(1)]).unwrap();
+    let result = evaluate_predicate(&not_in_op, &batch, false).unwrap();
+    assert_eq!(
+        result,
+        BooleanArray::from(vec![Some(false), Some(true), None])
+    );
+}
+
 #[test]
 fn test_literal_complex_type_array() {
     use crate::arrow::array::{Array as _, AsArray as _};
@@ -307,6 +491,34 @@ fn test_str_arrays() {
     assert_eq!(result, in_expected);
 }
 
+#[test]
+fn test_dictionary_column() {
+    let values: DictionaryArray<Int32Type> = vec![Some("x"), Some("y"), None, Some("x")]
+        .into_iter()
+        .collect();
+    let schema = Schema::new(vec![Field::new("s", values.data_type().clone(), true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(values)]).unwrap();
+
+    // The column is resolved to its (decoded) values
+    let result = evaluate_expression(&column_expr!("s"), &batch, None).unwrap();
+    let expected = StringArray::from(vec![Some("x"), Some("y"), None, Some("x")]);
+    assert_eq!(result.as_ref(), &expected);
+
+    let pred = Pred::eq(column_expr!("s"), Expr::literal("x"));
+    let result = evaluate_predicate(&pred, &batch, false).unwrap();
+    let expected = BooleanArray::from(vec![Some(true), Some(false), None, Some(true)]);
+    assert_eq!(result, expected);
+
+    let pred = Pred::is_in(column_expr!("s"), [Scalar::from("y"), Scalar::from("z")]).unwrap();
+    let result = evaluate_predicate(&pred, &batch, false).unwrap();
+    let expected = BooleanArray::from(vec![Some(false), Some(true), None, Some(false)]);
+    assert_eq!(result, expected);
+
+    let pred = Pred::is_null(column_expr!("s"));
+    let result = evaluate_predicate(&pred, &batch, false).unwrap();
+    assert_eq!(result, BooleanArray::from(vec![false, false, true, false]));
+}
+
 #[test]
 fn test_extract_column() {
     let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
@@ -339,6 +551,96 @@ fn test_extract_column() {
     assert_eq!(results.as_ref(), &values);
 }
 
+#[test]
+fn test_nested_struct_field_comparison() {
+    let zip_field = Arc::new(Field::new("zip", DataType::Utf8, true));
+    let zips = StringArray::from(vec![Some("90210"), Some("10001"), None, Some("90210")]);
+    let address = StructArray::from(vec![(zip_field.clone(), Arc::new(zips) as ArrayRef)]);
+    let schema = Schema::new(vec![Field::new(
+        "address",
+        DataType::Struct(Fields::from(vec![zip_field])),
+        false,
+    )]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(address)]).unwrap();
+
+    let predicate = column_expr!("address.zip").eq(Expr::literal("90210"));
+    let results = evaluate_predicate(&predicate, &batch, false).unwrap();
+    let expected = BooleanArray::from(vec![Some(true), Some(false), None, Some(true)]);
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_is_null_nested_columns() {
+    // Row 0 is a NULL struct (whose child slot still holds a value), row 1 is a struct of NULLs
+    let zip_field = Arc::new(Field::new("zip", DataType::Utf8, true));
+    let zips = StringArray::from(vec![Some("90210"), None, Some("10001")]);
+    let address = StructArray::new(
+        Fields::from(vec![zip_field.clone()]),
+        vec![Arc::new(zips) as ArrayRef],
+        Some(vec![false, true, true].into()),
+    );
+    let item_field = Arc::new(Field::new("item", DataType::Int32, true));
+    let tags = ListArray::new(
+        item_field.clone(),
+        OffsetBuffer::new(ScalarBuffer::from(vec![0, 0, 1, 2])),
+        Arc::new(Int32Array::from(vec![None, Some(1)])),
+        Some(vec![false, true, true].into()),
+    );
+    let mut props = MapBuilder::new(None, StringBuilder::new(), StringBuilder::new());
+    props.append(false).unwrap();
+    props.keys().append_value("k");
+    props.values().append_null();
+    props.append(true).unwrap();
+    props.append(true).unwrap();
+    let props = props.finish();
+    let schema = Schema::new(vec![
+        Field::new("address", address.data_type().clone(), true),
+        Field::new("tags", DataType::List(item_field), true),
+        Field::new("props", props.data_type().clone(), true),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(address), Arc::new(tags), Arc::new(props)],
+    )
+    .unwrap();
+
+    let expected = BooleanArray::from(vec![true, false, false]);
+    for col in [
+        column_expr!("address"),
+        column_expr!("tags"),
+        column_expr!("props"),
+    ] {
+        let results = evaluate_predicate(&col.clone().is_null(), &batch, false).unwrap();
+        assert_eq!(results, expected, "{col:?} IS NULL");
+        let results = evaluate_predicate(&col.clone().is_not_null(), &batch, false).unwrap();
+        assert_eq!(results, not(&expected).unwrap(), "{col:?} IS NOT NULL");
+    }
+
+    // The field of a NULL struct is NULL, even if its child array has a value in that slot
+    let predicate = column_expr!("address.zip").is_null();
+    let results = evaluate_predicate(&predicate, &batch, false).unwrap();
+    assert_eq!(results, BooleanArray::from(vec![true, true, false]));
+    let predicate = column_expr!("address.zip").eq(Expr::literal("90210"));
+    let results = evaluate_predicate(&predicate, &batch, false).unwrap();
+    assert_eq!(results, BooleanArray::from(vec![None, None, Some(false)]));
+
+    // A field whose nulls already cover those of its struct is extracted as is, without a copy
+    let zips: ArrayRef = Arc::new(StringArray::from(vec![None, None, Some("10001")]));
+    let address = StructArray::new(
+        Fields::from(vec![zip_field]),
+        vec![zips.clone()],
+        Some(vec![false, true, true].into()),
+    );
+    let schema = Schema::new(vec![Field::new(
+        "address",
+        address.data_type().clone(),
+        true,
+    )]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(address)]).unwrap();
+    let zip = evaluate_expression(&column_expr!("address.zip"), &batch, None).unwrap();
+    assert!(Arc::ptr_eq(&zip, &zips));
+}
+
 #[test]
 fn test_binary_op_scalar() {
     let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
@@ -399,6 +701,30 @@ fn test_binary_op() {
     assert_eq!(results.as_ref(), expected.as_ref());
 }
 
+#[test]
+fn test_coalesce() {
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Int64, true),
+    ]);
+    let a = Int64Array::from(vec![Some(1), None, None]);
+    let b = Int64Array::from(vec![Some(10), Some(20), None]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)]).unwrap();
+
+    let expression = Expr::coalesce([column_expr!("a"), column_expr!("b")]);
+    let results = evaluate_expression(&expression, &batch, None).unwrap();
+    let expected = Int64Array::from(vec![Some(1), Some(20), None]);
+    assert_eq!(results.as_ref(), &expected);
+
+    let expression = Expr::coalesce([column_expr!("a"), column_expr!("b"), Expr::literal(5i64)]);
+    let results = evaluate_expression(&expression, &batch, None).unwrap();
+    let expected = Int64Array::from(vec![1, 20, 5]);
+    assert_eq!(results.as_ref(), &expected);
+
+    let expression = Expr::coalesce(Vec::<Expr>::new());
+    assert!(evaluate_expression(&expression, &batch, None).is_err());
+}
+
 #[test]
 fn test_binary_cmp() {
     let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
@@ -515,6 +841,60 @@ fn test_logical() {
     assert_eq!(results, expected);
 }
 
+#[test]
+fn test_predicate_evaluator_mask() {
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![
+            Arc::new(Int32Array::from(vec![
+                Some(1),
+                Some(6),
+                Some(7),
+                None,
+                Some(10),
+            ])),
+            Arc::new(StringArray::from(vec![
+                Some("x"),
+                Some("x"),
+                Some("y"),
+                Some("x"),
+                None,
+            ])),
+        ],
+    )
+    .unwrap();
+    let input_schema: SchemaRef = Arc::new(StructType::try_from_arrow(&schema).unwrap());
+    let mask = |predicate| {
+        DefaultPredicateEvaluator::new(input_schema.clone(), predicate)
+            .evaluate_mask(&batch)
+            .unwrap()
+    };
+
+    // rows where either side is NULL are not selected
+    let predicate = Pred::and(
+        Pred::gt(column_expr!("a"), Expr::literal(5)),
+        Pred::eq(column_expr!("b"), Expr::literal("x")),
+    );
+    let expected = BooleanArray::from(vec![false, true, false, false, false]);
+    assert_eq!(mask(predicate), expected);
+
+    let predicate = Pred::or(
+        Pred::not(Pred::gt(
+            column_expr!("a") * Expr::literal(2),
+            Expr::literal(10),
+        )),
+        Pred::is_null(column_expr!("b")),
+    );
+    let expected = BooleanArray::from(vec![true, false, false, false, true]);
+    let result = mask(predicate);
+    assert_eq!(result, expected);
+    assert_eq!(result.null_count(), 0);
+}
+
 #[derive(Debug, PartialEq)]
 struct OpaqueLessThanOp;
 
@@ -885,6 +1265,32 @@ fn test_scalar_map() -> DeltaResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_scalar_map_with_long_keys() -> DeltaResult<()> {
+    // result: { 1: "a", 2: null }
+    let map_type = MapType::new(KernelDataType::LONG, KernelDataType::STRING, true);
+    let map_data = MapData::try_new(map_type, [(1i64, Some("a".to_string())), (2i64, None)])?;
+    let scalar_map = Scalar::Map(map_data);
+    let arrow_array = scalar_map.to_array(1)?;
+    let map_array = arrow_array.as_any().downcast_ref::<MapArray>().unwrap();
+
+    let names = MapFieldNames {
+        entry: "key_values".to_string(),
+        key: "keys".to_string(),
+        value: "values".to_string(),
+    };
+    let mut builder = MapBuilder::new(Some(names), Int64Builder::new(), StringBuilder::new());
+    builder.keys().append_value(1);
+    builder.values().append_value("a");
+    builder.keys().append_value(2);
+    builder.values().append_null();
+    builder.append(true).unwrap();
+    let expected = builder.finish();
+
+    assert_eq!(map_array, &expected);
+    Ok(())
+}
+
 #[test]
 fn test_null_scalar_map() -> DeltaResult<()> {
     let map_type = MapType::new(KernelDataType::STRING, KernelDataType::STRING, false);
@@ -898,3 +1304,189 @@ fn test_null_scalar_map() -> DeltaResult<()> {
 
     Ok(())
 }
+
+// Top-level NULL scalars never compare equal, so compare their types instead.
+fn assert_arrow_array_round_trip(scalar: &Scalar) {
+    let array = scalar.to_arrow_array().unwrap();
+    assert_eq!(array.len(), 1);
+    let round_trip = Scalar::from_arrow_array(&array, 0).unwrap();
+    if scalar.is_null() {
+        assert!(round_trip.is_null());
+        assert_eq!(round_trip.data_type(), scalar.data_type());
+    } else {
+        assert_eq!(&round_trip, scalar);
+    }
+    assert_eq!(&round_trip.to_arrow_array().unwrap(), &array);
+}
+
+#[test]
+fn test_scalar_arrow_array_round_trip() {
+    let decimal =
+        |bits: i128, precision, scale| Scalar::decimal256(bits, precision, scale).unwrap();
+    let primitives = [
+        Scalar::Integer(42),
+        Scalar::Long(-42),
+        Scalar::Short(7),
+        Scalar::Byte(-7),
+        Scalar::Float(1.5),
+        Scalar::Double(-2.25),
+        Scalar::String("hello".into()),
+        Scalar::Boolean(true),
+        Scalar::Binary(vec![0, 1, 2]),
+        Scalar::Date(19000),
+        Scalar::Timestamp(1_700_000_000_000_000),
+        Scalar::TimestampNtz(1_700_000_000_000_000),
+        decimal(12345, 10, 2),
+        decimal(-12345, 50, 4),
+        Scalar::Null(KernelDataType::INTEGER),
+        Scalar::Null(KernelDataType::Null),
+    ];
+    for scalar in &primitives {
+        assert_arrow_array_round_trip(scalar);
+    }
+
+    let array_type = ArrayType::new(KernelDataType::LONG, true);
+    let map_type = MapType::new(KernelDataType::STRING, KernelDataType::INTEGER, true);
+    let inner_fields = vec![
+        StructField::nullable("list", array_type.clone()),
+        StructField::nullable("map", map_type.clone()),
+    ];
+    let fields = vec![
+        StructField::not_null("id", KernelDataType::LONG),
+        StructField::nullable("name", KernelDataType::STRING),
+        StructField::nullable("inner", StructType::new(inner_fields.clone())),
+    ];
+    let inner = StructData::try_new(
+        inner_fields,
+        vec![
+            Scalar::Array(ArrayData::try_new(array_type, [Some(1i64), None, Some(3)]).unwrap()),
+            Scalar::Map(MapData::try_new(map_type, [("a", Some(1)), ("b", None)]).unwrap()),
+        ],
+    )
+    .unwrap();
+    let scalar = Scalar::Struct(
+        StructData::try_new(
+            fields,
+            vec![
+                Scalar::Long(1),
+                Scalar::Null(KernelDataType::STRING),
+                Scalar::Struct(inner),
+            ],
+        )
+        .unwrap(),
+    );
+    assert_arrow_array_round_trip(&scalar);
+}
+
+#[test]
+fn test_scalar_from_arrow_array() {
+    // non-canonical arrow types are converted to the corresponding kernel type
+    let strings = crate::arrow::array::LargeStringArray::from(vec![Some("a"), None]);
+    assert_eq!(
+        Scalar::from_arrow_array(&strings, 0).unwrap(),
+        Scalar::from("a")
+    );
+    let null = Scalar::from_arrow_array(&strings, 1).unwrap();
+    assert!(matches!(null, Scalar::Null(KernelDataType::STRING)));
+    let ints = crate::arrow::array::UInt32Array::from(vec![5, u32::MAX]);
+    assert_eq!(
+        Scalar::from_arrow_array(&ints, 0).unwrap(),
+        Scalar::Integer(5)
+    );
+    // values that don't fit the kernel type are an error, not NULL
+    assert!(Scalar::from_arrow_array(&ints, 1).is_err());
+    assert!(Scalar::from_arrow_array(&ints, 2).is_err());
+}
+
+#[test]
+fn test_compiled_predicate_matches_evaluate_predicate() {
+    let nested_fields = Fields::from(vec![
+        Field::new("x", DataType::Int32, true),
+        Field::new("s", DataType::Utf8, true),
+    ]);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("n", DataType::Struct(nested_fields.clone()), true),
+        Field::new("b", DataType::Int64, true),
+    ]));
+    let kernel_schema: StructType = schema.as_ref().try_into_kernel().unwrap();
+    let batches: Vec<_> = (0..5)
+        .map(|i| {
+            let nested = StructArray::try_new(
+                nested_fields.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![Some(i), None, Some(i * 2), Some(3)])),
+                    Arc::new(StringArray::from(vec![
+                        Some("a"),
+                        Some("b"),
+                        None,
+                        Some("c"),
+                    ])),
+                ],
+                Some(vec![true, true, true, i % 2 == 0].into()),
+            )
+            .unwrap();
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![Some(i), Some(1), None, Some(-i)])),
+                    Arc::new(nested),
+                    Arc::new(Int64Array::from(vec![
+                        Some(10),
+                        None,
+                        Some(i as i64),
+                        Some(2),
+                    ])),
+                ],
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let predicates = [
+        Pred::lt(column_expr!("a"), Expr::literal(2)),
+        Pred::and(
+            Pred::gt(column_expr!("n.x"), column_expr!("a")),
+            Pred::not(Pred::is_null(column_expr!("n.s"))),
+        ),
+        Pred::or_from([
+            Pred::eq(column_expr!("b"), Expr::literal(2i64)),
+            Pred::is_in(column_expr!("n.s"), ["a".into(), "c".into()]).unwrap(),
+            Pred::distinct(column_expr!("a"), column_expr!("n.x")),
+        ]),
+        Pred::not(Pred::lt(
+            Expr::binary(
+                BinaryExpressionOp::Plus,
+                column_expr!("a"),
+                column_expr!("a"),
+            ),
+            column_expr!("n.x"),
+        )),
+        Pred::literal(true),
+    ];
+    for pred in &predicates {
+        let compiled = CompiledPredicate::try_new(pred, &kernel_schema).unwrap();
+        for batch in &batches {
+            let expected = evaluate_predicate(pred, batch, false).unwrap();
+            assert_eq!(compiled.eval(batch).unwrap(), expected, "{pred}");
+        }
+    }
+
+    // columns are resolved at compile time
+    let missing = Pred::eq(column_expr!("n.y"), Expr::literal(1));
+    assert!(CompiledPredicate::try_new(&missing, &kernel_schema).is_err());
+    let not_a_struct = Pred::eq(column_expr!("a.x"), Expr::literal(1));
+    assert!(CompiledPredicate::try_new(&not_a_struct, &kernel_schema).is_err());
+
+    // batches of a different schema are rejected instead of evaluated over the wrong columns
+    let compiled = CompiledPredicate::try_new(&predicates[0], &kernel_schema).unwrap();
+    let reordered = batches[0].project(&[2, 1, 0]).unwrap();
+    assert!(matches!(compiled.eval(&reordered), Err(Error::Schema(_))));
+    let renamed_schema = Arc::new(Schema::new(vec![
+        Field::new("z", DataType::Int32, true),
+        Field::new("n", DataType::Struct(nested_fields), true),
+        Field::new("b", DataType::Int64, true),
+    ]));
+    let renamed = RecordBatch::try_new(renamed_schema, batches[0].columns().to_vec()).unwrap();
+    assert!(matches!(compiled.eval(&renamed), Err(Error::Schema(_))));
+}
@@ -1,21 +1,24 @@
 //! Some utilities for working with arrow data types
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::engine::arrow_conversion::{TryFromKernel as _, TryIntoArrow as _};
 use crate::engine::ensure_data_types::DataTypeCompat;
 use crate::{
     engine::arrow_data::ArrowEngineData,
-    schema::{DataType, Schema, SchemaRef, StructField, StructType},
+    schema::{
+        ColumnMetadataKey, DataType, MetadataValue, Schema, SchemaRef, StructField, StructType,
+    },
     utils::require,
     DeltaResult, EngineData, Error,
 };
 
 use crate::arrow::array::{
-    cast::AsArray, make_array, new_null_array, Array as ArrowArray, GenericListArray, MapArray,
-    OffsetSizeTrait, RecordBatch, StringArray, StructArray,
+    cast::AsArray, make_array, new_null_array, Array as ArrowArray, ArrayRef, GenericListArray,
+    Int64Array, MapArray, OffsetSizeTrait, RecordBatch, StringArray, StructArray,
 };
 use crate::arrow::buffer::NullBuffer;
 use crate::arrow::compute::concat_batches;
@@ -24,7 +27,9 @@ use crate::arrow::datatypes::{
     Schema as ArrowSchema, SchemaRef as ArrowSchemaRef,
 };
 use crate::arrow::json::{LineDelimitedWriter, ReaderBuilder};
-use crate::parquet::{arrow::ProjectionMask, schema::types::SchemaDescriptor};
+use crate::parquet::arrow::{ProjectionMask, PARQUET_FIELD_ID_META_KEY};
+use crate::parquet::file::metadata::RowGroupMetaData;
+use crate::parquet::schema::types::SchemaDescriptor;
 use delta_kernel_derive::internal_api;
 use itertools::Itertools;
 use tracing::debug;
@@ -71,18 +76,116 @@ pub(crate) fn make_arrow_error(s: impl Into<String>) -> Error {
 /// Applies post-processing to data read from parquet files. This includes `reorder_struct_array` to
 /// ensure schema compatibility, as well as `fix_nested_null_masks` to ensure that leaf columns have
 /// accurate null masks that row visitors rely on for correctness.
+///
+/// `row_indexes` yields the row index (within the file) of each row produced by the parquet reader,
+/// and is consumed to fill in any requested row index column (see [`StructField::row_index`]).
 pub(crate) fn fixup_parquet_read<T>(
     batch: RecordBatch,
+    requested_schema: &Schema,
     requested_ordering: &[ReorderIndex],
+    row_indexes: Option<&mut RowIndexIterator>,
 ) -> DeltaResult<T>
 where
     StructArray: Into<T>,
 {
-    let data = reorder_struct_array(batch.into(), requested_ordering)?;
+    let data = reorder_struct_array_impl(batch.into(), requested_ordering, row_indexes)?;
+    let data = rename_to_requested(data, requested_schema)?;
     let data = fix_nested_null_masks(data);
     Ok(data.into())
 }
 
+/// Columns matched by parquet field id (see [`get_requested_indices`]) can have a different name
+/// in the parquet file than in the requested schema. Rename such top-level columns to match the
+/// requested schema, so they can be referenced by name. Nested fields don't need renaming here,
+/// because they are mapped positionally when the data is transformed to its logical schema.
+fn rename_to_requested(data: StructArray, requested_schema: &Schema) -> DeltaResult<StructArray> {
+    let names_match = data
+        .fields()
+        .iter()
+        .zip(requested_schema.fields())
+        .all(|(field, requested_field)| field.name() == requested_field.name());
+    if names_match {
+        return Ok(data);
+    }
+    let (fields, columns, nulls) = data.into_parts();
+    let fields: Fields = fields
+        .iter()
+        .zip(requested_schema.fields())
+        .map(|(field, requested_field)| field.as_ref().clone().with_name(requested_field.name()))
+        .collect();
+    Ok(StructArray::try_new(fields, columns, nulls)?)
+}
+
+/// Iterator over the row indexes of the rows produced by a parquet reader. See [`RowIndexBuilder`].
+pub(crate) type RowIndexIterator = std::iter::Flatten<std::vec::IntoIter<Range<i64>>>;
+
+/// Computes the (file-relative) row index of each row produced by a parquet reader. The parquet
+/// reader returns the rows of each selected row group in order, so the row indexes are the
+/// concatenation of the row index ranges of those row groups. Row groups eliminated by row group
+/// skipping must be reported via [`RowIndexBuilder::select_row_groups`].
+#[derive(Debug, Clone)]
+pub(crate) struct RowIndexBuilder {
+    row_group_ranges: Vec<Range<i64>>,
+    selected_row_groups: Option<Vec<usize>>,
+}
+
+impl RowIndexBuilder {
+    pub(crate) fn new(row_groups: &[RowGroupMetaData]) -> Self {
+        let mut start = 0;
+        let row_group_ranges = row_groups
+            .iter()
+            .map(|row_group| {
+                let end = start + row_group.num_rows();
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect();
+        Self {
+            row_group_ranges,
+            selected_row_groups: None,
+        }
+    }
+
+    /// Only the row groups with the given ordinals (in ascending order) will be read.
+    pub(crate) fn select_row_groups(&mut self, ordinals: &[usize]) {
+        self.selected_row_groups = Some(ordinals.to_vec());
+    }
+
+    pub(crate) fn build(self) -> DeltaResult<RowIndexIterator> {
+        let ranges = match self.selected_row_groups {
+            Some(ordinals) => ordinals
+                .into_iter()
+                .map(|ordinal| {
+                    self.row_group_ranges.get(ordinal).cloned().ok_or_else(|| {
+                        Error::internal_error(format!("Row group ordinal {ordinal} out of bounds"))
+                    })
+                })
+                .try_collect()?,
+            None => self.row_group_ranges,
+        };
+        Ok(ranges.into_iter().flatten())
+    }
+}
+
+// The parquet field id of a field read from parquet, if the file has one.
+fn parquet_field_id(field: &ArrowField) -> Option<i64> {
+    field
+        .metadata()
+        .get(PARQUET_FIELD_ID_META_KEY)?
+        .parse()
+        .ok()
+}
+
+// The parquet field id of a requested field, if it was annotated with one (i.e. for column mapping
+// `id` mode).
+fn requested_field_id(field: &StructField) -> Option<i64> {
+    match field.get_config_value(&ColumnMetadataKey::ParquetFieldId)? {
+        MetadataValue::Number(id) => Some(*id),
+        _ => None,
+    }
+}
+
 /*
 * The code below implements proper pruning of columns when reading parquet, reordering of columns to
 * match the specified schema, and insertion of null columns if the requested schema includes a
@@ -136,6 +239,7 @@ where
 * 6. Additionally, if `ReorderIndex::transform` is not `Identity`, then if it is:
 *      - `Cast`: cast the column to the specified type
 *      - `Missing`: put a column of `null` at the correct location
+*      - `RowIndex`: put a column holding the row index of each row at the correct location
 *      - `Nested([child_order])` and the data is a `StructArray`: recursively call
 *         `reorder_struct_array` on the column with `child_order` to correctly ordered the child
 *         array
@@ -206,6 +310,8 @@ pub(crate) enum ReorderIndexTransform {
     Identity,
     /// Data is missing, fill in with a null column
     Missing(ArrowFieldRef),
+    /// Row index column, fill in with the index of each row within the parquet file
+    RowIndex(ArrowFieldRef),
 }
 
 impl ReorderIndex {
@@ -229,12 +335,18 @@ impl ReorderIndex {
         ReorderIndex::new(index, ReorderIndexTransform::Missing(field))
     }
 
+    fn row_index(index: usize, field: ArrowFieldRef) -> Self {
+        ReorderIndex::new(index, ReorderIndexTransform::RowIndex(field))
+    }
+
     /// Check if this reordering requires a transformation anywhere. See comment below on
     /// [`ordering_needs_transform`] to understand why this is needed.
     fn needs_transform(&self) -> bool {
         match self.transform {
-            // if we're casting or inserting null, we need to transform
-            ReorderIndexTransform::Cast(_) | ReorderIndexTransform::Missing(_) => true,
+            // if we're casting or inserting null or row indexes, we need to transform
+            ReorderIndexTransform::Cast(_)
+            | ReorderIndexTransform::Missing(_)
+            | ReorderIndexTransform::RowIndex(_) => true,
             // if our nested ordering needs a transform, we need a transform
             ReorderIndexTransform::Nested(ref children) => ordering_needs_transform(children),
             // no transform needed
@@ -274,11 +386,26 @@ fn get_indices(
     let mut found_fields = HashSet::with_capacity(requested_schema.fields.len());
     let mut reorder_indices = Vec::with_capacity(requested_schema.fields.len());
     let mut parquet_offset = start_parquet_offset;
+    // if the requested fields are annotated with parquet field ids (i.e. column mapping `id`
+    // mode), parquet fields that have a field id are matched by id rather than by name.
+    let requested_field_ids: HashMap<i64, usize> = requested_schema
+        .fields()
+        .enumerate()
+        .filter_map(|(index, field)| Some((requested_field_id(field)?, index)))
+        .collect();
     // for each field, get its position in the parquet (via enumerate), a reference to the arrow
     // field, and info about where it appears in the requested_schema, or None if the field is not
     // requested
     let all_field_info = fields.iter().enumerate().map(|(parquet_index, field)| {
-        let field_info = requested_schema.fields.get_full(field.name());
+        let field_info = match parquet_field_id(field) {
+            Some(id) if !requested_field_ids.is_empty() => {
+                requested_field_ids.get(&id).and_then(|&index| {
+                    let (name, requested_field) = requested_schema.fields.get_index(index)?;
+                    Some((index, name, requested_field))
+                })
+            }
+            _ => requested_schema.fields.get_full(field.name()),
+        };
         (parquet_index, field, field_info)
     });
     for (parquet_index, field, field_info) in all_field_info {
@@ -438,7 +565,13 @@ fn get_indices(
         // some fields are missing, but they might be nullable, need to insert them into the reorder_indices
         for (requested_position, field) in requested_schema.fields().enumerate() {
             if !found_fields.contains(field.name()) {
-                if field.nullable {
+                if field.is_row_index() {
+                    debug!("Inserting row index field: {}", field.name());
+                    reorder_indices.push(ReorderIndex::row_index(
+                        requested_position,
+                        Arc::new(field.try_into_arrow()?),
+                    ));
+                } else if field.nullable {
                     debug!("Inserting missing and nullable field: {}", field.name());
                     reorder_indices.push(ReorderIndex::missing(
                         requested_position,
@@ -525,6 +658,14 @@ type FieldArrayOpt = Option<(Arc<ArrowField>, Arc<dyn ArrowArray>)>;
 pub(crate) fn reorder_struct_array(
     input_data: StructArray,
     requested_ordering: &[ReorderIndex],
+) -> DeltaResult<StructArray> {
+    reorder_struct_array_impl(input_data, requested_ordering, None)
+}
+
+fn reorder_struct_array_impl(
+    input_data: StructArray,
+    requested_ordering: &[ReorderIndex],
+    mut row_indexes: Option<&mut RowIndexIterator>,
 ) -> DeltaResult<StructArray> {
     debug!("Reordering {input_data:?} with ordering: {requested_ordering:?}");
     if !ordering_needs_transform(requested_ordering) {
@@ -554,42 +695,11 @@ pub(crate) fn reorder_struct_array(
                     final_fields_cols[reorder_index.index] = Some((new_field, col));
                 }
                 ReorderIndexTransform::Nested(children) => {
-                    let input_field_name = input_fields[parquet_position].name();
-                    match input_cols[parquet_position].data_type() {
-                        ArrowDataType::Struct(_) => {
-                            let struct_array = input_cols[parquet_position].as_struct().clone();
-                            let result_array =
-                                Arc::new(reorder_struct_array(struct_array, children)?);
-                            // create the new field specifying the correct order for the struct
-                            let new_field = Arc::new(ArrowField::new_struct(
-                                input_field_name,
-                                result_array.fields().clone(),
-                                input_fields[parquet_position].is_nullable(),
-                            ));
-                            final_fields_cols[reorder_index.index] =
-                                Some((new_field, result_array));
-                        }
-                        ArrowDataType::List(_) => {
-                            let list_array = input_cols[parquet_position].as_list::<i32>().clone();
-                            final_fields_cols[reorder_index.index] =
-                                reorder_list(list_array, input_field_name, children)?;
-                        }
-                        ArrowDataType::LargeList(_) => {
-                            let list_array = input_cols[parquet_position].as_list::<i64>().clone();
-                            final_fields_cols[reorder_index.index] =
-                                reorder_list(list_array, input_field_name, children)?;
-                        }
-                        ArrowDataType::Map(_, _) => {
-                            let map_array = input_cols[parquet_position].as_map().clone();
-                            final_fields_cols[reorder_index.index] =
-                                reorder_map(map_array, input_field_name, children)?;
-                        }
-                        _ => {
-                            return Err(Error::internal_error(
-                                "Nested reorder can only apply to struct/list/map.",
-                            ));
-                        }
-                    }
+                    final_fields_cols[reorder_index.index] = Some(reorder_nested_column(
+                        &input_fields[parquet_position],
+                        &input_cols[parquet_position],
+                        children,
+                    )?);
                 }
                 ReorderIndexTransform::Identity => {
                     final_fields_cols[reorder_index.index] = Some((
@@ -602,6 +712,21 @@ pub(crate) fn reorder_struct_array(
                     let field = field.clone(); // cheap Arc clone
                     final_fields_cols[reorder_index.index] = Some((field, null_array));
                 }
+                ReorderIndexTransform::RowIndex(field) => {
+                    let Some(row_indexes) = row_indexes.as_deref_mut() else {
+                        return Err(Error::internal_error(
+                            "Row index column requested but no row indexes are available",
+                        ));
+                    };
+                    let row_index_array = Int64Array::from_iter_values(row_indexes.take(num_rows));
+                    require!(
+                        row_index_array.len() == num_rows,
+                        Error::internal_error("Ran out of row indexes for parquet data")
+                    );
+                    let field = field.clone(); // cheap Arc clone
+                    final_fields_cols[reorder_index.index] =
+                        Some((field, Arc::new(row_index_array)));
+                }
             }
         }
         let num_cols = final_fields_cols.len();
@@ -619,44 +744,63 @@ pub(crate) fn reorder_struct_array(
     }
 }
 
+// Applies a nested reorder to a struct, list or map column, returning the reordered column along
+// with its new field. The children of a list column apply to its elements, which can themselves be
+// of any nested type (e.g. a list of maps).
+fn reorder_nested_column(
+    field: &ArrowField,
+    column: &ArrayRef,
+    children: &[ReorderIndex],
+) -> DeltaResult<(ArrowFieldRef, ArrayRef)> {
+    match column.data_type() {
+        ArrowDataType::Struct(_) => {
+            let struct_array = column.as_struct().clone();
+            let result_array = Arc::new(reorder_struct_array(struct_array, children)?);
+            // create the new field specifying the correct order for the struct
+            let new_field = Arc::new(ArrowField::new_struct(
+                field.name(),
+                result_array.fields().clone(),
+                field.is_nullable(),
+            ));
+            Ok((new_field, result_array))
+        }
+        ArrowDataType::List(_) => reorder_list(column.as_list::<i32>().clone(), field, children),
+        ArrowDataType::LargeList(_) => {
+            reorder_list(column.as_list::<i64>().clone(), field, children)
+        }
+        ArrowDataType::Map(_, _) => reorder_map(column.as_map().clone(), field, children),
+        _ => Err(Error::internal_error(
+            "Nested reorder can only apply to struct/list/map.",
+        )),
+    }
+}
+
 fn reorder_list<O: OffsetSizeTrait>(
     list_array: GenericListArray<O>,
-    input_field_name: &str,
+    input_field: &ArrowField,
     children: &[ReorderIndex],
-) -> DeltaResult<FieldArrayOpt> {
-    let (list_field, offset_buffer, maybe_sa, null_buf) = list_array.into_parts();
-    if let Some(struct_array) = maybe_sa.as_struct_opt() {
-        let struct_array = struct_array.clone();
-        let result_array = Arc::new(reorder_struct_array(struct_array, children)?);
-        let new_list_field = Arc::new(ArrowField::new_struct(
-            list_field.name(),
-            result_array.fields().clone(),
-            result_array.is_nullable(),
-        ));
-        let new_field = Arc::new(ArrowField::new_list(
-            input_field_name,
-            new_list_field.clone(),
-            list_field.is_nullable(),
-        ));
-        let list = Arc::new(GenericListArray::try_new(
-            new_list_field,
-            offset_buffer,
-            result_array,
-            null_buf,
-        )?);
-        Ok(Some((new_field, list)))
-    } else {
-        Err(Error::internal_error(
-            "Nested reorder of list should have had struct child.",
-        ))
-    }
+) -> DeltaResult<(ArrowFieldRef, ArrayRef)> {
+    let (list_field, offset_buffer, values, null_buf) = list_array.into_parts();
+    let (new_list_field, result_array) = reorder_nested_column(&list_field, &values, children)?;
+    let new_field = Arc::new(ArrowField::new_list(
+        input_field.name(),
+        new_list_field.clone(),
+        input_field.is_nullable(),
+    ));
+    let list = Arc::new(GenericListArray::try_new(
+        new_list_field,
+        offset_buffer,
+        result_array,
+        null_buf,
+    )?);
+    Ok((new_field, list))
 }
 
 fn reorder_map(
     map_array: MapArray,
-    input_field_name: &str,
+    input_field: &ArrowField,
     children: &[ReorderIndex],
-) -> DeltaResult<FieldArrayOpt> {
+) -> DeltaResult<(ArrowFieldRef, ArrayRef)> {
     let (map_field, offset_buffer, struct_array, null_buf, ordered) = map_array.into_parts();
     let result_array = reorder_struct_array(struct_array, children)?;
     let result_fields = result_array.fields();
@@ -668,12 +812,12 @@ fn reorder_map(
     let key_field = result_fields[0].clone();
     let val_field = result_fields[1].clone();
     let new_field = Arc::new(ArrowField::new_map(
-        input_field_name,
+        input_field.name(),
         map_field.name(),
         key_field,
         val_field,
         ordered,
-        map_field.is_nullable(),
+        input_field.is_nullable(),
     ));
     let map = Arc::new(MapArray::try_new(
         new_map_field,
@@ -682,7 +826,7 @@ fn reorder_map(
         null_buf,
         ordered,
     )?);
-    Ok(Some((new_field, map)))
+    Ok((new_field, map))
 }
 
 /// Use this function to recursively compute properly unioned null masks for all nested
@@ -1004,6 +1148,69 @@ mod tests {
         assert_eq!(reorder_indices, expect_reorder);
     }
 
+    #[test]
+    fn row_index_field() {
+        let requested_schema = Arc::new(StructType::new([
+            StructField::row_index("row_index"),
+            StructField::not_null("i", DataType::INTEGER),
+        ]));
+        let parquet_schema = Arc::new(ArrowSchema::new(vec![ArrowField::new(
+            "i",
+            ArrowDataType::Int32,
+            false,
+        )]));
+        let (mask_indices, reorder_indices) =
+            get_requested_indices(&requested_schema, &parquet_schema).unwrap();
+        assert_eq!(mask_indices, vec![0]);
+        let row_index_field = requested_schema
+            .field("row_index")
+            .unwrap()
+            .try_into_arrow()
+            .unwrap();
+        let expect_reorder = vec![
+            ReorderIndex::identity(1),
+            ReorderIndex::row_index(0, Arc::new(row_index_field)),
+        ];
+        assert_eq!(reorder_indices, expect_reorder);
+
+        // the row indexes continue across batches, and skip over unselected row groups
+        let mut row_indexes = RowIndexBuilder {
+            row_group_ranges: vec![0..2, 2..5, 5..7],
+            selected_row_groups: None,
+        };
+        row_indexes.select_row_groups(&[0, 2]);
+        let mut row_indexes = row_indexes.build().unwrap();
+        let batch = |values: Vec<i32>| {
+            let schema = Arc::new(ArrowSchema::new(vec![ArrowField::new(
+                "i",
+                ArrowDataType::Int32,
+                false,
+            )]));
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(values))]).unwrap()
+        };
+        for (values, expected) in [(vec![1, 2, 3], vec![0, 1, 5]), (vec![4], vec![6])] {
+            let result: StructArray = fixup_parquet_read(
+                batch(values.clone()),
+                &requested_schema,
+                &reorder_indices,
+                Some(&mut row_indexes),
+            )
+            .unwrap();
+            assert_eq!(result.column_names(), vec!["row_index", "i"]);
+            assert_eq!(result.column(0).as_ref(), &Int64Array::from(expected));
+            assert_eq!(result.column(1).as_ref(), &Int32Array::from(values));
+        }
+
+        // running out of row indexes is an error
+        let result: DeltaResult<StructArray> = fixup_parquet_read(
+            batch(vec![5]),
+            &requested_schema,
+            &reorder_indices,
+            Some(&mut row_indexes),
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn nested_indices() {
         let requested_schema = Arc::new(StructType::new([
@@ -7,7 +7,7 @@ use itertools::Itertools;
 use url::Url;
 
 use crate::object_store::path::Path;
-use crate::object_store::{DynObjectStore, ObjectStore};
+use crate::object_store::{DynObjectStore, ObjectStore, PutMode};
 
 use super::UrlExt;
 use crate::engine::default::executor::TaskExecutor;
@@ -183,6 +183,26 @@ impl<E: TaskExecutor> StorageHandler for ObjectStoreStorageHandler<E> {
 
         Ok(Box::new(receiver.into_iter()))
     }
+
+    fn write_file(&self, path: &Url, data: Bytes, overwrite: bool) -> DeltaResult<()> {
+        let put_mode = if overwrite {
+            PutMode::Overwrite
+        } else {
+            PutMode::Create
+        };
+        let store = self.inner.clone();
+        let path_str = path.to_string();
+        let path = Path::from_url_path(path.path())?;
+        self.task_executor
+            .block_on(async move { store.put_opts(&path, data.into(), put_mode.into()).await })
+            .map_err(|e| match e {
+                crate::object_store::Error::AlreadyExists { .. } => {
+                    Error::FileAlreadyExists(path_str)
+                }
+                e => e.into(),
+            })?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -246,6 +266,28 @@ mod tests {
         assert_eq!(data[2], Bytes::from("el-da"));
     }
 
+    #[test]
+    fn test_write_file() {
+        let store = Arc::new(InMemory::new());
+        let executor = Arc::new(TokioBackgroundExecutor::new());
+        let storage = ObjectStoreStorageHandler::new(store, executor);
+        let url = Url::parse("memory:///dv.bin").unwrap();
+
+        storage
+            .write_file(&url, Bytes::from("kernel"), false)
+            .unwrap();
+        let res = storage.write_file(&url, Bytes::from("data"), false);
+        assert!(matches!(res, Err(Error::FileAlreadyExists(_))));
+
+        storage.write_file(&url, Bytes::from("data"), true).unwrap();
+        let data: Vec<Bytes> = storage
+            .read_files(vec![(url, None)])
+            .unwrap()
+            .try_collect()
+            .unwrap();
+        assert_eq!(data, vec![Bytes::from("data")]);
+    }
+
     #[tokio::test]
     async fn test_file_meta_is_correct() {
         let store = Arc::new(InMemory::new());
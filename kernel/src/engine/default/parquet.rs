@@ -6,34 +6,54 @@ use std::sync::Arc;
 
 use crate::arrow::array::builder::{MapBuilder, MapFieldNames, StringBuilder};
 use crate::arrow::array::{BooleanArray, Int64Array, RecordBatch, StringArray};
+use crate::object_store::coalesce_ranges;
 use crate::object_store::path::Path;
 use crate::object_store::DynObjectStore;
 use crate::parquet::arrow::arrow_reader::{
     ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
 };
 use crate::parquet::arrow::arrow_writer::ArrowWriter;
-use crate::parquet::arrow::async_reader::{ParquetObjectReader, ParquetRecordBatchStreamBuilder};
-use futures::StreamExt;
+use crate::parquet::arrow::async_reader::{
+    AsyncFileReader, ParquetObjectReader, ParquetRecordBatchStreamBuilder,
+};
+use crate::parquet::errors::{ParquetError, Result as ParquetResult};
+use crate::parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
+use crate::parquet::file::FOOTER_SIZE;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::{StreamExt, TryStreamExt};
+use url::Url;
 use uuid::Uuid;
 
 use super::file_stream::{FileOpenFuture, FileOpener, FileStream};
 use super::UrlExt;
 use crate::engine::arrow_conversion::TryIntoArrow as _;
 use crate::engine::arrow_data::ArrowEngineData;
-use crate::engine::arrow_utils::{fixup_parquet_read, generate_mask, get_requested_indices};
+use crate::engine::arrow_utils::{
+    fixup_parquet_read, generate_mask, get_requested_indices, RowIndexBuilder,
+};
 use crate::engine::default::executor::TaskExecutor;
-use crate::engine::parquet_row_group_skipping::ParquetRowGroupSkipping;
+use crate::engine::parquet_row_group_skipping::{parquet_file_metadata, ParquetRowGroupSkipping};
 use crate::schema::SchemaRef;
 use crate::{
-    DeltaResult, EngineData, Error, FileDataReadResultIterator, FileMeta, ParquetHandler,
-    PredicateRef,
+    DeltaResult, EngineData, Error, FileDataReadResultIterator, FileMeta, ParquetFileMetadata,
+    ParquetHandler, PredicateRef,
 };
 
+/// Default for [`DefaultParquetHandler::with_coalesce_size`], same as the object store default.
+const DEFAULT_COALESCE_SIZE: usize = 1024 * 1024;
+
+/// Number of bytes fetched from the end of a parquet file at a presigned URL to read its footer.
+/// Footers that are larger than this take a second request.
+const PRESIGNED_FOOTER_READ_SIZE: usize = 64 * 1024;
+
 #[derive(Debug)]
 pub struct DefaultParquetHandler<E: TaskExecutor> {
     store: Arc<DynObjectStore>,
     task_executor: Arc<E>,
     readahead: usize,
+    coalesce_size: usize,
+    client: reqwest::Client,
 }
 
 /// Metadata of a data file (typically a parquet file), currently just includes the file metadata
@@ -100,6 +120,8 @@ impl<E: TaskExecutor> DefaultParquetHandler<E> {
             store,
             task_executor,
             readahead: 10,
+            coalesce_size: DEFAULT_COALESCE_SIZE,
+            client: reqwest::Client::new(),
         }
     }
 
@@ -111,6 +133,17 @@ impl<E: TaskExecutor> DefaultParquetHandler<E> {
         self
     }
 
+    /// Maximum gap in bytes between two byte ranges of a parquet file for them to be merged into a
+    /// single range request while executing [Self::read_parquet_files()]. Larger values issue
+    /// fewer (but larger) requests, which helps on high-latency object stores. Together with
+    /// [Self::with_readahead()], this controls how much data is buffered ahead of the consumer.
+    ///
+    /// Defaults to 1MiB.
+    pub fn with_coalesce_size(mut self, coalesce_size: usize) -> Self {
+        self.coalesce_size = coalesce_size;
+        self
+    }
+
     // Write `data` to `{path}/<uuid>.parquet` as parquet using ArrowWriter and return the parquet
     // metadata (where `<uuid>` is a generated UUIDv4).
     //
@@ -205,14 +238,13 @@ impl<E: TaskExecutor> ParquetHandler for DefaultParquetHandler<E> {
                 1024,
                 physical_schema.clone(),
                 predicate,
+                self.client.clone(),
             ))
         } else {
-            Box::new(ParquetOpener::new(
-                1024,
-                physical_schema.clone(),
-                predicate,
-                self.store.clone(),
-            ))
+            Box::new(
+                ParquetOpener::new(1024, physical_schema.clone(), predicate, self.store.clone())
+                    .with_coalesce_size(self.coalesce_size),
+            )
         };
         FileStream::new_async_read_iterator(
             self.task_executor.clone(),
@@ -222,6 +254,177 @@ impl<E: TaskExecutor> ParquetHandler for DefaultParquetHandler<E> {
             self.readahead,
         )
     }
+
+    fn read_parquet_metadata(&self, files: &[FileMeta]) -> DeltaResult<Vec<ParquetFileMetadata>> {
+        let store = self.store.clone();
+        let client = self.client.clone();
+        let files = files.to_vec();
+        let readahead = self.readahead.max(1);
+        self.task_executor.block_on(async move {
+            futures::stream::iter(files)
+                .map(|file| {
+                    let store = store.clone();
+                    let client = client.clone();
+                    async move {
+                        if file.location.is_presigned() {
+                            let metadata = read_presigned_footer(&client, &file.location).await?;
+                            return Ok(parquet_file_metadata(file, &metadata));
+                        }
+                        let path = Path::from_url_path(file.location.path())?;
+                        let mut reader = object_store_reader(store, path, &file).await?;
+                        let metadata =
+                            ArrowReaderMetadata::load_async(&mut reader, Default::default())
+                                .await?;
+                        Ok(parquet_file_metadata(file, metadata.metadata()))
+                    }
+                })
+                .buffered(readahead)
+                .try_collect()
+                .await
+        })
+    }
+}
+
+/// Reads the footer of the parquet file at the presigned `url` with suffix range requests, so that
+/// the rest of the file is not downloaded.
+async fn read_presigned_footer(
+    client: &reqwest::Client,
+    url: &Url,
+) -> DeltaResult<ParquetMetaData> {
+    read_footer_with_suffix_requests(url, |len: usize| async move {
+        let response = client
+            .get(url.clone())
+            .header(reqwest::header::RANGE, format!("bytes=-{len}"))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(response.bytes().await?)
+    })
+    .await
+}
+
+/// Reads the footer of the parquet file at `url`, where `get_suffix(len)` fetches the last `len`
+/// bytes of the file. Footers larger than [`PRESIGNED_FOOTER_READ_SIZE`] take a second request.
+async fn read_footer_with_suffix_requests<F, Fut>(
+    url: &Url,
+    get_suffix: F,
+) -> DeltaResult<ParquetMetaData>
+where
+    F: Fn(usize) -> Fut,
+    Fut: std::future::Future<Output = DeltaResult<Bytes>>,
+{
+    let footer_size = |bytes: &Bytes| -> DeltaResult<usize> {
+        let tail = bytes
+            .len()
+            .checked_sub(FOOTER_SIZE)
+            .and_then(|start| <&[u8; FOOTER_SIZE]>::try_from(&bytes[start..]).ok())
+            .ok_or_else(|| Error::generic(format!("Parquet file {url} is too small")))?;
+        Ok(ParquetMetaDataReader::decode_footer_tail(tail)?.metadata_length() + FOOTER_SIZE)
+    };
+
+    let mut suffix = get_suffix(PRESIGNED_FOOTER_READ_SIZE).await?;
+    let footer_size = footer_size(&suffix)?;
+    if suffix.len() < footer_size {
+        suffix = get_suffix(footer_size).await?;
+    }
+    let metadata = suffix
+        .len()
+        .checked_sub(footer_size)
+        .map(|start| &suffix[start..suffix.len() - FOOTER_SIZE])
+        .ok_or_else(|| Error::generic(format!("Parquet file {url} has a truncated footer")))?;
+    Ok(ParquetMetaDataReader::decode_metadata(metadata)?)
+}
+
+/// Creates a [`ParquetObjectReader`] for the parquet file at `path` in `store`.
+#[cfg_attr(not(feature = "arrow-55"), allow(unused_variables))]
+async fn object_store_reader(
+    store: Arc<DynObjectStore>,
+    path: Path,
+    file_meta: &FileMeta,
+) -> DeltaResult<ParquetObjectReader> {
+    #[cfg(feature = "arrow-55")]
+    let reader = {
+        use crate::object_store::ObjectStoreScheme;
+        // HACK: unfortunately, `ParquetObjectReader` under the hood does a suffix range
+        // request which isn't supported by Azure. For now we just detect if the URL is
+        // pointing to azure and if so, do a HEAD request so we can pass in file size to the
+        // reader which will cause the reader to avoid a suffix range request.
+        // see also: https://github.com/delta-io/delta-kernel-rs/issues/968
+        //
+        // TODO(#1010): Note that we don't need this at all and can actually just _always_
+        // do the `with_file_size` but need to (1) update our unit tests which often
+        // hardcode size=0 and (2) update CDF execute which also hardcodes size=0.
+        if let Ok((ObjectStoreScheme::MicrosoftAzure, _)) =
+            ObjectStoreScheme::parse(&file_meta.location)
+        {
+            // also note doing HEAD then actual GET isn't atomic, and leaves us vulnerable
+            // to file changing between the two calls.
+            let meta = store.head(&path).await?;
+            ParquetObjectReader::new(store, path).with_file_size(meta.size)
+        } else {
+            ParquetObjectReader::new(store, path)
+        }
+    };
+    #[cfg(all(feature = "arrow-54", not(feature = "arrow-55")))]
+    let reader = {
+        // TODO avoid IO by converting passed file meta to ObjectMeta (no longer an issue
+        // in arrow 55)
+        let meta = store.head(&path).await?;
+        ParquetObjectReader::new(store, meta)
+    };
+    Ok(reader)
+}
+
+/// Wraps a [`ParquetObjectReader`] so that the byte ranges requested for a batch of column chunks
+/// are merged into range requests using a configurable coalesce size, instead of the fixed default
+/// used by [`ObjectStore::get_ranges`].
+///
+/// [`ObjectStore::get_ranges`]: crate::object_store::ObjectStore::get_ranges
+struct CoalescingReader {
+    inner: ParquetObjectReader,
+    store: Arc<DynObjectStore>,
+    path: Path,
+    coalesce_size: usize,
+}
+
+#[cfg(feature = "arrow-55")]
+type ByteRange = Range<u64>;
+#[cfg(all(feature = "arrow-54", not(feature = "arrow-55")))]
+type ByteRange = Range<usize>;
+
+impl AsyncFileReader for CoalescingReader {
+    fn get_bytes(&mut self, range: ByteRange) -> BoxFuture<'_, ParquetResult<Bytes>> {
+        self.inner.get_bytes(range)
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<ByteRange>,
+    ) -> BoxFuture<'_, ParquetResult<Vec<Bytes>>> {
+        #[cfg(feature = "arrow-55")]
+        let coalesce_size = self.coalesce_size as u64;
+        #[cfg(not(feature = "arrow-55"))]
+        let coalesce_size = self.coalesce_size;
+        Box::pin(async move {
+            let (store, path) = (&self.store, &self.path);
+            coalesce_ranges(&ranges, |range| store.get_range(path, range), coalesce_size)
+                .await
+                .map_err(ParquetError::from)
+        })
+    }
+
+    #[cfg(feature = "arrow-55")]
+    fn get_metadata<'a>(
+        &'a mut self,
+        options: Option<&'a ArrowReaderOptions>,
+    ) -> BoxFuture<'a, ParquetResult<Arc<ParquetMetaData>>> {
+        self.inner.get_metadata(options)
+    }
+
+    #[cfg(all(feature = "arrow-54", not(feature = "arrow-55")))]
+    fn get_metadata(&mut self) -> BoxFuture<'_, ParquetResult<Arc<ParquetMetaData>>> {
+        self.inner.get_metadata()
+    }
 }
 
 /// Implements [`FileOpener`] for a parquet file
@@ -232,6 +435,7 @@ struct ParquetOpener {
     predicate: Option<PredicateRef>,
     limit: Option<usize>,
     store: Arc<DynObjectStore>,
+    coalesce_size: usize,
 }
 
 impl ParquetOpener {
@@ -247,8 +451,14 @@ impl ParquetOpener {
             predicate,
             limit: None,
             store,
+            coalesce_size: DEFAULT_COALESCE_SIZE,
         }
     }
+
+    pub(crate) fn with_coalesce_size(mut self, coalesce_size: usize) -> Self {
+        self.coalesce_size = coalesce_size;
+        self
+    }
 }
 
 impl FileOpener for ParquetOpener {
@@ -261,37 +471,15 @@ impl FileOpener for ParquetOpener {
         let table_schema = self.table_schema.clone();
         let predicate = self.predicate.clone();
         let limit = self.limit;
+        let coalesce_size = self.coalesce_size;
 
         Ok(Box::pin(async move {
-            #[cfg(feature = "arrow-55")]
-            let mut reader = {
-                use crate::object_store::ObjectStoreScheme;
-                // HACK: unfortunately, `ParquetObjectReader` under the hood does a suffix range
-                // request which isn't supported by Azure. For now we just detect if the URL is
-                // pointing to azure and if so, do a HEAD request so we can pass in file size to the
-                // reader which will cause the reader to avoid a suffix range request.
-                // see also: https://github.com/delta-io/delta-kernel-rs/issues/968
-                //
-                // TODO(#1010): Note that we don't need this at all and can actually just _always_
-                // do the `with_file_size` but need to (1) update our unit tests which often
-                // hardcode size=0 and (2) update CDF execute which also hardcodes size=0.
-                if let Ok((ObjectStoreScheme::MicrosoftAzure, _)) =
-                    ObjectStoreScheme::parse(&file_meta.location)
-                {
-                    // also note doing HEAD then actual GET isn't atomic, and leaves us vulnerable
-                    // to file changing between the two calls.
-                    let meta = store.head(&path).await?;
-                    ParquetObjectReader::new(store, path).with_file_size(meta.size)
-                } else {
-                    ParquetObjectReader::new(store, path)
-                }
-            };
-            #[cfg(all(feature = "arrow-54", not(feature = "arrow-55")))]
-            let mut reader = {
-                // TODO avoid IO by converting passed file meta to ObjectMeta (no longer an issue
-                // in arrow 55)
-                let meta = store.head(&path).await?;
-                ParquetObjectReader::new(store, meta)
+            let reader = object_store_reader(store.clone(), path.clone(), &file_meta).await?;
+            let mut reader = CoalescingReader {
+                inner: reader,
+                store,
+                path,
+                coalesce_size,
             };
             let metadata = ArrowReaderMetadata::load_async(&mut reader, Default::default()).await?;
             let parquet_schema = metadata.schema();
@@ -309,16 +497,25 @@ impl FileOpener for ParquetOpener {
                 builder = builder.with_projection(mask)
             }
 
+            let mut row_indexes = RowIndexBuilder::new(builder.metadata().row_groups());
             if let Some(ref predicate) = predicate {
-                builder = builder.with_row_group_filter(predicate);
+                builder = builder.with_row_group_filter(predicate, Some(&mut row_indexes));
             }
             if let Some(limit) = limit {
                 builder = builder.with_limit(limit)
             }
+            let mut row_indexes = row_indexes.build()?;
 
             let stream = builder.with_batch_size(batch_size).build()?;
 
-            let stream = stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering));
+            let stream = stream.map(move |rbr| {
+                fixup_parquet_read(
+                    rbr?,
+                    &table_schema,
+                    &requested_ordering,
+                    Some(&mut row_indexes),
+                )
+            });
             Ok(stream.boxed())
         }))
     }
@@ -338,13 +535,14 @@ impl PresignedUrlOpener {
         batch_size: usize,
         schema: SchemaRef,
         predicate: Option<PredicateRef>,
+        client: reqwest::Client,
     ) -> Self {
         Self {
             batch_size,
             table_schema: schema,
             predicate,
             limit: None,
-            client: reqwest::Client::new(),
+            client,
         }
     }
 }
@@ -377,17 +575,26 @@ impl FileOpener for PresignedUrlOpener {
                 builder = builder.with_projection(mask)
             }
 
+            let mut row_indexes = RowIndexBuilder::new(builder.metadata().row_groups());
             if let Some(ref predicate) = predicate {
-                builder = builder.with_row_group_filter(predicate);
+                builder = builder.with_row_group_filter(predicate, Some(&mut row_indexes));
             }
             if let Some(limit) = limit {
                 builder = builder.with_limit(limit)
             }
+            let mut row_indexes = row_indexes.build()?;
 
             let reader = builder.with_batch_size(batch_size).build()?;
 
             let stream = futures::stream::iter(reader);
-            let stream = stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering));
+            let stream = stream.map(move |rbr| {
+                fixup_parquet_read(
+                    rbr?,
+                    &table_schema,
+                    &requested_ordering,
+                    Some(&mut row_indexes),
+                )
+            });
             Ok(stream.boxed())
         }))
     }
@@ -399,12 +606,24 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     use crate::arrow::array::{Array, RecordBatch};
-    use crate::object_store::{local::LocalFileSystem, memory::InMemory, ObjectStore};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[cfg(feature = "arrow-55")]
+    use crate::object_store::PutMultipartOptions;
+    #[cfg(not(feature = "arrow-55"))]
+    use crate::object_store::PutMultipartOpts as PutMultipartOptions;
+    use crate::object_store::{
+        self, local::LocalFileSystem, memory::InMemory, GetOptions, GetRange, GetResult,
+        ListResult, MultipartUpload, ObjectMeta, ObjectStore, PutOptions, PutPayload, PutResult,
+    };
+    use futures::stream::BoxStream;
     use url::Url;
 
     use crate::engine::arrow_conversion::TryIntoKernel as _;
     use crate::engine::arrow_data::ArrowEngineData;
     use crate::engine::default::executor::tokio::TokioBackgroundExecutor;
+    use crate::expressions::{ColumnName, Scalar};
     use crate::EngineData;
 
     use itertools::Itertools;
@@ -462,6 +681,64 @@ mod tests {
         assert_eq!(data[0].num_rows(), 10);
     }
 
+    #[test]
+    fn test_read_parquet_metadata() {
+        let path = std::fs::canonicalize(PathBuf::from(
+            "./tests/data/table-with-dv-small/part-00000-fae5310a-a37d-4e51-827b-c3d5516560ca-c000.snappy.parquet"
+        )).unwrap();
+        let url = Url::from_file_path(path).unwrap();
+        let file = FileMeta::new(url, 0, 0);
+
+        let handler = DefaultParquetHandler::new(
+            Arc::new(LocalFileSystem::new()),
+            Arc::new(TokioBackgroundExecutor::new()),
+        );
+        let metadata = handler
+            .read_parquet_metadata(std::slice::from_ref(&file))
+            .unwrap();
+
+        assert_eq!(metadata.len(), 1);
+        let metadata = &metadata[0];
+        assert_eq!(metadata.file, file);
+        assert_eq!(metadata.num_rows, 10);
+        assert_eq!(metadata.row_groups.len(), 1);
+        let row_group = &metadata.row_groups[0];
+        assert_eq!(row_group.num_rows, 10);
+        assert_eq!(row_group.first_row_index, 0);
+        assert_eq!(row_group.column_stats.len(), 1);
+        let stats = &row_group.column_stats[0];
+        assert_eq!(stats.column, ColumnName::new(["value"]));
+        assert_eq!(stats.null_count, Some(0));
+        assert_eq!(stats.min_value, Some(Scalar::Integer(0)));
+        assert_eq!(stats.max_value, Some(Scalar::Integer(9)));
+    }
+
+    #[tokio::test]
+    async fn test_read_footer_with_suffix_requests() {
+        let path = "./tests/data/table-with-dv-small/part-00000-fae5310a-a37d-4e51-827b-c3d5516560ca-c000.snappy.parquet";
+        let store = CountingStore::default();
+        let location = Path::from("file.parquet");
+        let data = std::fs::read(path).unwrap();
+        store.put(&location, data.into()).await.unwrap();
+
+        let url = Url::parse("memory:///file.parquet").unwrap();
+        let (store, location) = (&store, &location);
+        let metadata = read_footer_with_suffix_requests(&url, |len| async move {
+            let options = GetOptions {
+                range: Some(GetRange::Suffix(len.try_into().unwrap())),
+                ..Default::default()
+            };
+            Ok(store.get_opts(location, options).await?.bytes().await?)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(metadata.file_metadata().num_rows(), 10);
+        // the footer is read with a single suffix range request
+        let footer_range = GetRange::Suffix(PRESIGNED_FOOTER_READ_SIZE.try_into().unwrap());
+        assert_eq!(*store.ranges.lock().unwrap(), [Some(footer_range)]);
+    }
+
     #[test]
     fn test_as_record_batch() {
         let location = Url::parse("file:///test_url").unwrap();
@@ -604,4 +881,214 @@ mod tests {
             .await
             .is_err());
     }
+
+    /// An [`ObjectStore`] that counts the GET requests issued against an in-memory store, and
+    /// records the range each of them requested.
+    #[derive(Debug, Default)]
+    struct CountingStore {
+        inner: InMemory,
+        gets: AtomicUsize,
+        ranges: Mutex<Vec<Option<GetRange>>>,
+    }
+
+    impl std::fmt::Display for CountingStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "CountingStore({})", self.inner)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ObjectStore for CountingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOptions,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            self.gets.fetch_add(1, Ordering::SeqCst);
+            self.ranges.lock().unwrap().push(options.range.clone());
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        #[cfg(feature = "arrow-55")]
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        #[cfg(not(feature = "arrow-55"))]
+        fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_parquet_files_coalesce_size() {
+        // Three columns, of which only the first and last are read, so that the two column chunks
+        // to fetch are separated by a gap.
+        let column = |offset: i64| Arc::new(Int64Array::from_iter_values(offset..offset + 10_000));
+        let batch = RecordBatch::try_from_iter(vec![
+            ("a", column(0) as Arc<dyn Array>),
+            ("b", column(10_000) as Arc<dyn Array>),
+            ("c", column(20_000) as Arc<dyn Array>),
+        ])
+        .unwrap();
+        let mut buffer = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let store = Arc::new(CountingStore::default());
+        let location = Path::from("data.parquet");
+        let size = buffer.len();
+        store.put(&location, buffer.into()).await.unwrap();
+
+        let files = &[FileMeta {
+            location: Url::parse("memory:///data.parquet").unwrap(),
+            last_modified: 0,
+            size: size.try_into().unwrap(),
+        }];
+        let physical_schema = Arc::new(crate::schema::StructType::new([
+            crate::schema::StructField::nullable("a", crate::schema::DataType::LONG),
+            crate::schema::StructField::nullable("c", crate::schema::DataType::LONG),
+        ]));
+
+        let count_gets = |coalesce_size| {
+            let handler =
+                DefaultParquetHandler::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()))
+                    .with_coalesce_size(coalesce_size);
+            let before = store.gets.load(Ordering::SeqCst);
+            let data: Vec<RecordBatch> = handler
+                .read_parquet_files(files, physical_schema.clone(), None)
+                .unwrap()
+                .map(into_record_batch)
+                .try_collect()
+                .unwrap();
+            assert_eq!(data.iter().map(|b| b.num_rows()).sum::<usize>(), 10_000);
+            store.gets.load(Ordering::SeqCst) - before
+        };
+
+        let separate_gets = count_gets(0);
+        let coalesced_gets = count_gets(1024 * 1024);
+        assert_eq!(separate_gets, coalesced_gets + 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_nested_arrays_and_maps() {
+        use crate::expressions::{ArrayData, MapData, StructData};
+        use crate::schema::{ArrayType, DataType, MapType, StructField, StructType};
+
+        // Build `s: struct<arr: array<map<string, struct<x, y>>>>` and
+        // `m: map<string, array<struct<x, y>>>` values with fields in the given order
+        let values = |fields: Vec<StructField>| {
+            let leaf_type = DataType::struct_type(fields.clone());
+            let leaf = |x: i32, y: &str| {
+                let mut values = vec![Scalar::from(x), Scalar::from(y)];
+                if fields[0].name() == "y" {
+                    values.reverse();
+                }
+                Scalar::Struct(StructData::try_new(fields.clone(), values).unwrap())
+            };
+            let map_type = MapType::new(DataType::STRING, leaf_type.clone(), true);
+            let map = Scalar::Map(
+                MapData::try_new(
+                    map_type.clone(),
+                    [
+                        ("a", leaf(1, "one")),
+                        ("b", Scalar::Null(leaf_type.clone())),
+                    ],
+                )
+                .unwrap(),
+            );
+            let arr_type = ArrayType::new(map_type.into(), true);
+            let arr = ArrayData::try_new(arr_type.clone(), [map, Scalar::Null(DataType::Null)]);
+            let s = StructData::try_new(
+                vec![StructField::nullable("arr", arr_type)],
+                vec![Scalar::Array(arr.unwrap())],
+            )
+            .unwrap();
+            let leaf_arr_type = ArrayType::new(leaf_type, false);
+            let leaf_arr = ArrayData::try_new(leaf_arr_type.clone(), [leaf(2, "two"), leaf(3, "")]);
+            let m = MapData::try_new(
+                MapType::new(DataType::STRING, leaf_arr_type, true),
+                [("c", Scalar::Array(leaf_arr.unwrap()))],
+            )
+            .unwrap();
+            [Scalar::Struct(s), Scalar::Map(m)]
+        };
+        let x = StructField::nullable("x", DataType::INTEGER);
+        let y = StructField::nullable("y", DataType::STRING);
+        let written = values(vec![x.clone(), y.clone()]);
+        let schema = StructType::new([
+            StructField::nullable("s", written[0].data_type()),
+            StructField::nullable("m", written[1].data_type()),
+        ]);
+        let columns = written.iter().map(|v| v.to_array(2)).try_collect().unwrap();
+        let batch = RecordBatch::try_new(Arc::new((&schema).try_into_arrow().unwrap()), columns);
+
+        let store = Arc::new(InMemory::new());
+        let handler = DefaultParquetHandler::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let data = Box::new(ArrowEngineData::new(batch.unwrap()));
+        let url = Url::parse("memory:///data/").unwrap();
+        let meta = handler.write_parquet(&url, data).await.unwrap().file_meta;
+
+        // read the columns back, both as written and with the innermost struct fields reordered
+        for fields in [vec![x.clone(), y.clone()], vec![y.clone(), x.clone()]] {
+            let expected = values(fields);
+            let schema = StructType::new([
+                StructField::nullable("m", expected[1].data_type()),
+                StructField::nullable("s", expected[0].data_type()),
+            ]);
+            let data: Vec<RecordBatch> = handler
+                .read_parquet_files(std::slice::from_ref(&meta), Arc::new(schema), None)
+                .unwrap()
+                .map(into_record_batch)
+                .try_collect()
+                .unwrap();
+            assert_eq!(data.len(), 1);
+            for row in 0..2 {
+                let m = Scalar::from_arrow_array(data[0].column(0), row).unwrap();
+                let s = Scalar::from_arrow_array(data[0].column(1), row).unwrap();
+                assert_eq!(m, expected[1]);
+                assert_eq!(s, expected[0]);
+            }
+        }
+    }
 }
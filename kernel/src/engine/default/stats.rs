@@ -0,0 +1,292 @@
+//! Computes the statistics recorded in the `stats` field of an `add` action (`numRecords`,
+//! `minValues`, `maxValues` and `nullCount`) from the data of a file. Engines can use this to
+//! backfill stats for files that were written without them (e.g. during an OPTIMIZE), so that those
+//! files can take part in data skipping.
+
+use std::sync::Arc;
+
+use crate::arrow::array::{make_array, Array, ArrayRef, Int64Array, RecordBatch, StructArray};
+use crate::arrow::buffer::NullBuffer;
+use crate::arrow::compute::{sort_to_indices, take, SortOptions};
+use crate::arrow::datatypes::{DataType as ArrowDataType, Field, FieldRef, Fields, Schema};
+use crate::arrow::json::LineDelimitedWriter;
+use crate::engine::arrow_data::ArrowEngineData;
+use crate::schema::SchemaRef;
+use crate::{DeltaResult, Engine, Error, FileMeta};
+
+use itertools::Itertools;
+
+/// Reads the parquet file `file` with the given (physical) `schema` using the `engine`, and returns
+/// the JSON stats string for its `add` action, as computed by [`collect_stats`]. The engine's
+/// parquet handler must produce [`ArrowEngineData`].
+pub fn compute_file_stats(
+    engine: &dyn Engine,
+    file: &FileMeta,
+    schema: SchemaRef,
+) -> DeltaResult<String> {
+    let batches: Vec<_> = engine
+        .parquet_handler()
+        .read_parquet_files(std::slice::from_ref(file), schema, None)?
+        .map_ok(|data| -> DeltaResult<RecordBatch> {
+            Ok(ArrowEngineData::try_from_engine_data(data)?.into())
+        })
+        .flatten_ok()
+        .try_collect()?;
+    collect_stats(&batches)
+}
+
+/// Computes the JSON stats string of an `add` action for a file containing `batches`. Stats are
+/// keyed by the (physical) column names of the batches, with nested struct columns becoming nested
+/// objects:
+///
+/// - `numRecords`: the total number of rows.
+/// - `nullCount`: the number of null values of each leaf column (a row where an enclosing struct is
+///   null counts as null).
+/// - `minValues` and `maxValues`: the smallest and largest non-null value of each numeric, string,
+///   date, timestamp and decimal leaf column. Columns with only null values are omitted.
+pub fn collect_stats(batches: &[RecordBatch]) -> DeltaResult<String> {
+    let num_records: usize = batches.iter().map(RecordBatch::num_rows).sum();
+    let schema = match batches.first() {
+        Some(batch) => batch.schema(),
+        None => Arc::new(Schema::empty()),
+    };
+    let columns = (0..schema.fields().len())
+        .map(|i| {
+            let arrays = batches
+                .iter()
+                .map(|batch| batch.column(i).as_ref())
+                .collect_vec();
+            Ok(crate::arrow::compute::concat(&arrays)?)
+        })
+        .collect::<DeltaResult<Vec<_>>>()?;
+    let stats = struct_stats(schema.fields(), &columns, None)?;
+
+    let mut fields = vec![Field::new("numRecords", ArrowDataType::Int64, false)];
+    let mut values: Vec<ArrayRef> = vec![Arc::new(Int64Array::from(vec![num_records as i64]))];
+    for (name, stat) in [
+        ("minValues", stats.min),
+        ("maxValues", stats.max),
+        ("nullCount", stats.null_count),
+    ] {
+        if let Some(stat) = stat {
+            fields.push(Field::new(name, stat.data_type().clone(), true));
+            values.push(stat);
+        }
+    }
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), values)?;
+
+    let mut writer = LineDelimitedWriter::new(Vec::new());
+    writer.write(&batch)?;
+    writer.finish()?;
+    let json = String::from_utf8(writer.into_inner())
+        .map_err(|e| Error::generic(format!("Stats are not valid UTF-8: {e}")))?;
+    Ok(json.trim_end().to_string())
+}
+
+/// The stats of a column, each as a single-row array. Any of them can be missing, e.g. if the
+/// column's type has no min/max stats.
+#[derive(Default)]
+struct ColumnStats {
+    min: Option<ArrayRef>,
+    max: Option<ArrayRef>,
+    null_count: Option<ArrayRef>,
+}
+
+// Computes the stats of the fields of a struct, as single-row struct arrays. `parent_nulls` are the
+// nulls of the enclosing struct, if any, which apply to every field.
+fn struct_stats(
+    fields: &Fields,
+    columns: &[ArrayRef],
+    parent_nulls: Option<&NullBuffer>,
+) -> DeltaResult<ColumnStats> {
+    let mut min = vec![];
+    let mut max = vec![];
+    let mut null_count = vec![];
+    for (field, column) in fields.iter().zip(columns) {
+        let nulls = NullBuffer::union(parent_nulls, column.logical_nulls().as_ref());
+        let stats = match column.data_type() {
+            ArrowDataType::Struct(children) => {
+                let column = column
+                    .as_any()
+                    .downcast_ref::<StructArray>()
+                    .ok_or_else(|| Error::internal_error("Struct column is not a StructArray"))?;
+                struct_stats(children, column.columns(), nulls.as_ref())?
+            }
+            _ => leaf_stats(column, nulls)?,
+        };
+        let push = |stats: &mut Vec<(FieldRef, ArrayRef)>, stat: Option<ArrayRef>| {
+            if let Some(stat) = stat {
+                let field = Field::new(field.name(), stat.data_type().clone(), true);
+                stats.push((Arc::new(field), stat));
+            }
+        };
+        push(&mut min, stats.min);
+        push(&mut max, stats.max);
+        push(&mut null_count, stats.null_count);
+    }
+    let to_struct = |stats: Vec<(FieldRef, ArrayRef)>| -> Option<ArrayRef> {
+        (!stats.is_empty()).then(|| Arc::new(StructArray::from(stats)) as ArrayRef)
+    };
+    Ok(ColumnStats {
+        min: to_struct(min),
+        max: to_struct(max),
+        null_count: to_struct(null_count),
+    })
+}
+
+// Computes the stats of a non-struct column, whose effective nulls (including those of any
+// enclosing structs) are `nulls`.
+fn leaf_stats(column: &ArrayRef, nulls: Option<NullBuffer>) -> DeltaResult<ColumnStats> {
+    let null_count = nulls.as_ref().map_or(0, NullBuffer::null_count);
+    let mut stats = ColumnStats {
+        null_count: Some(Arc::new(Int64Array::from(vec![null_count as i64]))),
+        ..Default::default()
+    };
+    if !has_min_max(column.data_type()) || null_count == column.len() {
+        return Ok(stats);
+    }
+    let column = make_array(column.to_data().into_builder().nulls(nulls).build()?);
+    // Nulls sort last, so the first sorted value is the smallest (or largest) non-null value
+    let first_sorted = |descending| -> DeltaResult<ArrayRef> {
+        let options = SortOptions {
+            descending,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&column, Some(options), Some(1))?;
+        Ok(take(&column, &indices, None)?)
+    };
+    stats.min = Some(first_sorted(false)?);
+    stats.max = Some(first_sorted(true)?);
+    Ok(stats)
+}
+
+// Whether min/max stats are collected for columns of the given type
+fn has_min_max(data_type: &ArrowDataType) -> bool {
+    use ArrowDataType::*;
+    matches!(
+        data_type,
+        Int8 | Int16
+            | Int32
+            | Int64
+            | Float32
+            | Float64
+            | Utf8
+            | LargeUtf8
+            | Utf8View
+            | Date32
+            | Timestamp(_, _)
+            | Decimal128(_, _)
+            | Decimal256(_, _)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::object_store::memory::InMemory;
+    use crate::object_store::{path::Path, ObjectStore as _};
+    use url::Url;
+
+    use crate::arrow::array::{
+        ArrayRef, Int32Array, RecordBatch, StringArray, StructArray, TimestampMicrosecondArray,
+    };
+    use crate::arrow::buffer::NullBuffer;
+    use crate::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+    use crate::engine::arrow_conversion::TryIntoKernel as _;
+    use crate::engine::default::executor::tokio::TokioBackgroundExecutor;
+    use crate::engine::default::DefaultEngine;
+    use crate::parquet::arrow::ArrowWriter;
+    use crate::parquet::file::properties::{EnabledStatistics, WriterProperties};
+    use crate::FileMeta;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_compute_file_stats() {
+        let nested_fields = vec![Field::new("y", ArrowDataType::Int32, true)];
+        let nested = StructArray::try_new(
+            nested_fields.clone().into(),
+            vec![Arc::new(Int32Array::from(vec![Some(5), Some(7), Some(9)])) as ArrayRef],
+            Some(NullBuffer::from(vec![true, true, false])),
+        )
+        .unwrap();
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("id", ArrowDataType::Int32, true),
+            Field::new("name", ArrowDataType::Utf8, true),
+            Field::new(
+                "ts",
+                ArrowDataType::Timestamp(
+                    crate::arrow::datatypes::TimeUnit::Microsecond,
+                    Some("UTC".into()),
+                ),
+                true,
+            ),
+            Field::new("empty", ArrowDataType::Int32, true),
+            Field::new("nested", ArrowDataType::Struct(nested_fields.into()), true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(3), None, Some(-2)])),
+                Arc::new(StringArray::from(vec![Some("b"), Some("a"), Some("c")])),
+                Arc::new(
+                    TimestampMicrosecondArray::from(vec![1_000_000, 3_000_000, 2_000_000])
+                        .with_timezone("UTC"),
+                ),
+                Arc::new(Int32Array::from(vec![None::<i32>, None, None])),
+                Arc::new(nested),
+            ],
+        )
+        .unwrap();
+
+        // write the file without any parquet statistics
+        let props = WriterProperties::builder()
+            .set_statistics_enabled(EnabledStatistics::None)
+            .build();
+        let mut buffer = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema.clone(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let size = buffer.len() as u64;
+
+        let store = Arc::new(InMemory::new());
+        store
+            .put(&Path::from("file.parquet"), buffer.into())
+            .await
+            .unwrap();
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let file = FileMeta::new(Url::parse("memory:///file.parquet").unwrap(), 0, size);
+
+        let stats = compute_file_stats(
+            &engine,
+            &file,
+            Arc::new(schema.as_ref().try_into_kernel().unwrap()),
+        )
+        .unwrap();
+        let stats: serde_json::Value = serde_json::from_str(&stats).unwrap();
+        let expected = serde_json::json!({
+            "numRecords": 3,
+            "minValues": {
+                "id": -2,
+                "name": "a",
+                "ts": "1970-01-01T00:00:01Z",
+                "nested": { "y": 5 }
+            },
+            "maxValues": {
+                "id": 3,
+                "name": "c",
+                "ts": "1970-01-01T00:00:03Z",
+                "nested": { "y": 7 }
+            },
+            "nullCount": {
+                "id": 1,
+                "name": 0,
+                "ts": 0,
+                "empty": 3,
+                "nested": { "y": 1 }
+            }
+        });
+        assert_eq!(stats, expected);
+    }
+}
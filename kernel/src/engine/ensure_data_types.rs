@@ -65,10 +65,16 @@ impl EnsureDataTypes {
             (DataType::Primitive(_), _) if arrow_type.is_primitive() => {
                 check_cast_compat(kernel_type.try_into_arrow()?, arrow_type)
             }
-            // strings, bools, and binary  aren't primitive in arrow
+            // strings, bools, binary, and nulls aren't primitive in arrow
             (&DataType::BOOLEAN, ArrowDataType::Boolean)
             | (&DataType::STRING, ArrowDataType::Utf8)
-            | (&DataType::BINARY, ArrowDataType::Binary) => Ok(DataTypeCompat::Identical),
+            | (&DataType::BINARY, ArrowDataType::Binary)
+            | (DataType::Null, ArrowDataType::Null) => Ok(DataTypeCompat::Identical),
+            // parquet FIXED_LEN_BYTE_ARRAY columns (outside of decimals) are read as fixed-size
+            // binary, which holds the same values as a variable-length binary column
+            (&DataType::BINARY, ArrowDataType::FixedSizeBinary(_)) => {
+                Ok(DataTypeCompat::NeedsCast(ArrowDataType::Binary))
+            }
             (DataType::Array(inner_type), ArrowDataType::List(arrow_list_field)) => {
                 self.ensure_nullability(
                     "List",
@@ -192,7 +198,7 @@ fn check_cast_compat(
         (Int16, Int32 | Int64 | Float64) => Ok(DataTypeCompat::NeedsCast(target_type)),
         (Int32, Int64 | Float64) => Ok(DataTypeCompat::NeedsCast(target_type)),
         (Float32, Float64) => Ok(DataTypeCompat::NeedsCast(target_type)),
-        (_, Decimal128(p, s)) if can_upcast_to_decimal(source_type, *p, *s) => {
+        (_, Decimal128(p, s) | Decimal256(p, s)) if can_upcast_to_decimal(source_type, *p, *s) => {
             Ok(DataTypeCompat::NeedsCast(target_type))
         }
         (Date32, Timestamp(_, None)) => Ok(DataTypeCompat::NeedsCast(target_type)),
@@ -212,7 +218,7 @@ fn can_upcast_to_decimal(
     use ArrowDataType::*;
 
     let (source_precision, source_scale) = match source_type {
-        Decimal128(p, s) => (*p, *s),
+        Decimal128(p, s) | Decimal256(p, s) => (*p, *s),
         // Allow converting integers to a decimal that can hold all possible values.
         Int8 => (3u8, 0i8),
         Int16 => (5u8, 0i8),
@@ -1,11 +1,14 @@
 //! An implementation of parquet row group skipping using data skipping predicates over footer stats.
-use crate::expressions::{ColumnName, DecimalData, Predicate, Scalar};
+use crate::engine::arrow_utils::RowIndexBuilder;
+use crate::expressions::{ColumnName, DecimalData, DecimalValue, Predicate, Scalar, I256};
 use crate::kernel_predicates::parquet_stats_skipping::ParquetStatsProvider;
 use crate::parquet::arrow::arrow_reader::ArrowReaderBuilder;
-use crate::parquet::file::metadata::RowGroupMetaData;
+use crate::parquet::basic::{ConvertedType, LogicalType, TimeUnit, Type as PhysicalType};
+use crate::parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
 use crate::parquet::file::statistics::Statistics;
-use crate::parquet::schema::types::ColumnDescPtr;
+use crate::parquet::schema::types::{ColumnDescPtr, ColumnDescriptor};
 use crate::schema::{DataType, DecimalType, PrimitiveType};
+use crate::{FileMeta, ParquetColumnStatistics, ParquetFileMetadata, ParquetRowGroupMetadata};
 use chrono::{DateTime, Days};
 use std::collections::HashMap;
 use tracing::debug;
@@ -16,12 +19,21 @@ mod tests;
 /// An extension trait for [`ArrowReaderBuilder`] that injects row group skipping capability.
 pub(crate) trait ParquetRowGroupSkipping {
     /// Instructs the parquet reader to perform row group skipping, eliminating any row group whose
-    /// stats prove that none of the group's rows can satisfy the given `predicate`.
-    fn with_row_group_filter(self, predicate: &Predicate) -> Self;
+    /// stats prove that none of the group's rows can satisfy the given `predicate`. If
+    /// `row_indexes` is given, it is informed of the row groups that survive the filter.
+    fn with_row_group_filter(
+        self,
+        predicate: &Predicate,
+        row_indexes: Option<&mut RowIndexBuilder>,
+    ) -> Self;
 }
 impl<T> ParquetRowGroupSkipping for ArrowReaderBuilder<T> {
-    fn with_row_group_filter(self, predicate: &Predicate) -> Self {
-        let indices = self
+    fn with_row_group_filter(
+        self,
+        predicate: &Predicate,
+        row_indexes: Option<&mut RowIndexBuilder>,
+    ) -> Self {
+        let indices: Vec<_> = self
             .metadata()
             .row_groups()
             .iter()
@@ -32,6 +44,9 @@ impl<T> ParquetRowGroupSkipping for ArrowReaderBuilder<T> {
             })
             .collect();
         debug!("with_row_group_filter({predicate:#?}) = {indices:?})");
+        if let Some(row_indexes) = row_indexes {
+            row_indexes.select_row_groups(&indices);
+        }
         self.with_row_groups(indices)
     }
 }
@@ -53,6 +68,42 @@ impl<'a> RowGroupFilter<'a> {
         }
     }
 
+    /// Creates a row group "filter" that exposes the stats of every column of the row group.
+    fn for_all_columns(row_group: &'a RowGroupMetaData) -> Self {
+        let field_indices = row_group
+            .schema_descr()
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (ColumnName::new(f.path().parts()), i))
+            .collect();
+        Self {
+            row_group,
+            field_indices,
+        }
+    }
+
+    /// Collects the stats of a single leaf column of the row group.
+    fn column_statistics(&self, col: &ColumnDescriptor) -> ParquetColumnStatistics {
+        let column = ColumnName::new(col.path().parts());
+        let (min_value, max_value) = match stats_data_type(col) {
+            Some(data_type) => (
+                self.get_parquet_min_stat(&column, &data_type),
+                self.get_parquet_max_stat(&column, &data_type),
+            ),
+            None => (None, None),
+        };
+        let null_count = self
+            .get_parquet_nullcount_stat(&column)
+            .and_then(|n| n.try_into().ok());
+        ParquetColumnStatistics {
+            column,
+            null_count,
+            min_value,
+            max_value,
+        }
+    }
+
     /// Applies a filtering predicate to a row group. Return value false means to skip it.
     fn apply(row_group: &'a RowGroupMetaData, predicate: &Predicate) -> bool {
         use crate::kernel_predicates::KernelPredicateEvaluator as _;
@@ -67,13 +118,15 @@ impl<'a> RowGroupFilter<'a> {
     }
 
     fn decimal_from_bytes(bytes: Option<&[u8]>, dtype: DecimalType) -> Option<Scalar> {
-        // WARNING: The bytes are stored in big-endian order; reverse and then 0-pad to 16 bytes.
-        let bytes = bytes.filter(|b| b.len() <= 16)?;
+        // WARNING: The bytes are stored in big-endian order; reverse and then sign-extend to 32
+        // bytes, which is wide enough for both 128- and 256-bit decimals.
+        let bytes = bytes.filter(|b| !b.is_empty() && b.len() <= 32)?;
+        let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0u8 };
         let mut bytes = Vec::from(bytes);
         bytes.reverse();
-        bytes.resize(16, 0u8);
-        let bytes: [u8; 16] = bytes.try_into().ok()?;
-        let value = DecimalData::try_new(i128::from_le_bytes(bytes), dtype).ok()?;
+        bytes.resize(32, fill);
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        let value = DecimalValue::try_new(I256::from_le_bytes(bytes), dtype).ok()?;
         Some(value.into())
     }
 
@@ -221,6 +274,76 @@ impl ParquetStatsProvider for RowGroupFilter<'_> {
     }
 }
 
+/// Converts the footer of a parquet file into kernel [`ParquetFileMetadata`], including the
+/// statistics of every leaf column of every row group.
+pub(crate) fn parquet_file_metadata(
+    file: FileMeta,
+    metadata: &ParquetMetaData,
+) -> ParquetFileMetadata {
+    let mut first_row_index = 0;
+    let row_groups = metadata
+        .row_groups()
+        .iter()
+        .map(|row_group| {
+            let filter = RowGroupFilter::for_all_columns(row_group);
+            let column_stats = row_group
+                .schema_descr()
+                .columns()
+                .iter()
+                .map(|col| filter.column_statistics(col))
+                .collect();
+            let row_group_metadata = ParquetRowGroupMetadata {
+                num_rows: row_group.num_rows(),
+                first_row_index,
+                file_offset: row_group.file_offset(),
+                compressed_size: row_group.compressed_size(),
+                column_stats,
+            };
+            first_row_index += row_group.num_rows();
+            row_group_metadata
+        })
+        .collect();
+    ParquetFileMetadata {
+        file,
+        num_rows: metadata.file_metadata().num_rows(),
+        row_groups,
+    }
+}
+
+/// Infers the kernel type that best represents the stats of a parquet leaf column, or `None` if
+/// the column's stats cannot be represented as a [`Scalar`].
+fn stats_data_type(col: &ColumnDescriptor) -> Option<DataType> {
+    let data_type = match (col.physical_type(), col.logical_type()) {
+        (_, Some(LogicalType::String | LogicalType::Enum | LogicalType::Json)) => DataType::STRING,
+        (_, None) if col.converted_type() == ConvertedType::UTF8 => DataType::STRING,
+        (_, Some(LogicalType::Decimal { scale, precision })) => {
+            DataType::decimal(precision.try_into().ok()?, scale.try_into().ok()?).ok()?
+        }
+        (_, Some(LogicalType::Date)) => DataType::DATE,
+        (
+            PhysicalType::INT64,
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c,
+                unit: TimeUnit::MICROS(_),
+            }),
+        ) => match is_adjusted_to_u_t_c {
+            true => DataType::TIMESTAMP,
+            false => DataType::TIMESTAMP_NTZ,
+        },
+        (_, Some(LogicalType::Timestamp { .. })) => return None,
+        (PhysicalType::INT32, Some(LogicalType::Integer { bit_width: 8, .. })) => DataType::BYTE,
+        (PhysicalType::INT32, Some(LogicalType::Integer { bit_width: 16, .. })) => DataType::SHORT,
+        (PhysicalType::BOOLEAN, _) => DataType::BOOLEAN,
+        (PhysicalType::INT32, _) => DataType::INTEGER,
+        (PhysicalType::INT64, _) => DataType::LONG,
+        (PhysicalType::FLOAT, _) => DataType::FLOAT,
+        (PhysicalType::DOUBLE, _) => DataType::DOUBLE,
+        (PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY, _) => DataType::BINARY,
+        (PhysicalType::INT96, _) => return None,
+    };
+    Some(data_type)
+}
+
 /// Given a predicate of interest and a set of parquet column descriptors, build a column ->
 /// index mapping for columns the predicate references. This ensures O(1) lookup times, for an
 /// overall O(n) cost to evaluate a predicate tree with n nodes.
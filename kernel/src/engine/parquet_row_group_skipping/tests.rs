@@ -415,3 +415,64 @@ fn test_get_stat_values() {
         )
     );
 }
+
+#[test]
+fn test_row_group_filter_in_list() {
+    use crate::arrow::array::{Int32Array, RecordBatch};
+    use crate::arrow::datatypes::{DataType as ArrowDataType, Field, Schema};
+    use crate::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use crate::parquet::arrow::ArrowWriter;
+    use crate::parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    // Three row groups, with the values [1, 2, 3], [10, 11, 12] and [7, 7, 7]
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "x",
+        ArrowDataType::Int32,
+        false,
+    )]));
+    let values = Int32Array::from(vec![1, 2, 3, 10, 11, 12, 7, 7, 7]);
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(values)]).unwrap();
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(3)
+        .build();
+    let mut buffer = vec![];
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, Some(props)).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+    let buffer = bytes::Bytes::from(buffer);
+
+    let read = |predicate: Predicate| -> Vec<i32> {
+        assert!(predicate.is_parquet_pushable(), "{predicate}");
+        let reader = ParquetRecordBatchReaderBuilder::try_new(buffer.clone())
+            .unwrap()
+            .with_row_group_filter(&predicate, None)
+            .build()
+            .unwrap();
+        reader
+            .flat_map(|batch| {
+                let batch = batch.unwrap();
+                let values = batch.column(0).as_any().downcast_ref::<Int32Array>();
+                values.unwrap().values().to_vec()
+            })
+            .collect()
+    };
+    let x = || column_name!("x");
+
+    // Only the row groups whose [min, max] range brackets some element of the list are read
+    let pred = Predicate::is_in(x(), [5.into(), 11.into()]).unwrap();
+    assert_eq!(read(pred), [10, 11, 12]);
+    let pred = Predicate::is_in(x(), [2.into(), 12.into()]).unwrap();
+    assert_eq!(read(pred), [1, 2, 3, 10, 11, 12]);
+    let pred = Predicate::is_in(x(), [0.into(), 5.into(), 20.into()]).unwrap();
+    assert_eq!(read(pred), Vec::<i32>::new());
+    // NULL elements never match
+    let pred = Predicate::is_in(x(), [Scalar::Null(DataType::INTEGER), 2.into()]).unwrap();
+    assert_eq!(read(pred), [1, 2, 3]);
+
+    // NOT IN can only prune row groups whose min and max both equal the same element
+    let pred = Predicate::not_in(x(), [7.into(), 11.into()]).unwrap();
+    assert_eq!(read(pred), [1, 2, 3, 10, 11, 12]);
+    let pred = Predicate::not_in(x(), [1.into(), 2.into(), 3.into()]).unwrap();
+    assert_eq!(read(pred), [1, 2, 3, 10, 11, 12, 7, 7, 7]);
+}
@@ -5,10 +5,15 @@ use crate::parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatc
 
 use super::read_files;
 use crate::engine::arrow_data::ArrowEngineData;
-use crate::engine::arrow_utils::{fixup_parquet_read, generate_mask, get_requested_indices};
-use crate::engine::parquet_row_group_skipping::ParquetRowGroupSkipping;
+use crate::engine::arrow_utils::{
+    fixup_parquet_read, generate_mask, get_requested_indices, RowIndexBuilder,
+};
+use crate::engine::parquet_row_group_skipping::{parquet_file_metadata, ParquetRowGroupSkipping};
 use crate::schema::SchemaRef;
-use crate::{DeltaResult, FileDataReadResultIterator, FileMeta, ParquetHandler, PredicateRef};
+use crate::{
+    DeltaResult, Error, FileDataReadResultIterator, FileMeta, ParquetFileMetadata, ParquetHandler,
+    PredicateRef,
+};
 
 pub(crate) struct SyncParquetHandler;
 
@@ -25,11 +30,15 @@ fn try_create_from_parquet(
     if let Some(mask) = generate_mask(&schema, parquet_schema, builder.parquet_schema(), &indices) {
         builder = builder.with_projection(mask);
     }
+    let mut row_indexes = RowIndexBuilder::new(builder.metadata().row_groups());
     if let Some(predicate) = predicate {
-        builder = builder.with_row_group_filter(predicate.as_ref());
+        builder = builder.with_row_group_filter(predicate.as_ref(), Some(&mut row_indexes));
     }
+    let mut row_indexes = row_indexes.build()?;
     let stream = builder.build()?;
-    Ok(stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering)))
+    Ok(stream.map(move |rbr| {
+        fixup_parquet_read(rbr?, &schema, &requested_ordering, Some(&mut row_indexes))
+    }))
 }
 
 impl ParquetHandler for SyncParquetHandler {
@@ -41,4 +50,18 @@ impl ParquetHandler for SyncParquetHandler {
     ) -> DeltaResult<FileDataReadResultIterator> {
         read_files(files, schema, predicate, try_create_from_parquet)
     }
+
+    fn read_parquet_metadata(&self, files: &[FileMeta]) -> DeltaResult<Vec<ParquetFileMetadata>> {
+        files
+            .iter()
+            .map(|file| {
+                let path = file
+                    .location
+                    .to_file_path()
+                    .map_err(|_| Error::generic("can only read local files"))?;
+                let metadata = ArrowReaderMetadata::load(&File::open(path)?, Default::default())?;
+                Ok(parquet_file_metadata(file.clone(), metadata.metadata()))
+            })
+            .collect()
+    }
 }
@@ -1,3 +1,5 @@
+use std::io::Write as _;
+
 use bytes::Bytes;
 use itertools::Itertools;
 use url::Url;
@@ -70,6 +72,23 @@ impl StorageHandler for SyncStorageHandler {
         });
         Ok(Box::new(iter))
     }
+
+    fn write_file(&self, path: &Url, data: Bytes, overwrite: bool) -> DeltaResult<()> {
+        let file_path = path
+            .to_file_path()
+            .map_err(|_| Error::generic("Can only write to local filesystem"))?;
+        let mut options = std::fs::OpenOptions::new();
+        match overwrite {
+            true => options.write(true).create(true).truncate(true),
+            false => options.write(true).create_new(true),
+        };
+        let mut file = options.open(file_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => Error::FileAlreadyExists(path.to_string()),
+            _ => e.into(),
+        })?;
+        file.write_all(&data)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -282,4 +282,33 @@ pub trait EngineData: AsAny {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Return a new [`EngineData`] containing `length` rows of this data, starting at row
+    /// `offset`. Kernel uses this to re-batch data read by the engine, e.g. to honor
+    /// [`ScanBuilder::with_batch_size`]. The default implementation returns an
+    /// [`Error::Unsupported`] error, so engines must override it in order to use such features.
+    ///
+    /// [`ScanBuilder::with_batch_size`]: crate::scan::ScanBuilder::with_batch_size
+    fn slice(&self, offset: usize, length: usize) -> DeltaResult<Box<dyn EngineData>> {
+        let _ = (offset, length);
+        Err(Error::unsupported(format!(
+            "{} does not support slicing",
+            self.type_name()
+        )))
+    }
+
+    /// Return a new [`EngineData`] containing only the rows of this data which are selected by
+    /// `selection_vector`. Row `i` is kept if `selection_vector[i]` is `true`. If the selection
+    /// vector is *shorter* than the number of rows, missing elements are considered `true`. Kernel
+    /// uses this to apply deletion vectors in [`ScanResult::into_filtered_data`], which fails with
+    /// [`Error::Unsupported`] unless the engine implements this method.
+    ///
+    /// [`ScanResult::into_filtered_data`]: crate::scan::ScanResult::into_filtered_data
+    fn filter(&self, selection_vector: &[bool]) -> DeltaResult<Box<dyn EngineData>> {
+        let _ = selection_vector;
+        Err(Error::unsupported(format!(
+            "{} does not support filtering",
+            self.type_name()
+        )))
+    }
 }
@@ -198,6 +198,11 @@ pub enum Error {
     #[error("Change data feed is unsupported for the table at version {0}")]
     ChangeDataFeedUnsupported(Version),
 
+    /// Change data feed (`delta.enableChangeDataFeed`) is not enabled for the table at the given
+    /// version
+    #[error("Change data feed is not enabled for the table at version {0}")]
+    ChangeDataFeedNotEnabled(Version),
+
     #[error("Change data feed encountered incompatible schema. Expected {0}, got {1}")]
     ChangeDataFeedIncompatibleSchema(String, String),
 
@@ -214,6 +219,27 @@ pub enum Error {
     /// Schema mismatch has occurred or invalid schema used somewhere
     #[error("Schema error: {0}")]
     Schema(String),
+
+    /// A transaction conflicted with an existing commit at the given version
+    #[error("Commit conflict: version {0} already exists")]
+    CommitConflict(Version),
+
+    /// A scan which requires stats selected a file (at the given path) which has no stats
+    #[error("Scan requires stats, but file has no stats: {0}")]
+    MissingStats(String),
+
+    /// There is no Delta table (i.e. no `_delta_log`) at the given location
+    #[error("Table not found: {0}")]
+    TableNotFound(String),
+
+    /// The `_delta_log` of the table at the given location contains no commits or checkpoints
+    #[error("Table has no versions: {0}")]
+    EmptyTable(String),
+
+    /// The commit file at the given version could not be fully parsed or contains no actions, e.g.
+    /// because a commit write only partially succeeded
+    #[error("Corrupt log: commit at version {0} is incomplete or unreadable")]
+    CorruptLog(Version),
 }
 
 // Convenience constructors for Error types that take a String argument
@@ -284,6 +310,9 @@ impl Error {
     pub fn change_data_feed_unsupported(version: impl Into<Version>) -> Self {
         Self::ChangeDataFeedUnsupported(version.into())
     }
+    pub fn change_data_feed_not_enabled(version: impl Into<Version>) -> Self {
+        Self::ChangeDataFeedNotEnabled(version.into())
+    }
     pub(crate) fn change_data_feed_incompatible_schema(
         expected: &StructType,
         actual: &StructType,
@@ -299,6 +328,18 @@ impl Error {
         Self::Schema(msg.to_string())
     }
 
+    pub fn table_not_found(location: impl ToString) -> Self {
+        Self::TableNotFound(location.to_string())
+    }
+
+    pub fn empty_table(location: impl ToString) -> Self {
+        Self::EmptyTable(location.to_string())
+    }
+
+    pub fn corrupt_log(version: impl Into<Version>) -> Self {
+        Self::CorruptLog(version.into())
+    }
+
     // Capture a backtrace when the error is constructed.
     #[must_use]
     pub fn with_backtrace(self) -> Self {
@@ -0,0 +1,301 @@
+//! A minimal 256-bit signed integer, used to store the unscaled values of decimals whose precision
+//! exceeds the 38 digits a 128-bit integer can hold.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::schema::DecimalType;
+use crate::{DeltaResult, Error};
+
+/// A 256-bit signed (two's complement) integer. It only provides the operations kernel needs to
+/// parse, compare and display the unscaled values of decimals, and converts losslessly to and from
+/// arrow's `i256` via [`I256::from_le_bytes`] and [`I256::to_le_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct I256 {
+    high: i128,
+    low: u128,
+}
+
+// The magnitude of an `I256` as little-endian u64 limbs, to allow arithmetic with u128 carries.
+type Magnitude = [u64; 4];
+
+impl I256 {
+    pub const ZERO: Self = Self { high: 0, low: 0 };
+    pub const MAX: Self = Self {
+        high: i128::MAX,
+        low: u128::MAX,
+    };
+    pub const MIN: Self = Self {
+        high: i128::MIN,
+        low: 0,
+    };
+
+    /// Creates an `I256` from its little-endian two's complement bytes.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let (low, high) = bytes.split_at(16);
+        Self {
+            low: u128::from_le_bytes(low.try_into().unwrap()),
+            high: i128::from_le_bytes(high.try_into().unwrap()),
+        }
+    }
+
+    /// Returns the little-endian two's complement bytes of this `I256`.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&self.low.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.high.to_le_bytes());
+        bytes
+    }
+
+    /// Converts to an `i128`, or returns `None` if the value does not fit.
+    pub fn to_i128(&self) -> Option<i128> {
+        let low = self.low as i128;
+        let fits = match self.high {
+            0 => low >= 0,
+            -1 => low < 0,
+            _ => false,
+        };
+        fits.then_some(low)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.high < 0
+    }
+
+    /// The number of decimal digits needed to represent this value (zero has no digits).
+    pub fn num_digits(&self) -> u8 {
+        let mut magnitude = self.magnitude();
+        let mut digits = 0;
+        while magnitude != [0; 4] {
+            div_rem(&mut magnitude, 10);
+            digits += 1;
+        }
+        digits
+    }
+
+    /// Multiplies by `10^exp`, or returns `None` on overflow.
+    pub fn checked_mul_pow10(&self, exp: u32) -> Option<Self> {
+        if *self == Self::ZERO {
+            return Some(Self::ZERO);
+        }
+        // 10^77 already exceeds `I256::MAX`, so any non-zero value would overflow
+        if exp > DecimalType::MAX_WIDE_PRECISION as u32 {
+            return None;
+        }
+        let mut magnitude = self.magnitude();
+        for _ in 0..exp {
+            if mul_add(&mut magnitude, 10, 0) {
+                return None;
+            }
+        }
+        Self::from_magnitude(self.is_negative(), magnitude)
+    }
+
+    fn magnitude(&self) -> Magnitude {
+        let value = if self.is_negative() {
+            self.wrapping_neg()
+        } else {
+            *self
+        };
+        // NOTE: The magnitude of I256::MIN wraps to itself, which is still correct when viewed as
+        // an unsigned number.
+        let (low, high) = (value.low, value.high as u128);
+        [
+            low as u64,
+            (low >> 64) as u64,
+            high as u64,
+            (high >> 64) as u64,
+        ]
+    }
+
+    fn from_magnitude(negative: bool, magnitude: Magnitude) -> Option<Self> {
+        let low = magnitude[0] as u128 | (magnitude[1] as u128) << 64;
+        let high = magnitude[2] as u128 | (magnitude[3] as u128) << 64;
+        let value = Self {
+            high: high as i128,
+            low,
+        };
+        match (negative, value.is_negative()) {
+            (false, false) => Some(value),
+            (true, false) => Some(value.wrapping_neg()),
+            // Only -2^255 has a magnitude that doesn't fit in a positive I256
+            (true, true) if value == Self::MIN => Some(value),
+            _ => None,
+        }
+    }
+
+    fn wrapping_neg(&self) -> Self {
+        let (low, carry) = (!self.low).overflowing_add(1);
+        let high = (!self.high).wrapping_add(carry as i128);
+        Self { high, low }
+    }
+}
+
+// Computes `magnitude * mul + add` in place, returning true on overflow.
+fn mul_add(magnitude: &mut Magnitude, mul: u64, add: u64) -> bool {
+    let mut carry = add as u128;
+    for limb in magnitude.iter_mut() {
+        let product = *limb as u128 * mul as u128 + carry;
+        *limb = product as u64;
+        carry = product >> 64;
+    }
+    carry != 0
+}
+
+// Divides `magnitude` by `divisor` in place, returning the remainder.
+fn div_rem(magnitude: &mut Magnitude, divisor: u64) -> u64 {
+    let mut remainder = 0u128;
+    for limb in magnitude.iter_mut().rev() {
+        let dividend = remainder << 64 | *limb as u128;
+        *limb = (dividend / divisor as u128) as u64;
+        remainder = dividend % divisor as u128;
+    }
+    remainder as u64
+}
+
+impl From<i128> for I256 {
+    fn from(value: i128) -> Self {
+        Self {
+            high: if value < 0 { -1 } else { 0 },
+            low: value as u128,
+        }
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for I256 {
+    // The (signed) high half decides first, and the (unsigned) low half breaks ties.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.high, self.low).cmp(&(other.high, other.low))
+    }
+}
+
+impl Display for I256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut magnitude = self.magnitude();
+        let mut digits = vec![];
+        while magnitude != [0; 4] {
+            digits.push(b'0' + div_rem(&mut magnitude, 10) as u8);
+        }
+        if digits.is_empty() {
+            digits.push(b'0');
+        }
+        digits.reverse();
+        let digits = std::str::from_utf8(&digits).map_err(|_| std::fmt::Error)?;
+        f.pad_integral(!self.is_negative(), "", digits)
+    }
+}
+
+impl FromStr for I256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> DeltaResult<Self> {
+        let parse_error = || Error::generic(format!("Invalid 256-bit integer: {s}"));
+        let (negative, digits) = match s.as_bytes() {
+            [b'-', digits @ ..] => (true, digits),
+            [b'+', digits @ ..] => (false, digits),
+            digits => (false, digits),
+        };
+        if digits.is_empty() {
+            return Err(parse_error());
+        }
+        let mut magnitude = [0; 4];
+        for digit in digits {
+            if !digit.is_ascii_digit() || mul_add(&mut magnitude, 10, (digit - b'0') as u64) {
+                return Err(parse_error());
+            }
+        }
+        Self::from_magnitude(negative, magnitude).ok_or_else(parse_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_i256_round_trip() {
+        let cases = [
+            "0",
+            "1",
+            "-1",
+            "170141183460469231731687303715884105727",
+            "-170141183460469231731687303715884105728",
+            "1234567890123456789012345678901234567890",
+            "-1234567890123456789012345678901234567890",
+            "57896044618658097711785492504343953926634992332820282019728792003956564819967",
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+        ];
+        for case in cases {
+            let value: I256 = case.parse().unwrap();
+            assert_eq!(value.to_string(), case);
+            assert_eq!(I256::from_le_bytes(value.to_le_bytes()), value);
+        }
+        assert_eq!(I256::MAX.to_string(), cases[7]);
+        assert_eq!(I256::MIN.to_string(), cases[8]);
+
+        // out of range or malformed
+        assert!(
+            "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+                .parse::<I256>()
+                .is_err()
+        );
+        assert!("".parse::<I256>().is_err());
+        assert!("-".parse::<I256>().is_err());
+        assert!("12a".parse::<I256>().is_err());
+    }
+
+    #[test]
+    fn test_i256_i128_conversions() {
+        for value in [0, 1, -1, i128::MAX, i128::MIN] {
+            let wide = I256::from(value);
+            assert_eq!(wide.to_i128(), Some(value));
+            assert_eq!(wide.to_string(), value.to_string());
+        }
+        assert_eq!(I256::MAX.to_i128(), None);
+        assert_eq!(I256::MIN.to_i128(), None);
+        let too_big = I256::from(i128::MAX).checked_mul_pow10(1).unwrap();
+        assert_eq!(too_big.to_i128(), None);
+    }
+
+    #[test]
+    fn test_i256_ordering_and_digits() {
+        let mut values: Vec<I256> = ["5", "-1", "0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .chain([I256::MAX, I256::MIN])
+            .collect();
+        values.sort();
+        assert_eq!(
+            values,
+            [
+                I256::MIN,
+                I256::from(-1),
+                I256::ZERO,
+                I256::from(5),
+                I256::MAX
+            ]
+        );
+
+        assert_eq!(I256::ZERO.num_digits(), 0);
+        assert_eq!(I256::from(-999).num_digits(), 3);
+        assert_eq!(I256::MAX.num_digits(), 77);
+        assert_eq!(
+            I256::from(7).checked_mul_pow10(75).unwrap().num_digits(),
+            76
+        );
+        assert_eq!(I256::from(7).checked_mul_pow10(77), None);
+        assert_eq!(
+            I256::from(1).checked_mul_pow10(76).unwrap().num_digits(),
+            77
+        );
+        assert_eq!(I256::from(-1).checked_mul_pow10(u32::MAX), None);
+        assert_eq!(I256::ZERO.checked_mul_pow10(u32::MAX), Some(I256::ZERO));
+    }
+}
@@ -89,7 +89,7 @@ macro_rules! transform_leaf {
         };
 
         // NOTE: Grab a reference here so code below can leverage the blanket impl<T> Deref for &T
-        let $type_variant(ref scalar_type) = scalar.data_type() else {
+        let $type_variant(ref scalar_type) = *scalar.data_type_ref() else {
             $self.set_error(Error::Schema(format!(
                 "Mismatched scalar type while creating Expression: expected {}({:?}), got {:?}",
                 stringify!($type_variant),
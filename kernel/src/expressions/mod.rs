@@ -9,15 +9,22 @@ use itertools::Itertools;
 pub use self::column_names::{
     column_expr, column_name, column_pred, joined_column_expr, joined_column_name, ColumnName,
 };
-pub use self::scalars::{ArrayData, DecimalData, MapData, Scalar, StructData};
+pub use self::i256::I256;
+pub(crate) use self::scalars::{coerce_for_comparison, widest_type, DecimalValue};
+pub use self::scalars::{
+    scalar, ArrayData, BinaryDisplay, DecimalData, DecimalData256, MapData, Scalar, StructData,
+    TimestampPrecision,
+};
 use self::transforms::{ExpressionTransform as _, GetColumnReferences};
 use crate::kernel_predicates::{
     DirectDataSkippingPredicateEvaluator, DirectPredicateEvaluator,
     IndirectDataSkippingPredicateEvaluator,
 };
-use crate::{DataType, DeltaResult, DynPartialEq};
+use crate::schema::{ArrayType, PrimitiveType, StructField, StructType};
+use crate::{DataType, DeltaResult, DynPartialEq, Error};
 
 mod column_names;
+mod i256;
 pub(crate) mod literal_expression_transform;
 mod scalars;
 pub mod transforms;
@@ -25,6 +32,9 @@ pub mod transforms;
 pub type ExpressionRef = std::sync::Arc<Expression>;
 pub type PredicateRef = std::sync::Arc<Predicate>;
 
+/// IN lists with more than this many values are stored as columnar [`ArrayData`].
+const COLUMNAR_IN_LIST_THRESHOLD: usize = 32;
+
 ////////////////////////////////////////////////////////////////////////
 // Operators
 ////////////////////////////////////////////////////////////////////////
@@ -49,6 +59,8 @@ pub enum BinaryPredicateOp {
     Distinct,
     /// IN
     In,
+    /// NOT IN
+    NotIn,
 }
 
 /// A binary expression operator.
@@ -64,6 +76,13 @@ pub enum BinaryExpressionOp {
     Divide,
 }
 
+/// A variadic expression operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariadicExpressionOp {
+    /// The first of the input values that is not NULL, or NULL if they all are
+    Coalesce,
+}
+
 /// A junction (AND/OR) predicate operator.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JunctionPredicateOp {
@@ -203,6 +222,21 @@ pub struct BinaryExpression {
     pub right: Box<Expression>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariadicExpression {
+    /// The operator.
+    pub op: VariadicExpressionOp,
+    /// The input expressions.
+    pub exprs: Vec<Expression>,
+}
+
+impl VariadicExpression {
+    fn new(op: VariadicExpressionOp, exprs: impl IntoIterator<Item = Expression>) -> Self {
+        let exprs = exprs.into_iter().collect();
+        Self { op, exprs }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct JunctionPredicate {
     /// The operator.
@@ -260,6 +294,8 @@ pub enum Expression {
     Struct(Vec<Expression>),
     /// An expression that takes two expressions as input.
     Binary(BinaryExpression),
+    /// An expression that takes any number of expressions as input.
+    Variadic(VariadicExpression),
     /// An expression that the engine defines and implements. Kernel interacts with the expression
     /// only through methods provided by the [`OpaqueExpressionOp`] trait.
     Opaque(OpaqueExpression),
@@ -320,7 +356,7 @@ impl BinaryPredicateOp {
         use BinaryPredicateOp::*;
         match self {
             LessThan | GreaterThan | Equal => true,
-            Distinct | In => false, // tolerates NULL input
+            Distinct | In | NotIn => false, // tolerates NULL input
         }
     }
 }
@@ -380,6 +416,90 @@ impl Expression {
         references.into_inner()
     }
 
+    /// Infers the type of the values this expression produces, when evaluated against rows of
+    /// `input_schema`.
+    ///
+    /// Arithmetic on two different numeric types produces the wider of the two (see
+    /// [`Scalar::try_widen_to`]); decimal operands must have the same precision and scale. A struct
+    /// expression produces a struct whose fields are named after the column they reference, or
+    /// `col1`, `col2`, etc. (by position) if they are not column references. A field referencing a
+    /// nested column is nullable if the column or any struct enclosing it is. Returns an error if a
+    /// referenced column does not exist, if operand types are incompatible, or for opaque and
+    /// unknown expressions, whose output type kernel does not know.
+    pub fn data_type(&self, input_schema: &StructType) -> DeltaResult<DataType> {
+        match self {
+            Self::Literal(value) => Ok(value.data_type()),
+            Self::Column(name) => Ok(resolve_column(name, input_schema)?.0.data_type().clone()),
+            Self::Predicate(_) => Ok(DataType::BOOLEAN),
+            Self::Struct(exprs) => {
+                let fields = exprs.iter().enumerate().map(|(i, expr)| match expr {
+                    Self::Column(name) => {
+                        let (field, nullable) = resolve_column(name, input_schema)?;
+                        Ok(StructField::new(
+                            field.name(),
+                            field.data_type().clone(),
+                            nullable,
+                        ))
+                    }
+                    _ => Ok(StructField::nullable(
+                        format!("col{}", i + 1),
+                        expr.data_type(input_schema)?,
+                    )),
+                });
+                DataType::try_struct_type(fields)
+            }
+            Self::Binary(BinaryExpression { op, left, right }) => {
+                let left_type = left.data_type(input_schema)?;
+                let right_type = right.data_type(input_schema)?;
+                let is_numeric = |ty: &DataType| {
+                    use PrimitiveType::*;
+                    matches!(
+                        ty,
+                        DataType::Primitive(
+                            Byte | Short | Integer | Long | Float | Double | Decimal(_)
+                        )
+                    )
+                };
+                let result_type = match (&left_type, &right_type) {
+                    // An untyped NULL takes on the type of the other operand
+                    (DataType::Null, ty) | (ty, DataType::Null) => Some(ty.clone()),
+                    (l, r) if is_numeric(l) && is_numeric(r) => widest_type(l, r),
+                    _ => None,
+                };
+                match result_type {
+                    Some(ty) if ty == DataType::Null || is_numeric(&ty) => Ok(ty),
+                    _ => Err(Error::invalid_expression(format!(
+                        "Cannot apply {op:?} to operands of type {left_type} and {right_type}"
+                    ))),
+                }
+            }
+            Self::Variadic(VariadicExpression { op, exprs }) => {
+                let types: Vec<_> = exprs
+                    .iter()
+                    .map(|expr| expr.data_type(input_schema))
+                    .try_collect()?;
+                let result_type = types.iter().try_fold(DataType::Null, |acc, ty| match acc {
+                    DataType::Null => Some(ty.clone()),
+                    acc if *ty == DataType::Null || *ty == acc => Some(acc),
+                    acc => widest_type(&acc, ty),
+                });
+                result_type.ok_or_else(|| {
+                    Error::invalid_expression(format!(
+                        "Cannot apply {op:?} to operands of types {}",
+                        types.iter().join(", ")
+                    ))
+                })
+            }
+            Self::Opaque(OpaqueExpression { op, .. }) => Err(Error::unsupported(format!(
+                "Cannot infer the data type of opaque expression {}",
+                op.name()
+            ))),
+            Self::Unknown(name) => Err(Error::unsupported(format!(
+                "Cannot infer the data type of unknown expression {name}"
+            ))),
+        }
+    }
+
     /// Create a new column name expression from input satisfying `FromIterator for ColumnName`.
     pub fn column<A>(field_names: impl IntoIterator<Item = A>) -> Expression
     where
@@ -469,6 +589,22 @@ impl Expression {
         })
     }
 
+    /// Creates a new variadic expression OP(exprs...)
+    pub fn variadic(
+        op: VariadicExpressionOp,
+        exprs: impl IntoIterator<Item = impl Into<Expression>>,
+    ) -> Self {
+        Self::Variadic(VariadicExpression::new(
+            op,
+            exprs.into_iter().map(Into::into),
+        ))
+    }
+
+    /// Creates a new expression COALESCE(exprs...), the first of its inputs that is not NULL
+    pub fn coalesce(exprs: impl IntoIterator<Item = impl Into<Expression>>) -> Self {
+        Self::variadic(VariadicExpressionOp::Coalesce, exprs)
+    }
+
     /// Creates a new opaque expression
     pub fn opaque(
         op: impl OpaqueExpressionOp,
@@ -483,6 +619,34 @@ impl Expression {
     }
 }
 
+/// Looks up the (possibly nested) field a column name refers to in `schema`, along with whether the
+/// column is nullable, i.e. whether the field or any of its ancestors is nullable.
+fn resolve_column<'a>(
+    name: &ColumnName,
+    schema: &'a StructType,
+) -> DeltaResult<(&'a StructField, bool)> {
+    let mut fields = schema;
+    let mut nullable = false;
+    let mut path = name.path().iter().peekable();
+    while let Some(field_name) = path.next() {
+        let field = fields
+            .field(field_name)
+            .ok_or_else(|| Error::missing_column(name))?;
+        // The field of a NULL struct is NULL, so any nullable ancestor makes the column nullable
+        nullable |= field.is_nullable();
+        if path.peek().is_none() {
+            return Ok((field, nullable));
+        }
+        let DataType::Struct(ref struct_type) = field.data_type else {
+            return Err(Error::invalid_expression(format!(
+                "Column {name} references a field of non-struct column {field_name}"
+            )));
+        };
+        fields = struct_type;
+    }
+    Err(Error::invalid_expression("Column name must not be empty"))
+}
+
 impl Predicate {
     /// Returns a set of columns referenced by this predicate.
     pub fn references(&self) -> HashSet<&ColumnName> {
@@ -491,6 +655,52 @@ impl Predicate {
         references.into_inner()
     }
 
+    /// Returns true if every node of this predicate can be pushed down to a parquet reader for row
+    /// group pruning. Pruning only skips whole row groups, so the rows that are read may still not
+    /// satisfy the predicate: the engine must still apply it to the rows afterward.
+    ///
+    /// Pushable nodes are comparisons (`<`, `>`, `=`, `DISTINCT`) between a column and a primitive
+    /// literal (in either order), `IN`/`NOT IN` between a column and a literal list of primitive
+    /// values, `IS NULL` on a column, boolean columns and literals, and any NOT, AND, or OR of
+    /// pushable predicates. Everything else -- including opaque and unknown predicates (e.g.
+    /// `LIKE`), column-to-column comparisons, and arithmetic -- is not pushable.
+    pub fn is_parquet_pushable(&self) -> bool {
+        use BinaryPredicateOp::*;
+        use Expression::{Column, Literal};
+
+        fn is_primitive(val: &Scalar) -> bool {
+            matches!(*val.data_type_ref(), DataType::Primitive(_))
+        }
+        fn is_primitive_list(val: &Scalar) -> bool {
+            match val {
+                Scalar::Array(array) => {
+                    matches!(array.array_type().element_type(), DataType::Primitive(_))
+                }
+                _ => false,
+            }
+        }
+
+        match self {
+            Self::BooleanExpression(expr) => matches!(expr, Column(_) | Literal(_)),
+            Self::Not(pred) => pred.is_parquet_pushable(),
+            Self::Unary(UnaryPredicate { op, expr }) => match op {
+                UnaryPredicateOp::IsNull => matches!(**expr, Column(_) | Literal(_)),
+            },
+            Self::Binary(BinaryPredicate { op, left, right }) => match (op, &**left, &**right) {
+                (LessThan | GreaterThan | Equal | Distinct, Column(_), Literal(val))
+                | (LessThan | GreaterThan | Equal | Distinct, Literal(val), Column(_)) => {
+                    is_primitive(val)
+                }
+                (In | NotIn, Column(_), Literal(list)) => is_primitive_list(list),
+                _ => false,
+            },
+            Self::Junction(JunctionPredicate { preds, .. }) => {
+                preds.iter().all(Self::is_parquet_pushable)
+            }
+            Self::Opaque(_) | Self::Unknown(_) => false,
+        }
+    }
+
     /// Creates a new boolean column reference. See also [`Expression::column`].
     pub fn column<A>(field_names: impl IntoIterator<Item = A>) -> Predicate
     where
@@ -567,6 +777,85 @@ impl Predicate {
         Self::binary(BinaryPredicateOp::Distinct, a, b)
     }
 
+    /// Create a new predicate `expr IN (values...)` from a list of literal values. Unlike a
+    /// [`Scalar::Array`], the values need not all have the same type, so long as they can be widened
+    /// to a common numeric type (e.g. a mix of `Integer` and `Long` values becomes a list of `Long`).
+    /// The input is likewise widened to match the list (or vice versa) during evaluation, so e.g.
+    /// a `Long` column can be compared against a list of `Integer` values.
+    ///
+    /// NULL handling follows SQL semantics: the result is TRUE if the input equals any value in the
+    /// list. Otherwise, the result is NULL if the input is NULL or the list contains a NULL, and
+    /// FALSE if not. So for example `x IN (1, NULL)` is either TRUE or NULL, but never FALSE. An
+    /// empty list produces a literal FALSE.
+    ///
+    /// Returns an error if the values cannot be widened to a common type.
+    pub fn is_in(
+        expr: impl Into<Expression>,
+        values: impl IntoIterator<Item = Scalar>,
+    ) -> DeltaResult<Self> {
+        Self::in_list(BinaryPredicateOp::In, expr, values)
+    }
+
+    /// Create a new predicate `expr NOT IN (values...)` from a list of literal values, with the
+    /// same type widening rules as [`Predicate::is_in`].
+    ///
+    /// NULL handling follows SQL semantics: the result is FALSE if the input equals any value in
+    /// the list. Otherwise, the result is NULL if the input is NULL or the list contains a NULL,
+    /// and TRUE if not. So for example `x NOT IN (1, NULL)` is either FALSE or NULL, but never
+    /// TRUE. An empty list produces a literal TRUE.
+    ///
+    /// Returns an error if the values cannot be widened to a common type.
+    pub fn not_in(
+        expr: impl Into<Expression>,
+        values: impl IntoIterator<Item = Scalar>,
+    ) -> DeltaResult<Self> {
+        Self::in_list(BinaryPredicateOp::NotIn, expr, values)
+    }
+
+    // Helper for [`Predicate::is_in`] and [`Predicate::not_in`]
+    fn in_list(
+        op: BinaryPredicateOp,
+        expr: impl Into<Expression>,
+        values: impl IntoIterator<Item = Scalar>,
+    ) -> DeltaResult<Self> {
+        let values: Vec<_> = values.into_iter().collect();
+        let Some(element_type) = values
+            .iter()
+            .map(Scalar::data_type)
+            .try_fold(None, |ty, next| {
+                let ty = match ty {
+                    Some(ty) => widest_type(&ty, &next)?,
+                    None => next,
+                };
+                Some(Some(ty))
+            })
+        else {
+            return Err(Error::invalid_expression(format!(
+                "{op} list values have no common type: {}",
+                format_child_list(&values)
+            )));
+        };
+        let Some(element_type) = element_type else {
+            return Ok(Self::literal(op == BinaryPredicateOp::NotIn));
+        };
+        let contains_null = values.iter().any(Scalar::is_null);
+        let values = values.iter().map(|value| {
+            value.try_widen_to(&element_type).ok_or_else(|| {
+                Error::internal_error(format!("Failed to widen {value} to {element_type}"))
+            })
+        });
+        let values: Vec<_> = values.try_collect()?;
+        let array_type = ArrayType::new(element_type, contains_null);
+        // Large lists are stored columnar, to avoid the per-element overhead of a `Vec<Scalar>`
+        let columnar = values.len() > COLUMNAR_IN_LIST_THRESHOLD
+            && ArrayData::supports_columnar(array_type.element_type());
+        let array = match columnar {
+            true => ArrayData::try_new_columnar(array_type, values)?,
+            false => ArrayData::try_new(array_type, values)?,
+        };
+        Ok(Self::binary(op, expr, Scalar::Array(array)))
+    }
+
     /// Create a new predicate `self AND other`
     pub fn and(a: impl Into<Self>, b: impl Into<Self>) -> Self {
         Self::and_from([a.into(), b.into()])
@@ -663,6 +952,7 @@ impl Display for BinaryPredicateOp {
             // in our code we take care of this, but theirs might not ...
             Distinct => write!(f, "DISTINCT"),
             In => write!(f, "IN"),
+            NotIn => write!(f, "NOT IN"),
         }
     }
 }
@@ -681,6 +971,11 @@ impl Display for Expression {
             Predicate(p) => write!(f, "{p}"),
             Struct(exprs) => write!(f, "Struct({})", format_child_list(exprs)),
             Binary(BinaryExpression { op, left, right }) => write!(f, "{left} {op} {right}"),
+            Variadic(VariadicExpression { op, exprs }) => match op {
+                VariadicExpressionOp::Coalesce => {
+                    write!(f, "COALESCE({})", format_child_list(exprs))
+                }
+            },
             Opaque(OpaqueExpression { op, exprs }) => {
                 write!(f, "{op:?}({})", format_child_list(exprs))
             }
@@ -777,7 +1072,12 @@ impl<R: Into<Expression>> std::ops::Div<R> for Expression {
 
 #[cfg(test)]
 mod tests {
-    use super::{column_expr, column_pred, Expression as Expr, Predicate as Pred};
+    use super::{
+        column_expr, column_pred, ArrayData, BinaryPredicateOp, Expression as Expr,
+        Predicate as Pred, Scalar,
+    };
+    use crate::schema::{ArrayType, StructField, StructType};
+    use crate::{DataType, Error};
 
     #[test]
     fn test_expression_format() {
@@ -841,4 +1141,157 @@ mod tests {
             assert_eq!(result, expected);
         }
     }
+
+    #[test]
+    fn test_is_in() {
+        let in_list = |values: Vec<Scalar>| {
+            let Pred::Binary(pred) = Pred::is_in(column_expr!("x"), values).unwrap() else {
+                panic!("Expected a binary predicate");
+            };
+            assert_eq!(pred.op, BinaryPredicateOp::In);
+            assert_eq!(*pred.left, column_expr!("x"));
+            let Expr::Literal(Scalar::Array(array)) = *pred.right else {
+                panic!("Expected an array literal");
+            };
+            array
+        };
+
+        // mixed integral values are widened to the widest type in the list
+        let array = in_list(vec![Scalar::Integer(1), Scalar::Long(2)]);
+        assert_eq!(array.array_type(), &ArrayType::new(DataType::LONG, false));
+        #[allow(deprecated)]
+        let elements = array.array_elements();
+        assert_eq!(*elements, [Scalar::Long(1), Scalar::Long(2)]);
+
+        // NULL values are widened as well, and make the list nullable
+        let array = in_list(vec![
+            Scalar::Integer(1),
+            Scalar::Double(2.5),
+            Scalar::Null(DataType::SHORT),
+        ]);
+        assert_eq!(array.array_type(), &ArrayType::new(DataType::DOUBLE, true));
+
+        // large lists are stored columnar, but compare equal to a list of scalars
+        let values: Vec<_> = (0..100).map(Scalar::Integer).collect();
+        let array = in_list(values.clone());
+        let array_type = ArrayType::new(DataType::INTEGER, false);
+        assert_eq!(array, ArrayData::try_new(array_type, values).unwrap());
+
+        assert_eq!(
+            Pred::is_in(column_expr!("x"), []).unwrap(),
+            Pred::literal(false)
+        );
+        assert_eq!(
+            Pred::not_in(column_expr!("x"), []).unwrap(),
+            Pred::literal(true)
+        );
+        assert!(Pred::is_in(column_expr!("x"), [Scalar::Integer(1), "a".into()]).is_err());
+    }
+
+    #[test]
+    fn test_is_parquet_pushable() {
+        let pushable = Pred::and(
+            Pred::lt(column_expr!("a"), Expr::literal(10)),
+            Pred::eq(column_expr!("b"), Expr::literal("x")),
+        );
+        assert!(pushable.is_parquet_pushable());
+
+        let pushable = [
+            Pred::not(Pred::gt(Expr::literal(10), column_expr!("a"))),
+            Pred::or(
+                Pred::is_null(column_expr!("a")),
+                Pred::distinct(column_expr!("b"), Expr::literal("x")),
+            ),
+            Pred::is_in(column_expr!("a"), [Scalar::Integer(1), Scalar::Integer(2)]).unwrap(),
+            Pred::not_in(column_expr!("a"), [Scalar::Integer(1)]).unwrap(),
+            column_pred!("flag"),
+            Pred::literal(true),
+        ];
+        for pred in pushable {
+            assert!(pred.is_parquet_pushable(), "{pred}");
+        }
+
+        // A single unsupported node anywhere makes the whole predicate not pushable
+        let not_pushable = [
+            Pred::and(
+                Pred::lt(column_expr!("a"), Expr::literal(10)),
+                Pred::unknown("b LIKE 'x%'"),
+            ),
+            Pred::lt(column_expr!("a"), column_expr!("b")),
+            Pred::eq(column_expr!("a") + Expr::literal(1), Expr::literal(10)),
+            Pred::is_null(column_expr!("a") + Expr::literal(1)),
+            Pred::from_expr(column_expr!("a") + Expr::literal(1)),
+            Pred::not(Pred::unknown("udf(a)")),
+        ];
+        for pred in not_pushable {
+            assert!(!pred.is_parquet_pushable(), "{pred}");
+        }
+    }
+
+    #[test]
+    fn test_expression_data_type() {
+        let schema = StructType::new([
+            StructField::not_null("a", DataType::INTEGER),
+            StructField::nullable("b", DataType::INTEGER),
+            StructField::nullable("c", DataType::LONG),
+            StructField::nullable("s", DataType::STRING),
+            StructField::nullable(
+                "nested",
+                StructType::new([StructField::not_null("x", DataType::DATE)]),
+            ),
+            StructField::not_null(
+                "required",
+                StructType::new([StructField::not_null("y", DataType::DATE)]),
+            ),
+        ]);
+        let data_type = |expr: Expr| expr.data_type(&schema);
+
+        assert_eq!(
+            data_type(column_expr!("a") + column_expr!("b")).unwrap(),
+            DataType::INTEGER
+        );
+        assert_eq!(
+            data_type(column_expr!("a") + Expr::literal(1.0)).unwrap(),
+            DataType::DOUBLE
+        );
+        assert_eq!(
+            data_type(column_expr!("a") * column_expr!("c")).unwrap(),
+            DataType::LONG
+        );
+        assert_eq!(
+            data_type(column_expr!("b") - Expr::null_literal(DataType::Null)).unwrap(),
+            DataType::INTEGER
+        );
+        assert_eq!(data_type(column_expr!("nested.x")).unwrap(), DataType::DATE);
+        assert_eq!(
+            data_type(Expr::from_pred(column_expr!("a").lt(Expr::literal(3)))).unwrap(),
+            DataType::BOOLEAN
+        );
+
+        let expr = Expr::struct_from([
+            column_expr!("a"),
+            column_expr!("nested.x"),
+            column_expr!("b") / Expr::literal(2i64),
+            Expr::literal("hello"),
+            column_expr!("required.y"),
+        ]);
+        let expected = StructType::new([
+            StructField::not_null("a", DataType::INTEGER),
+            // `nested` is nullable, so `nested.x` is too even though `x` itself is not
+            StructField::nullable("x", DataType::DATE),
+            StructField::nullable("col3", DataType::LONG),
+            StructField::nullable("col4", DataType::STRING),
+            StructField::not_null("y", DataType::DATE),
+        ]);
+        assert_eq!(data_type(expr).unwrap(), expected.into());
+
+        // type mismatches and missing columns are errors
+        let err = data_type(column_expr!("a") + column_expr!("s")).unwrap_err();
+        assert!(matches!(err, Error::InvalidExpressionEvaluation(_)));
+        let err = data_type(column_expr!("nested.x") + Expr::literal(1)).unwrap_err();
+        assert!(matches!(err, Error::InvalidExpressionEvaluation(_)));
+        assert!(data_type(column_expr!("missing")).is_err());
+        assert!(data_type(column_expr!("a.x")).is_err());
+        assert!(data_type(Expr::unknown("mystery")).is_err());
+    }
 }
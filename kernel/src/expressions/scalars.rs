@@ -1,14 +1,19 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
 
-use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
-use itertools::Itertools;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+use itertools::{Either, EitherOrBoth, Itertools};
 
+use super::I256;
 use crate::schema::derive_macro_utils::ToDataType;
 use crate::schema::{ArrayType, DataType, DecimalType, MapType, PrimitiveType, StructField};
 use crate::utils::require;
 use crate::{DeltaResult, Error};
 
+/// A decimal value whose unscaled value is stored in 128 bits, for precisions up to
+/// [`DecimalType::MAX_PRECISION`].
 #[derive(Debug, Clone, PartialEq)]
 pub struct DecimalData {
     bits: i128,
@@ -18,6 +23,13 @@ pub struct DecimalData {
 impl DecimalData {
     pub fn try_new(bits: impl Into<i128>, ty: DecimalType) -> DeltaResult<Self> {
         let bits = bits.into();
+        require!(
+            !ty.requires_256_bits(),
+            Error::invalid_decimal(format!(
+                "Decimal precision {} requires 256-bit storage",
+                ty.precision()
+            ))
+        );
         require!(
             ty.precision() >= get_decimal_precision(bits),
             Error::invalid_decimal(format!(
@@ -44,6 +56,182 @@ impl DecimalData {
     pub fn scale(&self) -> u8 {
         self.ty.scale()
     }
+
+    /// Formats the value for display, e.g. `-1234567.89`. If `grouping` is true, the integer digits
+    /// are grouped in threes with (locale-neutral) comma separators, e.g. `-1,234,567.89`. The
+    /// [`Display`] impl of [`Scalar`] never groups digits.
+    pub fn format(&self, grouping: bool) -> String {
+        format_decimal(I256::from(self.bits()), self.scale(), grouping)
+    }
+}
+
+/// A decimal value whose unscaled value is stored in 256 bits, for precisions greater than
+/// [`DecimalType::MAX_PRECISION`]. Such values only arise from intermediate computations, since
+/// table schemas cannot declare decimals of that precision.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecimalData256 {
+    bits: I256,
+    ty: DecimalType,
+}
+
+impl DecimalData256 {
+    pub fn try_new(bits: impl Into<I256>, ty: DecimalType) -> DeltaResult<Self> {
+        let bits = bits.into();
+        require!(
+            ty.requires_256_bits(),
+            Error::invalid_decimal(format!(
+                "Decimal precision {} does not require 256-bit storage",
+                ty.precision()
+            ))
+        );
+        require!(
+            ty.precision() >= bits.num_digits(),
+            Error::invalid_decimal(format!(
+                "Decimal value {} exceeds precision {}",
+                bits,
+                ty.precision()
+            ))
+        );
+        Ok(Self { bits, ty })
+    }
+
+    pub fn bits(&self) -> I256 {
+        self.bits
+    }
+
+    pub fn ty(&self) -> &DecimalType {
+        &self.ty
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.ty.precision()
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.ty.scale()
+    }
+
+    /// Formats the value like [`DecimalData::format`].
+    pub fn format(&self, grouping: bool) -> String {
+        format_decimal(self.bits(), self.scale(), grouping)
+    }
+}
+
+/// Formats an unscaled decimal value with the given scale, see [`DecimalData::format`].
+fn format_decimal(bits: I256, scale: u8, grouping: bool) -> String {
+    let digits = bits.to_string();
+    let digits = digits.trim_start_matches('-');
+    let scale = scale as usize;
+    // pad with leading zeros so there is at least one digit before the decimal point
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3 + 2);
+    if bits.is_negative() {
+        result.push('-');
+    }
+    for (i, digit) in int_part.chars().enumerate() {
+        if grouping && i > 0 && (int_part.len() - i) % 3 == 0 {
+            result.push(',');
+        }
+        result.push(digit);
+    }
+    if scale > 0 {
+        result.push('.');
+        result.push_str(frac_part);
+    }
+    result
+}
+
+/// A decimal value of either storage width, so that code handling both [`Scalar::Decimal`] and
+/// [`Scalar::Decimal256`] can work with their unscaled values (widened to 256 bits) uniformly.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DecimalValue {
+    Decimal128(DecimalData),
+    Decimal256(DecimalData256),
+}
+
+impl DecimalValue {
+    /// Creates a decimal value of the given type, using 256-bit storage if the type requires it.
+    pub(crate) fn try_new(bits: impl Into<I256>, ty: DecimalType) -> DeltaResult<Self> {
+        let bits = bits.into();
+        if ty.requires_256_bits() {
+            return Ok(Self::Decimal256(DecimalData256::try_new(bits, ty)?));
+        }
+        let Some(bits) = bits.to_i128() else {
+            return Err(Error::invalid_decimal(format!(
+                "Decimal value {} exceeds precision {}",
+                bits,
+                ty.precision()
+            )));
+        };
+        Ok(Self::Decimal128(DecimalData::try_new(bits, ty)?))
+    }
+
+    /// The unscaled value, widened to 256 bits if needed.
+    pub(crate) fn bits(&self) -> I256 {
+        match self {
+            Self::Decimal128(d) => d.bits().into(),
+            Self::Decimal256(d) => d.bits(),
+        }
+    }
+
+    pub(crate) fn ty(&self) -> &DecimalType {
+        match self {
+            Self::Decimal128(d) => d.ty(),
+            Self::Decimal256(d) => d.ty(),
+        }
+    }
+
+    pub(crate) fn precision(&self) -> u8 {
+        self.ty().precision()
+    }
+
+    pub(crate) fn scale(&self) -> u8 {
+        self.ty().scale()
+    }
+}
+
+impl From<DecimalValue> for Scalar {
+    fn from(d: DecimalValue) -> Self {
+        match d {
+            DecimalValue::Decimal128(d) => Self::Decimal(d),
+            DecimalValue::Decimal256(d) => Self::Decimal256(d),
+        }
+    }
+}
+
+/// Lexicographically compares two sequences, where a proper prefix compares less.
+fn lexicographic_cmp<T>(
+    a: impl Iterator<Item = T>,
+    b: impl Iterator<Item = T>,
+    cmp: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    a.zip_longest(b)
+        .map(|pair| match pair {
+            EitherOrBoth::Both(a, b) => cmp(&a, &b),
+            EitherOrBoth::Left(_) => Ordering::Greater,
+            EitherOrBoth::Right(_) => Ordering::Less,
+        })
+        .find(|ord| ord.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Like [`lexicographic_cmp`], but the sequences are incomparable if the first pair of values that
+/// do not compare equal is incomparable.
+fn lexicographic_partial_cmp<T>(
+    a: impl Iterator<Item = T>,
+    b: impl Iterator<Item = T>,
+    cmp: impl Fn(&T, &T) -> Option<Ordering>,
+) -> Option<Ordering> {
+    a.zip_longest(b)
+        .map(|pair| match pair {
+            EitherOrBoth::Both(a, b) => cmp(&a, &b),
+            EitherOrBoth::Left(_) => Some(Ordering::Greater),
+            EitherOrBoth::Right(_) => Some(Ordering::Less),
+        })
+        .find(|ord| *ord != Some(Ordering::Equal))
+        .unwrap_or(Some(Ordering::Equal))
 }
 
 /// Computes the decimal precision of a 128-bit number. The largest possible magnitude is i128::MIN
@@ -53,11 +241,107 @@ fn get_decimal_precision(value: i128) -> u8 {
     value.unsigned_abs().checked_ilog10().map_or(0, |p| p + 1) as _
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct ArrayData {
     tpe: ArrayType,
     /// This exists currently for literal list comparisons, but should not be depended on see below
-    elements: Vec<Scalar>,
+    elements: ArrayElements,
+}
+
+/// Storage for the elements of an [`ArrayData`], selected by the constructor used.
+#[derive(Clone, Debug)]
+enum ArrayElements {
+    /// One [`Scalar`] per element. Cheap to build, and used for small arrays such as literals.
+    Scalars(Vec<Scalar>),
+    /// A single typed buffer of fixed-width element values, which avoids per-element [`Scalar`]
+    /// overhead for large arrays.
+    Columnar(Box<ColumnarElements>),
+}
+
+#[derive(Clone, Debug)]
+struct ColumnarElements {
+    values: ColumnarValues,
+    /// `validity[i]` is false if element `i` is null. `None` if no element is null.
+    validity: Option<Vec<bool>>,
+    /// The elements as scalars, only materialized by the deprecated [`ArrayData::array_elements`].
+    scalars: OnceLock<Box<[Scalar]>>,
+}
+
+macro_rules! define_columnar_values {
+    ($($variant:ident($ty:ty)),+ $(,)?) => {
+        /// A typed buffer of fixed-width primitive values. Null elements are stored as the type's
+        /// default value, and masked out by [`ColumnarElements::validity`].
+        #[derive(Clone, Debug)]
+        enum ColumnarValues {
+            $($variant(Vec<$ty>)),+
+        }
+
+        impl ColumnarValues {
+            fn with_capacity(element_type: &DataType, capacity: usize) -> Option<Self> {
+                match element_type {
+                    $(DataType::Primitive(PrimitiveType::$variant) => {
+                        Some(Self::$variant(Vec::with_capacity(capacity)))
+                    })+
+                    _ => None,
+                }
+            }
+
+            fn len(&self) -> usize {
+                match self {
+                    $(Self::$variant(values) => values.len()),+
+                }
+            }
+
+            fn push(&mut self, value: Scalar) -> DeltaResult<()> {
+                match (self, value) {
+                    $((Self::$variant(values), Scalar::$variant(value)) => values.push(value),)+
+                    $((Self::$variant(values), Scalar::Null(_)) => values.push(Default::default()),)+
+                    (_, value) => {
+                        return Err(Error::internal_error(format!(
+                            "Unexpected columnar array element {value}"
+                        )))
+                    }
+                }
+                Ok(())
+            }
+
+            fn get(&self, index: usize) -> Scalar {
+                match self {
+                    $(Self::$variant(values) => Scalar::$variant(values[index])),+
+                }
+            }
+
+            fn buffer_size(&self) -> usize {
+                match self {
+                    $(Self::$variant(values) => values.capacity() * size_of::<$ty>()),+
+                }
+            }
+        }
+    };
+}
+
+define_columnar_values! {
+    Long(i64),
+    Integer(i32),
+    Short(i16),
+    Byte(i8),
+    Float(f32),
+    Double(f64),
+    Boolean(bool),
+    Date(i32),
+    Timestamp(i64),
+    TimestampNtz(i64),
+}
+
+impl ColumnarElements {
+    fn approx_heap_size(&self) -> usize {
+        let validity = self.validity.as_ref().map_or(0, Vec::capacity);
+        let scalars = self
+            .scalars
+            .get()
+            .map_or(0, |scalars| size_of_val(&**scalars));
+        self.values.buffer_size() + validity + scalars
+    }
 }
 
 impl ArrayData {
@@ -67,37 +351,139 @@ impl ArrayData {
     ) -> DeltaResult<Self> {
         let elements = elements
             .into_iter()
-            .map(|v| {
-                let v = v.into();
-                // disallow nulls if the type is not allowed to contain nulls
-                if !tpe.contains_null() && v.is_null() {
-                    Err(Error::schema(
-                        "Array element cannot be null for non-nullable array",
-                    ))
-                // check element types match
-                } else if *tpe.element_type() != v.data_type() {
-                    Err(Error::Schema(format!(
-                        "Array scalar type mismatch: expected {}, got {}",
-                        tpe.element_type(),
-                        v.data_type()
-                    )))
-                } else {
-                    Ok(v)
-                }
-            })
+            .map(|v| Self::check_element(&tpe, v.into()))
             .try_collect()?;
-        Ok(Self { tpe, elements })
+        Ok(Self {
+            tpe,
+            elements: ArrayElements::Scalars(elements),
+        })
+    }
+
+    /// Like [`ArrayData::try_new`], but stores the elements in a single columnar buffer instead of
+    /// one [`Scalar`] per element. This is cheaper for large arrays (e.g. big `IN` lists), and
+    /// compares equal to the equivalent [`ArrayData::try_new`] array. Only fixed-width primitive
+    /// element types are supported (not strings, binary or decimals).
+    pub fn try_new_columnar(
+        tpe: ArrayType,
+        elements: impl IntoIterator<Item = impl Into<Scalar>>,
+    ) -> DeltaResult<Self> {
+        let elements = elements.into_iter();
+        let capacity = elements.size_hint().0;
+        let Some(mut values) = ColumnarValues::with_capacity(tpe.element_type(), capacity) else {
+            return Err(Error::unsupported(format!(
+                "Columnar arrays of {} are not supported",
+                tpe.element_type()
+            )));
+        };
+        let mut validity = Vec::with_capacity(capacity);
+        for element in elements {
+            let element = Self::check_element(&tpe, element.into())?;
+            validity.push(!element.is_null());
+            values.push(element)?;
+        }
+        let validity = validity.contains(&false).then_some(validity);
+        Ok(Self {
+            tpe,
+            elements: ArrayElements::Columnar(Box::new(ColumnarElements {
+                values,
+                validity,
+                scalars: OnceLock::new(),
+            })),
+        })
+    }
+
+    /// Whether [`ArrayData::try_new_columnar`] supports arrays of the given element type.
+    pub(crate) fn supports_columnar(element_type: &DataType) -> bool {
+        ColumnarValues::with_capacity(element_type, 0).is_some()
+    }
+
+    fn check_element(tpe: &ArrayType, v: Scalar) -> DeltaResult<Scalar> {
+        let v = v.bind_untyped_null(tpe.element_type());
+        // disallow nulls if the type is not allowed to contain nulls
+        if !tpe.contains_null() && v.is_null() {
+            Err(Error::schema(
+                "Array element cannot be null for non-nullable array",
+            ))
+        // check element types match
+        } else if *tpe.element_type() != *v.data_type_ref() {
+            Err(Error::Schema(format!(
+                "Array scalar type mismatch: expected {}, got {}",
+                tpe.element_type(),
+                v.data_type()
+            )))
+        } else {
+            Ok(v)
+        }
     }
 
     pub fn array_type(&self) -> &ArrayType {
         &self.tpe
     }
 
+    /// The number of elements in this array.
+    pub fn len(&self) -> usize {
+        match &self.elements {
+            ArrayElements::Scalars(elements) => elements.len(),
+            ArrayElements::Columnar(elements) => elements.values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the elements of this array. Elements of a columnar array are materialized
+    /// one at a time, without allocating.
+    pub(crate) fn elements(&self) -> impl Iterator<Item = Cow<'_, Scalar>> {
+        match &self.elements {
+            ArrayElements::Scalars(elements) => Either::Left(elements.iter().map(Cow::Borrowed)),
+            ArrayElements::Columnar(elements) => Either::Right((0..self.len()).map(|i| {
+                let is_valid = elements.validity.as_ref().is_none_or(|valid| valid[i]);
+                match is_valid {
+                    true => Cow::Owned(elements.values.get(i)),
+                    false => Cow::Owned(Scalar::Null(self.array_type().element_type().clone())),
+                }
+            })),
+        }
+    }
+
+    /// The elements of this array. The elements of a columnar array are materialized (once) on
+    /// the first call.
     #[deprecated(
         note = "These fields will be removed eventually and are unstable. See https://github.com/delta-io/delta-kernel-rs/issues/291"
     )]
     pub fn array_elements(&self) -> &[Scalar] {
-        &self.elements
+        match &self.elements {
+            ArrayElements::Scalars(elements) => elements,
+            ArrayElements::Columnar(columnar) => columnar
+                .scalars
+                .get_or_init(|| self.elements().map(Cow::into_owned).collect()),
+        }
+    }
+}
+
+impl PartialEq for ArrayData {
+    fn eq(&self, other: &Self) -> bool {
+        self.tpe == other.tpe
+            && self.len() == other.len()
+            && (self.elements())
+                .zip(other.elements())
+                .all(|(a, b)| nested_value_eq(&a, &b))
+    }
+}
+
+// Compares values nested in a struct, array or map for structural equality, where (unlike
+// top-level scalars) null values compare equal.
+fn nested_value_eq(a: &Scalar, b: &Scalar) -> bool {
+    a == b || (a.is_null() && b.is_null())
+}
+
+// Compares values nested in an array or map, consistently with [`nested_value_eq`]: values that
+// are equal compare equal, and otherwise they compare as top-level scalars.
+fn nested_value_partial_cmp(a: &Scalar, b: &Scalar) -> Option<Ordering> {
+    match nested_value_eq(a, b) {
+        true => Some(Ordering::Equal),
+        false => a.partial_cmp(b),
     }
 }
 
@@ -117,9 +503,9 @@ impl MapData {
         let pairs = values
             .into_iter()
             .map(|(key, val)| {
-                let (k, v) = (key.into(), val.into());
+                let (k, v) = (key.into(), val.into().bind_untyped_null(val_type));
                 // check key types match
-                if k.data_type() != *key_type {
+                if *k.data_type_ref() != *key_type {
                     Err(Error::Schema(format!(
                         "Map scalar type mismatch: expected key type {}, got key type {}",
                         key_type,
@@ -129,7 +515,7 @@ impl MapData {
                 } else if k.is_null() {
                     Err(Error::schema("Map key cannot be null"))
                 // check val types match
-                } else if v.data_type() != *val_type {
+                } else if *v.data_type_ref() != *val_type {
                     Err(Error::Schema(format!(
                         "Map scalar type mismatch: expected value type {}, got value type {}",
                         val_type,
@@ -148,8 +534,8 @@ impl MapData {
         Ok(Self { data_type, pairs })
     }
 
-    // TODO: array.elements is deprecated? do we want to expose this? How will FFI get pairs for
-    // visiting?
+    /// The key/value pairs of this map, in order. FFI engines receive them as two parallel lists
+    /// of keys and values when visiting a map literal.
     pub fn pairs(&self) -> &[(Scalar, Scalar)] {
         &self.pairs
     }
@@ -159,7 +545,7 @@ impl MapData {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct StructData {
     fields: Vec<StructField>,
     values: Vec<Scalar>,
@@ -172,6 +558,8 @@ impl StructData {
     /// - if the number of fields and values do not match
     /// - if the data types of the values do not match the data types of the fields
     /// - if a null value is assigned to a non-nullable field
+    ///
+    /// An untyped null value (of type [`DataType::Null`]) takes on the data type of its field.
     pub fn try_new(fields: Vec<StructField>, values: Vec<Scalar>) -> DeltaResult<Self> {
         require!(
             fields.len() == values.len(),
@@ -181,10 +569,15 @@ impl StructData {
                 values.len()
             ))
         );
+        let values: Vec<_> = values
+            .into_iter()
+            .zip(&fields)
+            .map(|(value, field)| value.bind_untyped_null(field.data_type()))
+            .collect();
 
         for (f, a) in fields.iter().zip(&values) {
             require!(
-                f.data_type() == &a.data_type(),
+                *f.data_type() == *a.data_type_ref(),
                 Error::invalid_struct_data(format!(
                     "Incorrect datatype for Struct field {:?}, expected {} got {}",
                     f.name(),
@@ -215,6 +608,15 @@ impl StructData {
     }
 }
 
+impl PartialEq for StructData {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+            && (self.values.iter())
+                .zip(&other.values)
+                .all(|(a, b)| nested_value_eq(a, b))
+    }
+}
+
 /// A single value, which can be null. Used for representing literal values
 /// in [Expressions][crate::expressions::Expression].
 #[derive(Debug, Clone)]
@@ -245,6 +647,9 @@ pub enum Scalar {
     Binary(Vec<u8>),
     /// Decimal value with a given precision and scale.
     Decimal(DecimalData),
+    /// Decimal value whose precision exceeds [`DecimalType::MAX_PRECISION`], e.g. the result of
+    /// an intermediate computation. See [`DecimalData256`].
+    Decimal256(DecimalData256),
     /// Null value with a given data type.
     Null(DataType),
     /// Struct value
@@ -256,25 +661,78 @@ pub enum Scalar {
 }
 
 impl Scalar {
+    /// The data type of this scalar. Prefer [`Self::data_type_ref`] when an owned type is not
+    /// needed, since that avoids cloning the type of primitive and null values.
     pub fn data_type(&self) -> DataType {
+        self.data_type_ref().into_owned()
+    }
+
+    /// The data type of this scalar, borrowed whenever possible. Only decimal values (whose type is
+    /// built on the fly, without allocating) and struct, array and map values (whose type is built
+    /// from, and clones, their nested types) produce an owned type.
+    pub fn data_type_ref(&self) -> Cow<'_, DataType> {
         match self {
-            Self::Integer(_) => DataType::INTEGER,
-            Self::Long(_) => DataType::LONG,
-            Self::Short(_) => DataType::SHORT,
-            Self::Byte(_) => DataType::BYTE,
-            Self::Float(_) => DataType::FLOAT,
-            Self::Double(_) => DataType::DOUBLE,
-            Self::String(_) => DataType::STRING,
-            Self::Boolean(_) => DataType::BOOLEAN,
-            Self::Timestamp(_) => DataType::TIMESTAMP,
-            Self::TimestampNtz(_) => DataType::TIMESTAMP_NTZ,
-            Self::Date(_) => DataType::DATE,
-            Self::Binary(_) => DataType::BINARY,
-            Self::Decimal(d) => DataType::from(*d.ty()),
-            Self::Null(data_type) => data_type.clone(),
-            Self::Struct(data) => DataType::struct_type(data.fields.clone()),
-            Self::Array(data) => data.tpe.clone().into(),
-            Self::Map(data) => data.data_type.clone().into(),
+            Self::Integer(_) => Cow::Borrowed(&DataType::INTEGER),
+            Self::Long(_) => Cow::Borrowed(&DataType::LONG),
+            Self::Short(_) => Cow::Borrowed(&DataType::SHORT),
+            Self::Byte(_) => Cow::Borrowed(&DataType::BYTE),
+            Self::Float(_) => Cow::Borrowed(&DataType::FLOAT),
+            Self::Double(_) => Cow::Borrowed(&DataType::DOUBLE),
+            Self::String(_) => Cow::Borrowed(&DataType::STRING),
+            Self::Boolean(_) => Cow::Borrowed(&DataType::BOOLEAN),
+            Self::Timestamp(_) => Cow::Borrowed(&DataType::TIMESTAMP),
+            Self::TimestampNtz(_) => Cow::Borrowed(&DataType::TIMESTAMP_NTZ),
+            Self::Date(_) => Cow::Borrowed(&DataType::DATE),
+            Self::Binary(_) => Cow::Borrowed(&DataType::BINARY),
+            Self::Decimal(d) => Cow::Owned(DataType::from(*d.ty())),
+            Self::Decimal256(d) => Cow::Owned(DataType::from(*d.ty())),
+            Self::Null(data_type) => Cow::Borrowed(data_type),
+            Self::Struct(data) => Cow::Owned(DataType::struct_type(data.fields.clone())),
+            Self::Array(data) => Cow::Owned(data.tpe.clone().into()),
+            Self::Map(data) => Cow::Owned(data.data_type.clone().into()),
+        }
+    }
+
+    /// Approximates the number of heap bytes owned by this scalar, e.g. for memory accounting of
+    /// large `IN` lists. This counts the (allocated) buffers of strings, binary values, and the
+    /// elements of arrays, maps and structs, recursively. The inline size of the scalar itself and
+    /// any type information (such as struct field names) are not included.
+    pub fn approx_heap_size(&self) -> usize {
+        fn scalars_heap_size(scalars: &Vec<Scalar>) -> usize {
+            let buffer = scalars.capacity() * size_of::<Scalar>();
+            buffer + scalars.iter().map(Scalar::approx_heap_size).sum::<usize>()
+        }
+        match self {
+            Self::String(s) => s.capacity(),
+            Self::Binary(b) => b.capacity(),
+            Self::Struct(data) => scalars_heap_size(&data.values),
+            Self::Array(data) => match &data.elements {
+                ArrayElements::Scalars(elements) => scalars_heap_size(elements),
+                ArrayElements::Columnar(elements) => {
+                    size_of::<ColumnarElements>() + elements.approx_heap_size()
+                }
+            },
+            Self::Map(data) => {
+                let buffer = data.pairs.capacity() * size_of::<(Scalar, Scalar)>();
+                let pairs = data.pairs.iter();
+                buffer
+                    + pairs
+                        .map(|(k, v)| k.approx_heap_size() + v.approx_heap_size())
+                        .sum::<usize>()
+            }
+            Self::Integer(_)
+            | Self::Long(_)
+            | Self::Short(_)
+            | Self::Byte(_)
+            | Self::Float(_)
+            | Self::Double(_)
+            | Self::Boolean(_)
+            | Self::Timestamp(_)
+            | Self::TimestampNtz(_)
+            | Self::Date(_)
+            | Self::Decimal(_)
+            | Self::Decimal256(_)
+            | Self::Null(_) => 0,
         }
     }
 
@@ -283,6 +741,167 @@ impl Scalar {
         matches!(self, Self::Null(_))
     }
 
+    // The value of a decimal scalar of either storage width, or `None` for any other scalar
+    pub(crate) fn as_decimal(&self) -> Option<DecimalValue> {
+        match self {
+            Self::Decimal(d) => Some(DecimalValue::Decimal128(d.clone())),
+            Self::Decimal256(d) => Some(DecimalValue::Decimal256(d.clone())),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this scalar is a floating point NaN.
+    pub fn is_nan(&self) -> bool {
+        match self {
+            Self::Float(f) => f.is_nan(),
+            Self::Double(d) => d.is_nan(),
+            _ => false,
+        }
+    }
+
+    /// Same as `==`, except that two NaNs of the same type compare equal, e.g. for grouping. The
+    /// [`PartialEq`] impl follows IEEE 754, where NaN is not equal to anything (including itself).
+    pub fn nan_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Float(a), Self::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Self::Double(a), Self::Double(b)) => a == b || (a.is_nan() && b.is_nan()),
+            _ => self == other,
+        }
+    }
+
+    /// Same as `==`, except that two nulls compare equal regardless of their types, e.g. for
+    /// grouping. This deviates from SQL semantics, where NULL is not equal to anything (including
+    /// itself), which the [`PartialEq`] impl follows.
+    pub fn null_eq_any_type(&self, other: &Self) -> bool {
+        (self.is_null() && other.is_null()) || self == other
+    }
+
+    /// Resolves an untyped null (a null of type [`DataType::Null`]) to a null of the given data
+    /// type. Any other value is returned unchanged.
+    fn bind_untyped_null(self, data_type: &DataType) -> Self {
+        match self {
+            Self::Null(DataType::Null) => Self::Null(data_type.clone()),
+            _ => self,
+        }
+    }
+
+    /// Compares two scalars under a total order, e.g. to deterministically sort a `Vec<Scalar>`
+    /// with `values.sort_by(Scalar::total_cmp)`. Unlike [`PartialOrd`], every pair of scalars is
+    /// comparable:
+    ///
+    /// - Values of the same type compare naturally. Floating point values are ordered by
+    ///   [`f64::total_cmp`], i.e. `-NaN < -inf < ... < inf < NaN`, except that `-0.0` and `0.0`
+    ///   compare equal, consistently with `==`.
+    /// - Values of different types are ordered by type, in [`Scalar`] variant declaration order
+    ///   (`Integer < Long < Short < ... < Map`). Decimals of different precision or scale are
+    ///   ordered by precision, then scale.
+    /// - Nulls sort after all non-null values, and compare equal to each other regardless of type.
+    /// - Structs, arrays and maps compare their values lexicographically.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        use Scalar::*;
+        match (self, other) {
+            (Null(_), Null(_)) => Ordering::Equal,
+            (Null(_), _) => Ordering::Greater,
+            (_, Null(_)) => Ordering::Less,
+            (Integer(a), Integer(b)) => a.cmp(b),
+            (Long(a), Long(b)) => a.cmp(b),
+            (Short(a), Short(b)) => a.cmp(b),
+            (Byte(a), Byte(b)) => a.cmp(b),
+            // Only -0.0 is normalized, so the sign (and payload) of NaNs is left untouched
+            (Float(a), Float(b)) => {
+                let normalize = |x: f32| if x == 0.0 { 0.0 } else { x };
+                normalize(*a).total_cmp(&normalize(*b))
+            }
+            (Double(a), Double(b)) => {
+                let normalize = |x: f64| if x == 0.0 { 0.0 } else { x };
+                normalize(*a).total_cmp(&normalize(*b))
+            }
+            (String(a), String(b)) => a.cmp(b),
+            (Boolean(a), Boolean(b)) => a.cmp(b),
+            (Timestamp(a), Timestamp(b)) => a.cmp(b),
+            (TimestampNtz(a), TimestampNtz(b)) => a.cmp(b),
+            (Date(a), Date(b)) => a.cmp(b),
+            (Binary(a), Binary(b)) => a.cmp(b),
+            (Decimal(_) | Decimal256(_), Decimal(_) | Decimal256(_)) => {
+                let (Some(a), Some(b)) = (self.as_decimal(), other.as_decimal()) else {
+                    unreachable!("both values are decimals");
+                };
+                (a.precision(), a.scale(), a.bits()).cmp(&(b.precision(), b.scale(), b.bits()))
+            }
+            (Struct(a), Struct(b)) => {
+                lexicographic_cmp(a.values.iter(), b.values.iter(), |a, b| a.total_cmp(b))
+            }
+            (Array(a), Array(b)) => {
+                lexicographic_cmp(a.elements(), b.elements(), |a, b| a.total_cmp(b))
+            }
+            (Map(a), Map(b)) => {
+                lexicographic_cmp(a.pairs.iter(), b.pairs.iter(), |(ak, av), (bk, bv)| {
+                    ak.total_cmp(bk).then_with(|| av.total_cmp(bv))
+                })
+            }
+            (a, b) => a.type_order().cmp(&b.type_order()),
+        }
+    }
+
+    // The position of this scalar's variant in declaration order, used by [`Self::total_cmp`]
+    fn type_order(&self) -> u8 {
+        match self {
+            Self::Integer(_) => 0,
+            Self::Long(_) => 1,
+            Self::Short(_) => 2,
+            Self::Byte(_) => 3,
+            Self::Float(_) => 4,
+            Self::Double(_) => 5,
+            Self::String(_) => 6,
+            Self::Boolean(_) => 7,
+            Self::Timestamp(_) => 8,
+            Self::TimestampNtz(_) => 9,
+            Self::Date(_) => 10,
+            Self::Binary(_) => 11,
+            Self::Decimal(_) | Self::Decimal256(_) => 12,
+            Self::Null(_) => 13,
+            Self::Struct(_) => 14,
+            Self::Array(_) => 15,
+            Self::Map(_) => 16,
+        }
+    }
+
+    /// Constructs a struct value from `(name, value)` pairs, inferring each field's type from its
+    /// value. A field is nullable if (and only if) its value is null. See also [`scalar!`].
+    ///
+    /// [`scalar!`]: crate::expressions::scalar
+    pub fn struct_from(
+        fields: impl IntoIterator<Item = (impl Into<String>, impl Into<Scalar>)>,
+    ) -> DeltaResult<Self> {
+        let (fields, values): (Vec<_>, Vec<_>) = fields
+            .into_iter()
+            .map(|(name, value)| {
+                let value = value.into();
+                let field = StructField::new(name, value.data_type(), value.is_null());
+                (field, value)
+            })
+            .unzip();
+        Ok(Self::Struct(StructData::try_new(fields, values)?))
+    }
+
+    /// Constructs an array value from its elements, inferring the element type from the first
+    /// non-null element (or the first element, if all are null). The array contains nulls if (and
+    /// only if) any element is null. Returns an error if there are no elements, since the element
+    /// type cannot be inferred. See also [`scalar!`].
+    ///
+    /// [`scalar!`]: crate::expressions::scalar
+    pub fn array_from(elements: impl IntoIterator<Item = impl Into<Scalar>>) -> DeltaResult<Self> {
+        let elements: Vec<Scalar> = elements.into_iter().map(Into::into).collect();
+        let Some(first) = elements.iter().find(|e| !e.is_null()).or(elements.first()) else {
+            return Err(Error::schema(
+                "Cannot infer the element type of an empty array",
+            ));
+        };
+        let contains_null = elements.iter().any(Scalar::is_null);
+        let array_type = ArrayType::new(first.data_type(), contains_null);
+        Ok(Self::Array(ArrayData::try_new(array_type, elements)?))
+    }
+
     /// Constructs a Decimal value from raw parts
     pub fn decimal(bits: impl Into<i128>, precision: u8, scale: u8) -> DeltaResult<Self> {
         let dtype = DecimalType::try_new(precision, scale)?;
@@ -290,6 +909,43 @@ impl Scalar {
         Ok(Self::Decimal(dval))
     }
 
+    /// Constructs a decimal value from raw parts, with an unscaled value that may need 256 bits.
+    /// Precisions up to [`DecimalType::MAX_PRECISION`] produce a [`Scalar::Decimal`], and greater
+    /// precisions (up to [`DecimalType::MAX_WIDE_PRECISION`]) a [`Scalar::Decimal256`].
+    pub fn decimal256(bits: impl Into<I256>, precision: u8, scale: u8) -> DeltaResult<Self> {
+        let dtype = DecimalType::try_new_wide(precision, scale)?;
+        Ok(DecimalValue::try_new(bits, dtype)?.into())
+    }
+
+    /// Parses a decimal value from a string, inferring its type instead of requiring one up front:
+    /// the scale is the number of fractional digits (after applying any exponent), and the
+    /// precision is the number of digits needed to represent the value at that scale. For example,
+    /// `"12.34"` parses as `DECIMAL(4, 2)`. Returns an error if the string is not a valid decimal,
+    /// or if the inferred precision or scale exceeds [`DecimalType::MAX_PRECISION`].
+    pub fn parse_decimal_infer(raw: &str) -> DeltaResult<Self> {
+        let parse_error = || Error::invalid_decimal(format!("Failed to parse decimal: {raw}"));
+        let (int, scale) = PrimitiveType::parse_decimal_parts(raw, parse_error)?;
+        // Check the scale before applying it, so that a huge exponent is rejected up front
+        let max_precision = i128::from(DecimalType::MAX_PRECISION);
+        require!(
+            (-max_precision..=max_precision).contains(&scale),
+            parse_error()
+        );
+        // A negative scale (e.g. `1.5E3`) means the value is an integer with trailing zeros
+        let (int, scale) = match u8::try_from(scale) {
+            Ok(scale) => (int, scale),
+            Err(_) => {
+                let zeros = u32::try_from(-scale).map_err(|_| parse_error())?;
+                let int = int.checked_mul_pow10(zeros).ok_or_else(parse_error)?;
+                (int, 0)
+            }
+        };
+        let precision = int.num_digits().max(scale).max(1);
+        require!(precision <= DecimalType::MAX_PRECISION, parse_error());
+        let bits = int.to_i128().ok_or_else(parse_error)?;
+        Self::decimal(bits, precision, scale)
+    }
+
     /// Constructs a Scalar timestamp (in UTC) from an `i64` millisecond since unix epoch
     pub(crate) fn timestamp_from_millis(millis: i64) -> DeltaResult<Self> {
         let Some(timestamp) = DateTime::from_timestamp_millis(millis) else {
@@ -300,7 +956,32 @@ impl Scalar {
         Ok(Self::Timestamp(timestamp.timestamp_micros()))
     }
 
-    /// Attempts to add two scalars, returning None if they were incompatible.
+    /// Constructs a Scalar timestamp (in UTC) from an `i64` microseconds since unix epoch. Unlike
+    /// constructing [`Scalar::Timestamp`] directly, this fails if the timestamp is outside the
+    /// range of dates that can be converted to a calendar date and time.
+    pub fn timestamp_from_micros(micros: i64) -> DeltaResult<Self> {
+        match DateTime::from_timestamp_micros(micros) {
+            Some(_) => Ok(Self::Timestamp(micros)),
+            None => Err(Error::generic(format!(
+                "Failed to create microsecond timestamp from {micros}"
+            ))),
+        }
+    }
+
+    /// Constructs a Scalar date from an `i32` days since unix epoch. Unlike constructing
+    /// [`Scalar::Date`] directly, this fails if the date is outside the range of dates that can be
+    /// converted to a calendar date.
+    pub fn date_from_days(days: i32) -> DeltaResult<Self> {
+        let date = TimeDelta::try_days(days.into())
+            .and_then(|days| DateTime::UNIX_EPOCH.checked_add_signed(days));
+        match date {
+            Some(_) => Ok(Self::Date(days)),
+            None => Err(Error::generic(format!("Failed to create date from {days}"))),
+        }
+    }
+
+    /// Attempts to add two scalars, returning None if they were incompatible. If either operand
+    /// is NULL, the result is a NULL of the operands' common type.
     pub fn try_add(&self, other: &Scalar) -> Option<Scalar> {
         use Scalar::*;
         let result = match (self, other) {
@@ -308,12 +989,14 @@ impl Scalar {
             (Long(a), Long(b)) => Long(a.checked_add(*b)?),
             (Short(a), Short(b)) => Short(a.checked_add(*b)?),
             (Byte(a), Byte(b)) => Byte(a.checked_add(*b)?),
+            (Null(_), _) | (_, Null(_)) => return self.null_arithmetic_result(other),
             _ => return None,
         };
         Some(result)
     }
 
-    /// Attempts to subtract two scalars, returning None if they were incompatible.
+    /// Attempts to subtract two scalars, returning None if they were incompatible. If either operand
+    /// is NULL, the result is a NULL of the operands' common type.
     pub fn try_sub(&self, other: &Scalar) -> Option<Scalar> {
         use Scalar::*;
         let result = match (self, other) {
@@ -321,102 +1004,412 @@ impl Scalar {
             (Long(a), Long(b)) => Long(a.checked_sub(*b)?),
             (Short(a), Short(b)) => Short(a.checked_sub(*b)?),
             (Byte(a), Byte(b)) => Byte(a.checked_sub(*b)?),
+            (Null(_), _) | (_, Null(_)) => return self.null_arithmetic_result(other),
             _ => return None,
         };
         Some(result)
     }
 
-    /// Attempts to multiply two scalars, returning None if they were incompatible.
-    pub fn try_mul(&self, other: &Scalar) -> Option<Scalar> {
-        use Scalar::*;
-        let result = match (self, other) {
-            (Integer(a), Integer(b)) => Integer(a.checked_mul(*b)?),
-            (Long(a), Long(b)) => Long(a.checked_mul(*b)?),
-            (Short(a), Short(b)) => Short(a.checked_mul(*b)?),
-            (Byte(a), Byte(b)) => Byte(a.checked_mul(*b)?),
-            _ => return None,
-        };
-        Some(result)
+    /// Attempts to multiply two scalars, returning None if they were incompatible. If either operand
+    /// is NULL, the result is a NULL of the operands' common type.
+    pub fn try_mul(&self, other: &Scalar) -> Option<Scalar> {
+        use Scalar::*;
+        let result = match (self, other) {
+            (Integer(a), Integer(b)) => Integer(a.checked_mul(*b)?),
+            (Long(a), Long(b)) => Long(a.checked_mul(*b)?),
+            (Short(a), Short(b)) => Short(a.checked_mul(*b)?),
+            (Byte(a), Byte(b)) => Byte(a.checked_mul(*b)?),
+            (Null(_), _) | (_, Null(_)) => return self.null_arithmetic_result(other),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// Attempts to divide two scalars, returning None if they were incompatible. If either operand
+    /// is NULL, the result is a NULL of the operands' common type.
+    pub fn try_div(&self, other: &Scalar) -> Option<Scalar> {
+        use Scalar::*;
+        let result = match (self, other) {
+            (Integer(a), Integer(b)) => Integer(a.checked_div(*b)?),
+            (Long(a), Long(b)) => Long(a.checked_div(*b)?),
+            (Short(a), Short(b)) => Short(a.checked_div(*b)?),
+            (Byte(a), Byte(b)) => Byte(a.checked_div(*b)?),
+            (Null(_), _) | (_, Null(_)) => return self.null_arithmetic_result(other),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// The result of an arithmetic operation with a NULL operand: a NULL of the operands' common
+    /// type (an untyped NULL adopts the type of the other operand). Returns None if the operand
+    /// types differ or do not support arithmetic.
+    fn null_arithmetic_result(&self, other: &Scalar) -> Option<Scalar> {
+        let result_type = match (self.data_type(), other.data_type()) {
+            (DataType::Null, data_type) | (data_type, DataType::Null) => data_type,
+            (a, b) if a == b => a,
+            _ => return None,
+        };
+        let arithmetic_types = [
+            DataType::INTEGER,
+            DataType::LONG,
+            DataType::SHORT,
+            DataType::BYTE,
+        ];
+        arithmetic_types
+            .contains(&result_type)
+            .then(|| Scalar::Null(result_type))
+    }
+
+    /// Attempts to widen this scalar to the data type `to`, which must either be this scalar's own
+    /// type or (for numeric scalars) a wider numeric type, as determined by [`widest_type`]. A NULL
+    /// widens to a NULL of type `to`. Returns None if the conversion is not a widening.
+    pub(crate) fn try_widen_to(&self, to: &DataType) -> Option<Scalar> {
+        use Scalar::*;
+        let from = self.data_type();
+        if from == *to {
+            return Some(self.clone());
+        }
+        if widest_type(&from, to).as_ref() != Some(to) {
+            return None;
+        }
+        let (int, float) = match self {
+            Byte(v) => (Some(*v as i64), *v as f64),
+            Short(v) => (Some(*v as i64), *v as f64),
+            Integer(v) => (Some(*v as i64), *v as f64),
+            Long(v) => (Some(*v), *v as f64),
+            Float(v) => (None, *v as f64),
+            Null(_) => return Some(Null(to.clone())),
+            _ => return None,
+        };
+        // The target is at least as wide as the source, so none of these casts can truncate.
+        let DataType::Primitive(to) = to else {
+            return None;
+        };
+        let result = match to {
+            PrimitiveType::Short => Short(int? as i16),
+            PrimitiveType::Integer => Integer(int? as i32),
+            PrimitiveType::Long => Long(int?),
+            PrimitiveType::Float => Float(float as f32),
+            PrimitiveType::Double => Double(float),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// Returns the narrowest type that all of the given scalars can be coerced to, e.g. to build an
+    /// `IN` list or array from literals of different types. Numeric types are promoted along the
+    /// precedence `byte -> short -> int -> long -> decimal -> float -> double`, as in Spark:
+    ///
+    /// - Integral and floating point types widen to the widest of them.
+    /// - Integral types and decimals become a decimal wide enough for any of their values.
+    /// - Decimals and floating point types become a `double`.
+    ///
+    /// Untyped nulls (of type [`DataType::Null`]) can be coerced to any type. Returns None if
+    /// `scalars` is empty or their types are incompatible (e.g. integer and string).
+    pub fn common_type(scalars: &[Scalar]) -> Option<DataType> {
+        let (first, rest) = scalars.split_first()?;
+        rest.iter().try_fold(first.data_type(), |common, scalar| {
+            common_type_of(&common, &scalar.data_type_ref())
+        })
+    }
+}
+
+// Returns the narrowest type that values of types `a` and `b` can both be coerced to, see
+// [`Scalar::common_type`].
+fn common_type_of(a: &DataType, b: &DataType) -> Option<DataType> {
+    use PrimitiveType::*;
+    let (DataType::Primitive(pa), DataType::Primitive(pb)) = (a, b) else {
+        return match (a, b) {
+            (DataType::Null, _) => Some(b.clone()),
+            (_, DataType::Null) => Some(a.clone()),
+            _ => (a == b).then(|| a.clone()),
+        };
+    };
+    let ty = match (pa, pb) {
+        (Decimal(da), Decimal(db)) => Decimal(common_decimal_type(da, db)?),
+        (Decimal(_), Float | Double) | (Float | Double, Decimal(_)) => Double,
+        (Decimal(d), other) | (other, Decimal(d)) => {
+            let precision = integral_decimal_precision(other)?;
+            Decimal(common_decimal_type(
+                d,
+                &DecimalType::try_new(precision, 0).ok()?,
+            )?)
+        }
+        _ => return widest_type(a, b),
+    };
+    Some(DataType::Primitive(ty))
+}
+
+/// The numeric types, in the order in which they can be implicitly widened.
+const NUMERIC_WIDENING_ORDER: [PrimitiveType; 6] = [
+    PrimitiveType::Byte,
+    PrimitiveType::Short,
+    PrimitiveType::Integer,
+    PrimitiveType::Long,
+    PrimitiveType::Float,
+    PrimitiveType::Double,
+];
+
+/// Returns the type that values of types `a` and `b` can both be widened to, so they can be compared
+/// with each other: either the type itself (if both are the same) or the wider of two numeric types.
+/// Like Spark, integral types widen to `Float` and `Double` even though large values lose precision.
+/// Returns None if the types are incompatible.
+pub(crate) fn widest_type(a: &DataType, b: &DataType) -> Option<DataType> {
+    if a == b {
+        return Some(a.clone());
+    }
+    let rank = |ty: &DataType| match ty {
+        DataType::Primitive(ty) => NUMERIC_WIDENING_ORDER.iter().position(|n| n == ty),
+        _ => None,
+    };
+    let widest = if rank(a)? >= rank(b)? { a } else { b };
+    Some(widest.clone())
+}
+
+/// Coerces two scalars to a common type so they can be compared with each other, following Spark's
+/// implicit type coercion rules for binary comparisons. Scalars that already have the same type (or
+/// where either is NULL) are returned unchanged. Otherwise, numeric operands are promoted along the
+/// precedence `byte -> short -> int -> long -> decimal -> float -> double`:
+///
+/// - Two integral or floating point values widen to the wider type, see [`widest_type`].
+/// - An integral value and a decimal both become a decimal wide enough for either value, as do two
+///   decimals of different precision or scale.
+/// - A decimal and a floating point value both become a `double`.
+/// - A string compared with a numeric value is parsed as that numeric type (or as a decimal of
+///   inferred precision and scale, when compared with a decimal).
+///
+/// Returns None if the types are incomparable (e.g. boolean vs string), or if a string cannot be
+/// parsed as the other operand's type -- which is consistent with Spark, where the failed cast
+/// produces NULL.
+pub(crate) fn coerce_for_comparison<'a>(
+    a: &'a Scalar,
+    b: &'a Scalar,
+) -> Option<(Cow<'a, Scalar>, Cow<'a, Scalar>)> {
+    use Scalar::*;
+    match (a, b) {
+        (Null(_), _) | (_, Null(_)) => return Some((Cow::Borrowed(a), Cow::Borrowed(b))),
+        _ if a.data_type() == b.data_type() => {
+            return Some((Cow::Borrowed(a), Cow::Borrowed(b)));
+        }
+        (String(s), _) => {
+            let a = parse_as_type_of(s, b)?;
+            let (a, b) = coerce_for_comparison(&a, b)?;
+            return Some((Cow::Owned(a.into_owned()), Cow::Owned(b.into_owned())));
+        }
+        (_, String(_)) => {
+            let (b, a) = coerce_for_comparison(b, a)?;
+            return Some((a, b));
+        }
+        _ => (),
+    }
+    let (a, b) = match (decimal_for_comparison(a), decimal_for_comparison(b)) {
+        // (decimal or integral) vs (decimal or integral), where at least one is a decimal
+        (Some(da), Some(db)) if a.as_decimal().is_some() || b.as_decimal().is_some() => {
+            coerce_decimals(&da, &db)?
+        }
+        // decimal vs floating point (or any other type, which fails to widen)
+        (Some(d), None) if a.as_decimal().is_some() => (
+            Double(decimal_to_f64(&d)?),
+            b.try_widen_to(&DataType::DOUBLE)?,
+        ),
+        (None, Some(d)) if b.as_decimal().is_some() => (
+            a.try_widen_to(&DataType::DOUBLE)?,
+            Double(decimal_to_f64(&d)?),
+        ),
+        _ => {
+            let ty = widest_type(&a.data_type(), &b.data_type())?;
+            (a.try_widen_to(&ty)?, b.try_widen_to(&ty)?)
+        }
+    };
+    Some((Cow::Owned(a), Cow::Owned(b)))
+}
+
+// Parses a string as the type of `other`, for comparing the string with `other`. Decimal strings
+// infer their own precision and scale, which are reconciled with `other` afterward.
+fn parse_as_type_of(raw: &str, other: &Scalar) -> Option<Scalar> {
+    let parsed = match other.data_type() {
+        DataType::Primitive(PrimitiveType::Decimal(_)) => Scalar::parse_decimal_infer(raw.trim()),
+        DataType::Primitive(
+            ty @ (PrimitiveType::Byte
+            | PrimitiveType::Short
+            | PrimitiveType::Integer
+            | PrimitiveType::Long
+            | PrimitiveType::Float
+            | PrimitiveType::Double),
+        ) => ty.parse_scalar(raw),
+        _ => return None,
+    };
+    parsed.ok().filter(|parsed| !parsed.is_null())
+}
+
+// Returns the value of a decimal or integral scalar as a decimal, or None for any other type.
+// Integral values use the smallest decimal type that can hold any value of their type.
+fn decimal_for_comparison(value: &Scalar) -> Option<DecimalValue> {
+    let bits = match value {
+        Scalar::Decimal(_) | Scalar::Decimal256(_) => return value.as_decimal(),
+        Scalar::Byte(v) => i128::from(*v),
+        Scalar::Short(v) => i128::from(*v),
+        Scalar::Integer(v) => i128::from(*v),
+        Scalar::Long(v) => i128::from(*v),
+        _ => return None,
+    };
+    let DataType::Primitive(ty) = value.data_type() else {
+        return None;
+    };
+    let precision = integral_decimal_precision(&ty)?;
+    DecimalValue::try_new(bits, DecimalType::try_new(precision, 0).ok()?).ok()
+}
+
+// The precision of the smallest decimal type that can hold any value of an integral type, or None
+// for any other type.
+fn integral_decimal_precision(ty: &PrimitiveType) -> Option<u8> {
+    match ty {
+        PrimitiveType::Byte => Some(3),
+        PrimitiveType::Short => Some(5),
+        PrimitiveType::Integer => Some(10),
+        PrimitiveType::Long => Some(20),
+        _ => None,
+    }
+}
+
+// The decimal type with the larger of the scales of `a` and `b`, and enough integer digits for
+// values of either. Returns None if that would need more than the maximum (wide) precision.
+fn common_decimal_type(a: &DecimalType, b: &DecimalType) -> Option<DecimalType> {
+    let scale = a.scale().max(b.scale());
+    let int_digits = (a.precision() - a.scale()).max(b.precision() - b.scale());
+    DecimalType::try_new_wide(int_digits.checked_add(scale)?, scale).ok()
+}
+
+// Rescales two decimals to a common type, see [`common_decimal_type`].
+fn coerce_decimals(a: &DecimalValue, b: &DecimalValue) -> Option<(Scalar, Scalar)> {
+    let ty = common_decimal_type(a.ty(), b.ty())?;
+    let scale = ty.scale();
+    let rescale = |d: &DecimalValue| -> Option<Scalar> {
+        let bits = d.bits().checked_mul_pow10((scale - d.scale()) as u32)?;
+        Some(DecimalValue::try_new(bits, ty).ok()?.into())
+    };
+    Some((rescale(a)?, rescale(b)?))
+}
+
+fn decimal_to_f64(d: &DecimalValue) -> Option<f64> {
+    format_decimal(d.bits(), d.scale(), false).parse().ok()
+}
+
+/// How [`Scalar::format_binary`] renders binary values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryDisplay {
+    /// All bytes in hex, e.g. `0x01abff`.
+    Hex,
+    /// All bytes in (padded, standard alphabet) base64, e.g. `Aav/`.
+    Base64,
+    /// At most the first `max` bytes in hex, followed by the total number of bytes if any were
+    /// left out, e.g. `0x01ab...(3 bytes)` with a `max` of 2.
+    Truncated { max: usize },
+}
+
+impl Default for BinaryDisplay {
+    /// The mode used by the [`Display`] impl of [`Scalar`]: hex, truncated to 16 bytes.
+    fn default() -> Self {
+        Self::Truncated { max: 16 }
+    }
+}
+
+impl BinaryDisplay {
+    fn fmt(self, bytes: &[u8], f: &mut Formatter<'_>) -> std::fmt::Result {
+        let hex = |f: &mut Formatter<'_>, bytes: &[u8]| {
+            write!(f, "0x")?;
+            bytes.iter().try_for_each(|b| write!(f, "{b:02x}"))
+        };
+        match self {
+            Self::Hex => hex(f, bytes),
+            Self::Base64 => f.write_str(&encode_base64(bytes)),
+            Self::Truncated { max } if bytes.len() > max => {
+                hex(f, &bytes[..max])?;
+                write!(f, "...({} bytes)", bytes.len())
+            }
+            Self::Truncated { .. } => hex(f, bytes),
+        }
+    }
+}
+
+// Encodes `bytes` as padded base64 with the standard alphabet
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, b)| bits | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => result.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char),
+                false => result.push('='),
+            }
+        }
     }
+    result
+}
 
-    /// Attempts to divide two scalars, returning None if they were incompatible.
-    pub fn try_div(&self, other: &Scalar) -> Option<Scalar> {
-        use Scalar::*;
-        let result = match (self, other) {
-            (Integer(a), Integer(b)) => Integer(a.checked_div(*b)?),
-            (Long(a), Long(b)) => Long(a.checked_div(*b)?),
-            (Short(a), Short(b)) => Short(a.checked_div(*b)?),
-            (Byte(a), Byte(b)) => Byte(a.checked_div(*b)?),
-            _ => return None,
-        };
-        Some(result)
+impl Scalar {
+    /// Formats this scalar like its [`Display`] impl, except that binary values (including any
+    /// nested in a struct, array or map) are rendered according to `mode`. [`Display`] uses
+    /// [`BinaryDisplay::default`], which keeps the output short for large binary values.
+    pub fn format_binary(&self, mode: BinaryDisplay) -> String {
+        ScalarDisplay(self, mode).to_string()
     }
 }
 
-impl Display for Scalar {
+// Displays a scalar, rendering binary values in the given mode
+struct ScalarDisplay<'a>(&'a Scalar, BinaryDisplay);
+
+impl Display for ScalarDisplay<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Integer(i) => write!(f, "{i}"),
-            Self::Long(i) => write!(f, "{i}"),
-            Self::Short(i) => write!(f, "{i}"),
-            Self::Byte(i) => write!(f, "{i}"),
-            Self::Float(fl) => write!(f, "{fl}"),
-            Self::Double(fl) => write!(f, "{fl}"),
-            Self::String(s) => write!(f, "'{s}'"),
-            Self::Boolean(b) => write!(f, "{b}"),
-            Self::Timestamp(ts) => write!(f, "{ts}"),
-            Self::TimestampNtz(ts) => write!(f, "{ts}"),
-            Self::Date(d) => write!(f, "{d}"),
-            Self::Binary(b) => write!(f, "{b:?}"),
-            Self::Decimal(d) => match d.scale().cmp(&0) {
-                Ordering::Equal => {
-                    write!(f, "{}", d.bits())
-                }
-                Ordering::Greater => {
-                    let scale = d.scale();
-                    let scalar_multiple = 10_i128.pow(scale as u32);
-                    let value = d.bits();
-                    write!(f, "{}", value / scalar_multiple)?;
-                    write!(f, ".")?;
-                    write!(
-                        f,
-                        "{:0>scale$}",
-                        value % scalar_multiple,
-                        scale = scale as usize
-                    )
-                }
-                Ordering::Less => {
-                    write!(f, "{}", d.bits())?;
-                    for _ in 0..d.scale() {
-                        write!(f, "0")?;
-                    }
-                    Ok(())
-                }
-            },
-            Self::Null(_) => write!(f, "null"),
-            Self::Struct(data) => {
+        let ScalarDisplay(scalar, mode) = *self;
+        match scalar {
+            Scalar::Integer(i) => write!(f, "{i}"),
+            Scalar::Long(i) => write!(f, "{i}"),
+            Scalar::Short(i) => write!(f, "{i}"),
+            Scalar::Byte(i) => write!(f, "{i}"),
+            Scalar::Float(fl) => write!(f, "{fl}"),
+            Scalar::Double(fl) => write!(f, "{fl}"),
+            Scalar::String(s) => write!(f, "'{s}'"),
+            Scalar::Boolean(b) => write!(f, "{b}"),
+            Scalar::Timestamp(ts) => write!(f, "{ts}"),
+            Scalar::TimestampNtz(ts) => write!(f, "{ts}"),
+            Scalar::Date(d) => write!(f, "{d}"),
+            Scalar::Binary(b) => mode.fmt(b, f),
+            Scalar::Decimal(d) => write!(f, "{}", d.format(false)),
+            Scalar::Decimal256(d) => write!(f, "{}", d.format(false)),
+            Scalar::Null(_) => write!(f, "null"),
+            Scalar::Struct(data) => {
                 write!(f, "{{")?;
                 let mut delim = "";
                 for (value, field) in data.values.iter().zip(data.fields.iter()) {
-                    write!(f, "{delim}{}: {value}", field.name)?;
+                    write!(f, "{delim}{}: {}", field.name, ScalarDisplay(value, mode))?;
                     delim = ", ";
                 }
                 write!(f, "}}")
             }
-            Self::Array(data) => {
+            Scalar::Array(data) => {
                 write!(f, "(")?;
                 let mut delim = "";
-                for element in &data.elements {
-                    write!(f, "{delim}{element}")?;
+                for element in data.elements() {
+                    write!(f, "{delim}{}", ScalarDisplay(&element, mode))?;
                     delim = ", ";
                 }
                 write!(f, ")")
             }
-            Self::Map(data) => {
+            Scalar::Map(data) => {
                 write!(f, "{{")?;
                 let mut delim = "";
                 for (key, val) in &data.pairs {
-                    write!(f, "{delim}{key}: {val}")?;
+                    write!(
+                        f,
+                        "{delim}{}: {}",
+                        ScalarDisplay(key, mode),
+                        ScalarDisplay(val, mode)
+                    )?;
                     delim = ", ";
                 }
                 write!(f, "}}")
@@ -425,9 +1418,28 @@ impl Display for Scalar {
     }
 }
 
+impl Display for Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        ScalarDisplay(self, BinaryDisplay::default()).fmt(f)
+    }
+}
+
 impl PartialEq for Scalar {
     fn eq(&self, other: &Scalar) -> bool {
-        self.partial_cmp(other) == Some(Ordering::Equal)
+        match (self, other) {
+            // Structs, arrays and maps are incomparable, but they are equal if they have the same
+            // type and the same values, in the same order. Nested null values compare equal.
+            (Scalar::Struct(a), Scalar::Struct(b)) => a == b,
+            (Scalar::Array(a), Scalar::Array(b)) => a == b,
+            (Scalar::Map(a), Scalar::Map(b)) => {
+                a.data_type == b.data_type
+                    && a.pairs.len() == b.pairs.len()
+                    && (a.pairs.iter())
+                        .zip(&b.pairs)
+                        .all(|((ak, av), (bk, bv))| ak == bk && nested_value_eq(av, bv))
+            }
+            _ => self.partial_cmp(other) == Some(Ordering::Equal),
+        }
     }
 }
 
@@ -465,10 +1477,35 @@ impl PartialOrd for Scalar {
                 .then(|| d1.bits().partial_cmp(&d2.bits()))
                 .flatten(),
             (Decimal(_), _) => None,
+            (Decimal256(d1), Decimal256(d2)) => (d1.ty() == d2.ty())
+                .then(|| d1.bits().partial_cmp(&d2.bits()))
+                .flatten(),
+            (Decimal256(_), _) => None,
             (Null(_), _) => None, // NOTE: NULL values are incomparable by definition
             (Struct(_), _) => None, // TODO: Support Struct?
-            (Array(_), _) => None, // TODO: Support Array?
-            (Map(_), _) => None,  // TODO: Support Map?
+            // Arrays and maps of the same type compare their values lexicographically, so an
+            // empty array (or map) compares equal to another empty one and less than any other.
+            (Array(a), Array(b)) => (a.tpe == b.tpe)
+                .then(|| {
+                    lexicographic_partial_cmp(a.elements(), b.elements(), |a, b| {
+                        nested_value_partial_cmp(a, b)
+                    })
+                })
+                .flatten(),
+            (Array(_), _) => None,
+            (Map(a), Map(b)) => (a.data_type == b.data_type)
+                .then(|| {
+                    lexicographic_partial_cmp(
+                        a.pairs.iter(),
+                        b.pairs.iter(),
+                        |(ak, av), (bk, bv)| match ak.partial_cmp(bk)? {
+                            Ordering::Equal => nested_value_partial_cmp(av, bv),
+                            ord => Some(ord),
+                        },
+                    )
+                })
+                .flatten(),
+            (Map(_), _) => None,
         }
     }
 }
@@ -521,6 +1558,12 @@ impl From<DecimalData> for Scalar {
     }
 }
 
+impl From<DecimalData256> for Scalar {
+    fn from(d: DecimalData256) -> Self {
+        Self::Decimal256(d)
+    }
+}
+
 impl From<&str> for Scalar {
     fn from(s: &str) -> Self {
         Self::String(s.into())
@@ -557,28 +1600,56 @@ impl<T: Into<Scalar> + ToDataType> From<Option<T>> for Scalar {
 
 // TODO: add more From impls
 
+/// The precision to which [`PrimitiveType::parse_scalar_with_precision`] parses `timestamp` and
+/// `timestamp_ntz` values. Parsed timestamps are always represented in microseconds, but engines
+/// which only deal in milliseconds can ask for values to be limited to millisecond precision.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimestampPrecision {
+    /// Keep full microsecond precision.
+    #[default]
+    Micros,
+    /// Truncate the value to millisecond precision, dropping any sub-millisecond digits.
+    MillisTruncate,
+    /// Require the value to have millisecond precision, failing to parse if any sub-millisecond
+    /// digits are present.
+    MillisStrict,
+}
+
 impl PrimitiveType {
     fn data_type(&self) -> DataType {
         DataType::Primitive(self.clone())
     }
 
     pub fn parse_scalar(&self, raw: &str) -> Result<Scalar, Error> {
+        self.parse_scalar_with_precision(raw, TimestampPrecision::Micros)
+    }
+
+    /// Like [`PrimitiveType::parse_scalar`], but parses timestamps to the given `precision`. The
+    /// precision has no effect on other types.
+    pub fn parse_scalar_with_precision(
+        &self,
+        raw: &str,
+        precision: TimestampPrecision,
+    ) -> Result<Scalar, Error> {
         use PrimitiveType::*;
 
         if raw.is_empty() {
             return Ok(Scalar::Null(self.data_type()));
         }
 
+        // Loosely-formatted numeric values may have surrounding whitespace. It is only
+        // significant for string and binary values, which are kept as-is.
+        let numeric = raw.trim_ascii();
         match self {
             String => Ok(Scalar::String(raw.to_string())),
             Binary => Ok(Scalar::Binary(raw.to_string().into_bytes())),
-            Byte => self.parse_str_as_scalar(raw, Scalar::Byte),
-            Decimal(dtype) => Self::parse_decimal(raw, *dtype),
-            Short => self.parse_str_as_scalar(raw, Scalar::Short),
-            Integer => self.parse_str_as_scalar(raw, Scalar::Integer),
-            Long => self.parse_str_as_scalar(raw, Scalar::Long),
-            Float => self.parse_str_as_scalar(raw, Scalar::Float),
-            Double => self.parse_str_as_scalar(raw, Scalar::Double),
+            Byte => self.parse_str_as_scalar(numeric, Scalar::Byte),
+            Decimal(dtype) => Self::parse_decimal(numeric, *dtype),
+            Short => self.parse_str_as_scalar(numeric, Scalar::Short),
+            Integer => self.parse_str_as_scalar(numeric, Scalar::Integer),
+            Long => self.parse_str_as_scalar(numeric, Scalar::Long),
+            Float => self.parse_str_as_scalar(numeric, Scalar::Float),
+            Double => self.parse_str_as_scalar(numeric, Scalar::Double),
             Boolean => {
                 if raw.eq_ignore_ascii_case("true") {
                     Ok(Scalar::Boolean(true))
@@ -600,7 +1671,8 @@ impl PrimitiveType {
             // NOTE: Timestamp and TimestampNtz are both parsed into microsecond since unix epoch.
             // They may both have the format `{year}-{month}-{day} {hour}:{minute}:{second}`.
             // Timestamps may additionally be encoded as a ISO 8601 formatted string such as
-            // `1970-01-01T00:00:00.123456Z`.
+            // `1970-01-01T00:00:00.123456Z`. TimestampNtz values have no time zone, so they only
+            // accept the ISO 8601 form without an offset, e.g. `1970-01-01T00:00:00.123456`.
             //
             // The difference arises mostly in how they are to be handled on the engine side - i.e. timestampNTZ
             // is not adjusted to UTC, this is just so we can (de-)serialize it as a date sting.
@@ -608,9 +1680,12 @@ impl PrimitiveType {
             TimestampNtz | Timestamp => {
                 let mut timestamp = NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.f");
 
-                if timestamp.is_err() && *self == Timestamp {
-                    // Note: `%+` specifies the ISO 8601 / RFC 3339 format
-                    timestamp = NaiveDateTime::parse_from_str(raw, "%+");
+                if timestamp.is_err() {
+                    timestamp = match self {
+                        // Note: `%+` specifies the ISO 8601 / RFC 3339 format
+                        Timestamp => NaiveDateTime::parse_from_str(raw, "%+"),
+                        _ => NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f"),
+                    };
                 }
                 let timestamp = timestamp.map_err(|_| self.parse_error(raw))?;
                 let timestamp = Utc.from_utc_datetime(&timestamp);
@@ -618,6 +1693,13 @@ impl PrimitiveType {
                     .signed_duration_since(DateTime::UNIX_EPOCH)
                     .num_microseconds()
                     .ok_or(self.parse_error(raw))?;
+                let sub_millis = micros.rem_euclid(1000);
+                let micros = match precision {
+                    TimestampPrecision::Micros => micros,
+                    TimestampPrecision::MillisTruncate => micros - sub_millis,
+                    TimestampPrecision::MillisStrict if sub_millis == 0 => micros,
+                    TimestampPrecision::MillisStrict => return Err(self.parse_error(raw)),
+                };
                 match self {
                     Timestamp => Ok(Scalar::Timestamp(micros)),
                     TimestampNtz => Ok(Scalar::TimestampNtz(micros)),
@@ -647,6 +1729,19 @@ impl PrimitiveType {
     }
 
     fn parse_decimal(raw: &str, dtype: DecimalType) -> Result<Scalar, Error> {
+        let parse_error = || PrimitiveType::from(dtype).parse_error(raw);
+        let (int, scale) = Self::parse_decimal_parts(raw, parse_error)?;
+        let scale: u8 = scale.try_into().map_err(|_| parse_error())?;
+        require!(scale == dtype.scale(), parse_error());
+        Ok(DecimalValue::try_new(int, dtype)?.into())
+    }
+
+    // Splits a decimal string into its unscaled integer value and its scale (which may be negative
+    // if the string has a positive exponent, e.g. `1E3` has scale -3).
+    fn parse_decimal_parts(
+        raw: &str,
+        parse_error: impl Fn() -> Error,
+    ) -> Result<(I256, i128), Error> {
         let (base, exp): (&str, i128) = match raw.find(['e', 'E']) {
             None => (raw, 0), // no 'e' or 'E', so there's no exponent
             Some(pos) => {
@@ -655,7 +1750,6 @@ impl PrimitiveType {
                 (base, exp[1..].parse()?)
             }
         };
-        let parse_error = || PrimitiveType::from(dtype).parse_error(raw);
         require!(!base.is_empty(), parse_error());
 
         // now split on any '.' and parse
@@ -674,19 +1768,59 @@ impl PrimitiveType {
             }
         };
 
-        // we can assume this won't underflow since `frac_digits` is at minimum 0, and exp is at
-        // most i128::MAX, and 0-i128::MAX doesn't underflow
-        let scale = frac_digits - exp;
-        let scale: u8 = scale.try_into().map_err(|_| parse_error())?;
-        require!(scale == dtype.scale(), parse_error());
-        let int: i128 = match frac_part {
-            None => int_part.parse()?,
-            Some(frac_part) => format!("{int_part}{frac_part}").parse()?,
+        // `frac_digits` is at minimum 0, but exp may be as small as i128::MIN
+        let scale = frac_digits.checked_sub(exp).ok_or_else(&parse_error)?;
+        let int: I256 = match frac_part {
+            None => int_part.parse().map_err(|_| parse_error())?,
+            Some(frac_part) => format!("{int_part}{frac_part}")
+                .parse()
+                .map_err(|_| parse_error())?,
         };
-        Ok(Scalar::Decimal(DecimalData::try_new(int, dtype)?))
+        Ok((int, scale))
     }
 }
 
+/// Constructs a (possibly nested) [`Scalar`] literal, inferring struct field and array element
+/// types from the values (see [`Scalar::struct_from`] and [`Scalar::array_from`]). Structs are
+/// written as `{name: value, ...}`, arrays as `[value, ...]`, and any other value is converted with
+/// [`Scalar::from`]. Values that are not a single token (e.g. `-1` or a function call) must be
+/// wrapped in parentheses. Returns a [`DeltaResult`], since e.g. an empty array has no element type
+/// to infer.
+///
+/// ```
+/// # use delta_kernel::expressions::{scalar, Scalar};
+/// # use delta_kernel::schema::{ArrayType, DataType, StructField};
+/// let value = scalar!({a: 1, b: [5i16, 0i16], c: {d: "x", e: (-1.5)}}).unwrap();
+/// let Scalar::Struct(data) = value else { unreachable!() };
+/// assert_eq!(data.fields()[0], StructField::not_null("a", DataType::INTEGER));
+/// assert_eq!(
+///     data.fields()[1],
+///     StructField::not_null("b", ArrayType::new(DataType::SHORT, false)),
+/// );
+/// ```
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __scalar {
+    ({ $($name:ident : $value:tt),* $(,)? }) => {
+        [$((stringify!($name), $crate::__scalar!($value))),*]
+            .into_iter()
+            .map(|(name, value)| value.map(|value| (name, value)))
+            .collect::<$crate::DeltaResult<::std::vec::Vec<(&str, $crate::expressions::Scalar)>>>()
+            .and_then($crate::expressions::Scalar::struct_from)
+    };
+    ([ $($value:tt),* $(,)? ]) => {
+        [$($crate::__scalar!($value)),*]
+            .into_iter()
+            .collect::<$crate::DeltaResult<::std::vec::Vec<$crate::expressions::Scalar>>>()
+            .and_then($crate::expressions::Scalar::array_from)
+    };
+    ($value:expr) => {
+        ::std::result::Result::<_, $crate::Error>::Ok($crate::expressions::Scalar::from($value))
+    };
+}
+#[doc(inline)]
+pub use __scalar as scalar;
+
 #[cfg(test)]
 mod tests {
     use std::f32::consts::PI;
@@ -713,6 +1847,210 @@ mod tests {
 
         let s = Scalar::decimal(123456789, 9, 9).unwrap();
         assert_eq!(s.to_string(), "0.123456789");
+
+        let s = Scalar::decimal(-5, 3, 2).unwrap();
+        assert_eq!(s.to_string(), "-0.05");
+
+        let s = Scalar::decimal(-123456789, 9, 2).unwrap();
+        assert_eq!(s.to_string(), "-1234567.89");
+    }
+
+    #[test]
+    fn test_decimal_format_grouping() {
+        let format = |bits: i128, precision: u8, scale: u8, grouping: bool| {
+            let ty = DecimalType::try_new(precision, scale).unwrap();
+            DecimalData::try_new(bits, ty).unwrap().format(grouping)
+        };
+        assert_eq!(format(123456789, 9, 2, true), "1,234,567.89");
+        assert_eq!(format(123456789, 9, 2, false), "1234567.89");
+        assert_eq!(format(-123456789, 9, 2, true), "-1,234,567.89");
+        assert_eq!(format(-123456789, 9, 2, false), "-1234567.89");
+        assert_eq!(format(123456789, 9, 0, true), "123,456,789");
+        assert_eq!(format(-1234567890, 10, 0, true), "-1,234,567,890");
+        assert_eq!(format(-1234567890, 10, 0, false), "-1234567890");
+        assert_eq!(format(999, 3, 0, true), "999");
+        assert_eq!(format(-5, 3, 2, true), "-0.05");
+        assert_eq!(format(123456789, 9, 9, true), "0.123456789");
+
+        // 256-bit decimals are grouped too
+        let bits = I256::from(i128::MAX).checked_mul_pow10(1).unwrap();
+        let ty = DecimalType::try_new_wide(40, 3).unwrap();
+        let value = DecimalData256::try_new(bits, ty).unwrap();
+        assert_eq!(
+            value.format(true),
+            "1,701,411,834,604,692,317,316,873,037,158,841,057.270"
+        );
+
+        // the default display never groups digits
+        let s = Scalar::decimal(-1234567890, 10, 0).unwrap();
+        assert_eq!(s.to_string(), "-1234567890");
+    }
+
+    #[test]
+    fn test_scalar_macro() {
+        // a struct with a nested array, as built by hand
+        let array_type = ArrayType::new(DataType::SHORT, false);
+        let array = ArrayData::try_new(array_type.clone(), [5i16, 0i16]).unwrap();
+        let fields = vec![
+            StructField::not_null("a", DataType::INTEGER),
+            StructField::not_null("b", array_type),
+        ];
+        let values = vec![Scalar::Integer(1), Scalar::Array(array.clone())];
+        let expected = Scalar::Struct(StructData::try_new(fields, values).unwrap());
+        assert_eq!(scalar!({a: 1, b: [5i16, 0i16]}).unwrap(), expected);
+        let Ok(Scalar::Array(actual)) = scalar!([5i16, 0i16]) else {
+            panic!("Expected an array");
+        };
+        assert_eq!(actual, array);
+
+        // nested structs, arrays of structs, nulls and parenthesized expressions
+        let inner_fields = vec![
+            StructField::not_null("x", DataType::STRING),
+            StructField::nullable("y", DataType::LONG),
+        ];
+        let inner = |x: &str| {
+            let values = vec![x.into(), Scalar::Null(DataType::LONG)];
+            Scalar::Struct(StructData::try_new(inner_fields.clone(), values).unwrap())
+        };
+        let inner_type = DataType::struct_type(inner_fields.clone());
+        let list_type = ArrayType::new(DataType::DOUBLE, true);
+        let list = vec![Scalar::Double(-1.5), Scalar::Null(DataType::DOUBLE)];
+        let fields = vec![
+            StructField::not_null("s", inner_type.clone()),
+            StructField::not_null("l", list_type.clone()),
+            StructField::not_null("ss", ArrayType::new(inner_type, false)),
+        ];
+        let values = vec![
+            inner("a"),
+            Scalar::Array(ArrayData::try_new(list_type, list).unwrap()),
+            Scalar::array_from([inner("b"), inner("c")]).unwrap(),
+        ];
+        let expected = Scalar::Struct(StructData::try_new(fields, values).unwrap());
+        let null = Scalar::Null(DataType::LONG);
+        let actual = scalar!({
+            s: {x: "a", y: (null.clone())},
+            l: [(-1.5), (Scalar::Null(DataType::DOUBLE))],
+            ss: [{x: "b", y: (null.clone())}, {x: "c", y: (null.clone())}],
+        });
+        assert_eq!(actual.unwrap(), expected);
+
+        // element types must agree, and empty arrays have no type to infer
+        assert!(scalar!([1, "a"]).is_err());
+        assert!(scalar!({a: []}).is_err());
+        assert_eq!(scalar!(5i64).unwrap(), Scalar::Long(5));
+    }
+
+    #[test]
+    fn test_approx_heap_size() {
+        assert_eq!(Scalar::Long(5).approx_heap_size(), 0);
+        assert_eq!(Scalar::Null(DataType::STRING).approx_heap_size(), 0);
+        assert!(Scalar::from("abc").approx_heap_size() >= 3);
+        assert!(Scalar::Binary(vec![1, 2, 3, 4]).approx_heap_size() >= 4);
+
+        // An array of scalars owns a buffer of (inline) scalars, and a columnar array owns a buffer
+        // of values
+        let array_type = ArrayType::new(DataType::LONG, false);
+        let array = ArrayData::try_new(array_type.clone(), [1i64, 2, 3]).unwrap();
+        let size = Scalar::Array(array).approx_heap_size();
+        assert!(size >= 3 * size_of::<Scalar>(), "{size}");
+        let array = ArrayData::try_new_columnar(array_type, [1i64, 2, 3]).unwrap();
+        let size = Scalar::Array(array).approx_heap_size();
+        assert!(size >= 3 * size_of::<i64>(), "{size}");
+
+        // Nested values are counted recursively
+        let array_type = ArrayType::new(DataType::STRING, false);
+        let strings = ["a".repeat(100), "b".repeat(100)];
+        let array = Scalar::Array(ArrayData::try_new(array_type, strings).unwrap());
+        let array_size = array.approx_heap_size();
+        assert!(array_size >= 200 + 2 * size_of::<Scalar>());
+        let fields = vec![StructField::not_null("a", array.data_type())];
+        let data = Scalar::Struct(StructData::try_new(fields, vec![array]).unwrap());
+        assert!(data.approx_heap_size() >= array_size + size_of::<Scalar>());
+        let map_type = MapType::new(DataType::STRING, DataType::STRING, false);
+        let map = MapData::try_new(map_type, [("key", "x".repeat(50))]).unwrap();
+        assert!(Scalar::Map(map).approx_heap_size() >= 53 + 2 * size_of::<Scalar>());
+    }
+
+    #[test]
+    fn test_data_type_ref() {
+        let primitives = [
+            Scalar::Integer(1),
+            Scalar::Long(1),
+            Scalar::String("a".into()),
+            Scalar::Boolean(true),
+            Scalar::Timestamp(1),
+            Scalar::Binary(vec![1]),
+            Scalar::Null(DataType::LONG),
+        ];
+        for scalar in primitives {
+            let data_type = scalar.data_type_ref();
+            assert!(matches!(data_type, Cow::Borrowed(_)), "{scalar}");
+            assert_eq!(*data_type, scalar.data_type());
+        }
+
+        // nested types are built from their fields and elements
+        let array_type = ArrayType::new(DataType::INTEGER, false);
+        let array = ArrayData::try_new(array_type.clone(), [1, 2]).unwrap();
+        let fields = vec![StructField::nullable("a", array_type.clone())];
+        let scalar =
+            Scalar::Struct(StructData::try_new(fields, vec![Scalar::Array(array)]).unwrap());
+        let data_type = scalar.data_type_ref();
+        let expected = DataType::struct_type([StructField::nullable("a", array_type)]);
+        assert_eq!(*data_type, expected);
+        assert_eq!(scalar.data_type(), expected);
+
+        // decimal types are built on the fly
+        let scalar = Scalar::decimal(1, 3, 1).unwrap();
+        assert_eq!(*scalar.data_type_ref(), DataType::decimal(3, 1).unwrap());
+    }
+
+    #[test]
+    fn test_decimal256() {
+        let forty_digits = "1234567890123456789012345678901234567890";
+        let bits: I256 = forty_digits.parse().unwrap();
+        let s = Scalar::decimal256(bits, 40, 10).unwrap();
+        assert!(matches!(s, Scalar::Decimal256(_)));
+        let wide_type = DecimalType::try_new_wide(40, 10).unwrap();
+        assert_eq!(s.data_type(), DataType::from(wide_type));
+        assert_eq!(s.to_string(), "123456789012345678901234567890.1234567890");
+
+        let neg = Scalar::decimal256(I256::from(-1), 40, 39).unwrap();
+        assert_eq!(
+            neg.to_string(),
+            "-0.000000000000000000000000000000000000001"
+        );
+
+        // the storage is chosen by precision, not by the magnitude of the value
+        let small = Scalar::decimal256(1, 40, 0).unwrap();
+        assert!(matches!(small, Scalar::Decimal256(_)));
+        let small_128 = Scalar::decimal256(1, 38, 0).unwrap();
+        assert!(matches!(small_128, Scalar::Decimal(_)));
+        let dtype = DecimalType::try_new_wide(40, 0).unwrap();
+        DecimalData::try_new(1, dtype).expect_err("precision 40 requires 256 bits");
+        let dtype = DecimalType::try_new(38, 0).unwrap();
+        DecimalData256::try_new(1, dtype).expect_err("precision 38 fits in 128 bits");
+
+        // value exceeds precision
+        Scalar::decimal256(bits, 39, 0).expect_err("should have failed");
+        Scalar::decimal256(1, 77, 0).expect_err("precision exceeds the maximum wide precision");
+
+        // wide precisions are only valid for intermediate values, not in a table schema
+        Scalar::decimal(1, 40, 0).expect_err("precision exceeds the maximum schema precision");
+        DataType::decimal(40, 10).expect_err("precision exceeds the maximum schema precision");
+
+        // parse with an explicit type
+        let ptype = PrimitiveType::Decimal(wide_type);
+        let parsed = ptype
+            .parse_scalar("123456789012345678901234567890.1234567890")
+            .unwrap();
+        assert_eq!(parsed, s);
+        let parsed = ptype.parse_scalar("-1.0000000000").unwrap();
+        assert_eq!(parsed.to_string(), "-1.0000000000");
+
+        assert_eq!(neg.partial_cmp(&small), None);
+        let neg = Scalar::decimal256(I256::from(-1), 40, 10).unwrap();
+        assert_eq!(neg.partial_cmp(&s), Some(Ordering::Less));
+        assert_eq!(neg.total_cmp(&s), Ordering::Less);
     }
 
     fn assert_decimal(
@@ -814,6 +2152,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_decimal_infer() {
+        let assert_inferred = |raw, bits: i128, precision, scale| {
+            let expected = Scalar::decimal(bits, precision, scale).unwrap();
+            let scalar = Scalar::parse_decimal_infer(raw).unwrap();
+            assert_eq!(scalar, expected, "{raw}");
+            assert_eq!(scalar.data_type(), expected.data_type(), "{raw}");
+        };
+        assert_inferred("12.34", 1234, 4, 2);
+        assert_inferred("-12.34", -1234, 4, 2);
+        assert_inferred("0", 0, 1, 0);
+        assert_inferred("0.00123", 123, 5, 5);
+        assert_inferred("123.", 123, 3, 0);
+        assert_inferred("1234.5E-4", 12345, 5, 5);
+        assert_inferred("1.5E3", 1500, 4, 0);
+        assert_inferred(
+            "99999999999999999999999999999999999999",
+            99999999999999999999999999999999999999,
+            38,
+            0,
+        );
+
+        // more than 38 digits of precision or scale is not a valid Delta decimal
+        assert!(Scalar::parse_decimal_infer("1234567890123456789012345678901234567890").is_err());
+        assert!(Scalar::parse_decimal_infer("123456789012345678901234567890.123456789").is_err());
+        assert!(Scalar::parse_decimal_infer("1E38").is_err());
+        assert!(Scalar::parse_decimal_infer("1E-39").is_err());
+        assert!(Scalar::parse_decimal_infer(&"9".repeat(77)).is_err());
+        // a huge exponent is rejected without scaling the value
+        assert!(Scalar::parse_decimal_infer("0E4294967295").is_err());
+        assert!(Scalar::parse_decimal_infer("1E-4294967295").is_err());
+        assert!(Scalar::parse_decimal_infer("1E-170141183460469231731687303715884105728").is_err());
+        assert!(Scalar::parse_decimal_infer("abc").is_err());
+        assert!(Scalar::parse_decimal_infer("").is_err());
+    }
+
+    #[test]
+    fn test_parse_scalar_trims_whitespace() {
+        let decimal = PrimitiveType::decimal(4, 2).unwrap();
+        let expected = Scalar::decimal(1234, 4, 2).unwrap();
+        assert_eq!(decimal.parse_scalar(" 12.34 ").unwrap(), expected);
+        assert_eq!(decimal.parse_scalar("\t12.34\n").unwrap(), expected);
+        let parsed = PrimitiveType::Integer.parse_scalar(" -5 ").unwrap();
+        assert_eq!(parsed, Scalar::Integer(-5));
+        let parsed = PrimitiveType::Double.parse_scalar(" 1.5").unwrap();
+        assert_eq!(parsed, Scalar::Double(1.5));
+        assert!(decimal.parse_scalar("   ").is_err());
+
+        // surrounding whitespace is significant for strings and binary
+        let parsed = PrimitiveType::String.parse_scalar(" 12.34 ").unwrap();
+        assert_eq!(parsed, Scalar::String(" 12.34 ".to_string()));
+        let parsed = PrimitiveType::Binary.parse_scalar(" ab ").unwrap();
+        assert_eq!(parsed, Scalar::Binary(b" ab ".to_vec()));
+    }
+
     fn expect_fail_parse(raw: &str, prec: u8, scale: u8) {
         let s = PrimitiveType::decimal(prec, scale).unwrap();
         let res = s.parse_scalar(raw);
@@ -849,11 +2242,13 @@ mod tests {
 
     #[test]
     fn test_arrays() {
-        #[allow(deprecated)]
-        let array = Scalar::Array(ArrayData {
-            tpe: ArrayType::new(DataType::INTEGER, false),
-            elements: vec![Scalar::Integer(1), Scalar::Integer(2), Scalar::Integer(3)],
-        });
+        let array = Scalar::Array(
+            ArrayData::try_new(
+                ArrayType::new(DataType::INTEGER, false),
+                [Scalar::Integer(1), Scalar::Integer(2), Scalar::Integer(3)],
+            )
+            .unwrap(),
+        );
 
         let column = column_expr!("item");
         let array_op = Pred::binary(BinaryPredicateOp::In, Expr::literal(10), array.clone());
@@ -874,6 +2269,262 @@ mod tests {
         assert_eq!(&format!("{column_not_op}"), "NOT('Cool' IN Column(item))");
     }
 
+    #[test]
+    fn test_array_of_structs() {
+        let fields = || {
+            vec![
+                StructField::not_null("id", DataType::INTEGER),
+                StructField::nullable("tags", ArrayType::new(DataType::STRING, true)),
+            ]
+        };
+        let element = |id: i32, tags: Option<&[&str]>| {
+            let tags = match tags {
+                Some(tags) => {
+                    let tags_type = ArrayType::new(DataType::STRING, true);
+                    Scalar::Array(ArrayData::try_new(tags_type, tags.iter().copied()).unwrap())
+                }
+                None => Scalar::Null(DataType::Null),
+            };
+            Scalar::Struct(StructData::try_new(fields(), vec![id.into(), tags]).unwrap())
+        };
+        let array_type = ArrayType::new(DataType::struct_type(fields()), false);
+        let array = || {
+            let elements = [element(1, Some(&["a", "b"])), element(2, None)];
+            Scalar::Array(ArrayData::try_new(array_type.clone(), elements).unwrap())
+        };
+        assert_eq!(
+            array().to_string(),
+            "({id: 1, tags: ('a', 'b')}, {id: 2, tags: null})"
+        );
+        // nested nulls compare equal
+        assert_eq!(array(), array());
+        let other = ArrayData::try_new(array_type.clone(), [element(1, Some(&["a", "b"]))]);
+        assert_ne!(array(), Scalar::Array(other.unwrap()));
+
+        // elements must match the nested struct type exactly
+        let mismatched = StructData::try_new(
+            vec![
+                StructField::not_null("id", DataType::INTEGER),
+                StructField::nullable("tags", ArrayType::new(DataType::STRING, false)),
+            ],
+            vec![Scalar::Integer(3), Scalar::Null(DataType::Null)],
+        )
+        .unwrap();
+        let result = ArrayData::try_new(array_type, [Scalar::Struct(mismatched)]);
+        assert!(matches!(result, Err(Error::Schema(_))));
+    }
+
+    #[test]
+    fn test_total_cmp() {
+        let mut values = vec![
+            Scalar::Null(DataType::INTEGER),
+            Scalar::from("b"),
+            Scalar::Double(f64::NAN),
+            Scalar::Integer(2),
+            Scalar::Double(0.0),
+            Scalar::Long(-5),
+            Scalar::Double(-0.0),
+            Scalar::Integer(-1),
+            Scalar::from("a"),
+            Scalar::Null(DataType::STRING),
+            Scalar::Double(f64::INFINITY),
+            Scalar::from(true),
+            Scalar::decimal(100, 5, 2).unwrap(),
+            Scalar::Double(-f64::NAN),
+            Scalar::decimal(1, 3, 0).unwrap(),
+            Scalar::Double(f64::NEG_INFINITY),
+        ];
+        values.sort_by(Scalar::total_cmp);
+        // -0.0 and 0.0 compare equal, so the sort keeps their original order
+        let expected = [
+            "-1", "2", "-5", "NaN", "-inf", "0", "-0", "inf", "NaN", "'a'", "'b'", "true", "1",
+            "1.00", "null", "null",
+        ];
+        let actual: Vec<_> = values.iter().map(ToString::to_string).collect();
+        assert_eq!(actual, expected);
+        // negative NaN sorts first, and positive NaN last
+        assert!(matches!(values[3], Scalar::Double(v) if v.is_nan() && v.is_sign_negative()));
+        assert!(matches!(values[8], Scalar::Double(v) if v.is_nan() && v.is_sign_positive()));
+        // nulls compare equal regardless of type, so the sort keeps their original order
+        assert!(matches!(values[14], Scalar::Null(DataType::INTEGER)));
+        assert!(matches!(values[15], Scalar::Null(DataType::STRING)));
+
+        // the same holds for floats, whose NaNs keep their sign
+        let float = Scalar::Float;
+        assert_eq!(
+            float(-f32::NAN).total_cmp(&float(f32::NEG_INFINITY)),
+            Ordering::Less
+        );
+        assert_eq!(
+            float(f32::NAN).total_cmp(&float(f32::INFINITY)),
+            Ordering::Greater
+        );
+        assert_eq!(float(-0.0).total_cmp(&float(0.0)), Ordering::Equal);
+
+        // nested values compare lexicographically
+        let array = |values: &[i32]| {
+            let array_type = ArrayType::new(DataType::INTEGER, false);
+            Scalar::Array(ArrayData::try_new(array_type, values.iter().copied()).unwrap())
+        };
+        assert_eq!(array(&[1, 2]).total_cmp(&array(&[1, 3])), Ordering::Less);
+        assert_eq!(array(&[1, 2]).total_cmp(&array(&[1])), Ordering::Greater);
+        assert_eq!(array(&[1, 2]).total_cmp(&array(&[1, 2])), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_timestamp_and_date_construction() {
+        let micros = 1_700_000_000_123_456;
+        assert_eq!(
+            Scalar::timestamp_from_micros(micros).unwrap(),
+            Scalar::Timestamp(micros)
+        );
+        assert_eq!(
+            Scalar::timestamp_from_micros(-micros).unwrap(),
+            Scalar::Timestamp(-micros)
+        );
+        assert!(Scalar::timestamp_from_micros(i64::MAX).is_err());
+        assert!(Scalar::timestamp_from_micros(i64::MIN).is_err());
+
+        assert_eq!(
+            Scalar::date_from_days(19_000).unwrap(),
+            Scalar::Date(19_000)
+        );
+        assert_eq!(Scalar::date_from_days(-1).unwrap(), Scalar::Date(-1));
+        assert!(Scalar::date_from_days(i32::MAX).is_err());
+        assert!(Scalar::date_from_days(i32::MIN).is_err());
+    }
+
+    #[test]
+    fn test_common_type() {
+        let common_type = |scalars: &[Scalar]| Scalar::common_type(scalars);
+        let decimal = |precision, scale| DataType::decimal(precision, scale).unwrap();
+
+        assert_eq!(
+            common_type(&[Scalar::Integer(1), Scalar::Long(2)]),
+            Some(DataType::LONG)
+        );
+        assert_eq!(
+            common_type(&[Scalar::Integer(1), Scalar::Double(2.0)]),
+            Some(DataType::DOUBLE)
+        );
+        assert_eq!(common_type(&[Scalar::Integer(1), Scalar::from("a")]), None);
+
+        // integral types and decimals widen to a decimal that fits either
+        let d = Scalar::decimal(123, 5, 2).unwrap();
+        assert_eq!(
+            common_type(&[Scalar::Byte(1), d.clone()]),
+            Some(decimal(5, 2))
+        );
+        assert_eq!(
+            common_type(&[d.clone(), Scalar::Integer(1)]),
+            Some(decimal(12, 2))
+        );
+        assert_eq!(
+            common_type(&[d.clone(), Scalar::Float(1.0)]),
+            Some(DataType::DOUBLE)
+        );
+        let wide = Scalar::decimal256(1, 76, 70).unwrap();
+        assert_eq!(common_type(&[wide, Scalar::Long(1)]), None);
+
+        // nulls take part by type, and untyped nulls coerce to anything
+        let untyped_null = Scalar::Null(DataType::Null);
+        assert_eq!(
+            common_type(&[
+                untyped_null.clone(),
+                Scalar::Short(1),
+                Scalar::Null(DataType::INTEGER)
+            ]),
+            Some(DataType::INTEGER)
+        );
+        assert_eq!(common_type(&[untyped_null]), Some(DataType::Null));
+        assert_eq!(
+            common_type(&[Scalar::from("a"), Scalar::from("b")]),
+            Some(DataType::STRING)
+        );
+        assert_eq!(common_type(&[]), None);
+    }
+
+    #[test]
+    fn test_empty_array_and_map() {
+        let array_type = ArrayType::new(DataType::INTEGER, false);
+        let array = |values: &[i32]| {
+            Scalar::Array(ArrayData::try_new(array_type.clone(), values.iter().copied()).unwrap())
+        };
+        let map_type = MapType::new(DataType::STRING, DataType::INTEGER, false);
+        let map = |pairs: &[(&str, i32)]| {
+            Scalar::Map(MapData::try_new(map_type.clone(), pairs.iter().copied()).unwrap())
+        };
+
+        assert_eq!(array(&[]).to_string(), "()");
+        assert_eq!(map(&[]).to_string(), "{}");
+
+        // empty values of the same type are equal
+        assert_eq!(array(&[]), array(&[]));
+        assert_eq!(array(&[]).partial_cmp(&array(&[])), Some(Ordering::Equal));
+        assert_eq!(map(&[]), map(&[]));
+        assert_eq!(map(&[]).partial_cmp(&map(&[])), Some(Ordering::Equal));
+
+        // empty values compare less than non-empty ones
+        assert!(array(&[]) < array(&[1]));
+        assert!(array(&[i32::MIN]) > array(&[]));
+        assert!(map(&[]) < map(&[("a", 1)]));
+        assert!(map(&[("a", 1)]) > map(&[]));
+
+        // non-empty values compare lexicographically
+        assert!(array(&[1, 2]) < array(&[1, 3]));
+        assert!(array(&[1]) < array(&[1, 2]));
+        assert!(map(&[("a", 1)]) < map(&[("b", 0)]));
+
+        // values of different types are incomparable, even when empty
+        let long_array_type = ArrayType::new(DataType::LONG, false);
+        let long_array = Scalar::Array(ArrayData::try_new(long_array_type, [0i64; 0]).unwrap());
+        assert_ne!(array(&[]), long_array);
+        assert_eq!(array(&[]).partial_cmp(&long_array), None);
+        assert_eq!(array(&[]).partial_cmp(&map(&[])), None);
+
+        // null elements are equal, but incomparable to non-null elements
+        let nullable_type = ArrayType::new(DataType::INTEGER, true);
+        let nullable = |values: &[Option<i32>]| {
+            let elements = values.iter().copied();
+            Scalar::Array(ArrayData::try_new(nullable_type.clone(), elements).unwrap())
+        };
+        assert_eq!(
+            nullable(&[None]).partial_cmp(&nullable(&[None])),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(nullable(&[None]).partial_cmp(&nullable(&[Some(1)])), None);
+        assert!(nullable(&[]) < nullable(&[None]));
+    }
+
+    #[test]
+    fn test_untyped_null() {
+        let untyped_null = || Scalar::Null(DataType::Null);
+        let fields = vec![
+            StructField::nullable("i", DataType::INTEGER),
+            StructField::nullable("s", DataType::STRING),
+        ];
+        let data = StructData::try_new(fields, vec![untyped_null(), untyped_null()]).unwrap();
+        assert!(matches!(
+            data.values(),
+            [
+                Scalar::Null(DataType::INTEGER),
+                Scalar::Null(DataType::STRING)
+            ]
+        ));
+
+        // an untyped null is still rejected by a non-nullable field
+        let fields = vec![StructField::not_null("i", DataType::INTEGER)];
+        StructData::try_new(fields, vec![untyped_null()]).expect_err("should have failed");
+
+        for data_type in [DataType::INTEGER, DataType::STRING] {
+            let array_type = ArrayType::new(data_type.clone(), true);
+            let array = ArrayData::try_new(array_type, [untyped_null()]).unwrap();
+            #[allow(deprecated)]
+            let elements = array.array_elements();
+            assert!(matches!(elements, [Scalar::Null(dt)] if *dt == data_type));
+        }
+    }
+
     #[test]
     fn test_invalid_array() {
         assert!(ArrayData::try_new(
@@ -887,6 +2538,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_columnar_arrays() {
+        let array_type = ArrayType::new(DataType::LONG, true);
+        let values = || (0..100).map(|i| (i % 7 != 0).then_some(i as i64));
+        let scalars = ArrayData::try_new(array_type.clone(), values()).unwrap();
+        let columnar = ArrayData::try_new_columnar(array_type.clone(), values()).unwrap();
+        assert!(matches!(scalars.elements, ArrayElements::Scalars(_)));
+        assert!(matches!(columnar.elements, ArrayElements::Columnar(_)));
+        assert_eq!(columnar.len(), 100);
+        assert!(columnar.elements().nth(7).unwrap().is_null());
+        assert_eq!(
+            Scalar::Array(scalars.clone()).to_string(),
+            Scalar::Array(columnar.clone()).to_string()
+        );
+
+        // Arrays compare structurally, so null elements in the same positions compare equal
+        assert_eq!(scalars, columnar);
+        let other = ArrayData::try_new_columnar(array_type.clone(), values().map(|_| Some(0i64)));
+        assert_ne!(scalars, other.unwrap());
+
+        // Scalar and columnar arrays of the same values compare equal both ways
+        let array_type = ArrayType::new(DataType::INTEGER, false);
+        let values = [1, 2, 3];
+        let scalars = ArrayData::try_new(array_type.clone(), values).unwrap();
+        let columnar = ArrayData::try_new_columnar(array_type.clone(), values).unwrap();
+        assert_eq!(scalars, columnar);
+        assert_eq!(columnar, scalars);
+        let other = ArrayData::try_new_columnar(array_type.clone(), [1, 2, 4]).unwrap();
+        assert_ne!(scalars, other);
+        #[allow(deprecated)]
+        let (left, right) = (scalars.array_elements(), columnar.array_elements());
+        assert_eq!(left, right);
+
+        // Columnar arrays validate their elements the same way
+        ArrayData::try_new_columnar(array_type.clone(), [Scalar::Long(1)])
+            .expect_err("type mismatch");
+        ArrayData::try_new_columnar(array_type, [Some(1), None]).expect_err("null element");
+
+        // ... but only support fixed-width primitive element types
+        for element_type in [
+            DataType::STRING,
+            DataType::BINARY,
+            DataType::decimal(10, 2).unwrap(),
+        ] {
+            let array_type = ArrayType::new(element_type, false);
+            ArrayData::try_new_columnar(array_type, [] as [Scalar; 0]).expect_err("unsupported");
+        }
+    }
+
     #[test]
     fn test_invalid_map() {
         // incorrect schema
@@ -932,6 +2632,51 @@ mod tests {
         assert_timestamp_eq("1970-01-01 00:00:00", 0);
     }
 
+    #[test]
+    fn test_timestamp_parse_with_precision() {
+        let parse = |ptype: PrimitiveType, raw, precision| {
+            ptype.parse_scalar_with_precision(raw, precision)
+        };
+        let micros = "2011-01-11 13:06:07.123456";
+        let millis = "2011-01-11 13:06:07.123";
+        for ptype in [PrimitiveType::Timestamp, PrimitiveType::TimestampNtz] {
+            use TimestampPrecision::*;
+            let value = |micros| match ptype {
+                PrimitiveType::Timestamp => Scalar::Timestamp(micros),
+                _ => Scalar::TimestampNtz(micros),
+            };
+            assert_eq!(
+                parse(ptype.clone(), micros, Micros).unwrap(),
+                value(1294751167123456)
+            );
+            assert_eq!(
+                parse(ptype.clone(), micros, MillisTruncate).unwrap(),
+                value(1294751167123000)
+            );
+            assert!(matches!(
+                parse(ptype.clone(), micros, MillisStrict),
+                Err(Error::ParseError(raw, _)) if raw == micros
+            ));
+            assert_eq!(
+                parse(ptype.clone(), millis, MillisStrict).unwrap(),
+                value(1294751167123000)
+            );
+        }
+
+        // truncation goes towards the past, also before the epoch
+        let before_epoch = "1969-12-31 23:59:59.999999";
+        let truncated = PrimitiveType::Timestamp
+            .parse_scalar_with_precision(before_epoch, TimestampPrecision::MillisTruncate)
+            .unwrap();
+        assert_eq!(truncated, Scalar::Timestamp(-1000));
+
+        // other types ignore the precision
+        let parsed = PrimitiveType::Long
+            .parse_scalar_with_precision("1234567", TimestampPrecision::MillisStrict)
+            .unwrap();
+        assert_eq!(parsed, Scalar::Long(1234567));
+    }
+
     #[test]
     fn test_timestamp_ntz_parse() {
         let assert_timestamp_eq = |scalar_string, micros| {
@@ -943,6 +2688,8 @@ mod tests {
         assert_timestamp_eq("2011-01-11 13:06:07", 1294751167000000);
         assert_timestamp_eq("2011-01-11 13:06:07.123456", 1294751167123456);
         assert_timestamp_eq("1970-01-01 00:00:00", 0);
+        assert_timestamp_eq("2011-01-11T13:06:07", 1294751167000000);
+        assert_timestamp_eq("2011-01-11T13:06:07.123456", 1294751167123456);
     }
 
     #[test]
@@ -992,4 +2739,279 @@ mod tests {
         let null = Scalar::Null(DataType::INTEGER);
         assert!(!null.eq(&null));
     }
+
+    #[test]
+    fn test_null_eq_any_type() {
+        let int_null = Scalar::Null(DataType::INTEGER);
+        let string_null = Scalar::Null(DataType::STRING);
+        let untyped_null = Scalar::Null(DataType::Null);
+
+        // nulls are never equal under the default comparison
+        assert_ne!(int_null, int_null);
+        assert_ne!(int_null, string_null);
+        assert_ne!(int_null, untyped_null);
+
+        // ... but are always equal when ignoring null types
+        assert!(int_null.null_eq_any_type(&int_null));
+        assert!(int_null.null_eq_any_type(&string_null));
+        assert!(string_null.null_eq_any_type(&untyped_null));
+
+        // non-null values compare as usual
+        let a = Scalar::Integer(1);
+        assert!(a.null_eq_any_type(&Scalar::Integer(1)));
+        assert!(!a.null_eq_any_type(&Scalar::Integer(2)));
+        assert!(!a.null_eq_any_type(&Scalar::Long(1)));
+        assert!(!a.null_eq_any_type(&int_null));
+        assert!(!int_null.null_eq_any_type(&a));
+    }
+
+    #[test]
+    fn test_nan() {
+        let float_nan = Scalar::Float(f32::NAN);
+        let double_nan = Scalar::Double(f64::NAN);
+        assert!(float_nan.is_nan());
+        assert!(double_nan.is_nan());
+        assert!(!Scalar::Float(1.0).is_nan());
+        assert!(!Scalar::Double(f64::INFINITY).is_nan());
+        assert!(!Scalar::Null(DataType::DOUBLE).is_nan());
+        assert!(!Scalar::Integer(0).is_nan());
+
+        // the default comparisons are IEEE 754 compliant: NaN is incomparable, even to itself
+        assert_eq!(double_nan.partial_cmp(&double_nan), None);
+        assert_ne!(double_nan, double_nan);
+        assert_ne!(float_nan, float_nan);
+
+        // NaN-aware equality treats NaNs of the same type as equal
+        assert!(double_nan.nan_eq(&Scalar::Double(-f64::NAN)));
+        assert!(float_nan.nan_eq(&float_nan));
+        assert!(!float_nan.nan_eq(&double_nan));
+        assert!(!double_nan.nan_eq(&Scalar::Double(1.0)));
+        assert!(Scalar::Double(1.0).nan_eq(&Scalar::Double(1.0)));
+        assert!(Scalar::Double(0.0).nan_eq(&Scalar::Double(-0.0)));
+        assert!(Scalar::Integer(1).nan_eq(&Scalar::Integer(1)));
+
+        // nulls are still not equal to each other
+        let null = Scalar::Null(DataType::DOUBLE);
+        assert!(!null.nan_eq(&null));
+    }
+
+    #[test]
+    fn test_arithmetic_with_null() {
+        let one = Scalar::Integer(1);
+        let null = Scalar::Null(DataType::INTEGER);
+        for op in [
+            Scalar::try_add,
+            Scalar::try_sub,
+            Scalar::try_mul,
+            Scalar::try_div,
+        ] {
+            let check = |a: &Scalar, b: &Scalar| {
+                let result = op(a, b);
+                assert!(matches!(result, Some(Scalar::Null(ref t)) if *t == DataType::INTEGER));
+            };
+            check(&one, &null);
+            check(&null, &one);
+            check(&null, &null);
+            check(&one, &Scalar::Null(DataType::Null));
+        }
+        assert_eq!(one.try_add(&Scalar::Integer(2)), Some(Scalar::Integer(3)));
+
+        // incompatible types are still rejected
+        assert_eq!(one.try_add(&Scalar::Null(DataType::LONG)), None);
+        assert_eq!(
+            Scalar::from("a").try_add(&Scalar::Null(DataType::STRING)),
+            None
+        );
+        let untyped_null = Scalar::Null(DataType::Null);
+        assert_eq!(untyped_null.try_add(&untyped_null), None);
+    }
+
+    #[test]
+    fn test_map_with_non_string_keys() {
+        let long_map = |pairs: Vec<(i64, Option<&str>)>| {
+            let map_type = MapType::new(DataType::LONG, DataType::STRING, true);
+            let pairs = pairs.into_iter().map(|(k, v)| (k, v.map(String::from)));
+            Scalar::Map(MapData::try_new(map_type, pairs).unwrap())
+        };
+        let a = long_map(vec![(1, Some("a")), (2, None)]);
+        assert_eq!(a.to_string(), "{1: 'a', 2: null}");
+        assert_eq!(a, long_map(vec![(1, Some("a")), (2, None)]));
+        assert_ne!(a, long_map(vec![(1, Some("a")), (3, None)]));
+        assert_ne!(a, long_map(vec![(1, Some("b")), (2, None)]));
+        assert_ne!(a, long_map(vec![(1, Some("a"))]));
+        assert_eq!(a.partial_cmp(&a), Some(Ordering::Equal));
+
+        let map_type = MapType::new(DataType::INTEGER, DataType::STRING, false);
+        let int_map = Scalar::Map(MapData::try_new(map_type, [(1, "a")]).unwrap());
+        assert_eq!(int_map.to_string(), "{1: 'a'}");
+        assert_ne!(int_map, long_map(vec![(1, Some("a"))]));
+
+        let map_type = MapType::new(DataType::BINARY, DataType::STRING, false);
+        let key = Scalar::Binary(vec![1, 2]);
+        let binary_map = Scalar::Map(MapData::try_new(map_type, [(key, "a")]).unwrap());
+        assert_eq!(binary_map.to_string(), "{0x0102: 'a'}");
+        assert_eq!(binary_map, binary_map.clone());
+    }
+
+    #[test]
+    fn test_deeply_nested_literal() {
+        // map<string, array<struct<x: int not null, tags: array<string>>>>
+        let tags_type = ArrayType::new(DataType::STRING, true);
+        let fields = vec![
+            StructField::not_null("x", DataType::INTEGER),
+            StructField::nullable("tags", tags_type.clone()),
+        ];
+        let leaf_type = DataType::struct_type(fields.clone());
+        let leaf = |x: Scalar, tags: Scalar| StructData::try_new(fields.clone(), vec![x, tags]);
+        let tags = Scalar::Array(ArrayData::try_new(tags_type, ["a", "b"]).unwrap());
+        let arr_type = ArrayType::new(leaf_type.clone(), true);
+        let arr = ArrayData::try_new(
+            arr_type.clone(),
+            [
+                Scalar::Struct(leaf(1.into(), tags).unwrap()),
+                Scalar::Struct(leaf(2.into(), Scalar::Null(DataType::Null)).unwrap()),
+                Scalar::Null(DataType::Null),
+            ],
+        )
+        .unwrap();
+        let map_type = MapType::new(DataType::STRING, arr_type.clone(), true);
+        let map = MapData::try_new(
+            map_type.clone(),
+            [
+                ("k", Scalar::Array(arr)),
+                ("empty", Scalar::Null(DataType::Null)),
+            ],
+        )
+        .unwrap();
+        let map = Scalar::Map(map);
+        assert_eq!(map.data_type(), DataType::Map(Box::new(map_type.clone())));
+        assert_eq!(
+            map.to_string(),
+            "{'k': ({x: 1, tags: ('a', 'b')}, {x: 2, tags: null}, null), 'empty': null}"
+        );
+
+        // type errors are caught at every level of nesting
+        assert!(leaf(Scalar::Null(DataType::Null), Scalar::Null(DataType::Null)).is_err());
+        assert!(leaf(1i64.into(), Scalar::Null(DataType::Null)).is_err());
+        let wrong_tags = Scalar::Array(
+            ArrayData::try_new(ArrayType::new(DataType::INTEGER, true), [1]).unwrap(),
+        );
+        assert!(leaf(1.into(), wrong_tags).is_err());
+        let wrong_leaf_type =
+            DataType::struct_type([StructField::nullable("x", DataType::INTEGER)]);
+        let wrong_leaf = StructData::try_new(
+            vec![StructField::nullable("x", DataType::INTEGER)],
+            vec![1.into()],
+        )
+        .unwrap();
+        assert_ne!(wrong_leaf_type, leaf_type);
+        assert!(
+            ArrayData::try_new(arr_type.clone(), [Scalar::Struct(wrong_leaf.clone())]).is_err()
+        );
+        let wrong_arr = ArrayData::try_new(
+            ArrayType::new(wrong_leaf_type, true),
+            [Scalar::Struct(wrong_leaf)],
+        );
+        assert!(MapData::try_new(map_type, [("k", Scalar::Array(wrong_arr.unwrap()))]).is_err());
+        let non_null_arr_type = ArrayType::new(leaf_type, false);
+        assert!(ArrayData::try_new(non_null_arr_type, [Scalar::Null(DataType::Null)]).is_err());
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        let pairs = [
+            (Scalar::Float(-0.0), Scalar::Float(0.0)),
+            (Scalar::Double(-0.0), Scalar::Double(0.0)),
+        ];
+        for (neg, pos) in pairs {
+            assert_eq!(neg, pos);
+            assert_eq!(neg.partial_cmp(&pos), Some(Ordering::Equal));
+            assert_eq!(neg.total_cmp(&pos), Ordering::Equal);
+            assert_eq!(pos.total_cmp(&neg), Ordering::Equal);
+        }
+        // NaN still sorts after everything else, and other values are unaffected
+        let mut values = [
+            Scalar::Double(f64::NAN),
+            Scalar::Double(0.0),
+            Scalar::Double(-1.0),
+            Scalar::Double(-0.0),
+            Scalar::Double(f64::INFINITY),
+        ];
+        values.sort_by(Scalar::total_cmp);
+        assert!(values
+            .iter()
+            .skip(1)
+            .take(2)
+            .all(|v| *v == Scalar::Double(0.0)));
+        assert_eq!(values[0], Scalar::Double(-1.0));
+        assert_eq!(values[3], Scalar::Double(f64::INFINITY));
+        assert!(values[4].is_nan());
+
+        // zeros nested in arrays compare equal too
+        let array = |v: f32| {
+            Scalar::Array(ArrayData::try_new(ArrayType::new(DataType::FLOAT, false), [v]).unwrap())
+        };
+        assert_eq!(array(-0.0), array(0.0));
+        assert_eq!(array(-0.0).total_cmp(&array(0.0)), Ordering::Equal);
+
+        // decimals have no negative zero, since their unscaled values are integers
+        let ty = DecimalType::try_new(5, 2).unwrap();
+        let neg = Scalar::Decimal(DecimalData::try_new(-0i128, ty).unwrap());
+        let pos = Scalar::Decimal(DecimalData::try_new(0i128, ty).unwrap());
+        assert_eq!(neg, pos);
+        assert_eq!(neg.total_cmp(&pos), Ordering::Equal);
+        assert_eq!(neg.to_string(), pos.to_string());
+    }
+
+    #[test]
+    fn test_format_binary() {
+        let bytes: Vec<u8> = (0..20).map(|i| i * 13).collect();
+        let binary = Scalar::Binary(bytes.clone());
+        let hex = "0x000d1a2734414e5b6875828f9ca9b6c3d0ddeaf7";
+        assert_eq!(binary.format_binary(BinaryDisplay::Hex), hex);
+        assert_eq!(
+            binary.format_binary(BinaryDisplay::Base64),
+            "AA0aJzRBTltodYKPnKm2w9Dd6vc="
+        );
+        let truncated = binary.format_binary(BinaryDisplay::Truncated { max: 4 });
+        assert_eq!(truncated, "0x000d1a27...(20 bytes)");
+        let not_truncated = binary.format_binary(BinaryDisplay::Truncated { max: 20 });
+        assert_eq!(not_truncated, hex);
+
+        // Display truncates to 16 bytes
+        assert_eq!(
+            binary.to_string(),
+            "0x000d1a2734414e5b6875828f9ca9b6c3...(20 bytes)"
+        );
+        let empty = Scalar::Binary(vec![]);
+        assert_eq!(empty.to_string(), "0x");
+        assert_eq!(empty.format_binary(BinaryDisplay::Base64), "");
+
+        // base64 padding
+        for (bytes, expected) in [(&b"f"[..], "Zg=="), (b"fo", "Zm8="), (b"foo", "Zm9v")] {
+            let scalar = Scalar::Binary(bytes.to_vec());
+            assert_eq!(scalar.format_binary(BinaryDisplay::Base64), expected);
+        }
+
+        // nested binary values use the same mode, and other values are unaffected
+        let array_type = ArrayType::new(DataType::BINARY, true);
+        let array = ArrayData::try_new(
+            array_type,
+            [Scalar::Binary(bytes), Scalar::Null(DataType::BINARY)],
+        );
+        let fields = vec![
+            StructField::nullable("b", DataType::BINARY),
+            StructField::nullable("s", DataType::STRING),
+        ];
+        let values = vec![Scalar::Binary(vec![0xff; 3]), Scalar::from("x")];
+        let nested = Scalar::Struct(StructData::try_new(fields, values).unwrap());
+        assert_eq!(
+            nested.format_binary(BinaryDisplay::Base64),
+            "{b: ////, s: 'x'}"
+        );
+        assert_eq!(
+            Scalar::Array(array.unwrap()).format_binary(BinaryDisplay::Truncated { max: 2 }),
+            "(0x000d...(20 bytes), null)"
+        );
+    }
 }
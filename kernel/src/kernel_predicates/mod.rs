@@ -4,14 +4,18 @@
 //! but data skipping "evaluation" actually produces a transformed predicate that replaces column
 //! references with stats column references, which log replay will instruct the engine to evaluate.
 use crate::expressions::{
-    BinaryExpression, BinaryExpressionOp, BinaryPredicate, BinaryPredicateOp, ColumnName,
-    Expression as Expr, JunctionPredicate, JunctionPredicateOp, OpaqueExpression,
-    OpaqueExpressionOpRef, OpaquePredicate, OpaquePredicateOpRef, Predicate as Pred, Scalar,
-    UnaryPredicate, UnaryPredicateOp,
+    coerce_for_comparison, widest_type, BinaryExpression, BinaryExpressionOp, BinaryPredicate,
+    BinaryPredicateOp, ColumnName, Expression as Expr, JunctionPredicate, JunctionPredicateOp,
+    OpaqueExpression, OpaqueExpressionOpRef, OpaquePredicate, OpaquePredicateOpRef,
+    Predicate as Pred, Scalar, StructData, UnaryPredicate, UnaryPredicateOp, VariadicExpression,
+    VariadicExpressionOp,
 };
 use crate::schema::DataType;
 
+use delta_kernel_derive::internal_api;
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use tracing::{debug, warn};
 
 pub(crate) mod parquet_stats_skipping;
@@ -145,6 +149,10 @@ pub trait KernelPredicateEvaluator {
     /// AND and OR are implemented by first evaluating its (possibly inverted) inputs. This part is
     /// always the same, provided by [`Self::eval_pred_junction`]). The results are then combined to
     /// become the predicate's output in some implementation-defined way (this method).
+    ///
+    /// The inputs are evaluated lazily, as `preds` is consumed. Implementations should stop
+    /// consuming `preds` as soon as the output is known, so that any remaining (potentially
+    /// expensive) inputs are never evaluated.
     fn finish_eval_pred_junction(
         &self,
         op: JunctionPredicateOp,
@@ -177,7 +185,7 @@ pub trait KernelPredicateEvaluator {
             Expr::Opaque(OpaqueExpression { op, exprs }) => {
                 self.eval_pred_expr_opaque(op, exprs, inverted)
             }
-            Expr::Struct(_) | Expr::Binary(_) | Expr::Unknown(_) => None,
+            Expr::Struct(_) | Expr::Binary(_) | Expr::Variadic(_) | Expr::Unknown(_) => None,
         }
     }
 
@@ -200,6 +208,7 @@ pub trait KernelPredicateEvaluator {
                 Expr::Predicate(_)
                 | Expr::Struct(_)
                 | Expr::Binary(_)
+                | Expr::Variadic(_)
                 | Expr::Opaque(_)
                 | Expr::Unknown(_) => {
                     debug!("Unsupported operand: IS [NOT] NULL: {expr:?}");
@@ -266,6 +275,8 @@ pub trait KernelPredicateEvaluator {
                 Equal => self.eval_pred_eq(col, val, inverted),
                 Distinct => self.eval_pred_distinct(col, val, inverted),
                 In => self.eval_pred_in(col, val, inverted),
+                // With SQL NULL semantics, `x NOT IN list` is exactly the inverse of `x IN list`
+                NotIn => self.eval_pred_in(col, val, !inverted),
             },
             (Literal(val), Column(col)) => match op {
                 // NOTE: The column has to be on the left, so e.g. `10 < x` becomes `x > 10`
@@ -273,7 +284,7 @@ pub trait KernelPredicateEvaluator {
                 GreaterThan => self.eval_pred_lt(col, val, inverted),
                 Equal => self.eval_pred_eq(col, val, inverted),
                 Distinct => self.eval_pred_distinct(col, val, inverted),
-                In => None, // arg order is semantically important
+                In | NotIn => None, // arg order is semantically important
             },
             _ => {
                 debug!("Unsupported binary operand(s): {left:?} {op:?} {right:?}");
@@ -283,7 +294,7 @@ pub trait KernelPredicateEvaluator {
     }
 
     /// Dispatches a predicate junction operation (AND or OR), leveraging each implementation's
-    /// [`Self::finish_eval_pred_junction`].
+    /// [`Self::finish_eval_pred_junction`]. Each input is only evaluated once it is requested.
     fn eval_pred_junction(
         &self,
         op: JunctionPredicateOp,
@@ -425,23 +436,23 @@ pub trait KernelPredicateEvaluator {
                 self.finish_eval_pred_junction(*op, &mut preds, inverted)
             }
             Binary(BinaryPredicate { op, left, right }) if op.is_null_intolerant() => {
-                // Perform a nullsafe comparison instead of the usual `eval_pred_binary`
-                let mut preds = [
-                    self.eval_pred_unary(UnaryPredicateOp::IsNull, left, true),
-                    self.eval_pred_unary(UnaryPredicateOp::IsNull, right, true),
-                    self.eval_pred_binary(*op, left, right, inverted),
-                ]
-                .into_iter();
+                // Perform a nullsafe comparison instead of the usual `eval_pred_binary`. The
+                // inputs are evaluated lazily, so a NULL operand skips the comparison entirely.
+                let preds: [&dyn Fn() -> _; 3] = [
+                    &|| self.eval_pred_unary(UnaryPredicateOp::IsNull, left, true),
+                    &|| self.eval_pred_unary(UnaryPredicateOp::IsNull, right, true),
+                    &|| self.eval_pred_binary(*op, left, right, inverted),
+                ];
+                let mut preds = preds.into_iter().map(|pred| pred());
                 self.finish_eval_pred_junction(JunctionPredicateOp::And, &mut preds, false)
             }
             Not(pred) => self.eval_pred_sql_where(pred, !inverted),
             BooleanExpression(Expr::Column(col)) => {
                 // Perform a nullsafe comparison instead of the usual `eval_pred_column`
-                let mut preds = [
-                    self.eval_pred_is_null(col, true),
-                    self.eval_pred_column(col, inverted),
-                ]
-                .into_iter();
+                let preds: [&dyn Fn() -> _; 2] = [&|| self.eval_pred_is_null(col, true), &|| {
+                    self.eval_pred_column(col, inverted)
+                }];
+                let mut preds = preds.into_iter().map(|pred| pred());
                 self.finish_eval_pred_junction(JunctionPredicateOp::And, &mut preds, false)
             }
             BooleanExpression(Expr::Literal(val)) if val.is_null() => {
@@ -527,13 +538,104 @@ impl KernelPredicateEvaluatorDefaults {
             Equal => Self::partial_cmp_scalars(Ordering::Equal, left, right, inverted),
             LessThan => Self::partial_cmp_scalars(Ordering::Less, left, right, inverted),
             GreaterThan => Self::partial_cmp_scalars(Ordering::Greater, left, right, inverted),
-            Distinct | In => {
+            In => Self::eval_pred_in_scalars(left, right, inverted),
+            NotIn => Self::eval_pred_in_scalars(left, right, !inverted),
+            Distinct => {
                 debug!("Unsupported binary operator: {left:?} {op:?} {right:?}");
                 None
             }
         }
     }
 
+    /// Like [`Self::eval_pred_binary_scalars`], but the operands are first coerced to a common type
+    /// following Spark's implicit coercion rules, e.g. so that a decimal can be compared with a long
+    /// or a string with a double. This applies to every operator: comparisons (`=`, `<`, `>`),
+    /// DISTINCT, and IN-list checks (where the value is coerced against each element in turn). A
+    /// comparison of incomparable types (e.g. boolean vs string) is NULL.
+    pub fn eval_pred_binary_scalars_coerced(
+        op: BinaryPredicateOp,
+        left: &Scalar,
+        right: &Scalar,
+        inverted: bool,
+    ) -> Option<bool> {
+        use BinaryPredicateOp::*;
+        match op {
+            Equal | LessThan | GreaterThan => {
+                let (left, right) = coerce_for_comparison(left, right)?;
+                Self::eval_pred_binary_scalars(op, &left, &right, inverted)
+            }
+            In => Self::eval_pred_in_scalars_coerced(left, right, inverted),
+            NotIn => Self::eval_pred_in_scalars_coerced(left, right, !inverted),
+            // NULL is not distinct from NULL, but is distinct from any other value
+            Distinct => match (left.is_null(), right.is_null()) {
+                (true, true) => Some(inverted),
+                (true, false) | (false, true) => Some(!inverted),
+                (false, false) => {
+                    let (left, right) = coerce_for_comparison(left, right)?;
+                    Self::partial_cmp_scalars(Ordering::Equal, &left, &right, !inverted)
+                }
+            },
+        }
+    }
+
+    /// Directly evaluates a (possibly inverted) IN-list check of a scalar against the elements of
+    /// an array scalar, widening numeric values to a common type as needed. NULL handling follows
+    /// SQL semantics (see [`Pred::is_in`]), and inverting the result does not change a NULL output.
+    pub fn eval_pred_in_scalars(val: &Scalar, list: &Scalar, inverted: bool) -> Option<bool> {
+        let Scalar::Array(list) = list else {
+            debug!("Unsupported right operand for IN: {list:?}");
+            return None;
+        };
+        if list.is_empty() {
+            return Some(inverted);
+        }
+        let ty = widest_type(&val.data_type(), list.array_type().element_type())?;
+        let val = val.try_widen_to(&ty)?;
+        if val.is_null() {
+            return None;
+        }
+        let mut found_null = false;
+        for element in list.elements() {
+            match val.partial_cmp(&element.try_widen_to(&ty)?) {
+                Some(Ordering::Equal) => return Some(!inverted), // short circuit!
+                Some(_) => (),
+                None => found_null = true,
+            }
+        }
+        (!found_null).then_some(inverted)
+    }
+
+    /// Like [`Self::eval_pred_in_scalars`], but the value is coerced against each element as for a
+    /// comparison (see [`Self::eval_pred_binary_scalars_coerced`]). An element that cannot be
+    /// coerced compares as NULL.
+    pub fn eval_pred_in_scalars_coerced(
+        val: &Scalar,
+        list: &Scalar,
+        inverted: bool,
+    ) -> Option<bool> {
+        let Scalar::Array(list) = list else {
+            debug!("Unsupported right operand for IN: {list:?}");
+            return None;
+        };
+        if list.is_empty() {
+            return Some(inverted);
+        }
+        if val.is_null() {
+            return None;
+        }
+        let mut found_null = false;
+        for element in list.elements() {
+            let cmp = coerce_for_comparison(val, &element)
+                .and_then(|(val, element)| val.partial_cmp(&element));
+            match cmp {
+                Some(Ordering::Equal) => return Some(!inverted), // short circuit!
+                Some(_) => (),
+                None => found_null = true,
+            }
+        }
+        (!found_null).then_some(inverted)
+    }
+
     /// Finishes evaluating a (possibly inverted) junction operation. See
     /// [`KernelPredicateEvaluator::finish_eval_pred_junction`].
     ///
@@ -564,8 +666,12 @@ impl KernelPredicateEvaluatorDefaults {
 }
 
 /// Resolves columns as scalars, as a building block for [`DefaultKernelPredicateEvaluator`].
+///
+/// Resolvers return a [`Cow`] so that they can hand out references to scalars they already own,
+/// instead of cloning them for every column reference the evaluator encounters.
+#[internal_api]
 pub(crate) trait ResolveColumnAsScalar {
-    fn resolve_column(&self, col: &ColumnName) -> Option<Scalar>;
+    fn resolve_column(&self, col: &ColumnName) -> Option<Cow<'_, Scalar>>;
 }
 
 // Some tests do not actually require column resolution
@@ -573,7 +679,7 @@ pub(crate) trait ResolveColumnAsScalar {
 pub(crate) struct UnimplementedColumnResolver;
 #[cfg(test)]
 impl ResolveColumnAsScalar for UnimplementedColumnResolver {
-    fn resolve_column(&self, _col: &ColumnName) -> Option<Scalar> {
+    fn resolve_column(&self, _col: &ColumnName) -> Option<Cow<'_, Scalar>> {
         unimplemented!()
     }
 }
@@ -581,32 +687,53 @@ impl ResolveColumnAsScalar for UnimplementedColumnResolver {
 // Used internally and by some tests
 pub(crate) struct EmptyColumnResolver;
 impl ResolveColumnAsScalar for EmptyColumnResolver {
-    fn resolve_column(&self, _col: &ColumnName) -> Option<Scalar> {
+    fn resolve_column(&self, _col: &ColumnName) -> Option<Cow<'_, Scalar>> {
         None
     }
 }
 
-impl ResolveColumnAsScalar for std::collections::HashMap<ColumnName, Scalar> {
-    fn resolve_column(&self, col: &ColumnName) -> Option<Scalar> {
-        self.get(col).cloned()
+impl ResolveColumnAsScalar for HashMap<ColumnName, Scalar> {
+    fn resolve_column(&self, col: &ColumnName) -> Option<Cow<'_, Scalar>> {
+        self.get(col).map(Cow::Borrowed)
+    }
+}
+
+/// Resolves top-level columns by name, borrowing both names and values. This allows e.g. partition
+/// pruning to evaluate a predicate without allocating a [`ColumnName`] or cloning a [`Scalar`] for
+/// every partition value of every file.
+impl ResolveColumnAsScalar for HashMap<&str, &Scalar> {
+    fn resolve_column(&self, col: &ColumnName) -> Option<Cow<'_, Scalar>> {
+        match col.path() {
+            [name] => self.get(name.as_str()).map(|value| Cow::Borrowed(*value)),
+            _ => None,
+        }
     }
 }
 
 /// A predicate evaluator that directly evaluates the predicate to produce an `Option<bool>`
 /// result. Column resolution is handled by an embedded [`ResolveColumnAsScalar`] instance.
+#[internal_api]
 pub(crate) struct DefaultKernelPredicateEvaluator<R: ResolveColumnAsScalar> {
     resolver: R,
 }
 impl<R: ResolveColumnAsScalar> DefaultKernelPredicateEvaluator<R> {
-    // Convenient thin wrapper
-    fn resolve_column(&self, col: &ColumnName) -> Option<Scalar> {
-        self.resolver.resolve_column(col)
+    // Resolves a column by name. If the resolver does not know a nested column, falls back to
+    // resolving its nearest enclosing struct column and extracting the field from the struct value.
+    fn resolve_column(&self, col: &ColumnName) -> Option<Cow<'_, Scalar>> {
+        if let Some(value) = self.resolver.resolve_column(col) {
+            return Some(value);
+        }
+        let path = col.path();
+        (1..path.len()).rev().find_map(|i| {
+            let parent = self.resolver.resolve_column(&ColumnName::new(&path[..i]))?;
+            path[i..].iter().try_fold(parent, struct_field)
+        })
     }
 
     pub(crate) fn eval_expr(&self, expr: &Expr) -> Option<Scalar> {
         match expr {
             Expr::Literal(value) => Some(value.clone()),
-            Expr::Column(name) => self.resolve_column(name),
+            Expr::Column(name) => self.resolve_column(name).map(Cow::into_owned),
             Expr::Predicate(pred) => self.eval_pred(pred, false).map(Scalar::from),
             Expr::Struct(_) => None, // TODO
             Expr::Binary(BinaryExpression { op, left, right }) => {
@@ -618,6 +745,23 @@ impl<R: ResolveColumnAsScalar> DefaultKernelPredicateEvaluator<R> {
                 };
                 op_fn(&self.eval_expr(left)?, &self.eval_expr(right)?)
             }
+            Expr::Variadic(VariadicExpression {
+                op: VariadicExpressionOp::Coalesce,
+                exprs,
+            }) => {
+                // The first non-NULL input, or the last (NULL) input if all are NULL. Inputs after
+                // the first non-NULL one are never evaluated.
+                let mut result = None;
+                for expr in exprs {
+                    let value = self.eval_expr(expr)?;
+                    let is_null = value.is_null();
+                    result = Some(value);
+                    if !is_null {
+                        break;
+                    }
+                }
+                result
+            }
             Expr::Opaque(OpaqueExpression { op, exprs }) => op
                 .eval_expr_scalar(&|expr| self.eval_expr(expr), exprs)
                 .inspect_err(|err| {
@@ -629,7 +773,27 @@ impl<R: ResolveColumnAsScalar> DefaultKernelPredicateEvaluator<R> {
     }
 }
 
-impl<R: ResolveColumnAsScalar + 'static> From<R> for DefaultKernelPredicateEvaluator<R> {
+/// Extracts the named field of a struct value. The fields of a NULL struct are NULL.
+fn struct_field<'a>(value: Cow<'a, Scalar>, name: &String) -> Option<Cow<'a, Scalar>> {
+    let field_index = |data: &StructData| data.fields().iter().position(|f| f.name() == name);
+    match value {
+        Cow::Borrowed(Scalar::Struct(data)) => {
+            Some(Cow::Borrowed(&data.values()[field_index(data)?]))
+        }
+        Cow::Owned(Scalar::Struct(data)) => {
+            Some(Cow::Owned(data.values()[field_index(&data)?].clone()))
+        }
+        value => match value.as_ref() {
+            Scalar::Null(DataType::Struct(fields)) => {
+                let field = fields.field(name)?;
+                Some(Cow::Owned(Scalar::Null(field.data_type().clone())))
+            }
+            _ => None,
+        },
+    }
+}
+
+impl<R: ResolveColumnAsScalar> From<R> for DefaultKernelPredicateEvaluator<R> {
     fn from(resolver: R) -> Self {
         Self { resolver }
     }
@@ -669,6 +833,13 @@ impl<R: ResolveColumnAsScalar> KernelPredicateEvaluator for DefaultKernelPredica
         self.eval_pred_binary_scalars(BinaryPredicateOp::Equal, &col, val, inverted)
     }
 
+    fn eval_pred_in(&self, col: &ColumnName, val: &Scalar, inverted: bool) -> Option<bool> {
+        let col = self.resolve_column(col)?;
+        self.eval_pred_binary_scalars(BinaryPredicateOp::In, &col, val, inverted)
+    }
+
+    /// Comparisons between scalars of different types first coerce both operands to a common type,
+    /// see [`KernelPredicateEvaluatorDefaults::eval_pred_binary_scalars_coerced`].
     fn eval_pred_binary_scalars(
         &self,
         op: BinaryPredicateOp,
@@ -676,7 +847,9 @@ impl<R: ResolveColumnAsScalar> KernelPredicateEvaluator for DefaultKernelPredica
         right: &Scalar,
         inverted: bool,
     ) -> Option<bool> {
-        KernelPredicateEvaluatorDefaults::eval_pred_binary_scalars(op, left, right, inverted)
+        KernelPredicateEvaluatorDefaults::eval_pred_binary_scalars_coerced(
+            op, left, right, inverted,
+        )
     }
 
     fn eval_pred_binary_columns(
@@ -899,6 +1072,26 @@ pub trait DataSkippingPredicateEvaluator {
         };
         self.finish_eval_pred_junction(op, &mut preds.into_iter(), false)
     }
+
+    /// See [`KernelPredicateEvaluator::eval_pred_in`]
+    fn eval_pred_in(&self, col: &ColumnName, val: &Scalar, inverted: bool) -> Option<Self::Output> {
+        let Scalar::Array(list) = val else {
+            debug!("Unsupported right operand for IN: {val:?}");
+            return None;
+        };
+        // Given `col IN (v1, v2, ...)`, keep if `col = v1 OR col = v2 OR ...` could be true, i.e.
+        // if the min/max values bracket some element of the list. Given `col NOT IN (...)`, keep if
+        // `col != v1 AND col != v2 AND ...` could be true. Ignoring NULL elements is conservative:
+        // they never make IN true, and only ever keep NOT IN from being true.
+        let op = if inverted {
+            JunctionPredicateOp::And
+        } else {
+            JunctionPredicateOp::Or
+        };
+        let elements = list.elements().filter(|element| !element.is_null());
+        let mut preds = elements.map(|element| self.eval_pred_eq(col, &element, inverted));
+        self.finish_eval_pred_junction(op, &mut preds, false)
+    }
 }
 
 impl<T: DataSkippingPredicateEvaluator + ?Sized> KernelPredicateEvaluator for T {
@@ -928,6 +1121,10 @@ impl<T: DataSkippingPredicateEvaluator + ?Sized> KernelPredicateEvaluator for T
         self.eval_pred_eq(col, val, inverted)
     }
 
+    fn eval_pred_in(&self, col: &ColumnName, val: &Scalar, inverted: bool) -> Option<Self::Output> {
+        self.eval_pred_in(col, val, inverted)
+    }
+
     fn eval_pred_binary_scalars(
         &self,
         op: BinaryPredicateOp,
@@ -1,11 +1,12 @@
 use super::*;
 use crate::expressions::{
-    column_expr, column_name, column_pred, ArrayData, Expression as Expr, OpaqueExpressionOp,
-    OpaquePredicateOp, Predicate as Pred, ScalarExpressionEvaluator, StructData,
+    column_expr, column_name, column_pred, ArrayData, Expression as Expr, MapData,
+    OpaqueExpressionOp, OpaquePredicateOp, Predicate as Pred, ScalarExpressionEvaluator,
+    StructData,
 };
 use crate::kernel_predicates::parquet_stats_skipping::ParquetStatsProvider;
 use crate::scan::data_skipping::as_data_skipping_predicate;
-use crate::schema::ArrayType;
+use crate::schema::{ArrayType, MapType, StructField};
 use crate::DataType;
 use crate::DeltaResult;
 
@@ -26,8 +27,8 @@ macro_rules! expect_eq {
 }
 
 impl ResolveColumnAsScalar for Scalar {
-    fn resolve_column(&self, _col: &ColumnName) -> Option<Scalar> {
-        Some(self.clone())
+    fn resolve_column(&self, _col: &ColumnName) -> Option<Cow<'_, Scalar>> {
+        Some(Cow::Borrowed(self))
     }
 }
 
@@ -94,7 +95,7 @@ fn test_default_partial_cmp_scalars() {
         Scalar::decimal(10, 10, 10).unwrap(),
         Null(DataType::LONG),
         Struct(StructData::try_new(vec![], vec![]).unwrap()),
-        Array(ArrayData::try_new(ArrayType::new(DataType::LONG, false), &[] as &[i64]).unwrap()),
+        Array(ArrayData::try_new(ArrayType::new(DataType::LONG, false), [10i64]).unwrap()),
     ];
 
     // scalars of different types are always incomparable
@@ -115,7 +116,7 @@ fn test_default_partial_cmp_scalars() {
     }
 
     let expect_if_comparable_type = |s: &_, expect| match s {
-        Null(_) | Struct(_) | Array(_) => None,
+        Null(_) | Struct(_) => None,
         _ => Some(expect),
     };
 
@@ -184,6 +185,35 @@ fn test_default_partial_cmp_scalars() {
     }
 }
 
+#[test]
+fn test_default_coalesce() {
+    let filter = DefaultKernelPredicateEvaluator::from(Scalar::from(1));
+    let null = || Expr::null_literal(DataType::LONG);
+    let coalesce = |exprs: Vec<Expr>| filter.eval_expr(&Expr::coalesce(exprs));
+    expect_eq!(
+        coalesce(vec![null(), Expr::literal(2i64), Expr::literal(3i64)]),
+        Some(Scalar::Long(2)),
+        "first non-null input"
+    );
+    // NULL scalars never compare equal, so check the result is NULL directly
+    let result = coalesce(vec![null(), null()]);
+    assert!(
+        result.is_some_and(|value| value.is_null()),
+        "all inputs null"
+    );
+    expect_eq!(
+        coalesce(vec![Expr::literal(1i64), Expr::unknown("missing")]),
+        Some(Scalar::Long(1)),
+        "inputs after the first non-null are not evaluated"
+    );
+    expect_eq!(
+        coalesce(vec![Expr::unknown("missing"), Expr::literal(1i64)]),
+        None,
+        "unknown input before the first non-null"
+    );
+    expect_eq!(coalesce(vec![]), None, "no inputs");
+}
+
 #[test]
 fn test_default_scalar_arithmetic() {
     use Scalar::*;
@@ -356,6 +386,269 @@ fn test_eval_binary_scalars() {
     }
 }
 
+#[test]
+fn test_eval_binary_scalars_coerced() {
+    let decimal = |bits, precision, scale| Scalar::decimal(bits, precision, scale).unwrap();
+    let x = column_expr!("x");
+    let test_cases = [
+        // decimal vs long: both become decimals with a common scale
+        (
+            Pred::eq(x.clone(), Scalar::Long(12)),
+            decimal(1200, 4, 2),
+            Some(true),
+        ),
+        (
+            Pred::lt(x.clone(), Scalar::Long(12)),
+            decimal(1199, 4, 2),
+            Some(true),
+        ),
+        (
+            Pred::gt(x.clone(), Scalar::Long(12)),
+            decimal(1201, 4, 2),
+            Some(true),
+        ),
+        (
+            Pred::gt(x.clone(), Scalar::Long(i64::MAX)),
+            decimal(-1, 4, 2),
+            Some(false),
+        ),
+        (
+            Pred::eq(x.clone(), decimal(12, 2, 0)),
+            Scalar::Long(12),
+            Some(true),
+        ),
+        (
+            Pred::lt(x.clone(), decimal(125, 3, 1)),
+            Scalar::Integer(12),
+            Some(true),
+        ),
+        // decimals of different precision and scale
+        (
+            Pred::eq(x.clone(), decimal(12, 3, 1)),
+            decimal(120, 4, 2),
+            Some(true),
+        ),
+        // long vs double: the long is widened to a double
+        (
+            Pred::eq(x.clone(), Scalar::Double(12.0)),
+            Scalar::Long(12),
+            Some(true),
+        ),
+        (
+            Pred::lt(x.clone(), Scalar::Double(12.5)),
+            Scalar::Long(12),
+            Some(true),
+        ),
+        (
+            Pred::gt(x.clone(), Scalar::Long(12)),
+            Scalar::Double(12.5),
+            Some(true),
+        ),
+        // decimal vs double: the decimal becomes a double
+        (
+            Pred::lt(x.clone(), Scalar::Double(12.5)),
+            decimal(1249, 4, 2),
+            Some(true),
+        ),
+        // string vs numeric: the string is parsed as the numeric type
+        (
+            Pred::eq(x.clone(), Scalar::Long(12)),
+            Scalar::from("12"),
+            Some(true),
+        ),
+        (
+            Pred::lt(x.clone(), Scalar::from("12.5")),
+            Scalar::Double(12.0),
+            Some(true),
+        ),
+        (
+            Pred::eq(x.clone(), decimal(1250, 4, 2)),
+            Scalar::from("12.5"),
+            Some(true),
+        ),
+        (
+            Pred::eq(x.clone(), Scalar::Long(12)),
+            Scalar::from("twelve"),
+            None,
+        ),
+        // genuinely incomparable types
+        (
+            Pred::eq(x.clone(), Scalar::from("true")),
+            Scalar::Boolean(true),
+            None,
+        ),
+        (
+            Pred::eq(x.clone(), Scalar::Long(1)),
+            Scalar::Boolean(true),
+            None,
+        ),
+        (Pred::eq(x.clone(), Scalar::Long(1)), Scalar::Date(1), None),
+        (
+            Pred::eq(x.clone(), Scalar::Long(1)),
+            Scalar::Null(DataType::STRING),
+            None,
+        ),
+    ];
+    for (pred, value, expect) in test_cases {
+        let filter = DefaultKernelPredicateEvaluator::from(value.clone());
+        expect_eq!(filter.eval(&pred), expect, "{pred} with x = {value}");
+        let expect_inverted = expect.map(|v| !v);
+        expect_eq!(
+            filter.eval(&Pred::not(pred.clone())),
+            expect_inverted,
+            "NOT({pred}) with x = {value}"
+        );
+    }
+}
+
+#[test]
+fn test_eval_distinct_and_in_scalars_coerced() {
+    let decimal = |bits, precision, scale| Scalar::decimal(bits, precision, scale).unwrap();
+    let x = column_expr!("x");
+    let test_cases = [
+        // DISTINCT coerces like `=`, whether against a column or between two literals
+        (
+            Pred::distinct(x.clone(), Scalar::Long(12)),
+            decimal(1200, 4, 2),
+            Some(false),
+        ),
+        (
+            Pred::distinct(x.clone(), Scalar::Long(12)),
+            Scalar::from("13"),
+            Some(true),
+        ),
+        (
+            Pred::distinct(
+                Expr::literal(decimal(1200, 4, 2)),
+                Expr::literal(Scalar::Long(12)),
+            ),
+            Scalar::Long(0),
+            Some(false),
+        ),
+        (
+            Pred::distinct(
+                Expr::literal(Scalar::Null(DataType::LONG)),
+                Expr::literal(Scalar::from("12")),
+            ),
+            Scalar::Long(0),
+            Some(true),
+        ),
+        // IN coerces the value against each element
+        (
+            Pred::is_in(x.clone(), [Scalar::Long(11), Scalar::Long(12)]).unwrap(),
+            decimal(1200, 4, 2),
+            Some(true),
+        ),
+        (
+            Pred::is_in(x.clone(), [Scalar::Long(11), Scalar::Long(12)]).unwrap(),
+            Scalar::from("12"),
+            Some(true),
+        ),
+        (
+            Pred::is_in(x.clone(), [decimal(125, 3, 1)]).unwrap(),
+            Scalar::Double(12.5),
+            Some(true),
+        ),
+        (
+            Pred::is_in(x.clone(), [Scalar::Long(11), Scalar::Long(12)]).unwrap(),
+            Scalar::from("13"),
+            Some(false),
+        ),
+        // an element that cannot be coerced compares as NULL
+        (
+            Pred::is_in(x.clone(), [Scalar::Long(11)]).unwrap(),
+            Scalar::from("twelve"),
+            None,
+        ),
+        (
+            Pred::is_in(x.clone(), [Scalar::Long(1)]).unwrap(),
+            Scalar::Boolean(true),
+            None,
+        ),
+        // a string with a huge exponent fails to parse as a decimal, without scaling it
+        (
+            Pred::eq(x.clone(), decimal(0, 1, 0)),
+            Scalar::from("0E4294967295"),
+            None,
+        ),
+    ];
+    for (pred, value, expect) in test_cases {
+        let filter = DefaultKernelPredicateEvaluator::from(value.clone());
+        expect_eq!(filter.eval(&pred), expect, "{pred} with x = {value}");
+        let expect_inverted = expect.map(|v| !v);
+        expect_eq!(
+            filter.eval(&Pred::not(pred.clone())),
+            expect_inverted,
+            "NOT({pred}) with x = {value}"
+        );
+    }
+}
+
+#[test]
+fn test_eval_in_scalars() {
+    let list = |values: &[Scalar]| Pred::is_in(column_expr!("x"), values.to_vec()).unwrap();
+    let ints = list(&[Scalar::Integer(1), Scalar::Integer(2), Scalar::Integer(3)]);
+    let ints_with_null = list(&[Scalar::Integer(1), Scalar::Null(DataType::INTEGER)]);
+    let test_cases = [
+        // `x` is a LONG, which is widened to compare against the INTEGER list elements
+        (&ints, Scalar::Long(2), Some(true)),
+        (&ints, Scalar::Long(4), Some(false)),
+        (&ints, Scalar::Long(1 << 40), Some(false)),
+        (&ints, Scalar::Short(3), Some(true)),
+        (&ints, Scalar::Null(DataType::LONG), None),
+        // a string is parsed as the type of each element, as for a comparison
+        (&ints, Scalar::String("1".into()), Some(true)),
+        (&ints, Scalar::String("one".into()), None),
+        (&ints_with_null, Scalar::Long(1), Some(true)),
+        (&ints_with_null, Scalar::Long(4), None),
+        (&ints_with_null, Scalar::Null(DataType::LONG), None),
+    ];
+    for (pred, value, expect) in test_cases {
+        let filter = DefaultKernelPredicateEvaluator::from(value.clone());
+        expect_eq!(filter.eval(pred), expect, "{pred} with x = {value}");
+        let expect_inverted = expect.map(|v| !v);
+        expect_eq!(
+            filter.eval(&Pred::not(pred.clone())),
+            expect_inverted,
+            "NOT({pred}) with x = {value}"
+        );
+    }
+}
+
+#[test]
+fn test_eval_not_in_scalars() {
+    let values = vec![Scalar::Integer(1), Scalar::Null(DataType::INTEGER)];
+    let not_in = Pred::not_in(column_expr!("x"), values.clone()).unwrap();
+    let not_of_in = Pred::not(Pred::is_in(column_expr!("x"), values).unwrap());
+    let test_cases = [
+        (Scalar::Long(1), Some(false)),
+        (Scalar::Long(2), None),
+        (Scalar::Null(DataType::LONG), None),
+    ];
+    for (value, expect) in test_cases {
+        let filter = DefaultKernelPredicateEvaluator::from(value.clone());
+        // NOT IN with a NULL in the list can never be TRUE...
+        expect_eq!(filter.eval(&not_in), expect, "{not_in} with x = {value}");
+        // ... and neither can NOT(IN), because NOT(NULL) is still NULL
+        expect_eq!(
+            filter.eval(&not_of_in),
+            expect,
+            "{not_of_in} with x = {value}"
+        );
+        // Inverting NOT IN is the same as IN
+        let expect_inverted = expect.map(|v| !v);
+        expect_eq!(
+            filter.eval(&Pred::not(not_in.clone())),
+            expect_inverted,
+            "NOT({not_in}) with x = {value}"
+        );
+    }
+
+    let not_in = Pred::not_in(column_expr!("x"), [Scalar::Integer(1), Scalar::Integer(2)]).unwrap();
+    let filter = DefaultKernelPredicateEvaluator::from(Scalar::Long(3));
+    expect_eq!(filter.eval(&not_in), Some(true), "{not_in} with x = 3");
+}
+
 // NOTE: We're testing routing here -- the actual comparisons are already validated by test_eval_binary_scalars.
 #[test]
 fn test_eval_binary_columns() {
@@ -427,6 +720,88 @@ fn test_eval_junction() {
     }
 }
 
+/// Resolves columns from a map, counting how many times it was asked to resolve a column.
+struct CountingColumnResolver {
+    columns: HashMap<ColumnName, Scalar>,
+    count: std::cell::Cell<usize>,
+}
+
+impl CountingColumnResolver {
+    fn new(columns: impl IntoIterator<Item = (&'static str, Scalar)>) -> Self {
+        let columns = columns
+            .into_iter()
+            .map(|(name, value)| (ColumnName::new([name]), value))
+            .collect();
+        let count = Default::default();
+        Self { columns, count }
+    }
+}
+
+impl ResolveColumnAsScalar for &CountingColumnResolver {
+    fn resolve_column(&self, col: &ColumnName) -> Option<Cow<'_, Scalar>> {
+        self.count.set(self.count.get() + 1);
+        self.columns.get(col).map(Cow::Borrowed)
+    }
+}
+
+#[test]
+fn test_eval_junction_short_circuit() {
+    use JunctionPredicateOp::{And, Or};
+    let resolver = CountingColumnResolver::new([
+        ("t", Scalar::from(true)),
+        ("f", Scalar::from(false)),
+        ("n", Scalar::Null(DataType::BOOLEAN)),
+    ]);
+    let filter = DefaultKernelPredicateEvaluator::from(&resolver);
+    let cols = |names: &[&str]| -> Vec<_> {
+        names
+            .iter()
+            .map(|name| Pred::from_expr(ColumnName::new([*name])))
+            .collect()
+    };
+    let test_cases = [
+        // op, inputs, inverted, expected output, expected number of resolved columns
+        (And, cols(&["f", "t", "t"]), false, Some(false), 1),
+        (And, cols(&["t", "f", "t"]), false, Some(false), 2),
+        (Or, cols(&["t", "f", "f"]), false, Some(true), 1),
+        (Or, cols(&["f", "t", "f"]), false, Some(true), 2),
+        // inverted junctions short circuit on their inverted inputs
+        (And, cols(&["f", "t", "t"]), true, Some(true), 1),
+        (Or, cols(&["t", "f", "f"]), true, Some(false), 1),
+        // a NULL cannot short circuit, because a later dominant value still decides the output
+        (And, cols(&["n", "f", "t"]), false, Some(false), 2),
+        (Or, cols(&["n", "t", "f"]), false, Some(true), 2),
+        (And, cols(&["n", "t", "t"]), false, None, 3),
+        // without a dominant value, all inputs are evaluated
+        (And, cols(&["t", "t", "t"]), false, Some(true), 3),
+        (Or, cols(&["f", "f", "f"]), false, Some(false), 3),
+    ];
+    for (op, inputs, inverted, expect, expect_count) in test_cases {
+        resolver.count.set(0);
+        expect_eq!(
+            filter.eval_pred_junction(op, &inputs, inverted),
+            expect,
+            "{op:?}({inputs:?}) (inverted: {inverted})"
+        );
+        expect_eq!(
+            resolver.count.get(),
+            expect_count,
+            "columns resolved by {op:?}({inputs:?}) (inverted: {inverted})"
+        );
+    }
+
+    // The null-safe comparisons of SQL WHERE semantics also short circuit: a NULL operand skips
+    // the comparison itself.
+    let pred = Pred::lt(column_expr!("n"), Expr::literal(10));
+    resolver.count.set(0);
+    expect_eq!(
+        filter.eval_pred_sql_where(&pred, false),
+        Some(false),
+        "{pred:?}"
+    );
+    expect_eq!(resolver.count.get(), 1, "columns resolved by {pred:?}");
+}
+
 #[test]
 fn test_eval_column() {
     let test_cases = [
@@ -498,6 +873,48 @@ fn test_eval_is_null() {
     );
 }
 
+#[test]
+fn test_eval_is_null_nested_types() {
+    let zip_type = DataType::struct_type([StructField::nullable("zip", DataType::STRING)]);
+    let array_type = ArrayType::new(DataType::INTEGER, true);
+    let map_type = MapType::new(DataType::STRING, DataType::STRING, true);
+    let null_zip = Scalar::Null(DataType::STRING);
+    let struct_of_nulls = Scalar::Struct(
+        StructData::try_new(
+            vec![StructField::nullable("zip", DataType::STRING)],
+            vec![null_zip.clone()],
+        )
+        .unwrap(),
+    );
+    let array_of_nulls = Scalar::Array(
+        ArrayData::try_new(array_type.clone(), [Scalar::Null(DataType::INTEGER)]).unwrap(),
+    );
+    let map_of_nulls = Scalar::Map(MapData::try_new(map_type.clone(), [("k", null_zip)]).unwrap());
+
+    // A NULL value is NULL, but a (non-NULL) value that contains NULLs is not
+    let cases = [
+        (Scalar::Null(zip_type), true),
+        (struct_of_nulls, false),
+        (Scalar::Null(array_type.into()), true),
+        (array_of_nulls, false),
+        (Scalar::Null(map_type.into()), true),
+        (map_of_nulls, false),
+    ];
+    for (value, is_null) in cases {
+        let filter = DefaultKernelPredicateEvaluator::from(value.clone());
+        expect_eq!(
+            filter.eval(&column_expr!("x").is_null()),
+            Some(is_null),
+            "{value} IS NULL"
+        );
+        expect_eq!(
+            filter.eval(&column_expr!("x").is_not_null()),
+            Some(!is_null),
+            "{value} IS NOT NULL"
+        );
+    }
+}
+
 #[test]
 fn test_eval_distinct() {
     let one = &Scalar::from(1);
@@ -899,6 +1316,63 @@ fn test_eval_opaque_complex() {
     );
 }
 
+#[test]
+fn test_eval_borrowed_column_names() {
+    let (x, y) = (Scalar::from(1), Scalar::from("foo"));
+    let resolver: HashMap<&str, &Scalar> = [("x", &x), ("y", &y)].into();
+    let filter = DefaultKernelPredicateEvaluator::from(resolver);
+    expect_eq!(
+        filter.eval_expr(&column_expr!("x")),
+        Some(Scalar::from(1)),
+        "x"
+    );
+    expect_eq!(
+        filter.eval(&column_expr!("y").eq(Expr::literal("foo"))),
+        Some(true),
+        "y = 'foo'"
+    );
+    // Only top-level columns can be resolved by name
+    expect_eq!(filter.eval_expr(&column_expr!("x.y")), None, "x.y");
+    expect_eq!(filter.eval_expr(&column_expr!("z")), None, "z");
+}
+
+#[test]
+fn test_eval_nested_struct_fields() {
+    let zip = |zip: &str| Scalar::struct_from([("zip", zip)]).unwrap();
+    let address = Scalar::struct_from([("address", zip("90210"))]).unwrap();
+    let resolver = HashMap::from([(column_name!("person"), address)]);
+    let filter = DefaultKernelPredicateEvaluator::from(resolver);
+
+    let zip_col = column_expr!("person.address.zip");
+    expect_eq!(
+        filter.eval_expr(&zip_col),
+        Some(Scalar::from("90210")),
+        "person.address.zip"
+    );
+    expect_eq!(
+        filter.eval(&zip_col.clone().eq(Expr::literal("90210"))),
+        Some(true),
+        "person.address.zip = '90210'"
+    );
+    expect_eq!(
+        filter.eval(&zip_col.eq(Expr::literal("10001"))),
+        Some(false),
+        "person.address.zip = '10001'"
+    );
+    expect_eq!(
+        filter.eval_expr(&column_expr!("person.address.city")),
+        None,
+        "person.address.city"
+    );
+
+    // The fields of a NULL struct are NULL
+    let address_type = DataType::struct_type([StructField::nullable("zip", DataType::STRING)]);
+    let resolver = HashMap::from([(column_name!("address"), Scalar::Null(address_type))]);
+    let filter = DefaultKernelPredicateEvaluator::from(resolver);
+    let is_null = column_expr!("address.zip").is_null();
+    expect_eq!(filter.eval(&is_null), Some(true), "address.zip IS NULL");
+}
+
 #[test]
 fn test_eval_unknown() {
     let filter = DefaultKernelPredicateEvaluator::from(Scalar::from(1));
@@ -909,8 +1383,8 @@ fn test_eval_unknown() {
 // NOTE: `None` is NOT equivalent to `Some(Scalar::Null)`
 struct NullColumnResolver;
 impl ResolveColumnAsScalar for NullColumnResolver {
-    fn resolve_column(&self, _col: &ColumnName) -> Option<Scalar> {
-        Some(Scalar::Null(DataType::INTEGER))
+    fn resolve_column(&self, _col: &ColumnName) -> Option<Cow<'_, Scalar>> {
+        Some(Cow::Owned(Scalar::Null(DataType::INTEGER)))
     }
 }
 
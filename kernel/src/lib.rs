@@ -213,6 +213,47 @@ impl FileMeta {
     }
 }
 
+/// Footer metadata of a Parquet file, as returned by [`ParquetHandler::read_parquet_metadata`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetFileMetadata {
+    /// The file this metadata was read from
+    pub file: FileMeta,
+    /// The total number of rows in the file
+    pub num_rows: i64,
+    /// The row groups of the file, in file order
+    pub row_groups: Vec<ParquetRowGroupMetadata>,
+}
+
+/// Metadata of a single row group within a Parquet file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetRowGroupMetadata {
+    /// The number of rows in this row group
+    pub num_rows: i64,
+    /// The index (within the file) of the first row of this row group
+    pub first_row_index: i64,
+    /// The byte offset of this row group within the file, if recorded in the footer
+    pub file_offset: Option<i64>,
+    /// The total compressed size in bytes of this row group
+    pub compressed_size: i64,
+    /// Statistics of each leaf column of this row group, in Parquet schema order
+    pub column_stats: Vec<ParquetColumnStatistics>,
+}
+
+/// Statistics of a single leaf column within a Parquet row group. Any statistic may be missing,
+/// either because the writer did not record it or because its type cannot be represented as a
+/// [`Scalar`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetColumnStatistics {
+    /// The (physical) path of the column
+    pub column: expressions::ColumnName,
+    /// The number of null values in the column
+    pub null_count: Option<u64>,
+    /// The minimum value of the column
+    pub min_value: Option<Scalar>,
+    /// The maximum value of the column
+    pub max_value: Option<Scalar>,
+}
+
 /// Extension trait that makes it easier to work with traits objects that implement [`Any`],
 /// implemented automatically for any type that satisfies `Any`, `Send`, and `Sync`. In particular,
 /// given some `trait T: Any + Send + Sync`, it allows upcasting `T` to `dyn Any + Send + Sync`,
@@ -503,6 +544,17 @@ pub trait StorageHandler: AsAny {
         &self,
         files: Vec<FileSlice>,
     ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<Bytes>>>>;
+
+    /// Write `data` to the file at `path`. If `overwrite` is false and the file already exists,
+    /// this must return [`Error::FileAlreadyExists`].
+    ///
+    /// The default implementation returns [`Error::Unsupported`].
+    fn write_file(&self, path: &Url, data: Bytes, overwrite: bool) -> DeltaResult<()> {
+        let _ = (data, overwrite);
+        Err(Error::unsupported(format!(
+            "This StorageHandler does not support writing files: {path}"
+        )))
+    }
 }
 
 /// Provides JSON handling functionality to Delta Kernel.
@@ -596,6 +648,21 @@ pub trait ParquetHandler: AsAny {
         physical_schema: SchemaRef,
         predicate: Option<PredicateRef>,
     ) -> DeltaResult<FileDataReadResultIterator>;
+
+    /// Read only the footer of each of the given Parquet files, without decoding any data. The
+    /// returned metadata _must_ be in the same order as `files`.
+    ///
+    /// # Parameters
+    ///
+    /// - `files` - File metadata for files whose footers should be read.
+    ///
+    /// The default implementation returns [`Error::Unsupported`].
+    fn read_parquet_metadata(&self, files: &[FileMeta]) -> DeltaResult<Vec<ParquetFileMetadata>> {
+        let _ = files;
+        Err(Error::unsupported(
+            "This ParquetHandler does not support reading parquet metadata",
+        ))
+    }
 }
 
 /// The `Engine` trait encapsulates all the functionality an engine or connector needs to provide
@@ -4,18 +4,19 @@ use std::collections::HashMap;
 use std::convert::identity;
 use std::sync::{Arc, LazyLock};
 
-use crate::actions::visitors::SidecarVisitor;
+use crate::actions::visitors::{SelectionVectorVisitor, SidecarVisitor};
 use crate::actions::{
     get_log_schema, Metadata, Protocol, ADD_NAME, METADATA_NAME, PROTOCOL_NAME, REMOVE_NAME,
     SIDECAR_NAME,
 };
 use crate::log_replay::ActionsBatch;
 use crate::path::{LogPathFileType, ParsedLogPath};
-use crate::schema::SchemaRef;
+use crate::schema::{SchemaRef, StructField, StructType};
 use crate::snapshot::LastCheckpointHint;
 use crate::utils::require;
 use crate::{
-    DeltaResult, Engine, EngineData, Error, Expression, FileMeta, ParquetHandler, Predicate,
+    DeltaResult, Engine, EngineData, Error, EvaluationHandler, Expression, ExpressionEvaluator,
+    FileDataReadResultIterator, JsonHandler, ParquetHandler, Predicate, PredicateEvaluator,
     PredicateRef, RowVisitor, StorageHandler, Version,
 };
 use delta_kernel_derive::internal_api;
@@ -146,11 +147,24 @@ impl LogSegment {
         let time_travel_version = time_travel_version.into();
 
         let listed_files = match (checkpoint_hint.into(), time_travel_version) {
-            (Some(cp), None) => list_log_files_with_checkpoint(&cp, storage, &log_root, None)?,
+            (Some(cp), None) => list_log_files_with_checkpoint(&cp, storage, &log_root, None),
             (Some(cp), Some(end_version)) if cp.version <= end_version => {
-                list_log_files_with_checkpoint(&cp, storage, &log_root, Some(end_version))?
+                list_log_files_with_checkpoint(&cp, storage, &log_root, Some(end_version))
             }
-            _ => list_log_files_with_version(storage, &log_root, None, time_travel_version)?,
+            _ => list_log_files_with_version(storage, &log_root, None, time_travel_version),
+        };
+        let listed_files = match listed_files {
+            Ok(files)
+                if files.ascending_commit_files.is_empty() && files.checkpoint_parts.is_empty() =>
+            {
+                ensure_table_has_versions(storage, &log_root)?;
+                files
+            }
+            Err(err) if is_not_found(&err) => {
+                ensure_table_has_versions(storage, &log_root)?;
+                return Err(err);
+            }
+            result => result?,
         };
 
         LogSegment::try_new(listed_files, log_root, time_travel_version)
@@ -224,15 +238,15 @@ impl LogSegment {
         meta_predicate: Option<PredicateRef>,
     ) -> DeltaResult<impl Iterator<Item = DeltaResult<ActionsBatch>> + Send> {
         // `replay` expects commit files to be sorted in descending order, so the return value here is correct
-        let commits_and_compactions = self.find_commit_cover();
-        let commit_stream = engine
-            .json_handler()
-            .read_json_files(
-                &commits_and_compactions,
-                commit_read_schema,
-                meta_predicate.clone(),
-            )?
-            .map_ok(|batch| ActionsBatch::new(batch, true));
+        let commits_and_compactions: Vec<_> =
+            self.find_commit_cover().into_iter().cloned().collect();
+        let commit_stream = CommitBatches::try_new(
+            engine,
+            commits_and_compactions,
+            commit_read_schema,
+            meta_predicate.clone(),
+        )?
+        .map_ok(|batch| ActionsBatch::new(batch, true));
 
         let checkpoint_stream =
             self.create_checkpoint_stream(engine, checkpoint_read_schema, meta_predicate)?;
@@ -245,7 +259,7 @@ impl LogSegment {
     /// returns files is DESCENDING ORDER, as that's what `replay` expects. This function assumes
     /// that all files in `self.ascending_commit_files` and `self.ascending_compaction_files` are in
     /// range for this log segment. This invariant is maintained by our listing code.
-    fn find_commit_cover(&self) -> Vec<FileMeta> {
+    fn find_commit_cover(&self) -> Vec<&ParsedLogPath> {
         // Create an iterator sorted in ascending order by (initial version, end version), e.g.
         // [00.json, 00.09.compacted.json, 00.99.compacted.json, 01.json, 02.json, ..., 10.json,
         //  10.19.compacted.json, 11.json, ...]
@@ -277,7 +291,7 @@ impl LogSegment {
             }
             debug!("Provisionally selecting {next:?}");
             last_pushed = Some(next);
-            selected_files.push(next.location.clone());
+            selected_files.push(next);
         }
         selected_files.reverse();
         selected_files
@@ -499,6 +513,291 @@ fn list_log_files(
         }))
 }
 
+/// The batches read from the commit (and compacted commit) files of a log segment, which ensures
+/// that every file parses fully and contains at least one action. A commit file that does not
+/// (e.g. because the commit was only partially written) yields [`Error::CorruptLog`] with its
+/// version.
+///
+/// All the files are read at once. The read schema may be projected to a subset of the actions, so
+/// the other actions are added to it as marker columns (structs with no fields), which make every
+/// row show whether it holds an action. The engine's batches do not say which file they came from,
+/// so the files are only checked one at a time once there is a sign of corruption: a parse error,
+/// a row with no action, or fewer rows than files. An empty file is known to be corrupt from its
+/// size alone, without reading anything.
+struct CommitBatches {
+    batches: FileDataReadResultIterator,
+    // The files being read, in the order they were passed to the engine
+    commit_files: Vec<ParsedLogPath>,
+    json_handler: Arc<dyn JsonHandler>,
+    evaluation_handler: Arc<dyn EvaluationHandler>,
+    // Whether each row of a batch holds an action
+    has_action_evaluator: Arc<dyn PredicateEvaluator>,
+    // Drops the marker columns from each batch, if any were added to the read schema
+    drop_markers: Option<Arc<dyn ExpressionEvaluator>>,
+    num_rows: usize,
+    all_rows_have_action: bool,
+    // Whether the files have already been checked one at a time
+    checked_files: bool,
+    done: bool,
+}
+
+impl CommitBatches {
+    fn try_new(
+        engine: &dyn Engine,
+        commit_files: Vec<ParsedLogPath>,
+        read_schema: SchemaRef,
+        predicate: Option<PredicateRef>,
+    ) -> DeltaResult<Self> {
+        if let Some(empty_file) = commit_files.iter().find(|file| file.location.size == 0) {
+            warn!("Commit at version {} is empty", empty_file.version);
+            return Err(Error::corrupt_log(empty_file.version));
+        }
+        let evaluation_handler = engine.evaluation_handler();
+        let markers = get_log_schema()
+            .fields()
+            .filter(|field| !read_schema.contains(field.name()))
+            .map(|field| StructField::nullable(field.name(), StructType::new([])))
+            .collect_vec();
+        let (physical_schema, drop_markers) = if markers.is_empty() {
+            (read_schema, None)
+        } else {
+            let physical_schema = Arc::new(StructType::new(
+                read_schema.fields().cloned().chain(markers),
+            ));
+            let drop_markers = evaluation_handler.new_expression_evaluator(
+                physical_schema.clone(),
+                Expression::struct_from(
+                    read_schema
+                        .fields()
+                        .map(|field| Expression::column([field.name()])),
+                ),
+                read_schema.as_ref().clone().into(),
+            );
+            (physical_schema, Some(drop_markers))
+        };
+        let has_action_evaluator =
+            new_has_action_evaluator(evaluation_handler.as_ref(), physical_schema.clone());
+        let json_handler = engine.json_handler();
+        let commit_locations = commit_files
+            .iter()
+            .map(|commit_file| commit_file.location.clone())
+            .collect_vec();
+        let batches =
+            json_handler.read_json_files(&commit_locations, physical_schema, predicate)?;
+        Ok(Self {
+            batches,
+            commit_files,
+            json_handler,
+            evaluation_handler,
+            has_action_evaluator,
+            drop_markers,
+            num_rows: 0,
+            all_rows_have_action: true,
+            checked_files: false,
+            done: false,
+        })
+    }
+
+    fn check_batch(&mut self, batch: Box<dyn EngineData>) -> DeltaResult<Box<dyn EngineData>> {
+        self.num_rows += batch.len();
+        if self.all_rows_have_action {
+            let has_action = self.has_action_evaluator.evaluate(batch.as_ref())?;
+            let mut visitor = SelectionVectorVisitor::default();
+            visitor.visit_rows_of(has_action.as_ref())?;
+            self.all_rows_have_action = visitor.selection_vector.iter().all(|&selected| selected);
+            if !self.all_rows_have_action {
+                self.check_files()?;
+            }
+        }
+        match &self.drop_markers {
+            Some(drop_markers) => drop_markers.evaluate(batch.as_ref()),
+            None => Ok(batch),
+        }
+    }
+
+    // Checks the files one at a time, failing with the version of the first commit file that does
+    // not parse or has no action at all. Only the presence of each action is read (and without a
+    // predicate, which may have caused the engine to skip rows).
+    fn check_files(&mut self) -> DeltaResult<()> {
+        if std::mem::replace(&mut self.checked_files, true) {
+            return Ok(());
+        }
+        // Each action is read as a struct with no fields, so that only its presence is decoded and
+        // writers' non-standard field values (e.g. numeric `operationMetrics`) cannot fail the read
+        static ACTION_PRESENCE_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+            Arc::new(StructType::new(get_log_schema().fields().map(|field| {
+                StructField::nullable(field.name(), StructType::new([]))
+            })))
+        });
+        let schema = ACTION_PRESENCE_SCHEMA.clone();
+        let evaluator = new_has_action_evaluator(self.evaluation_handler.as_ref(), schema.clone());
+        let file_has_action = |commit_file: &ParsedLogPath| -> DeltaResult<bool> {
+            let batches = self.json_handler.read_json_files(
+                std::slice::from_ref(&commit_file.location),
+                schema.clone(),
+                None,
+            )?;
+            for batch in batches {
+                if batch_has_action(evaluator.as_ref(), batch?.as_ref())? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        };
+        for commit_file in &self.commit_files {
+            match file_has_action(commit_file) {
+                Ok(true) => {}
+                Ok(false) => {
+                    warn!("Commit at version {} has no actions", commit_file.version);
+                    return Err(Error::corrupt_log(commit_file.version));
+                }
+                Err(err) if is_parse_error(&err) => {
+                    warn!(
+                        "Failed to parse commit at version {}: {err}",
+                        commit_file.version
+                    );
+                    return Err(Error::corrupt_log(commit_file.version));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    // Stops reading, so that a file which fails to parse cannot keep producing errors
+    fn finish(&mut self) {
+        self.done = true;
+        self.batches = Box::new(std::iter::empty());
+    }
+}
+
+impl Iterator for CommitBatches {
+    type Item = DeltaResult<Box<dyn EngineData>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = match self.batches.next() {
+            Some(Ok(batch)) => self.check_batch(batch),
+            Some(Err(err)) if is_parse_error(&err) => {
+                self.finish();
+                // The error may also come from e.g. a field of the wrong type, which is not
+                // corruption, so it is returned if no file is found to be corrupt
+                self.check_files().and(Err(err))
+            }
+            Some(Err(err)) => Err(err),
+            None => {
+                self.finish();
+                // Every action is on a row of its own, so a file with no action either produced no
+                // rows or a row with no action (which was already checked)
+                if self.num_rows >= self.commit_files.len() {
+                    return None;
+                }
+                return self.check_files().err().map(Err);
+            }
+        };
+        if result
+            .as_ref()
+            .is_err_and(|err| matches!(err, Error::CorruptLog(_)))
+        {
+            self.finish();
+        }
+        Some(result)
+    }
+}
+
+// Creates an evaluator of whether each row of a batch with the given schema holds an action, i.e.
+// whether any of its top-level columns is non-null.
+fn new_has_action_evaluator(
+    evaluation_handler: &dyn EvaluationHandler,
+    schema: SchemaRef,
+) -> Arc<dyn PredicateEvaluator> {
+    let has_action = Predicate::or_from(
+        schema
+            .fields()
+            .map(|field| Expression::column([field.name()]).is_not_null()),
+    );
+    evaluation_handler.new_predicate_evaluator(schema, has_action)
+}
+
+fn batch_has_action(
+    evaluator: &dyn PredicateEvaluator,
+    batch: &dyn EngineData,
+) -> DeltaResult<bool> {
+    let has_action = evaluator.evaluate(batch)?;
+    let mut visitor = SelectionVectorVisitor::default();
+    visitor.visit_rows_of(has_action.as_ref())?;
+    Ok(visitor.selection_vector.contains(&true))
+}
+
+/// Whether `err` indicates that a log file's contents could not be parsed (as opposed to e.g. the
+/// file not being readable at all, or not matching the read schema).
+fn is_parse_error(err: &Error) -> bool {
+    match err {
+        #[cfg(feature = "default-engine-base")]
+        Error::Arrow(
+            crate::arrow::error::ArrowError::JsonError(_)
+            | crate::arrow::error::ArrowError::ParseError(_),
+        ) => true,
+        Error::MalformedJson(_) => true,
+        Error::Backtraced { source, .. } => is_parse_error(source),
+        _ => false,
+    }
+}
+
+fn is_not_found(err: &Error) -> bool {
+    match err {
+        Error::FileNotFound(_) => true,
+        Error::IOError(err) => err.kind() == std::io::ErrorKind::NotFound,
+        Error::Backtraced { source, .. } => is_not_found(source),
+        _ => false,
+    }
+}
+
+/// Checks that the log at `log_root` has at least one commit or checkpoint, returning
+/// [`Error::TableNotFound`] if the `_delta_log` does not exist, and [`Error::EmptyTable`] if it
+/// exists without any versions.
+///
+/// Object stores have no real directories, so an empty `_delta_log` there is indistinguishable
+/// from a missing one and is reported as [`Error::TableNotFound`].
+fn ensure_table_has_versions(storage: &dyn StorageHandler, log_root: &Url) -> DeltaResult<()> {
+    let table_root = log_root.join("..")?;
+    let log_entries = match storage.list_from(log_root) {
+        Ok(entries) => entries,
+        Err(err) if is_not_found(&err) => return Err(Error::table_not_found(table_root)),
+        Err(err) => return Err(err),
+    };
+    let mut log_exists = false;
+    for entry in log_entries {
+        log_exists = true;
+        let is_version = ParsedLogPath::try_from(entry?)
+            .ok()
+            .flatten()
+            .is_some_and(|path| path.is_commit() || path.is_checkpoint());
+        if is_version {
+            return Ok(());
+        }
+    }
+
+    // The log may still be an existing but empty directory, which some storage handlers list as
+    // an entry of the table root. Listings are sorted, so stop once past the log's own path.
+    let log_path = log_root.path().trim_end_matches('/');
+    log_exists = log_exists
+        || match storage.list_from(&table_root) {
+            Ok(entries) => entries
+                .map_while(Result::ok)
+                .take_while(|entry| entry.location.path() <= log_root.path())
+                .any(|entry| entry.location.path().trim_end_matches('/') == log_path),
+            Err(err) if is_not_found(&err) => false,
+            Err(err) => return Err(err),
+        };
+    match log_exists {
+        true => Err(Error::empty_table(table_root)),
+        false => Err(Error::table_not_found(table_root)),
+    }
+}
+
 /// A struct to hold the result of listing log files. The commit and compaction files are guaranteed
 /// to be sorted in ascending order by version. The elements of `checkpoint_parts` are all the parts
 /// of the same checkpoint. Checkpoint parts share the same version. The `latest_crc_file` includes
@@ -12,6 +12,7 @@ use crate::actions::{
     get_log_add_schema, get_log_schema, Add, Sidecar, ADD_NAME, METADATA_NAME, REMOVE_NAME,
     SIDECAR_NAME,
 };
+use crate::engine::arrow_conversion::TryIntoArrow as _;
 use crate::engine::arrow_data::ArrowEngineData;
 use crate::engine::default::executor::tokio::TokioBackgroundExecutor;
 use crate::engine::default::filesystem::ObjectStoreStorageHandler;
@@ -1532,8 +1533,8 @@ fn test_commit_cover(
             .expect("Couldn't join"),
     });
     assert_eq!(cover.len(), expected_locations.len());
-    for (location, expected_location) in cover.iter().zip(expected_locations) {
-        assert_eq!(location.location, expected_location);
+    for (path, expected_location) in cover.iter().zip(expected_locations) {
+        assert_eq!(path.location.location, expected_location);
     }
 }
 
@@ -1744,3 +1745,184 @@ fn test_debug_assert_listed_log_file_invalid_multipart_checkpoint() {
         None,
     );
 }
+
+// A JSON handler whose batches all fail with a (non-parse) schema error
+struct SchemaErrorJsonHandler;
+
+impl JsonHandler for SchemaErrorJsonHandler {
+    fn parse_json(
+        &self,
+        _json_strings: Box<dyn EngineData>,
+        _output_schema: SchemaRef,
+    ) -> DeltaResult<Box<dyn EngineData>> {
+        unimplemented!()
+    }
+
+    fn read_json_files(
+        &self,
+        files: &[FileMeta],
+        _physical_schema: SchemaRef,
+        _predicate: Option<PredicateRef>,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        let errors = files.iter().map(|_| {
+            Err(crate::arrow::error::ArrowError::SchemaError("unexpected field".into()).into())
+        });
+        Ok(Box::new(errors.collect_vec().into_iter()))
+    }
+
+    fn write_json_file(
+        &self,
+        _path: &Url,
+        _data: Box<dyn Iterator<Item = DeltaResult<Box<dyn EngineData>>> + Send + '_>,
+        _overwrite: bool,
+    ) -> DeltaResult<()> {
+        unimplemented!()
+    }
+}
+
+struct SchemaErrorEngine(SyncEngine);
+
+impl Engine for SchemaErrorEngine {
+    fn evaluation_handler(&self) -> Arc<dyn crate::EvaluationHandler> {
+        self.0.evaluation_handler()
+    }
+
+    fn json_handler(&self) -> Arc<dyn JsonHandler> {
+        Arc::new(SchemaErrorJsonHandler)
+    }
+
+    fn parquet_handler(&self) -> Arc<dyn crate::ParquetHandler> {
+        self.0.parquet_handler()
+    }
+
+    fn storage_handler(&self) -> Arc<dyn StorageHandler> {
+        self.0.storage_handler()
+    }
+}
+
+#[test]
+fn test_read_actions_passes_through_non_parse_errors() {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-without-dv-small/"));
+    let url = url::Url::from_directory_path(path.unwrap()).unwrap();
+    let snapshot = Snapshot::try_new(url, &SyncEngine::new(), None).unwrap();
+
+    // A read error that is not a parse error is not reported as a corrupt commit
+    let engine = SchemaErrorEngine(SyncEngine::new());
+    let schema = get_log_schema().project(&[METADATA_NAME]).unwrap();
+    let mut actions = snapshot
+        .log_segment()
+        .read_actions(&engine, schema.clone(), schema, None)
+        .unwrap();
+    let Some(Err(err)) = actions.next() else {
+        panic!("expected an error");
+    };
+    let err = match err {
+        Error::Backtraced { source, .. } => *source,
+        err => err,
+    };
+    assert!(
+        matches!(
+            err,
+            Error::Arrow(crate::arrow::error::ArrowError::SchemaError(_))
+        ),
+        "{err:?}"
+    );
+}
+
+// A JSON handler that counts the calls to `read_json_files`
+struct CountingJsonHandler {
+    inner: Arc<dyn JsonHandler>,
+    reads: std::sync::atomic::AtomicUsize,
+}
+
+impl JsonHandler for CountingJsonHandler {
+    fn parse_json(
+        &self,
+        json_strings: Box<dyn EngineData>,
+        output_schema: SchemaRef,
+    ) -> DeltaResult<Box<dyn EngineData>> {
+        self.inner.parse_json(json_strings, output_schema)
+    }
+
+    fn read_json_files(
+        &self,
+        files: &[FileMeta],
+        physical_schema: SchemaRef,
+        predicate: Option<PredicateRef>,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.inner
+            .read_json_files(files, physical_schema, predicate)
+    }
+
+    fn write_json_file(
+        &self,
+        path: &Url,
+        data: Box<dyn Iterator<Item = DeltaResult<Box<dyn EngineData>>> + Send + '_>,
+        overwrite: bool,
+    ) -> DeltaResult<()> {
+        self.inner.write_json_file(path, data, overwrite)
+    }
+}
+
+struct CountingJsonEngine {
+    inner: SyncEngine,
+    json_handler: Arc<CountingJsonHandler>,
+}
+
+impl Engine for CountingJsonEngine {
+    fn evaluation_handler(&self) -> Arc<dyn crate::EvaluationHandler> {
+        self.inner.evaluation_handler()
+    }
+
+    fn json_handler(&self) -> Arc<dyn JsonHandler> {
+        self.json_handler.clone()
+    }
+
+    fn parquet_handler(&self) -> Arc<dyn crate::ParquetHandler> {
+        self.inner.parquet_handler()
+    }
+
+    fn storage_handler(&self) -> Arc<dyn StorageHandler> {
+        self.inner.storage_handler()
+    }
+}
+
+#[test]
+fn test_read_actions_reads_commits_in_one_call() {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/"));
+    let url = url::Url::from_directory_path(path.unwrap()).unwrap();
+    let inner = SyncEngine::new();
+    let snapshot = Snapshot::try_new(url, &inner, None).unwrap();
+    assert_eq!(snapshot.log_segment().ascending_commit_files.len(), 2);
+
+    let engine = CountingJsonEngine {
+        json_handler: Arc::new(CountingJsonHandler {
+            inner: inner.json_handler(),
+            reads: Default::default(),
+        }),
+        inner,
+    };
+    // The commits hold actions other than metadata, which are found without reading them again
+    let schema = get_log_schema().project(&[METADATA_NAME]).unwrap();
+    let batches: Vec<_> = snapshot
+        .log_segment()
+        .read_actions(&engine, schema.clone(), schema.clone(), None)
+        .unwrap()
+        .map_ok(|batch| ArrowEngineData::try_from_engine_data(batch.actions).unwrap())
+        .try_collect()
+        .unwrap();
+    assert_eq!(
+        engine
+            .json_handler
+            .reads
+            .load(std::sync::atomic::Ordering::SeqCst),
+        1
+    );
+
+    // The batches are projected back to the read schema
+    let expected: crate::arrow::datatypes::Schema = schema.as_ref().try_into_arrow().unwrap();
+    for batch in batches {
+        assert_eq!(batch.record_batch().schema().fields(), expected.fields());
+    }
+}
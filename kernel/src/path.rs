@@ -61,6 +61,28 @@ fn parse_path_part<T: FromStr>(value: &str, expect_len: usize, location: &Url) -
     }
 }
 
+/// Resolves the `path` of a file referenced by a log action (e.g. `add.path`) against `root`.
+///
+/// Delta stores these paths as URIs: they may be absolute or relative to `root`, and special
+/// characters are percent-encoded (e.g. a file `a b.parquet` is stored as `a%20b.parquet`).
+/// [`Url::join`] keeps such encodings as-is, so the engine decodes them when it accesses the file,
+/// and it encodes characters that are not allowed in a URL path, such as spaces. The exceptions
+/// are `?` and `#` in a relative path, which would start a query or fragment and silently resolve
+/// to a different file, so we encode them here in case a writer did not. Absolute paths are used
+/// as-is, since their query may be meaningful (e.g. the signature of a presigned URL).
+pub(crate) fn resolve_file_path(root: &Url, path: &str) -> DeltaResult<Url> {
+    let is_relative = matches!(
+        Url::parse(path),
+        Err(url::ParseError::RelativeUrlWithoutBase)
+    );
+    let location = if is_relative && path.contains(['?', '#']) {
+        root.join(&path.replace('?', "%3F").replace('#', "%23"))?
+    } else {
+        root.join(path)?
+    };
+    Ok(location)
+}
+
 // We normally construct ParsedLogPath from FileMeta, but in testing it's convenient to use
 // a Url directly instead. This trait decouples the two.
 #[internal_api]
@@ -253,6 +275,40 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_resolve_file_path() {
+        let root = Url::parse("s3://bucket/table/").unwrap();
+        let resolve = |path| resolve_file_path(&root, path).unwrap();
+
+        let location = resolve("part=a%20b/file%2500.parquet");
+        assert_eq!(
+            location.as_str(),
+            "s3://bucket/table/part=a%20b/file%2500.parquet"
+        );
+        let location = resolve("part=a b/file.parquet");
+        assert_eq!(
+            location.as_str(),
+            "s3://bucket/table/part=a%20b/file.parquet"
+        );
+        let location = resolve("part=a?b/file#1.parquet");
+        assert_eq!(
+            location.as_str(),
+            "s3://bucket/table/part=a%3Fb/file%231.parquet"
+        );
+        assert_eq!(location.query(), None);
+        assert_eq!(location.fragment(), None);
+
+        let location = resolve("s3://other/file%20a.parquet");
+        assert_eq!(location.as_str(), "s3://other/file%20a.parquet");
+
+        // the query of an absolute (e.g. presigned) URL is kept
+        let presigned = "https://host/file.parquet?X-Amz-Signature=abc#frag";
+        let location = resolve(presigned);
+        assert_eq!(location.as_str(), presigned);
+        assert_eq!(location.query(), Some("X-Amz-Signature=abc"));
+        assert_eq!(location.fragment(), Some("frag"));
+    }
+
     fn table_log_dir_url() -> Url {
         let path = PathBuf::from("./tests/data/table-with-dv-small/_delta_log/");
         let path = std::fs::canonicalize(path).unwrap();
@@ -106,6 +106,33 @@ fn test_eval_binary_comparisons() {
     do_test(five, fifteen, &[TRUE, TRUE, TRUE, TRUE, TRUE, TRUE]);
 }
 
+#[test]
+fn test_eval_nested_column_comparison() {
+    let pred = column_expr!("address.zip").eq(Expr::literal("50000"));
+    let skipping_pred = as_data_skipping_predicate(&pred).unwrap();
+
+    // The stats of nested columns are nested structs within `minValues` and `maxValues`
+    let stats = |zip: &str| {
+        let address = Scalar::struct_from([("zip", zip)]).unwrap();
+        Scalar::struct_from([("address", address)]).unwrap()
+    };
+    let do_test = |min: &str, max: &str, expect: Option<bool>| {
+        let resolver = HashMap::from_iter([
+            (column_name!("minValues"), stats(min)),
+            (column_name!("maxValues"), stats(max)),
+        ]);
+        let filter = DefaultKernelPredicateEvaluator::from(resolver);
+        expect_eq!(
+            filter.eval(&skipping_pred),
+            expect,
+            "{pred:#?} became {skipping_pred:#?} with [{min}..{max}]"
+        );
+    };
+    do_test("10001", "90210", TRUE);
+    do_test("10001", "20000", FALSE);
+    do_test("60000", "90210", FALSE);
+}
+
 #[test]
 fn test_eval_junction() {
     let test_cases = &[
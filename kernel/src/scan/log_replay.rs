@@ -10,11 +10,12 @@ use crate::actions::deletion_vector::DeletionVectorDescriptor;
 use crate::actions::get_log_add_schema;
 use crate::engine_data::{GetData, RowVisitor, TypedGetData as _};
 use crate::expressions::{
-    column_expr, column_name, ColumnName, Expression, ExpressionRef, PredicateRef,
+    column_expr, column_name, ColumnName, Expression, ExpressionRef, JunctionPredicate,
+    JunctionPredicateOp, Predicate, PredicateRef,
 };
 use crate::kernel_predicates::{DefaultKernelPredicateEvaluator, KernelPredicateEvaluator as _};
 use crate::log_replay::{ActionsBatch, FileActionDeduplicator, FileActionKey, LogReplayProcessor};
-use crate::scan::{Scalar, TransformExpr};
+use crate::scan::{MaterializedRowTrackingColumns, Scalar, TransformExpr, ROW_INDEX_COLUMN_NAME};
 use crate::schema::ToSchema as _;
 use crate::schema::{ColumnNamesAndTypes, DataType, MapType, SchemaRef, StructField, StructType};
 use crate::utils::require;
@@ -27,8 +28,10 @@ use crate::{DeltaResult, Engine, Error, ExpressionEvaluator};
 ///
 /// - Data Skipping: Applies a predicate-based filter (via [`DataSkippingFilter`]) to quickly skip
 ///   files that are irrelevant for the query.
-/// - Partition Pruning: Uses an optional partition filter (extracted from a physical predicate)
-///   to exclude actions whose partition values do not meet the required criteria.
+/// - Partition Pruning: Uses an optional partition filter (the conjuncts of the physical predicate
+///   that reference partition columns) to exclude actions whose partition values do not meet the
+///   required criteria. The data skipping filter only sees the conjuncts that reference data
+///   columns, so that each mechanism can prune files independently (see [`split_predicate`]).
 /// - Action Deduplication: Leverages the [`FileActionDeduplicator`] to ensure that for each unique file
 ///   (identified by its path and deletion vector unique ID), only the latest valid Add action is processed.
 /// - Transformation: Applies a built-in transformation (`add_transform`) to convert selected Add actions
@@ -59,12 +62,20 @@ impl ScanLogReplayProcessor {
     fn new(
         engine: &dyn Engine,
         physical_predicate: Option<(PredicateRef, SchemaRef)>,
+        partition_columns: &[String],
         logical_schema: SchemaRef,
         transform: Option<Arc<Transform>>,
     ) -> Self {
+        let (partition_filter, data_skipping_predicate) = match physical_predicate {
+            Some((predicate, schema)) => {
+                let (partition_pred, data_pred) = split_predicate(&predicate, partition_columns);
+                (partition_pred, data_pred.map(|pred| (pred, schema)))
+            }
+            None => (None, None),
+        };
         Self {
-            partition_filter: physical_predicate.as_ref().map(|(e, _)| e.clone()),
-            data_skipping_filter: DataSkippingFilter::new(engine, physical_predicate),
+            partition_filter,
+            data_skipping_filter: DataSkippingFilter::new(engine, data_skipping_predicate),
             add_transform: engine.evaluation_handler().new_expression_evaluator(
                 get_log_add_schema().clone(),
                 get_add_transform_expr(),
@@ -96,8 +107,10 @@ impl AddRemoveDedupVisitor<'_> {
     const ADD_PATH_INDEX: usize = 0; // Position of "add.path" in getters
     const ADD_PARTITION_VALUES_INDEX: usize = 1; // Position of "add.partitionValues" in getters
     const ADD_DV_START_INDEX: usize = 2; // Start position of add deletion vector columns
-    const REMOVE_PATH_INDEX: usize = 5; // Position of "remove.path" in getters
-    const REMOVE_DV_START_INDEX: usize = 6; // Start position of remove deletion vector columns
+    const ADD_BASE_ROW_ID_INDEX: usize = 5; // Position of "add.baseRowId" in getters
+    const ADD_DEFAULT_ROW_COMMIT_VERSION_INDEX: usize = 6; // Position of "add.defaultRowCommitVersion"
+    const REMOVE_PATH_INDEX: usize = 7; // Position of "remove.path" in getters
+    const REMOVE_DV_START_INDEX: usize = 8; // Start position of remove deletion vector columns
 
     fn new(
         seen: &mut HashSet<FileActionKey>,
@@ -124,65 +137,136 @@ impl AddRemoveDedupVisitor<'_> {
         }
     }
 
+    /// Parse the value of the partition column at `field_path` in the logical schema. Nested
+    /// partition values are keyed by the dotted path of physical names leading to the column.
     fn parse_partition_value(
         &self,
-        field_idx: usize,
+        field_path: &[usize],
         partition_values: &HashMap<String, String>,
-    ) -> DeltaResult<(usize, (String, Scalar))> {
-        let field = self.logical_schema.fields.get_index(field_idx);
-        let Some((_, field)) = field else {
-            return Err(Error::InternalError(format!(
-                "out of bounds partition column field index {field_idx}"
-            )));
+    ) -> DeltaResult<(String, Scalar)> {
+        let mut fields = self.logical_schema.as_ref();
+        let mut names = Vec::with_capacity(field_path.len());
+        let mut field = None;
+        for field_idx in field_path {
+            let Some((_, next)) = fields.fields.get_index(*field_idx) else {
+                return Err(Error::InternalError(format!(
+                    "out of bounds partition column field index {field_idx}"
+                )));
+            };
+            if let DataType::Struct(struct_type) = next.data_type() {
+                fields = struct_type;
+            }
+            names.push(next.physical_name());
+            field = Some(next);
+        }
+        let Some(field) = field else {
+            return Err(Error::internal_error("empty partition column field path"));
         };
-        let name = field.physical_name();
+        let name = names.join(".");
         let partition_value =
-            super::parse_partition_value(partition_values.get(name), field.data_type())?;
-        Ok((field_idx, (name.to_string(), partition_value)))
+            super::parse_partition_value(partition_values.get(&name), field.data_type())?;
+        Ok((name, partition_value))
     }
 
     fn parse_partition_values(
         &self,
-        transform: &Transform,
+        transform: &[TransformExpr],
         partition_values: &HashMap<String, String>,
-    ) -> DeltaResult<HashMap<usize, (String, Scalar)>> {
-        transform
-            .iter()
-            .filter_map(|transform_expr| match transform_expr {
-                TransformExpr::Partition(field_idx) => {
-                    Some(self.parse_partition_value(*field_idx, partition_values))
+        parsed: &mut HashMap<Vec<usize>, (String, Scalar)>,
+    ) -> DeltaResult<()> {
+        for transform_expr in transform {
+            match transform_expr {
+                TransformExpr::Partition(field_path) => {
+                    let value = self.parse_partition_value(field_path, partition_values)?;
+                    parsed.insert(field_path.clone(), value);
                 }
-                TransformExpr::Static(_) => None,
-            })
-            .try_collect()
+                TransformExpr::Struct(nested) => {
+                    self.parse_partition_values(nested, partition_values, parsed)?
+                }
+                TransformExpr::Static(_) | TransformExpr::RowTracking(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Compute the row tracking metadata column of a file with the given `baseRowId` and
+    /// `defaultRowCommitVersion`: the row id of each row is its materialized row id if it has one,
+    /// and otherwise the file's `baseRowId` plus its row index. Likewise, its row commit version is
+    /// its materialized row commit version if it has one, and otherwise the file's
+    /// `defaultRowCommitVersion`.
+    fn get_row_tracking_expr(
+        materialized: &MaterializedRowTrackingColumns,
+        (base_row_id, default_row_commit_version): (i64, i64),
+    ) -> Expression {
+        let with_materialized = |column: &Option<String>, expr: Expression| match column {
+            Some(column) => Expression::coalesce([Expression::column([column]), expr]),
+            None => expr,
+        };
+        Expression::struct_from([
+            with_materialized(
+                &materialized.row_id,
+                Expression::column([ROW_INDEX_COLUMN_NAME]) + Expression::literal(base_row_id),
+            ),
+            with_materialized(
+                &materialized.row_commit_version,
+                Expression::literal(default_row_commit_version),
+            ),
+        ])
     }
 
     /// Compute an expression that will transform from physical to logical for a given Add file action
     fn get_transform_expr(
         &self,
-        transform: &Transform,
-        mut partition_values: HashMap<usize, (String, Scalar)>,
+        transform: &[TransformExpr],
+        partition_values: &mut HashMap<Vec<usize>, (String, Scalar)>,
+        row_tracking: &mut Option<(i64, i64)>,
     ) -> DeltaResult<ExpressionRef> {
+        Ok(Arc::new(Self::get_struct_expr(
+            transform,
+            partition_values,
+            row_tracking,
+        )?))
+    }
+
+    fn get_struct_expr(
+        transform: &[TransformExpr],
+        partition_values: &mut HashMap<Vec<usize>, (String, Scalar)>,
+        row_tracking: &mut Option<(i64, i64)>,
+    ) -> DeltaResult<Expression> {
         let transforms = transform
             .iter()
             .map(|transform_expr| match transform_expr {
-                TransformExpr::Partition(field_idx) => {
-                    let Some((_, partition_value)) = partition_values.remove(field_idx) else {
+                TransformExpr::Partition(field_path) => {
+                    let Some((_, partition_value)) = partition_values.remove(field_path) else {
                         return Err(Error::InternalError(format!(
-                            "missing partition value for field index {field_idx}"
+                            "missing partition value for field index path {field_path:?}"
                         )));
                     };
                     Ok(partition_value.into())
                 }
+                TransformExpr::Struct(nested) => {
+                    Self::get_struct_expr(nested, partition_values, row_tracking)
+                }
                 TransformExpr::Static(field_expr) => Ok(field_expr.clone()),
+                TransformExpr::RowTracking(materialized) => {
+                    let metadata = row_tracking
+                        .take()
+                        .ok_or_else(|| Error::internal_error("missing row tracking metadata"))?;
+                    Ok(Self::get_row_tracking_expr(materialized, metadata))
+                }
             })
             .try_collect()?;
-        Ok(Arc::new(Expression::Struct(transforms)))
+        Ok(Expression::Struct(transforms))
     }
 
+    /// Evaluate the partition filter against the file's partition values. The values were already
+    /// parsed into [`Scalar`]s of the partition columns' types, so comparisons follow the column
+    /// type's ordering (e.g. `Date`) rather than the lexicographic ordering of the raw strings.
+    ///
+    /// NOTE: Only top-level partition columns take part in pruning.
     fn is_file_partition_pruned(
         &self,
-        partition_values: &HashMap<usize, (String, Scalar)>,
+        partition_values: &HashMap<Vec<usize>, (String, Scalar)>,
     ) -> bool {
         if partition_values.is_empty() {
             return false;
@@ -191,8 +275,9 @@ impl AddRemoveDedupVisitor<'_> {
             return false;
         };
         let partition_values: HashMap<_, _> = partition_values
-            .values()
-            .map(|(k, v)| (ColumnName::new([k]), v.clone()))
+            .iter()
+            .filter(|(field_path, _)| field_path.len() == 1)
+            .map(|(_, (k, v))| (k.as_str(), v))
             .collect();
         let evaluator = DefaultKernelPredicateEvaluator::from(partition_values);
         evaluator.eval_sql_where(partition_filter) == Some(false)
@@ -203,7 +288,7 @@ impl AddRemoveDedupVisitor<'_> {
     fn is_valid_add<'a>(&mut self, i: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<bool> {
         // When processing file actions, we extract path and deletion vector information based on action type:
         // - For Add actions: path is at index 0, followed by DV fields at indexes 2-4
-        // - For Remove actions (in log batches only): path is at index 5, followed by DV fields at indexes 6-8
+        // - For Remove actions (in log batches only): path is at index 7, followed by DV fields at indexes 8-10
         // The file extraction logic selects the appropriate indexes based on whether we found a valid path.
         // Remove getters are not included when visiting a non-log batch (checkpoint batch), so do
         // not try to extract remove actions in that case.
@@ -222,11 +307,12 @@ impl AddRemoveDedupVisitor<'_> {
         // WARNING: It's not safe to partition-prune removes (just like it's not safe to data skip
         // removes), because they are needed to suppress earlier incompatible adds we might
         // encounter if the table's schema was replaced after the most recent checkpoint.
-        let partition_values = match &self.transform {
+        let mut partition_values = match &self.transform {
             Some(transform) if is_add => {
-                let partition_values =
+                let raw_values =
                     getters[Self::ADD_PARTITION_VALUES_INDEX].get(i, "add.partitionValues")?;
-                let partition_values = self.parse_partition_values(transform, &partition_values)?;
+                let mut partition_values = HashMap::new();
+                self.parse_partition_values(transform, &raw_values, &mut partition_values)?;
                 if self.is_file_partition_pruned(&partition_values) {
                     return Ok(false);
                 }
@@ -236,13 +322,40 @@ impl AddRemoveDedupVisitor<'_> {
         };
 
         // Check both adds and removes (skipping already-seen), but only transform and return adds
+        let needs_row_tracking = self.transform.as_ref().is_some_and(|transform| {
+            transform
+                .iter()
+                .any(|expr| matches!(expr, TransformExpr::RowTracking(_)))
+        });
+        let path = needs_row_tracking.then(|| file_key.path.clone());
         if self.deduplicator.check_and_record_seen(file_key) || !is_add {
             return Ok(false);
         }
+        let mut row_tracking = match path {
+            Some(path) => {
+                let base_row_id =
+                    getters[Self::ADD_BASE_ROW_ID_INDEX].get_opt(i, "add.baseRowId")?;
+                let default_row_commit_version = getters
+                    [Self::ADD_DEFAULT_ROW_COMMIT_VERSION_INDEX]
+                    .get_opt(i, "add.defaultRowCommitVersion")?;
+                let (Some(base_row_id), Some(default_row_commit_version)) =
+                    (base_row_id, default_row_commit_version)
+                else {
+                    return Err(Error::generic(format!(
+                        "Cannot read row tracking metadata: file {path} has no baseRowId or \
+                        defaultRowCommitVersion"
+                    )));
+                };
+                Some((base_row_id, default_row_commit_version))
+            }
+            None => None,
+        };
         let transform = self
             .transform
             .as_ref()
-            .map(|transform| self.get_transform_expr(transform, partition_values))
+            .map(|transform| {
+                self.get_transform_expr(transform, &mut partition_values, &mut row_tracking)
+            })
             .transpose()?;
         if transform.is_some() {
             // fill in any needed `None`s for previous rows
@@ -259,6 +372,7 @@ impl RowVisitor for AddRemoveDedupVisitor<'_> {
         static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
             const STRING: DataType = DataType::STRING;
             const INTEGER: DataType = DataType::INTEGER;
+            const LONG: DataType = DataType::LONG;
             let ss_map: DataType = MapType::new(STRING, STRING, true).into();
             let types_and_names = vec![
                 (STRING, column_name!("add.path")),
@@ -266,6 +380,8 @@ impl RowVisitor for AddRemoveDedupVisitor<'_> {
                 (STRING, column_name!("add.deletionVector.storageType")),
                 (STRING, column_name!("add.deletionVector.pathOrInlineDv")),
                 (INTEGER, column_name!("add.deletionVector.offset")),
+                (LONG, column_name!("add.baseRowId")),
+                (LONG, column_name!("add.defaultRowCommitVersion")),
                 (STRING, column_name!("remove.path")),
                 (STRING, column_name!("remove.deletionVector.storageType")),
                 (STRING, column_name!("remove.deletionVector.pathOrInlineDv")),
@@ -280,13 +396,13 @@ impl RowVisitor for AddRemoveDedupVisitor<'_> {
         } else {
             // All checkpoint actions are already reconciled and Remove actions in checkpoint files
             // only serve as tombstones for vacuum jobs. So we only need to examine the adds here.
-            (&names[..5], &types[..5])
+            (&names[..7], &types[..7])
         }
     }
 
     fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
         let is_log_batch = self.deduplicator.is_log_batch();
-        let expected_getters = if is_log_batch { 9 } else { 5 };
+        let expected_getters = if is_log_batch { 11 } else { 7 };
         require!(
             getters.len() == expected_getters,
             Error::InternalError(format!(
@@ -319,6 +435,12 @@ pub(crate) static SCAN_ROW_SCHEMA: LazyLock<Arc<StructType>> = LazyLock::new(||
         StructField::nullable("stats", DataType::STRING),
         StructField::nullable("deletionVector", DeletionVectorDescriptor::to_schema()),
         StructField::nullable("fileConstantValues", file_constant_values),
+        StructField::nullable(
+            "tags",
+            MapType::new(DataType::STRING, DataType::STRING, true),
+        ),
+        StructField::nullable("baseRowId", DataType::LONG),
+        StructField::nullable("defaultRowCommitVersion", DataType::LONG),
     ]))
 });
 
@@ -333,6 +455,9 @@ fn get_add_transform_expr() -> Expression {
         column_expr!("add.stats"),
         column_expr!("add.deletionVector"),
         Expression::Struct(vec![column_expr!("add.partitionValues")]),
+        column_expr!("add.tags"),
+        column_expr!("add.baseRowId"),
+        column_expr!("add.defaultRowCommitVersion"),
     ])
 }
 
@@ -346,6 +471,9 @@ pub(crate) fn get_scan_metadata_transform_expr() -> Expression {
         column_expr!("modificationTime"),
         column_expr!("stats"),
         column_expr!("deletionVector"),
+        column_expr!("tags"),
+        column_expr!("baseRowId"),
+        column_expr!("defaultRowCommitVersion"),
     ])])
 }
 
@@ -387,6 +515,52 @@ impl LogReplayProcessor for ScanLogReplayProcessor {
     }
 }
 
+/// Splits the top-level conjuncts (AND) of `predicate` into a partition filter and a data skipping
+/// predicate, based on the columns each conjunct references. `partition_columns` are the physical
+/// names of the table's top-level partition columns.
+///
+/// Conjuncts that only reference partition columns go to the partition filter, and conjuncts that
+/// only reference data columns go to the data skipping predicate. A conjunct that mixes both (e.g.
+/// `date = '2017-12-10' OR id > 100`) goes to both: each mechanism treats the other kind of column
+/// as unknown (NULL), which can never prune a file that should have been kept.
+fn split_predicate(
+    predicate: &PredicateRef,
+    partition_columns: &[String],
+) -> (Option<PredicateRef>, Option<PredicateRef>) {
+    fn flatten_conjuncts<'a>(pred: &'a Predicate, out: &mut Vec<&'a Predicate>) {
+        match pred {
+            Predicate::Junction(JunctionPredicate {
+                op: JunctionPredicateOp::And,
+                preds,
+            }) => preds.iter().for_each(|pred| flatten_conjuncts(pred, out)),
+            _ => out.push(pred),
+        }
+    }
+    fn conjoin(preds: Vec<&Predicate>) -> Option<PredicateRef> {
+        match preds.as_slice() {
+            [] => None,
+            [pred] => Some(Arc::new((*pred).clone())),
+            _ => Some(Arc::new(Predicate::and_from(preds.into_iter().cloned()))),
+        }
+    }
+
+    let is_partition_column =
+        |col: &ColumnName| matches!(&col[..], [name] if partition_columns.contains(name));
+    let mut conjuncts = vec![];
+    flatten_conjuncts(predicate, &mut conjuncts);
+    let (mut partition_preds, mut data_preds) = (vec![], vec![]);
+    for pred in conjuncts {
+        let references = pred.references();
+        if references.iter().any(|col| is_partition_column(col)) {
+            partition_preds.push(pred);
+        }
+        if !references.iter().all(|col| is_partition_column(col)) {
+            data_preds.push(pred);
+        }
+    }
+    (conjoin(partition_preds), conjoin(data_preds))
+}
+
 /// Given an iterator of [`ActionsBatch`]s (batches of actions read from the log) and a predicate,
 /// returns an iterator of [`ScanMetadata`]s (which includes the files to be scanned as
 /// [`FilteredEngineData`] and transforms that must be applied to correctly read the data). Each row
@@ -401,51 +575,57 @@ pub(crate) fn scan_action_iter(
     logical_schema: SchemaRef,
     transform: Option<Arc<Transform>>,
     physical_predicate: Option<(PredicateRef, SchemaRef)>,
+    partition_columns: &[String],
 ) -> impl Iterator<Item = DeltaResult<ScanMetadata>> {
-    ScanLogReplayProcessor::new(engine, physical_predicate, logical_schema, transform)
-        .process_actions_iter(action_iter)
+    ScanLogReplayProcessor::new(
+        engine,
+        physical_predicate,
+        partition_columns,
+        logical_schema,
+        transform,
+    )
+    .process_actions_iter(action_iter)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, sync::Arc};
+    use std::sync::Arc;
 
     use crate::actions::get_log_schema;
-    use crate::expressions::{column_name, Scalar};
+    use crate::arrow::array::StringArray;
+    use crate::expressions::{column_expr, column_name, Scalar};
     use crate::log_replay::ActionsBatch;
-    use crate::scan::state::{DvInfo, Stats};
+    use crate::scan::state::ScanFile;
     use crate::scan::test_utils::{
-        add_batch_simple, add_batch_with_partition_col, add_batch_with_remove,
+        add_batch_simple, add_batch_with_partition_col, add_batch_with_partition_values,
+        add_batch_with_partition_values_and_stats, add_batch_with_remove,
         run_with_validate_callback,
     };
-    use crate::scan::{get_state_info, Scan};
+    use crate::scan::{get_state_info, PhysicalPredicate, Scan};
+    use crate::schema::PrimitiveType;
+    use crate::table_features::ColumnMappingMode;
+    use crate::utils::test_utils::parse_json_batch;
     use crate::Expression as Expr;
+    use crate::Predicate as Pred;
     use crate::{
         engine::sync::SyncEngine,
         schema::{DataType, SchemaRef, StructField, StructType},
         ExpressionRef,
     };
 
-    use super::scan_action_iter;
+    use super::{scan_action_iter, split_predicate};
 
     // dv-info is more complex to validate, we validate that works in the test for visit_scan_files
     // in state.rs
-    fn validate_simple(
-        _: &mut (),
-        path: &str,
-        size: i64,
-        stats: Option<Stats>,
-        _: DvInfo,
-        _: Option<ExpressionRef>,
-        part_vals: HashMap<String, String>,
-    ) {
+    fn validate_simple(_: &mut (), scan_file: ScanFile) {
         assert_eq!(
-            path,
+            scan_file.path,
             "part-00000-fae5310a-a37d-4e51-827b-c3d5516560ca-c000.snappy.parquet"
         );
-        assert_eq!(size, 635);
-        assert!(stats.is_some());
-        assert_eq!(stats.as_ref().unwrap().num_records, 10);
+        assert_eq!(scan_file.size, 635);
+        assert!(scan_file.stats.is_some());
+        assert_eq!(scan_file.stats.as_ref().unwrap().num_records, 10);
+        let part_vals = scan_file.partition_values;
         assert_eq!(part_vals.get("date"), Some(&"2017-12-10".to_string()));
         assert_eq!(part_vals.get("non-existent"), None);
     }
@@ -474,6 +654,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_scan_action_iter_with_minimal_removes() {
+        // Removes with and without extended file metadata both act as tombstones for older adds
+        let json_strings: StringArray = vec![
+            r#"{"remove":{"path":"part-00000.snappy.parquet","deletionTimestamp":1677811194426,"dataChange":true}}"#,
+            r#"{"remove":{"path":"part-00001.snappy.parquet","deletionTimestamp":1677811194426,"dataChange":true,"extendedFileMetadata":true,"partitionValues":{},"size":635,"tags":{"INSERTION_TIME":"1677811178336000"}}}"#,
+            r#"{"add":{"path":"part-00000.snappy.parquet","partitionValues":{},"size":635,"modificationTime":1677811178336,"dataChange":true}}"#,
+            r#"{"add":{"path":"part-00001.snappy.parquet","partitionValues":{},"size":635,"modificationTime":1677811178336,"dataChange":true}}"#,
+            r#"{"add":{"path":"part-00002.snappy.parquet","partitionValues":{},"size":635,"modificationTime":1677811178336,"dataChange":true}}"#,
+        ]
+        .into();
+        let batch = parse_json_batch(json_strings);
+        let logical_schema = Arc::new(StructType::new([StructField::nullable(
+            "value",
+            DataType::INTEGER,
+        )]));
+        let iter = scan_action_iter(
+            &SyncEngine::new(),
+            [Ok(ActionsBatch::new(batch, true))].into_iter(),
+            logical_schema,
+            None,
+            None,
+            &[],
+        );
+        let selection_vectors: Vec<_> = iter
+            .map(|res| res.unwrap().scan_files.selection_vector)
+            .collect();
+        assert_eq!(selection_vectors, [vec![false, false, false, false, true]]);
+    }
+
     #[test]
     fn test_no_transforms() {
         let batch = vec![add_batch_simple(get_log_schema().clone())];
@@ -486,6 +696,7 @@ mod tests {
             logical_schema,
             None,
             None,
+            &[],
         );
         for res in iter {
             let scan_metadata = res.unwrap();
@@ -503,7 +714,8 @@ mod tests {
             StructField::new("date", DataType::DATE, true),
         ]));
         let partition_cols = ["date".to_string()];
-        let state_info = get_state_info(schema.as_ref(), &partition_cols).unwrap();
+        let state_info =
+            get_state_info(schema.as_ref(), &partition_cols, ColumnMappingMode::None).unwrap();
         let static_transform = Some(Arc::new(Scan::get_static_transform(&state_info.all_fields)));
         let batch = vec![add_batch_with_partition_col()];
         let iter = scan_action_iter(
@@ -514,6 +726,7 @@ mod tests {
             schema,
             static_transform,
             None,
+            &[],
         );
 
         fn validate_transform(transform: Option<&ExpressionRef>, expected_date_offset: i32) {
@@ -550,4 +763,123 @@ mod tests {
             validate_transform(transforms[3].as_ref(), 17510);
         }
     }
+
+    #[test]
+    fn test_partition_pruning_uses_typed_values() {
+        // Unpadded dates are valid partition values, but they don't sort lexicographically
+        let dates = ["2017-9-30", "2017-12-10", "2018-1-5"];
+        let bound = PrimitiveType::Date.parse_scalar("2017-12-01").unwrap();
+        let typed: Vec<_> = dates
+            .iter()
+            .map(|date| PrimitiveType::Date.parse_scalar(date).unwrap() > bound)
+            .collect();
+        let lexicographic: Vec<_> = dates.iter().map(|date| *date > "2017-12-01").collect();
+        assert_eq!(typed, [false, true, true]);
+        assert_eq!(lexicographic, [true, true, true]);
+
+        let schema: SchemaRef = Arc::new(StructType::new([
+            StructField::new("value", DataType::INTEGER, true),
+            StructField::new("date", DataType::DATE, true),
+        ]));
+        let partition_cols = ["date".to_string()];
+        let state_info =
+            get_state_info(schema.as_ref(), &partition_cols, ColumnMappingMode::None).unwrap();
+        let static_transform = Some(Arc::new(Scan::get_static_transform(&state_info.all_fields)));
+        let predicate = Arc::new(column_expr!("date").gt(Expr::literal(bound)));
+        let PhysicalPredicate::Some(predicate, predicate_schema) =
+            PhysicalPredicate::try_new(&predicate, &schema).unwrap()
+        else {
+            panic!("Expected a physical predicate");
+        };
+        let batch = vec![add_batch_with_partition_values(&dates)];
+        let iter = scan_action_iter(
+            &SyncEngine::new(),
+            batch
+                .into_iter()
+                .map(|batch| Ok(ActionsBatch::new(batch as _, true))),
+            schema,
+            static_transform,
+            Some((predicate, predicate_schema)),
+            &partition_cols,
+        );
+        let selection_vectors: Vec<_> = iter
+            .map(|res| res.unwrap().scan_files.selection_vector)
+            .collect();
+        // The first row is the metaData action, which is never selected
+        assert_eq!(selection_vectors, [vec![false, false, true, true]]);
+    }
+
+    #[test]
+    fn test_split_predicate() {
+        let partition_cols = ["date".to_string()];
+        let date_pred = column_expr!("date").eq(Expr::literal("2017-12-10"));
+        let value_pred = column_expr!("value").gt(Expr::literal(100));
+        let mixed_pred = Pred::or(date_pred.clone(), value_pred.clone());
+        let split = |pred: Pred| split_predicate(&Arc::new(pred), &partition_cols);
+
+        let (partition, data) = split(Pred::and(date_pred.clone(), value_pred.clone()));
+        assert_eq!(partition.as_deref(), Some(&date_pred));
+        assert_eq!(data.as_deref(), Some(&value_pred));
+
+        // Nested ANDs are flattened, and mixed conjuncts are kept on both sides
+        let (partition, data) = split(Pred::and_from([
+            Pred::and(date_pred.clone(), mixed_pred.clone()),
+            value_pred.clone(),
+        ]));
+        let expected = Pred::and(date_pred.clone(), mixed_pred.clone());
+        assert_eq!(partition.as_deref(), Some(&expected));
+        let expected = Pred::and(mixed_pred.clone(), value_pred.clone());
+        assert_eq!(data.as_deref(), Some(&expected));
+
+        let (partition, data) = split(value_pred.clone());
+        assert_eq!(partition, None);
+        assert_eq!(data.as_deref(), Some(&value_pred));
+
+        let (partition, data) = split(date_pred.clone());
+        assert_eq!(partition.as_deref(), Some(&date_pred));
+        assert_eq!(data, None);
+    }
+
+    #[test]
+    fn test_partition_pruning_and_data_skipping() {
+        let schema: SchemaRef = Arc::new(StructType::new([
+            StructField::new("value", DataType::INTEGER, true),
+            StructField::new("date", DataType::DATE, true),
+        ]));
+        let partition_cols = ["date".to_string()];
+        let state_info =
+            get_state_info(schema.as_ref(), &partition_cols, ColumnMappingMode::None).unwrap();
+        let static_transform = Some(Arc::new(Scan::get_static_transform(&state_info.all_fields)));
+        let date = PrimitiveType::Date.parse_scalar("2017-12-10").unwrap();
+        let predicate = Pred::and(
+            column_expr!("date").eq(Expr::literal(date)),
+            column_expr!("value").gt(Expr::literal(100)),
+        );
+        let PhysicalPredicate::Some(predicate, predicate_schema) =
+            PhysicalPredicate::try_new(&predicate, &schema).unwrap()
+        else {
+            panic!("Expected a physical predicate");
+        };
+        let batch = vec![add_batch_with_partition_values_and_stats(&[
+            ("2017-12-10", (0, 9)),
+            ("2017-12-10", (50, 200)),
+            ("2017-12-11", (50, 200)),
+            ("2017-12-11", (0, 9)),
+        ])];
+        let iter = scan_action_iter(
+            &SyncEngine::new(),
+            batch
+                .into_iter()
+                .map(|batch| Ok(ActionsBatch::new(batch as _, true))),
+            schema,
+            static_transform,
+            Some((predicate, predicate_schema)),
+            &partition_cols,
+        );
+        let selection_vectors: Vec<_> = iter
+            .map(|res| res.unwrap().scan_files.selection_vector)
+            .collect();
+        // Only the second file is in the right partition AND may have values > 100
+        assert_eq!(selection_vectors, [vec![false, false, true, false, false]]);
+    }
 }
@@ -5,8 +5,8 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, LazyLock};
 
 use delta_kernel_derive::internal_api;
-use itertools::Itertools;
-use tracing::debug;
+use itertools::{Either, Itertools};
+use tracing::{debug, warn};
 use url::Url;
 
 use self::log_replay::get_scan_metadata_transform_expr;
@@ -14,17 +14,20 @@ use crate::actions::deletion_vector::{
     deletion_treemap_to_bools, split_vector, DeletionVectorDescriptor,
 };
 use crate::actions::{get_log_schema, ADD_NAME, REMOVE_NAME, SIDECAR_NAME};
-use crate::engine_data::FilteredEngineData;
+use crate::engine_data::{FilteredEngineData, GetData, RowVisitor, TypedGetData as _};
 use crate::expressions::transforms::ExpressionTransform;
-use crate::expressions::{ColumnName, Expression, ExpressionRef, Predicate, PredicateRef, Scalar};
+use crate::expressions::{
+    column_name, ColumnName, Expression, ExpressionRef, Predicate, PredicateRef, Scalar,
+};
 use crate::kernel_predicates::{DefaultKernelPredicateEvaluator, EmptyColumnResolver};
 use crate::log_replay::{ActionsBatch, HasSelectionVector};
 use crate::log_segment::{ListedLogFiles, LogSegment};
-use crate::scan::state::{DvInfo, Stats};
+use crate::path::resolve_file_path;
+use crate::scan::state::{DvInfo, ScanFile};
 use crate::schema::ToSchema as _;
 use crate::schema::{
-    ArrayType, DataType, MapType, PrimitiveType, Schema, SchemaRef, SchemaTransform, StructField,
-    StructType,
+    ArrayType, ColumnNamesAndTypes, DataType, MapType, PrimitiveType, Schema, SchemaRef,
+    SchemaTransform, StructField, StructType,
 };
 use crate::snapshot::Snapshot;
 use crate::table_features::ColumnMappingMode;
@@ -41,11 +44,35 @@ static COMMIT_READ_SCHEMA: LazyLock<SchemaRef> =
 static CHECKPOINT_READ_SCHEMA: LazyLock<SchemaRef> =
     LazyLock::new(|| get_log_schema().project(&[ADD_NAME, SIDECAR_NAME]).unwrap());
 
+/// Name of the struct column holding the row tracking metadata of each row, which is appended to
+/// the scan's schema when requested via [`ScanBuilder::with_row_tracking`].
+pub const ROW_TRACKING_METADATA_COLUMN_NAME: &str = "_metadata";
+/// Name of the field of [`ROW_TRACKING_METADATA_COLUMN_NAME`] holding the row id of each row.
+pub const ROW_ID_FIELD_NAME: &str = "row_id";
+/// Name of the field of [`ROW_TRACKING_METADATA_COLUMN_NAME`] holding the row commit version of
+/// each row.
+pub const ROW_COMMIT_VERSION_FIELD_NAME: &str = "row_commit_version";
+/// Name of the column holding the index of each row within its data file, which is appended to the
+/// scan's schema when requested via [`ScanBuilder::with_row_index`].
+pub const ROW_INDEX_FIELD_NAME: &str = "_row_index";
+/// Name of the row index column added to the physical schema of scans which read row indexes or
+/// row tracking metadata. Row ids are computed from it.
+pub(crate) const ROW_INDEX_COLUMN_NAME: &str = "__delta_internal_row_index";
+
 /// Builder to scan a snapshot of a table.
 pub struct ScanBuilder {
     snapshot: Arc<Snapshot>,
     schema: Option<SchemaRef>,
     predicate: Option<PredicateRef>,
+    batch_size: Option<usize>,
+    require_stats: bool,
+    row_tracking: bool,
+    row_index: bool,
+    apply_deletion_vectors: bool,
+    deterministic_file_order: bool,
+    strict_nullability: bool,
+    file_limit: Option<usize>,
+    unknown_stats_columns: UnknownStatsColumns,
 }
 
 impl std::fmt::Debug for ScanBuilder {
@@ -53,6 +80,15 @@ impl std::fmt::Debug for ScanBuilder {
         f.debug_struct("ScanBuilder")
             .field("schema", &self.schema)
             .field("predicate", &self.predicate)
+            .field("batch_size", &self.batch_size)
+            .field("require_stats", &self.require_stats)
+            .field("row_tracking", &self.row_tracking)
+            .field("row_index", &self.row_index)
+            .field("apply_deletion_vectors", &self.apply_deletion_vectors)
+            .field("deterministic_file_order", &self.deterministic_file_order)
+            .field("strict_nullability", &self.strict_nullability)
+            .field("file_limit", &self.file_limit)
+            .field("unknown_stats_columns", &self.unknown_stats_columns)
             .finish()
     }
 }
@@ -64,6 +100,15 @@ impl ScanBuilder {
             snapshot: snapshot.into(),
             schema: None,
             predicate: None,
+            batch_size: None,
+            require_stats: false,
+            row_tracking: false,
+            row_index: false,
+            apply_deletion_vectors: true,
+            deterministic_file_order: false,
+            strict_nullability: false,
+            file_limit: None,
+            unknown_stats_columns: UnknownStatsColumns::default(),
         }
     }
 
@@ -99,6 +144,112 @@ impl ScanBuilder {
         self
     }
 
+    /// Cap the number of rows in each [`ScanResult`] produced by [`Scan::execute`]. Batches read by
+    /// the engine's [`ParquetHandler`] which exceed `batch_size` rows are split into consecutive
+    /// smaller batches (along with their deletion vector masks); smaller batches are returned
+    /// as-is. By default, batches are returned exactly as the engine produced them.
+    ///
+    /// Splitting relies on [`EngineData::slice`], which the engine's data must support.
+    ///
+    /// [`ParquetHandler`]: crate::ParquetHandler
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Require every file selected by the scan to have stats. When enabled, [`Scan::scan_metadata`]
+    /// (and therefore [`Scan::execute`]) returns an [`Error::MissingStats`] naming the first selected
+    /// file found to have no `stats`, since such files can never be pruned by data skipping.
+    /// Engines which prefer to fall back to a full scan can catch this error and retry without
+    /// requiring stats. Disabled by default.
+    pub fn with_require_stats(mut self, require_stats: bool) -> Self {
+        self.require_stats = require_stats;
+        self
+    }
+
+    /// Materialize the row tracking metadata of each row read by the scan. When enabled, a
+    /// non-nullable `_metadata` struct column with the (`LONG`) fields `row_id` and
+    /// `row_commit_version` is appended to the scan's logical schema. The row id of a row is the
+    /// one materialized in its data file (see [`materialized_row_id_column_name`]) if any, and
+    /// otherwise the `baseRowId` of the file it was read from plus the row's index within that
+    /// file. Likewise, its row commit version is the materialized one (see
+    /// [`materialized_row_commit_version_column_name`]) if any, and otherwise the file's
+    /// `defaultRowCommitVersion`. Building the scan fails if the table does not support row
+    /// tracking. Disabled by default.
+    ///
+    /// [`materialized_row_id_column_name`]: crate::table_properties::TableProperties::materialized_row_id_column_name
+    /// [`materialized_row_commit_version_column_name`]: crate::table_properties::TableProperties::materialized_row_commit_version_column_name
+    pub fn with_row_tracking(mut self, row_tracking: bool) -> Self {
+        self.row_tracking = row_tracking;
+        self
+    }
+
+    /// Materialize the index of each row read by the scan within its data file. When enabled, a
+    /// non-nullable `LONG` column named [`ROW_INDEX_FIELD_NAME`] is appended to the scan's logical
+    /// schema (after the row tracking metadata column, if that is also requested). Row indexes
+    /// count all rows of the file, including rows removed by its deletion vector. Disabled by
+    /// default.
+    pub fn with_row_index(mut self, row_index: bool) -> Self {
+        self.row_index = row_index;
+        self
+    }
+
+    /// Whether [`Scan::execute`] should apply the deletion vectors of the files it reads. When
+    /// disabled, every [`ScanResult`] has a `raw_mask` of `None` and holds all the rows of the
+    /// underlying file, including deleted ones. The deletion vectors are not read at all; engines
+    /// that apply them downstream can get them from the [`DvInfo`] passed to the
+    /// [`ScanMetadata::visit_scan_files`] callback. Enabled by default.
+    pub fn with_apply_deletion_vectors(mut self, apply_deletion_vectors: bool) -> Self {
+        self.apply_deletion_vectors = apply_deletion_vectors;
+        self
+    }
+
+    /// Emit the files selected by the scan in a deterministic order, sorted by path, so that the
+    /// output of [`Scan::scan_metadata`] (and therefore [`Scan::execute`]) is stable across runs
+    /// and engines. By default, files are emitted in log replay order, which depends on how the
+    /// table's log happens to be laid out (e.g. its commits and checkpoints).
+    ///
+    /// When enabled, `scan_metadata` replays the entire log before returning, and slices the
+    /// resulting batches into runs of sorted files using [`EngineData::slice`], which the engine's
+    /// data must support. Disabled by default.
+    pub fn with_deterministic_file_order(mut self, deterministic_file_order: bool) -> Self {
+        self.deterministic_file_order = deterministic_file_order;
+        self
+    }
+
+    /// Validate that the data read by [`Scan::execute`] respects the nullability of the scan's
+    /// schema. Parquet files commonly mark columns nullable even when the table schema declares
+    /// them non-nullable, so a file containing NULLs in such a column can otherwise be read without
+    /// complaint. When enabled, reading a file whose data has a NULL in a top-level non-nullable
+    /// column fails with an [`Error::InvalidStructData`]. Disabled by default.
+    pub fn with_strict_nullability(mut self, strict_nullability: bool) -> Self {
+        self.strict_nullability = strict_nullability;
+        self
+    }
+
+    /// Stop the scan after `file_limit` files, e.g. to sample or preview a table. The limit
+    /// applies to the files that survive partition pruning and data skipping: once that many files
+    /// have been selected, [`Scan::scan_metadata`] (and therefore [`Scan::execute`]) stops replaying
+    /// the log and returns no further files. The selected files are a prefix of the files the scan
+    /// would otherwise return, in the same order. By default, there is no limit.
+    pub fn with_file_limit(mut self, file_limit: usize) -> Self {
+        self.file_limit = Some(file_limit);
+        self
+    }
+
+    /// How [`Scan::scan_metadata`] (and therefore [`Scan::execute`]) handles stats of selected
+    /// files whose `minValues`, `maxValues` or `nullCount` reference a column that is not in the
+    /// table schema, e.g. because the column was dropped. Such stats are harmless for data
+    /// skipping, but may hint at a corrupt or mismatched log. Defaults to
+    /// [`UnknownStatsColumns::Ignore`], which does not inspect the stats at all.
+    pub fn with_unknown_stats_columns(
+        mut self,
+        unknown_stats_columns: UnknownStatsColumns,
+    ) -> Self {
+        self.unknown_stats_columns = unknown_stats_columns;
+        self
+    }
+
     /// Build the [`Scan`].
     ///
     /// This does not scan the table at this point, but does do some work to ensure that the
@@ -106,18 +257,99 @@ impl ScanBuilder {
     /// [`Scan`] type itself can be used to fetch the files and associated metadata required to
     /// perform actual data reads.
     pub fn build(self) -> DeltaResult<Scan> {
+        if self.batch_size == Some(0) {
+            return Err(Error::generic("Scan batch size must be greater than zero"));
+        }
+        let format = self.snapshot.format();
+        if !format.is_parquet() {
+            return Err(Error::unsupported(format!(
+                "Cannot scan table with data file format '{}': only parquet is supported",
+                format.provider()
+            )));
+        }
         // if no schema is provided, use snapshot's entire schema (e.g. SELECT *)
-        let logical_schema = self.schema.unwrap_or_else(|| self.snapshot.schema());
-        let state_info = get_state_info(
+        let mut logical_schema = self.schema.unwrap_or_else(|| self.snapshot.schema());
+        let mut state_info = get_state_info(
             logical_schema.as_ref(),
             &self.snapshot.metadata().partition_columns,
+            self.snapshot.column_mapping_mode(),
         )?;
 
+        // The predicate can only reference table columns, not the row tracking metadata
         let physical_predicate = match self.predicate {
             Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?,
             None => PhysicalPredicate::None,
         };
 
+        if self.row_tracking {
+            if !self
+                .snapshot
+                .table_configuration()
+                .is_row_tracking_supported()
+            {
+                return Err(Error::unsupported(
+                    "Cannot read row tracking metadata: table does not support row tracking",
+                ));
+            }
+            if logical_schema
+                .field(ROW_TRACKING_METADATA_COLUMN_NAME)
+                .is_some()
+            {
+                return Err(Error::schema(format!(
+                    "Cannot read row tracking metadata: scan schema already has a \
+                    {ROW_TRACKING_METADATA_COLUMN_NAME} column"
+                )));
+            }
+            let row_tracking_field = StructField::not_null(
+                ROW_TRACKING_METADATA_COLUMN_NAME,
+                StructType::new([
+                    StructField::not_null(ROW_ID_FIELD_NAME, DataType::LONG),
+                    StructField::not_null(ROW_COMMIT_VERSION_FIELD_NAME, DataType::LONG),
+                ]),
+            );
+            let fields = logical_schema.fields().cloned().chain([row_tracking_field]);
+            logical_schema = Arc::new(StructType::new(fields));
+            // Rows updated since their file was added have their row tracking metadata
+            // materialized in these columns, which are NULL (or missing) for all other rows
+            let table_properties = self.snapshot.table_properties();
+            let materialized = MaterializedRowTrackingColumns {
+                row_id: table_properties.materialized_row_id_column_name.clone(),
+                row_commit_version: table_properties
+                    .materialized_row_commit_version_column_name
+                    .clone(),
+            };
+            for name in [&materialized.row_id, &materialized.row_commit_version]
+                .into_iter()
+                .flatten()
+            {
+                if !state_info.read_fields.iter().any(|f| f.name() == name) {
+                    let field = StructField::nullable(name.as_str(), DataType::LONG);
+                    state_info.read_fields.push(field);
+                }
+            }
+            state_info
+                .all_fields
+                .push(ColumnType::RowTracking(materialized));
+        }
+
+        if self.row_index {
+            if logical_schema.field(ROW_INDEX_FIELD_NAME).is_some() {
+                return Err(Error::schema(format!(
+                    "Cannot read row indexes: scan schema already has a {ROW_INDEX_FIELD_NAME} column"
+                )));
+            }
+            let row_index_field = StructField::not_null(ROW_INDEX_FIELD_NAME, DataType::LONG);
+            let fields = logical_schema.fields().cloned().chain([row_index_field]);
+            logical_schema = Arc::new(StructType::new(fields));
+            state_info.all_fields.push(ColumnType::RowIndex);
+        }
+
+        if self.row_tracking || self.row_index {
+            state_info
+                .read_fields
+                .push(StructField::row_index(ROW_INDEX_COLUMN_NAME));
+        }
+
         Ok(Scan {
             snapshot: self.snapshot,
             logical_schema,
@@ -125,10 +357,33 @@ impl ScanBuilder {
             physical_predicate,
             all_fields: Arc::new(state_info.all_fields),
             have_partition_cols: state_info.have_partition_cols,
+            batch_size: self.batch_size,
+            require_stats: self.require_stats,
+            row_tracking: self.row_tracking,
+            row_index: self.row_index,
+            apply_deletion_vectors: self.apply_deletion_vectors,
+            deterministic_file_order: self.deterministic_file_order,
+            strict_nullability: self.strict_nullability,
+            file_limit: self.file_limit,
+            unknown_stats_columns: self.unknown_stats_columns,
         })
     }
 }
 
+/// How a scan handles file stats that reference columns which are not in the table schema. See
+/// [`ScanBuilder::with_unknown_stats_columns`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownStatsColumns {
+    /// Ignore stats of unknown columns.
+    #[default]
+    Ignore,
+    /// Log a warning for each selected file whose stats reference an unknown column.
+    Warn,
+    /// Fail with an [`Error::Schema`] naming the first selected file whose stats reference an
+    /// unknown column.
+    Error,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) enum PhysicalPredicate {
     Some(PredicateRef, SchemaRef),
@@ -143,14 +398,12 @@ impl PhysicalPredicate {
     /// to rewrite the expression with physical column names.
     ///
     /// NOTE: It is possible the predicate resolves to FALSE even ignoring column references,
-    /// e.g. `col > 10 AND FALSE`. Such predicates can statically skip the whole query.
+    /// e.g. `col > 10 AND FALSE`. Such predicates can statically skip the whole query, but their
+    /// column references must still be valid.
     pub(crate) fn try_new(
         predicate: &Predicate,
         logical_schema: &Schema,
     ) -> DeltaResult<PhysicalPredicate> {
-        if can_statically_skip_all_files(predicate) {
-            return Ok(PhysicalPredicate::StaticSkipAll);
-        }
         let mut get_referenced_fields = GetReferencedFields {
             unresolved_references: predicate.references(),
             column_mappings: HashMap::new(),
@@ -170,6 +423,9 @@ impl PhysicalPredicate {
                 "Predicate references unknown column: {unresolved}"
             )));
         }
+        if can_statically_skip_all_files(predicate) {
+            return Ok(PhysicalPredicate::StaticSkipAll);
+        }
         let Some(schema) = schema_opt else {
             // The predicate doesn't statically skip all files, and it doesn't reference any columns
             // that could dynamically change its behavior, so it's useless for data skipping.
@@ -297,18 +553,70 @@ impl ScanResult {
         mask.resize(self.raw_data.as_ref().ok()?.len(), true);
         Some(mask)
     }
+
+    /// Consume this result, returning only the rows of its data which are selected by its mask
+    /// (i.e. with any rows removed by a deletion vector filtered out). This is a convenience for
+    /// consumers who don't need to handle the mask themselves; see [`ScanResult::raw_mask`] and
+    /// [`ScanResult::full_mask`] for lower-level access to it.
+    ///
+    /// Filtering relies on [`EngineData::filter`], which the engine's data must support if a mask
+    /// is present.
+    pub fn into_filtered_data(self) -> DeltaResult<Box<dyn EngineData>> {
+        let data = self.raw_data?;
+        match self.raw_mask {
+            Some(mask) => data.filter(&mask),
+            None => Ok(data),
+        }
+    }
+
+    /// Split this result into consecutive results of at most `batch_size` rows each, slicing the
+    /// raw mask to match. Errors and results that already fit are returned unchanged.
+    fn split_into_batches(self, batch_size: usize) -> DeltaResult<Vec<ScanResult>> {
+        let data = match self.raw_data {
+            Ok(ref data) if data.len() > batch_size => data,
+            _ => return Ok(vec![self]),
+        };
+        (0..data.len())
+            .step_by(batch_size)
+            .map(|offset| {
+                let length = batch_size.min(data.len() - offset);
+                // The raw mask may be shorter than the data; missing entries are implicitly true.
+                let raw_mask = self.raw_mask.as_ref().map(|mask| {
+                    let end = mask.len().min(offset + length);
+                    mask.get(offset..end).unwrap_or_default().to_vec()
+                });
+                Ok(ScanResult {
+                    raw_data: Ok(data.slice(offset, length)?),
+                    raw_mask,
+                })
+            })
+            .collect()
+    }
 }
 
 /// Scan uses this to set up what kinds of top-level columns it is scanning. For `Selected` we just
 /// store the name of the column, as that's all that's needed during the actual query. For
 /// `Partition` we store an index into the logical schema for this query since later we need the
-/// data type as well to materialize the partition column.
+/// data type as well to materialize the partition column. A struct column containing nested
+/// partition columns is `Nested`, which holds the physical name of the column and the
+/// `ColumnType` of each of its fields; indexes of nested `Partition` fields are relative to the
+/// enclosing struct. The row tracking metadata column (see [`ScanBuilder::with_row_tracking`]) is
+/// `RowTracking`, which is computed from the row index column of the physical schema and the
+/// materialized row tracking columns it names, as is the row index column (see
+/// [`ScanBuilder::with_row_index`]), which is `RowIndex`.
 #[derive(PartialEq, Debug)]
 pub enum ColumnType {
     // A column, selected from the data, as is
     Selected(String),
     // A partition column that needs to be added back in
     Partition(usize),
+    // A struct column that has some partition columns nested inside it
+    Nested(String, Vec<ColumnType>),
+    // The row tracking metadata column, computed from the row index of each row and the columns in
+    // which row tracking metadata is materialized
+    RowTracking(MaterializedRowTrackingColumns),
+    // The row index column, read as the row index of each row
+    RowIndex,
 }
 
 /// A transform is ultimately a `Struct` expr. This holds the set of expressions that make that struct expr up
@@ -329,7 +637,25 @@ pub fn get_transform_for_row(
 /// `Transform`.
 pub(crate) enum TransformExpr {
     Static(Expression),
-    Partition(usize),
+    /// A partition column, identified by the path of field indexes leading to it in the logical
+    /// schema. Top-level partition columns have a path of length one.
+    Partition(Vec<usize>),
+    /// A struct column with nested partition columns, built from the transforms of its fields.
+    Struct(Vec<TransformExpr>),
+    /// The row tracking metadata column, which depends on the row tracking metadata of each file.
+    RowTracking(MaterializedRowTrackingColumns),
+}
+
+/// The physical columns of a table's data files in which row ids and row commit versions are
+/// materialized, for rows whose row tracking metadata can no longer be derived from the metadata of
+/// their file. Each is `None` if the table does not name such a column.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct MaterializedRowTrackingColumns {
+    /// The column named by the `delta.rowTracking.materializedRowIdColumnName` table property
+    pub row_id: Option<String>,
+    /// The column named by the `delta.rowTracking.materializedRowCommitVersionColumnName` table
+    /// property
+    pub row_commit_version: Option<String>,
 }
 
 /// [`ScanMetadata`] contains (1) a batch of [`FilteredEngineData`] specifying data files to be scanned
@@ -385,6 +711,15 @@ pub struct Scan {
     physical_predicate: PhysicalPredicate,
     all_fields: Arc<Vec<ColumnType>>,
     have_partition_cols: bool,
+    batch_size: Option<usize>,
+    require_stats: bool,
+    row_tracking: bool,
+    row_index: bool,
+    apply_deletion_vectors: bool,
+    deterministic_file_order: bool,
+    strict_nullability: bool,
+    file_limit: Option<usize>,
+    unknown_stats_columns: UnknownStatsColumns,
 }
 
 impl std::fmt::Debug for Scan {
@@ -392,6 +727,15 @@ impl std::fmt::Debug for Scan {
         f.debug_struct("Scan")
             .field("schema", &self.logical_schema)
             .field("predicate", &self.physical_predicate)
+            .field("batch_size", &self.batch_size)
+            .field("require_stats", &self.require_stats)
+            .field("row_tracking", &self.row_tracking)
+            .field("row_index", &self.row_index)
+            .field("apply_deletion_vectors", &self.apply_deletion_vectors)
+            .field("deterministic_file_order", &self.deterministic_file_order)
+            .field("strict_nullability", &self.strict_nullability)
+            .field("file_limit", &self.file_limit)
+            .field("unknown_stats_columns", &self.unknown_stats_columns)
             .finish()
     }
 }
@@ -421,6 +765,15 @@ impl Scan {
         &self.logical_schema
     }
 
+    /// The final schema of the data produced by the scan, which engines can use to set up their
+    /// sinks. This is the requested projection (or the table schema), including partition columns,
+    /// followed by any enabled virtual columns: the row tracking metadata column (see
+    /// [`ScanBuilder::with_row_tracking`]) and the row index column (see
+    /// [`ScanBuilder::with_row_index`]). It is the same as [`Scan::logical_schema`].
+    pub fn output_schema(&self) -> SchemaRef {
+        self.logical_schema.clone()
+    }
+
     /// Get a shared reference to the physical [`Schema`] of the scan. This represents the schema
     /// of the underlying data files which must be read from storage.
     ///
@@ -429,6 +782,54 @@ impl Scan {
         &self.physical_schema
     }
 
+    /// Get the maximum number of rows per [`ScanResult`] returned by [`Scan::execute`], if one
+    /// was configured via [`ScanBuilder::with_batch_size`].
+    pub fn batch_size(&self) -> Option<usize> {
+        self.batch_size
+    }
+
+    /// Whether this scan requires every selected file to have stats, as configured via
+    /// [`ScanBuilder::with_require_stats`].
+    pub fn require_stats(&self) -> bool {
+        self.require_stats
+    }
+
+    /// How this scan handles stats that reference columns which are not in the table schema, as
+    /// configured via [`ScanBuilder::with_unknown_stats_columns`].
+    pub fn unknown_stats_columns(&self) -> UnknownStatsColumns {
+        self.unknown_stats_columns
+    }
+
+    /// Whether this scan materializes row tracking metadata, as configured via
+    /// [`ScanBuilder::with_row_tracking`].
+    pub fn row_tracking(&self) -> bool {
+        self.row_tracking
+    }
+
+    /// Whether this scan materializes the row index of each row, as configured via
+    /// [`ScanBuilder::with_row_index`].
+    pub fn row_index(&self) -> bool {
+        self.row_index
+    }
+
+    /// Whether [`Scan::execute`] applies deletion vectors, as configured via
+    /// [`ScanBuilder::with_apply_deletion_vectors`].
+    pub fn apply_deletion_vectors(&self) -> bool {
+        self.apply_deletion_vectors
+    }
+
+    /// Whether this scan emits files sorted by path, as configured via
+    /// [`ScanBuilder::with_deterministic_file_order`].
+    pub fn deterministic_file_order(&self) -> bool {
+        self.deterministic_file_order
+    }
+
+    /// The maximum number of files this scan selects, if one was configured via
+    /// [`ScanBuilder::with_file_limit`].
+    pub fn file_limit(&self) -> Option<usize> {
+        self.file_limit
+    }
+
     /// Get the predicate [`Expression`] of the scan.
     pub fn physical_predicate(&self) -> Option<PredicateRef> {
         if let PhysicalPredicate::Some(ref predicate, _) = self.physical_predicate {
@@ -438,17 +839,97 @@ impl Scan {
         }
     }
 
+    /// True if data read from the scan's files is already in the logical form of the scan, i.e. no
+    /// transform is needed (and [`ScanMetadata::scan_file_transforms`] is always `None`). This is
+    /// the case when the scan reads no partition columns, row indexes or row tracking metadata, and
+    /// the table does not use column mapping. Engines can use this to skip [`transform_to_logical`].
+    ///
+    /// [`transform_to_logical`]: crate::scan::state::transform_to_logical
+    pub fn is_identity_transform(&self) -> bool {
+        !(self.have_partition_cols
+            || self.row_tracking
+            || self.row_index
+            || self.snapshot.column_mapping_mode() != ColumnMappingMode::None)
+    }
+
+    /// The (logical) columns of the scan's output that the transform computes, rather than passing
+    /// through as read from the data files. These are partition columns (possibly nested inside a
+    /// struct), the row tracking metadata and row index columns and, if the table uses column
+    /// mapping, every top-level column read from the files (which must be renamed). Empty if
+    /// [`Scan::is_identity_transform`] is true.
+    pub fn transform_columns(&self) -> Vec<ColumnName> {
+        fn collect(
+            fields: &[ColumnType],
+            schema: &StructType,
+            column_mapping: bool,
+            path: &mut Vec<String>,
+            columns: &mut Vec<ColumnName>,
+        ) {
+            for (column_type, field) in fields.iter().zip(schema.fields()) {
+                path.push(field.name().clone());
+                match (column_type, field.data_type()) {
+                    (ColumnType::Selected(_), _) if !column_mapping => {}
+                    (ColumnType::Nested(_, nested), DataType::Struct(nested_schema))
+                        if !column_mapping =>
+                    {
+                        collect(nested, nested_schema, column_mapping, path, columns)
+                    }
+                    _ => columns.push(ColumnName::new(path.iter())),
+                }
+                path.pop();
+            }
+        }
+
+        let column_mapping = self.snapshot.column_mapping_mode() != ColumnMappingMode::None;
+        let mut columns = vec![];
+        collect(
+            &self.all_fields,
+            &self.logical_schema,
+            column_mapping,
+            &mut vec![],
+            &mut columns,
+        );
+        columns
+    }
+
     /// Convert the parts of the transform that can be computed statically into `Expression`s. For
     /// parts that cannot be computed statically, include enough metadata so lower levels of
     /// processing can create and fill in an expression.
     fn get_static_transform(all_fields: &[ColumnType]) -> Transform {
-        all_fields
+        Self::get_nested_static_transform(all_fields, &[], &[])
+    }
+
+    /// Convert the [`ColumnType`]s of the fields of a (possibly nested) struct, given the physical
+    /// column path and logical field index path leading to that struct.
+    fn get_nested_static_transform(
+        fields: &[ColumnType],
+        column_path: &[&str],
+        field_path: &[usize],
+    ) -> Transform {
+        let child_path = |idx: usize| field_path.iter().copied().chain([idx]).collect_vec();
+        fields
             .iter()
-            .map(|field| match field {
+            .enumerate()
+            .map(|(idx, field)| match field {
                 ColumnType::Selected(col_name) => {
-                    TransformExpr::Static(ColumnName::new([col_name]).into())
+                    let path = column_path.iter().copied().chain([col_name.as_str()]);
+                    TransformExpr::Static(ColumnName::new(path).into())
+                }
+                ColumnType::Partition(idx) => TransformExpr::Partition(child_path(*idx)),
+                ColumnType::Nested(col_name, nested_fields) => {
+                    let column_path = [column_path, &[col_name.as_str()]].concat();
+                    TransformExpr::Struct(Self::get_nested_static_transform(
+                        nested_fields,
+                        &column_path,
+                        &child_path(idx),
+                    ))
+                }
+                ColumnType::RowTracking(materialized) => {
+                    TransformExpr::RowTracking(materialized.clone())
+                }
+                ColumnType::RowIndex => {
+                    TransformExpr::Static(ColumnName::new([ROW_INDEX_COLUMN_NAME]).into())
                 }
-                ColumnType::Partition(idx) => TransformExpr::Partition(*idx),
             })
             .collect()
     }
@@ -530,16 +1011,19 @@ impl Scan {
         _existing_predicate: Option<PredicateRef>,
     ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<ScanMetadata>>>> {
         static RESTORED_ADD_SCHEMA: LazyLock<DataType> = LazyLock::new(|| {
-            let partition_values = MapType::new(DataType::STRING, DataType::STRING, true);
+            let string_map = MapType::new(DataType::STRING, DataType::STRING, true);
             DataType::struct_type(vec![StructField::nullable(
                 "add",
                 DataType::struct_type(vec![
                     StructField::not_null("path", DataType::STRING),
-                    StructField::not_null("partitionValues", partition_values),
+                    StructField::not_null("partitionValues", string_map.clone()),
                     StructField::not_null("size", DataType::LONG),
                     StructField::nullable("modificationTime", DataType::LONG),
                     StructField::nullable("stats", DataType::STRING),
                     StructField::nullable("deletionVector", DeletionVectorDescriptor::to_schema()),
+                    StructField::nullable("tags", string_map),
+                    StructField::nullable("baseRowId", DataType::LONG),
+                    StructField::nullable("defaultRowCommitVersion", DataType::LONG),
                 ]),
             )])
         });
@@ -617,23 +1101,63 @@ impl Scan {
         action_batch_iter: impl Iterator<Item = DeltaResult<ActionsBatch>>,
     ) -> DeltaResult<impl Iterator<Item = DeltaResult<ScanMetadata>>> {
         // Compute the static part of the transformation. This is `None` if no transformation is
-        // needed (currently just means no partition cols AND no column mapping but will be extended
-        // for other transforms as we support them)
-        let static_transform = (self.have_partition_cols
-            || self.snapshot.column_mapping_mode() != ColumnMappingMode::None)
+        // needed (currently just means no partition cols AND no column mapping AND no row tracking
+        // metadata but will be extended for other transforms as we support them)
+        let static_transform = (!self.is_identity_transform())
             .then(|| Arc::new(Scan::get_static_transform(&self.all_fields)));
         let physical_predicate = match self.physical_predicate.clone() {
             PhysicalPredicate::StaticSkipAll => return Ok(None.into_iter().flatten()),
             PhysicalPredicate::Some(predicate, schema) => Some((predicate, schema)),
             PhysicalPredicate::None => None,
         };
+        // Physical names of the top-level partition columns, used to split the predicate into a
+        // partition filter and a data skipping predicate.
+        let table_schema = self.snapshot.schema();
+        let partition_columns: Vec<_> = self
+            .snapshot
+            .metadata()
+            .partition_columns
+            .iter()
+            .filter_map(|col| table_schema.field(col))
+            .map(|field| field.physical_name().to_string())
+            .collect();
+        let require_stats = self.require_stats;
+        let unknown_stats_columns = self.unknown_stats_columns;
+        // Stats reference columns by their physical names
+        let physical_table_schema =
+            (unknown_stats_columns != UnknownStatsColumns::Ignore).then(|| {
+                let column_mapping_mode = self.snapshot.column_mapping_mode();
+                let fields = table_schema.fields();
+                StructType::new(
+                    fields.map(|field| field.make_physical_with_mode(column_mapping_mode)),
+                )
+            });
         let it = scan_action_iter(
             engine,
             action_batch_iter,
             self.logical_schema.clone(),
             static_transform,
             physical_predicate,
-        );
+            &partition_columns,
+        )
+        .map(move |scan_metadata| {
+            let scan_metadata = scan_metadata?;
+            if require_stats {
+                ensure_stats_present(&scan_metadata)?;
+            }
+            if let Some(table_schema) = &physical_table_schema {
+                check_stats_columns(&scan_metadata, table_schema, unknown_stats_columns)?;
+            }
+            Ok(scan_metadata)
+        });
+        let it = match self.deterministic_file_order {
+            true => Either::Right(sort_scan_metadata_by_path(it.try_collect()?)?.map(Ok)),
+            false => Either::Left(it),
+        };
+        let it = match self.file_limit {
+            Some(file_limit) => Either::Right(limit_scan_files(it, file_limit)),
+            None => Either::Left(it),
+        };
         Ok(Some(it).into_iter().flatten())
     }
 
@@ -652,6 +1176,71 @@ impl Scan {
         )
     }
 
+    /// Estimate the number of rows this scan will return, without reading any data files. This sums
+    /// the `numRecords` statistic of every file selected by the scan (after data skipping), less the
+    /// number of rows removed by each file's deletion vector.
+    ///
+    /// The result is exact only if every selected file has stats. Files without a `numRecords`
+    /// statistic contribute nothing, so the result is a lower bound in that case -- and a table
+    /// whose files have no stats at all will always report an estimate of zero. Note also that the
+    /// estimate does not account for any rows the engine might filter out by applying the scan's
+    /// predicate to the data itself.
+    pub fn estimated_row_count(&self, engine: &dyn Engine) -> DeltaResult<u64> {
+        #[derive(Default)]
+        struct RowCount {
+            num_rows: u64,
+            files_without_stats: usize,
+        }
+        fn scan_metadata_callback(count: &mut RowCount, scan_file: ScanFile) {
+            let Some(stats) = scan_file.stats else {
+                count.files_without_stats += 1;
+                return;
+            };
+            let deleted_rows = scan_file
+                .dv_info
+                .deletion_vector
+                .map_or(0, |dv| dv.cardinality.try_into().unwrap_or(0));
+            count.num_rows += stats.num_records.saturating_sub(deleted_rows);
+        }
+
+        let mut count = RowCount::default();
+        for scan_metadata in self.scan_metadata(engine)? {
+            count = scan_metadata?.for_each_scan_file(count, scan_metadata_callback)?;
+        }
+        if count.files_without_stats > 0 {
+            debug!(
+                "{} files have no numRecords stat, row count estimate is a lower bound",
+                count.files_without_stats
+            );
+        }
+        Ok(count.num_rows)
+    }
+
+    /// Get the rows deleted by deletion vectors across all files of this scan, as a map from each
+    /// file's path (as it appears in the scan metadata) to the zero-based indexes of its deleted
+    /// rows, in ascending order. Files without a deletion vector have no entry. This is useful for
+    /// engines that want to build a single table-wide deletion mask rather than applying each
+    /// file's selection vector separately.
+    pub fn deleted_rows(&self, engine: &dyn Engine) -> DeltaResult<HashMap<String, Vec<u64>>> {
+        fn scan_metadata_callback(files: &mut Vec<(String, DvInfo)>, scan_file: ScanFile) {
+            if scan_file.dv_info.has_vector() {
+                files.push((scan_file.path, scan_file.dv_info));
+            }
+        }
+
+        let table_root = self.snapshot.table_root();
+        let mut deleted = HashMap::new();
+        for scan_metadata in self.scan_metadata(engine)? {
+            let files = scan_metadata?.for_each_scan_file(vec![], scan_metadata_callback)?;
+            for (path, dv_info) in files {
+                if let Some(row_indexes) = dv_info.get_row_indexes(engine, table_root)? {
+                    deleted.insert(path, row_indexes);
+                }
+            }
+        }
+        Ok(deleted)
+    }
+
     /// Perform an "all in one" scan. This will use the provided `engine` to read and process all
     /// the data for the query. Each [`ScanResult`] in the resultant iterator encapsulates the raw
     /// data and an optional boolean vector built from the deletion vector if it was present. See
@@ -664,27 +1253,8 @@ impl Scan {
         &self,
         engine: Arc<dyn Engine>,
     ) -> DeltaResult<impl Iterator<Item = DeltaResult<ScanResult>> + use<'_>> {
-        struct ScanFile {
-            path: String,
-            size: i64,
-            dv_info: DvInfo,
-            transform: Option<ExpressionRef>,
-        }
-        fn scan_metadata_callback(
-            batches: &mut Vec<ScanFile>,
-            path: &str,
-            size: i64,
-            _: Option<Stats>,
-            dv_info: DvInfo,
-            transform: Option<ExpressionRef>,
-            _: HashMap<String, String>,
-        ) {
-            batches.push(ScanFile {
-                path: path.to_string(),
-                size,
-                dv_info,
-                transform,
-            });
+        fn scan_metadata_callback(batches: &mut Vec<ScanFile>, scan_file: ScanFile) {
+            batches.push(scan_file);
         }
 
         debug!(
@@ -699,7 +1269,7 @@ impl Scan {
             .map(|res| {
                 let scan_metadata = res?;
                 let scan_files = vec![];
-                scan_metadata.visit_scan_files(scan_files, scan_metadata_callback)
+                scan_metadata.for_each_scan_file(scan_files, scan_metadata_callback)
             })
             // Iterator<DeltaResult<Vec<ScanFile>>> to Iterator<DeltaResult<ScanFile>>
             .flatten_ok();
@@ -707,10 +1277,14 @@ impl Scan {
         let result = scan_files_iter
             .map(move |scan_file| -> DeltaResult<_> {
                 let scan_file = scan_file?;
-                let file_path = table_root.join(&scan_file.path)?;
-                let mut selection_vector = scan_file
-                    .dv_info
-                    .get_selection_vector(engine.as_ref(), &table_root)?;
+                let file_path = resolve_file_path(&table_root, &scan_file.path)?;
+                let mut selection_vector = if self.apply_deletion_vectors {
+                    scan_file
+                        .dv_info
+                        .get_selection_vector(engine.as_ref(), &table_root)?
+                } else {
+                    None
+                };
                 let meta = FileMeta {
                     last_modified: 0,
                     size: scan_file.size.try_into().map_err(|_| {
@@ -735,6 +1309,14 @@ impl Scan {
                 let engine = engine.clone();
                 Ok(read_result_iter.map(move |read_result| -> DeltaResult<_> {
                     let read_result = read_result?;
+                    if self.strict_nullability {
+                        state::check_non_nullable_columns(
+                            engine.as_ref(),
+                            read_result.as_ref(),
+                            self.physical_schema(),
+                            selection_vector.as_deref(),
+                        )?;
+                    }
                     // transform the physical data into the correct logical form
                     let logical = state::transform_to_logical(
                         engine.as_ref(),
@@ -761,11 +1343,216 @@ impl Scan {
             // Iterator<DeltaResult<Iterator<DeltaResult<ScanResult>>>> to Iterator<DeltaResult<DeltaResult<ScanResult>>>
             .flatten_ok()
             // Iterator<DeltaResult<DeltaResult<ScanResult>>> to Iterator<DeltaResult<ScanResult>>
-            .map(|x| x?);
+            .map(|x| x?)
+            // re-batch the results (if requested). The selection vector was already split to
+            // match each result above, so each smaller batch gets the matching slice of its mask.
+            .map(move |result| match self.batch_size {
+                Some(batch_size) => result?.split_into_batches(batch_size),
+                None => Ok(vec![result?]),
+            })
+            .flatten_ok();
         Ok(result)
     }
 }
 
+// Returns an error naming the first selected file in `scan_metadata` which has no stats.
+fn ensure_stats_present(scan_metadata: &ScanMetadata) -> DeltaResult<()> {
+    fn scan_metadata_callback(missing_stats: &mut Option<String>, scan_file: ScanFile) {
+        if scan_file.stats.is_none() && missing_stats.is_none() {
+            *missing_stats = Some(scan_file.path);
+        }
+    }
+    match scan_metadata.for_each_scan_file(None, scan_metadata_callback)? {
+        Some(path) => Err(Error::MissingStats(path)),
+        None => Ok(()),
+    }
+}
+
+// Applies the `policy` to each selected file in `scan_metadata` whose stats reference a column
+// that is not in the (physical) `table_schema`.
+fn check_stats_columns(
+    scan_metadata: &ScanMetadata,
+    table_schema: &StructType,
+    policy: UnknownStatsColumns,
+) -> DeltaResult<()> {
+    struct StatsColumnsVisitor<'a> {
+        selection_vector: &'a [bool],
+        table_schema: &'a StructType,
+        policy: UnknownStatsColumns,
+    }
+    impl RowVisitor for StatsColumnsVisitor<'_> {
+        fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+            static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
+                let names = vec![column_name!("path"), column_name!("stats")];
+                (names, vec![DataType::STRING, DataType::STRING]).into()
+            });
+            NAMES_AND_TYPES.as_ref()
+        }
+        fn visit<'a>(
+            &mut self,
+            row_count: usize,
+            getters: &[&'a dyn GetData<'a>],
+        ) -> DeltaResult<()> {
+            for row_index in (0..row_count).filter(|&i| self.selection_vector[i]) {
+                let path: Option<&str> = getters[0].get_opt(row_index, "scanFile.path")?;
+                let stats: Option<&str> = getters[1].get_opt(row_index, "scanFile.stats")?;
+                let (Some(path), Some(stats)) = (path, stats) else {
+                    continue;
+                };
+                // Invalid stats are already reported (and ignored) when the files are visited
+                let Ok(stats) = serde_json::from_str::<serde_json::Value>(stats) else {
+                    continue;
+                };
+                let Some(column) = unknown_stats_column(&stats, self.table_schema) else {
+                    continue;
+                };
+                match self.policy {
+                    UnknownStatsColumns::Ignore => {}
+                    UnknownStatsColumns::Warn => {
+                        warn!("Stats of file {path} reference unknown column {column}")
+                    }
+                    UnknownStatsColumns::Error => {
+                        return Err(Error::schema(format!(
+                            "Stats of file {path} reference column {column}, which is not in \
+                            the table schema"
+                        )));
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let mut visitor = StatsColumnsVisitor {
+        selection_vector: &scan_metadata.scan_files.selection_vector,
+        table_schema,
+        policy,
+    };
+    visitor.visit_rows_of(scan_metadata.scan_files.data.as_ref())
+}
+
+// Returns the first column referenced by the `minValues`, `maxValues` or `nullCount` of `stats`
+// which is not in `schema`.
+fn unknown_stats_column(stats: &serde_json::Value, schema: &StructType) -> Option<ColumnName> {
+    fn find_unknown(
+        values: &serde_json::Map<String, serde_json::Value>,
+        schema: &StructType,
+        path: &[&str],
+    ) -> Option<ColumnName> {
+        values.iter().find_map(|(name, value)| {
+            let path = || path.iter().copied().chain([name.as_str()]);
+            let Some(field) = schema.field(name) else {
+                return Some(ColumnName::new(path()));
+            };
+            match (value, field.data_type()) {
+                (serde_json::Value::Object(values), DataType::Struct(schema)) => {
+                    find_unknown(values, schema, &path().collect_vec())
+                }
+                _ => None,
+            }
+        })
+    }
+    ["minValues", "maxValues", "nullCount"]
+        .into_iter()
+        .filter_map(|key| stats.get(key)?.as_object())
+        .find_map(|values| find_unknown(values, schema, &[]))
+}
+
+// Deselects all files of `scan_metadata` past the first `file_limit` selected ones, and stops
+// iterating (and thus replaying the log) as soon as the limit is reached.
+fn limit_scan_files(
+    mut scan_metadata: impl Iterator<Item = DeltaResult<ScanMetadata>>,
+    file_limit: usize,
+) -> impl Iterator<Item = DeltaResult<ScanMetadata>> {
+    let mut remaining = file_limit;
+    // The remaining count is checked before pulling the next batch, so that no batch is requested
+    // once the limit is reached.
+    std::iter::from_fn(move || {
+        if remaining == 0 {
+            return None;
+        }
+        let mut scan_metadata = match scan_metadata.next()? {
+            Ok(scan_metadata) => scan_metadata,
+            Err(err) => return Some(Err(err)),
+        };
+        // Rows missing from the end of the selection vector are selected
+        let scan_files = &mut scan_metadata.scan_files;
+        scan_files
+            .selection_vector
+            .resize(scan_files.data.len(), true);
+        for selected in scan_files.selection_vector.iter_mut().filter(|s| **s) {
+            match remaining {
+                0 => *selected = false,
+                _ => remaining -= 1,
+            }
+        }
+        Some(Ok(scan_metadata))
+    })
+}
+
+// Reorders the selected files of `scan_metadata` by path. Consecutive (sorted) files which are
+// also in order within the same batch are sliced out of that batch as a single `ScanMetadata`.
+fn sort_scan_metadata_by_path(
+    scan_metadata: Vec<ScanMetadata>,
+) -> DeltaResult<impl Iterator<Item = ScanMetadata>> {
+    struct SelectedPathsVisitor<'a> {
+        selection_vector: &'a [bool],
+        batch_index: usize,
+        // (path, batch index, row index) of each selected file
+        files: &'a mut Vec<(String, usize, usize)>,
+    }
+    impl RowVisitor for SelectedPathsVisitor<'_> {
+        fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+            static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> =
+                LazyLock::new(|| (vec![column_name!("path")], vec![DataType::STRING]).into());
+            NAMES_AND_TYPES.as_ref()
+        }
+        fn visit<'a>(
+            &mut self,
+            row_count: usize,
+            getters: &[&'a dyn GetData<'a>],
+        ) -> DeltaResult<()> {
+            for row_index in (0..row_count).filter(|&i| self.selection_vector[i]) {
+                if let Some(path) = getters[0].get_opt(row_index, "scanFile.path")? {
+                    self.files.push((path, self.batch_index, row_index));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let mut files = vec![];
+    for (batch_index, batch) in scan_metadata.iter().enumerate() {
+        let mut visitor = SelectedPathsVisitor {
+            selection_vector: &batch.scan_files.selection_vector,
+            batch_index,
+            files: &mut files,
+        };
+        visitor.visit_rows_of(batch.scan_files.data.as_ref())?;
+    }
+    files.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+    let runs = files
+        .chunk_by(|(_, batch_a, row_a), (_, batch_b, row_b)| batch_a == batch_b && row_a < row_b);
+    let sorted: Vec<_> = runs
+        .map(|run| -> DeltaResult<_> {
+            let (_, batch_index, start) = run[0];
+            let end = run[run.len() - 1].2 + 1;
+            let batch = &scan_metadata[batch_index];
+            let mut selection_vector = vec![false; end - start];
+            for (_, _, row_index) in run {
+                selection_vector[row_index - start] = true;
+            }
+            let transforms = (start..end)
+                .map(|i| get_transform_for_row(i, &batch.scan_file_transforms))
+                .collect();
+            let data = batch.scan_files.data.slice(start, end - start)?;
+            Ok(ScanMetadata::new(data, selection_vector, transforms))
+        })
+        .try_collect()?;
+    Ok(sorted.into_iter())
+}
+
 /// Get the schema that scan rows (from [`Scan::scan_metadata`]) will be returned with.
 ///
 /// It is:
@@ -784,7 +1571,10 @@ impl Scan {
 ///    },
 ///    fileConstantValues: {
 ///      partitionValues: map<string, string>
-///    }
+///    },
+///    tags: map<string, string>,
+///    baseRowId: long,
+///    defaultRowCommitVersion: long
 /// }
 /// ```
 pub fn scan_row_schema() -> SchemaRef {
@@ -815,7 +1605,11 @@ struct StateInfo {
 }
 
 /// Get the state needed to process a scan, see [`StateInfo`] for details.
-fn get_state_info(logical_schema: &Schema, partition_columns: &[String]) -> DeltaResult<StateInfo> {
+fn get_state_info(
+    logical_schema: &Schema,
+    partition_columns: &[String],
+    column_mapping_mode: ColumnMappingMode,
+) -> DeltaResult<StateInfo> {
     let mut have_partition_cols = false;
     let mut read_fields = Vec::with_capacity(logical_schema.fields.len());
     // Loop over all selected fields and note if they are columns that will be read from the
@@ -831,10 +1625,17 @@ fn get_state_info(logical_schema: &Schema, partition_columns: &[String]) -> Delt
                 // data type, which we need to properly materialize the column.
                 have_partition_cols = true;
                 Ok(ColumnType::Partition(index))
+            } else if let Some((column_type, read_field)) =
+                get_nested_partition_column(logical_field, logical_field.name(), partition_columns)
+            {
+                // Only read the non-partition fields of the struct, the rest are filled in later
+                have_partition_cols = true;
+                read_fields.push(read_field.make_physical_with_mode(column_mapping_mode));
+                Ok(column_type)
             } else {
                 // Add to read schema, store field so we can build a `Column` expression later
                 // if needed (i.e. if we have partition columns)
-                let physical_field = logical_field.make_physical();
+                let physical_field = logical_field.make_physical_with_mode(column_mapping_mode);
                 debug!("\n\n{logical_field:#?}\nAfter mapping: {physical_field:#?}\n\n");
                 let physical_name = physical_field.name.clone();
                 read_fields.push(physical_field);
@@ -849,6 +1650,48 @@ fn get_state_info(logical_schema: &Schema, partition_columns: &[String]) -> Delt
     })
 }
 
+/// If `logical_field` is a struct containing nested partition columns, i.e. `partition_columns`
+/// holds dotted paths beneath `logical_path` (the field's own dotted path), returns its
+/// [`ColumnType::Nested`] along with the (logical) field to read from the parquet file, which only
+/// keeps the struct's non-partition fields. Returns `None` if the field has no nested partition
+/// columns.
+pub(crate) fn get_nested_partition_column(
+    logical_field: &StructField,
+    logical_path: &str,
+    partition_columns: &[String],
+) -> Option<(ColumnType, StructField)> {
+    let DataType::Struct(struct_type) = logical_field.data_type() else {
+        return None;
+    };
+    let prefix = format!("{logical_path}.");
+    if !partition_columns.iter().any(|col| col.starts_with(&prefix)) {
+        return None;
+    }
+    let mut read_fields = vec![];
+    let nested_fields = struct_type
+        .fields()
+        .enumerate()
+        .map(|(index, field)| {
+            let path = format!("{prefix}{}", field.name());
+            if partition_columns.contains(&path) {
+                ColumnType::Partition(index)
+            } else if let Some((column_type, read_field)) =
+                get_nested_partition_column(field, &path, partition_columns)
+            {
+                read_fields.push(read_field);
+                column_type
+            } else {
+                read_fields.push(field.clone());
+                ColumnType::Selected(field.physical_name().to_string())
+            }
+        })
+        .collect();
+    let column_type = ColumnType::Nested(logical_field.physical_name().to_string(), nested_fields);
+    let mut read_field = logical_field.clone();
+    read_field.data_type = DataType::struct_type(read_fields);
+    Some((column_type, read_field))
+}
+
 pub fn selection_vector(
     engine: &dyn Engine,
     descriptor: &DeletionVectorDescriptor,
@@ -879,7 +1722,7 @@ pub(crate) mod test_utils {
         JsonHandler,
     };
 
-    use super::{state::ScanCallback, Transform};
+    use super::{state::ScanFileCallback, Transform};
 
     // Generates a batch of sidecar actions with the given paths.
     // The schema is provided as null columns affect equality checks.
@@ -960,6 +1803,45 @@ pub(crate) mod test_utils {
         ArrowEngineData::try_from_engine_data(parsed).unwrap()
     }
 
+    // Generates a batch with the `date`-partitioned table metadata followed by one add action per
+    // given (raw, unparsed) partition value.
+    pub(crate) fn add_batch_with_partition_values(dates: &[&str]) -> Box<ArrowEngineData> {
+        let adds = dates.iter().enumerate().map(|(i, date)| {
+            format!(
+                r#"{{"add":{{"path":"part-0000{i}.snappy.parquet","partitionValues":{{"date":"{date}"}},"size":635,"modificationTime":1677811178336,"dataChange":true}}}}"#
+            )
+        });
+        partitioned_batch(adds)
+    }
+
+    // Like `add_batch_with_partition_values`, but each add action also has stats with the given
+    // (min, max) range of the `value` column.
+    pub(crate) fn add_batch_with_partition_values_and_stats(
+        files: &[(&str, (i32, i32))],
+    ) -> Box<ArrowEngineData> {
+        let adds = files.iter().enumerate().map(|(i, (date, (min, max)))| {
+            format!(
+                r#"{{"add":{{"path":"part-0000{i}.snappy.parquet","partitionValues":{{"date":"{date}"}},"size":635,"modificationTime":1677811178336,"dataChange":true,"stats":"{{\"numRecords\":10,\"minValues\":{{\"value\":{min}}},\"maxValues\":{{\"value\":{max}}},\"nullCount\":{{\"value\":0}}}}"}}}}"#
+            )
+        });
+        partitioned_batch(adds)
+    }
+
+    fn partitioned_batch(adds: impl Iterator<Item = String>) -> Box<ArrowEngineData> {
+        let handler = SyncJsonHandler {};
+        let mut json_strings = vec![r#"{"metaData":{"id":"testId","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"value\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}},{\"name\":\"date\",\"type\":\"date\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":["date"],"configuration":{},"createdTime":1677811175819}}"#.to_string()];
+        json_strings.extend(adds);
+        let json_strings: StringArray =
+            json_strings.iter().map(|s| s.as_str()).collect_vec().into();
+        let parsed = handler
+            .parse_json(
+                string_array_to_engine_data(json_strings),
+                get_log_schema().clone(),
+            )
+            .unwrap();
+        ArrowEngineData::try_from_engine_data(parsed).unwrap()
+    }
+
     /// Create a scan action iter and validate what's called back. If you pass `None` as
     /// `logical_schema`, `transform` should also be `None`
     #[allow(clippy::vec_box)]
@@ -969,7 +1851,7 @@ pub(crate) mod test_utils {
         transform: Option<Arc<Transform>>,
         expected_sel_vec: &[bool],
         context: T,
-        validate_callback: ScanCallback<T>,
+        validate_callback: ScanFileCallback<T>,
     ) {
         let logical_schema =
             logical_schema.unwrap_or_else(|| Arc::new(crate::schema::StructType::new(vec![])));
@@ -981,13 +1863,14 @@ pub(crate) mod test_utils {
             logical_schema,
             transform,
             None,
+            &[],
         );
         let mut batch_count = 0;
         for res in iter {
             let scan_metadata = res.unwrap();
             assert_eq!(scan_metadata.scan_files.selection_vector, expected_sel_vec);
             scan_metadata
-                .visit_scan_files(context.clone(), validate_callback)
+                .for_each_scan_file(context.clone(), validate_callback)
                 .unwrap();
             batch_count += 1;
         }
@@ -999,8 +1882,9 @@ pub(crate) mod test_utils {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::arrow::array::BooleanArray;
+    use crate::arrow::array::{AsArray as _, BooleanArray};
     use crate::arrow::compute::filter_record_batch;
+    use crate::arrow::datatypes::Int32Type;
     use crate::arrow::record_batch::RecordBatch;
     use crate::engine::arrow_data::ArrowEngineData;
     use crate::engine::sync::SyncEngine;
@@ -1077,6 +1961,7 @@ mod tests {
             (Pred::literal(true), Some(PhysicalPredicate::None)),
             (Pred::literal(false), Some(PhysicalPredicate::StaticSkipAll)),
             (column_pred!("x"), None), // no such column
+            (Pred::and(column_pred!("x"), Pred::literal(false)), None), // no such column
             (
                 column_pred!("a"),
                 Some(PhysicalPredicate::Some(
@@ -1177,22 +2062,14 @@ mod tests {
 
     fn get_files_for_scan(scan: Scan, engine: &dyn Engine) -> DeltaResult<Vec<String>> {
         let scan_metadata_iter = scan.scan_metadata(engine)?;
-        fn scan_metadata_callback(
-            paths: &mut Vec<String>,
-            path: &str,
-            _size: i64,
-            _: Option<Stats>,
-            dv_info: DvInfo,
-            _transform: Option<ExpressionRef>,
-            _partition_values: HashMap<String, String>,
-        ) {
-            paths.push(path.to_string());
-            assert!(dv_info.deletion_vector.is_none());
+        fn scan_metadata_callback(paths: &mut Vec<String>, scan_file: ScanFile) {
+            paths.push(scan_file.path);
+            assert!(scan_file.dv_info.deletion_vector.is_none());
         }
         let mut files = vec![];
         for res in scan_metadata_iter {
             let scan_metadata = res?;
-            files = scan_metadata.visit_scan_files(files, scan_metadata_callback)?;
+            files = scan_metadata.for_each_scan_file(files, scan_metadata_callback)?;
         }
         Ok(files)
     }
@@ -1214,6 +2091,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_identity_transform() {
+        let engine = SyncEngine::new();
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-without-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let snapshot = Snapshot::try_new(url, &engine, None).unwrap();
+        let scan = snapshot.into_scan_builder().build().unwrap();
+        assert!(scan.is_identity_transform());
+        assert!(scan.transform_columns().is_empty());
+        let transforms = scan
+            .scan_metadata(&engine)
+            .unwrap()
+            .map(|res| res.unwrap().scan_file_transforms)
+            .concat();
+        assert!(transforms.iter().all(Option::is_none));
+
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let snapshot = Snapshot::try_new(url, &engine, None).unwrap();
+        let scan = snapshot.into_scan_builder().build().unwrap();
+        assert!(!scan.is_identity_transform());
+        assert_eq!(scan.transform_columns(), vec![column_name!("letter")]);
+    }
+
     #[test_log::test]
     fn test_scan_metadata() {
         let path =
@@ -1230,6 +2132,291 @@ mod tests {
         assert_eq!(num_rows, 10)
     }
 
+    #[test]
+    fn test_scan_with_batch_size() {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = Arc::new(SyncEngine::new());
+        let snapshot = Arc::new(Snapshot::try_new(url, engine.as_ref(), None).unwrap());
+
+        // Read the table, returning the surviving values of each batch along with the batch sizes
+        let read_table = |scan: Scan| -> (Vec<usize>, Vec<i32>) {
+            let mut batch_sizes = vec![];
+            let mut values = vec![];
+            for result in scan.execute(engine.clone()).unwrap() {
+                let result = result.unwrap();
+                let mask = result.full_mask();
+                let batch: RecordBatch =
+                    ArrowEngineData::try_from_engine_data(result.raw_data.unwrap())
+                        .unwrap()
+                        .into();
+                batch_sizes.push(batch.num_rows());
+                let batch = match mask {
+                    Some(mask) => filter_record_batch(&batch, &mask.into()).unwrap(),
+                    None => batch,
+                };
+                let column = batch.column(0).as_primitive::<Int32Type>();
+                values.extend(column.iter().flatten());
+            }
+            values.sort();
+            (batch_sizes, values)
+        };
+
+        let (batch_sizes, expected) = read_table(snapshot.clone().scan_builder().build().unwrap());
+        assert_eq!(batch_sizes, [10]);
+        assert_eq!(expected, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let scan = snapshot.clone().scan_builder().with_batch_size(3);
+        let (batch_sizes, values) = read_table(scan.build().unwrap());
+        assert_eq!(batch_sizes, [3, 3, 3, 1]);
+        assert_eq!(values, expected);
+
+        // A batch size larger than the data leaves the batches untouched
+        let scan = snapshot.clone().scan_builder().with_batch_size(100);
+        let (batch_sizes, values) = read_table(scan.build().unwrap());
+        assert_eq!(batch_sizes, [10]);
+        assert_eq!(values, expected);
+
+        snapshot
+            .scan_builder()
+            .with_batch_size(0)
+            .build()
+            .expect_err("zero batch size");
+    }
+
+    #[test]
+    fn test_scan_without_applying_deletion_vectors() {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = Arc::new(SyncEngine::new());
+        let snapshot = Arc::new(Snapshot::try_new(url, engine.as_ref(), None).unwrap());
+
+        let scan = snapshot
+            .scan_builder()
+            .with_apply_deletion_vectors(false)
+            .build()
+            .unwrap();
+        assert!(!scan.apply_deletion_vectors());
+
+        // All rows of the file are returned, and no mask is provided
+        let mut values = vec![];
+        for result in scan.execute(engine.clone()).unwrap() {
+            let result = result.unwrap();
+            assert!(result.raw_mask().is_none());
+            let batch: RecordBatch =
+                ArrowEngineData::try_from_engine_data(result.raw_data.unwrap())
+                    .unwrap()
+                    .into();
+            values.extend(batch.column(0).as_primitive::<Int32Type>().iter().flatten());
+        }
+        values.sort();
+        assert_eq!(values, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        // The deletion vector is still available from the scan metadata
+        fn scan_metadata_callback(cardinalities: &mut Vec<i64>, scan_file: ScanFile) {
+            let dv = scan_file.dv_info.deletion_vector;
+            cardinalities.extend(dv.map(|dv| dv.cardinality));
+        }
+        let mut cardinalities = vec![];
+        for scan_metadata in scan.scan_metadata(engine.as_ref()).unwrap() {
+            cardinalities = scan_metadata
+                .unwrap()
+                .for_each_scan_file(cardinalities, scan_metadata_callback)
+                .unwrap();
+        }
+        assert_eq!(cardinalities, [2]);
+    }
+
+    #[test]
+    fn test_scan_with_deterministic_file_order() {
+        // Version 1 adds 3 files to the 3 files of version 0, so log replay emits the files of the
+        // second commit before those of the first
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = Arc::new(SyncEngine::new());
+        let snapshot = Arc::new(Snapshot::try_new(url, engine.as_ref(), None).unwrap());
+
+        fn scan_metadata_callback(paths: &mut Vec<String>, scan_file: ScanFile) {
+            paths.push(scan_file.path);
+        }
+        // The paths of the files in each scan metadata batch
+        let scan_paths = |deterministic_file_order| -> Vec<Vec<String>> {
+            let scan = ScanBuilder::new(snapshot.clone())
+                .with_deterministic_file_order(deterministic_file_order)
+                .build()
+                .unwrap();
+            assert_eq!(scan.deterministic_file_order(), deterministic_file_order);
+            scan.scan_metadata(engine.as_ref())
+                .unwrap()
+                .map(|res| {
+                    res.unwrap()
+                        .for_each_scan_file(vec![], scan_metadata_callback)
+                })
+                .try_collect()
+                .unwrap()
+        };
+
+        let unordered = scan_paths(false).concat();
+        assert!(!unordered.is_sorted());
+        let ordered = scan_paths(true);
+        assert_eq!(ordered, scan_paths(true));
+        let ordered = ordered.concat();
+        assert!(ordered.is_sorted());
+        assert_eq!(ordered, unordered.into_iter().sorted().collect_vec());
+
+        // The data read by the scan follows the same order
+        let scan = ScanBuilder::new(snapshot)
+            .with_deterministic_file_order(true)
+            .build()
+            .unwrap();
+        let read_batches = || -> Vec<RecordBatch> {
+            scan.execute(engine.clone())
+                .unwrap()
+                .map(|result| {
+                    let data = result.unwrap().into_filtered_data().unwrap();
+                    ArrowEngineData::try_from_engine_data(data).unwrap().into()
+                })
+                .collect()
+        };
+        let batches = read_batches();
+        assert_eq!(batches.len(), 6);
+        assert_eq!(batches, read_batches());
+    }
+
+    #[test]
+    fn test_scan_with_file_limit() {
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = Arc::new(SyncEngine::new());
+        let snapshot = Arc::new(Snapshot::try_new(url, engine.as_ref(), None).unwrap());
+        let scan_files = |predicate: Option<PredicateRef>, file_limit: Option<usize>| {
+            let mut builder = ScanBuilder::new(snapshot.clone()).with_predicate(predicate);
+            if let Some(file_limit) = file_limit {
+                builder = builder.with_file_limit(file_limit);
+            }
+            let scan = builder.build().unwrap();
+            assert_eq!(scan.file_limit(), file_limit);
+            get_files_for_scan(scan, engine.as_ref()).unwrap()
+        };
+
+        let all_files = scan_files(None, None);
+        assert_eq!(all_files.len(), 6);
+        for file_limit in [0, 1, 4, 6, 10] {
+            let files = scan_files(None, Some(file_limit));
+            assert_eq!(files, all_files[..file_limit.min(6)]);
+        }
+
+        // The limit applies to the files that survive partition pruning
+        let predicate = Arc::new(column_expr!("letter").eq(Expr::literal("a")));
+        let pruned_files = scan_files(Some(predicate.clone()), None);
+        assert_eq!(pruned_files.len(), 2);
+        let files = scan_files(Some(predicate), Some(1));
+        assert_eq!(files, pruned_files[..1]);
+
+        // The data read by the scan is a prefix of the full scan's data
+        let read_batches = |scan: Scan| -> Vec<RecordBatch> {
+            scan.execute(engine.clone())
+                .unwrap()
+                .map(|result| {
+                    let data = result.unwrap().into_filtered_data().unwrap();
+                    ArrowEngineData::try_from_engine_data(data).unwrap().into()
+                })
+                .collect()
+        };
+        let all_batches = read_batches(ScanBuilder::new(snapshot.clone()).build().unwrap());
+        let scan = ScanBuilder::new(snapshot.clone())
+            .with_file_limit(2)
+            .build()
+            .unwrap();
+        let batches = read_batches(scan);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches, all_batches[..2]);
+
+        // No more batches are pulled once the limit is reached
+        let scan = ScanBuilder::new(snapshot).build().unwrap();
+        let scan_metadata: Vec<_> = scan
+            .scan_metadata(engine.as_ref())
+            .unwrap()
+            .try_collect()
+            .unwrap();
+        assert!(scan_metadata.len() > 1);
+        let first_batch_files = scan_metadata[0]
+            .scan_files
+            .selection_vector
+            .iter()
+            .filter(|s| **s)
+            .count();
+        let mut pulled = 0;
+        let limited = scan_metadata.into_iter().inspect(|_| pulled += 1).map(Ok);
+        let limited: Vec<_> = limit_scan_files(limited, first_batch_files)
+            .try_collect()
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(pulled, 1);
+    }
+
+    #[test]
+    fn test_estimated_row_count() {
+        let engine = Arc::new(SyncEngine::new());
+        let scan_table = |table: &str| {
+            let path = std::fs::canonicalize(PathBuf::from(table)).unwrap();
+            let url = url::Url::from_directory_path(path).unwrap();
+            let snapshot = Snapshot::try_new(url, engine.as_ref(), None).unwrap();
+            snapshot.into_scan_builder().build().unwrap()
+        };
+
+        // numRecords is 10 and the deletion vector removes 2 rows, which matches the rows we read
+        let scan = scan_table("./tests/data/table-with-dv-small/");
+        let mut scanned_rows = 0;
+        for result in scan.execute(engine.clone()).unwrap() {
+            let result = result.unwrap();
+            scanned_rows += match result.full_mask() {
+                Some(mask) => mask.iter().filter(|&&keep| keep).count(),
+                None => result.raw_data.unwrap().len(),
+            };
+        }
+        assert_eq!(scanned_rows, 8);
+        assert_eq!(scan.estimated_row_count(engine.as_ref()).unwrap(), 8);
+
+        let scan = scan_table("./tests/data/table-without-dv-small/");
+        assert_eq!(scan.estimated_row_count(engine.as_ref()).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_scan_result_into_filtered_data() {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = Arc::new(SyncEngine::new());
+        let snapshot = Arc::new(Snapshot::try_new(url, engine.as_ref(), None).unwrap());
+
+        // use a batch size so that the deletion vector mask spans multiple results
+        let scan = snapshot.scan_builder().with_batch_size(3).build().unwrap();
+        let mut num_rows = 0;
+        for result in scan.execute(engine.clone()).unwrap() {
+            let result = result.unwrap();
+            // copy the raw data, so we can compare against manually applying the mask
+            let raw_data = result.raw_data.as_ref().unwrap();
+            let raw_data = raw_data.slice(0, raw_data.len()).unwrap();
+            let batch: RecordBatch = ArrowEngineData::try_from_engine_data(raw_data)
+                .unwrap()
+                .into();
+            let expected = match result.full_mask() {
+                Some(mask) => filter_record_batch(&batch, &mask.into()).unwrap(),
+                None => batch,
+            };
+            let filtered: RecordBatch =
+                ArrowEngineData::try_from_engine_data(result.into_filtered_data().unwrap())
+                    .unwrap()
+                    .into();
+            assert_eq!(filtered, expected);
+            num_rows += filtered.num_rows();
+        }
+        assert_eq!(num_rows, 8);
+    }
+
     #[test_log::test]
     fn test_scan_metadata_from_same_version() {
         let path =
@@ -1444,10 +2631,20 @@ mod tests {
         // Predicate over a logically missing column fails the scan
         let predicate = Arc::new(column_expr!("numeric.ints.invalid").lt(Expr::literal(1000)));
         snapshot
+            .clone()
             .scan_builder()
             .with_predicate(predicate)
             .build()
             .expect_err("unknown column");
+
+        // ... even if the predicate statically skips all files
+        let predicate = Arc::new(Pred::and(
+            column_expr!("numeric.ints.invalid").lt(Expr::literal(1000)),
+            Pred::literal(false),
+        ));
+        let result = snapshot.scan_builder().with_predicate(predicate).build();
+        let err = result.expect_err("unknown column");
+        assert!(err.to_string().contains("numeric.ints.invalid"));
     }
 
     #[test_log::test]
@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use std::sync::LazyLock;
 
 use crate::actions::deletion_vector::deletion_treemap_to_bools;
+use crate::actions::visitors::SelectionVectorVisitor;
+use crate::expressions::Predicate;
 use crate::scan::get_transform_for_row;
 use crate::schema::Schema;
 use crate::utils::require;
@@ -113,6 +115,41 @@ pub fn transform_to_logical(
     }
 }
 
+/// Returns an error if any top-level column that `physical_schema` declares non-nullable contains a
+/// NULL value in `physical_data`. Parquet writers often mark columns nullable even when the table
+/// schema does not, so reading such a file does not by itself catch data that violates the schema.
+/// Rows deleted by `selection_vector` (if any) are not checked. As elsewhere, rows past the end of
+/// the selection vector are selected.
+pub(crate) fn check_non_nullable_columns(
+    engine: &dyn Engine,
+    physical_data: &dyn EngineData,
+    physical_schema: &SchemaRef,
+    selection_vector: Option<&[bool]>,
+) -> DeltaResult<()> {
+    let is_selected = |row: usize| selection_vector.is_none_or(|sv| sv.get(row) != Some(&false));
+    let non_nullable_columns = physical_schema
+        .fields()
+        .filter(|field| !field.is_nullable() && !field.is_row_index());
+    for field in non_nullable_columns {
+        let predicate = Predicate::is_null(ColumnName::new([field.name()]));
+        let mut visitor = SelectionVectorVisitor::default();
+        let is_null = engine
+            .evaluation_handler()
+            .new_predicate_evaluator(physical_schema.clone(), predicate)
+            .evaluate(physical_data)?;
+        visitor.visit_rows_of(is_null.as_ref())?;
+        let mut is_null = visitor.selection_vector.iter().enumerate();
+        require!(
+            !is_null.any(|(row, &is_null)| is_null && is_selected(row)),
+            Error::invalid_struct_data(format!(
+                "Non-nullable column {} contains null values",
+                field.name()
+            ))
+        );
+    }
+    Ok(())
+}
+
 pub type ScanCallback<T> = fn(
     context: &mut T,
     path: &str,
@@ -136,6 +173,10 @@ pub type ScanCallback<T> = fn(
 ///   convert it to the correct logical format
 /// * `partition_values`: a `HashMap<String, String>` which are partition values
 ///
+/// See also [`ScanMetadata::for_each_scan_file`], which passes all of a file's metadata (including
+/// fields not passed here, such as its tags and row tracking fields) to the callback as a single
+/// [`ScanFile`].
+///
 /// ## Context
 /// A note on the `context`. This can be any value the engine wants. This function takes ownership
 /// of the passed arg, but then returns it, so the engine can repeatedly call `visit_scan_files`
@@ -154,6 +195,43 @@ pub type ScanCallback<T> = fn(
 /// ```
 impl ScanMetadata {
     pub fn visit_scan_files<T>(&self, context: T, callback: ScanCallback<T>) -> DeltaResult<T> {
+        self.visit_scan_files_impl(context, |context, scan_file| {
+            callback(
+                context,
+                &scan_file.path,
+                scan_file.size,
+                scan_file.stats,
+                scan_file.dv_info,
+                scan_file.transform,
+                scan_file.partition_values,
+            )
+        })
+    }
+
+    /// Same as [`Self::visit_scan_files`], except that the callback receives each file as a
+    /// [`ScanFile`], which carries all of the file's metadata.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let mut paths = vec![];
+    /// for res in scan_metadata_iter { // scan metadata iterator from scan.scan_metadata()
+    ///     let scan_metadata = res?;
+    ///     paths = scan_metadata.for_each_scan_file(paths, |paths, file| paths.push(file.path))?;
+    /// }
+    /// ```
+    pub fn for_each_scan_file<T>(
+        &self,
+        context: T,
+        callback: ScanFileCallback<T>,
+    ) -> DeltaResult<T> {
+        self.visit_scan_files_impl(context, callback)
+    }
+
+    fn visit_scan_files_impl<T>(
+        &self,
+        context: T,
+        callback: impl FnMut(&mut T, ScanFile),
+    ) -> DeltaResult<T> {
         let mut visitor = ScanFileVisitor {
             callback,
             selection_vector: &self.scan_files.selection_vector,
@@ -164,14 +242,46 @@ impl ScanMetadata {
         Ok(visitor.context)
     }
 }
+
+/// A file that needs to be read for a scan, as passed to a [`ScanFileCallback`].
+#[derive(Debug, Clone)]
+pub struct ScanFile {
+    /// The path to the file
+    pub path: String,
+    /// The size of the file
+    pub size: i64,
+    /// The statistics of the file, if present and valid
+    pub stats: Option<Stats>,
+    /// A [`DvInfo`] struct, which allows getting the selection vector for this file
+    pub dv_info: DvInfo,
+    /// An optional expression that, if present, _must_ be applied to physical data to convert it to
+    /// the correct logical format
+    pub transform: Option<ExpressionRef>,
+    /// The partition values of the file
+    pub partition_values: HashMap<String, String>,
+    /// The engine-specific tags of the file's add action, which is empty if the add action has no
+    /// tags
+    pub tags: HashMap<String, String>,
+    /// The `baseRowId` of the file's add action, used to compute row ids when the table has row
+    /// tracking enabled. `None` if the add action has no `baseRowId`
+    pub base_row_id: Option<i64>,
+    /// The `defaultRowCommitVersion` of the file's add action, which is the row commit version of
+    /// rows in the file that have none. `None` if the add action does not have one
+    pub default_row_commit_version: Option<i64>,
+}
+
+/// A callback that is invoked with each [`ScanFile`] of a [`ScanMetadata`], see
+/// [`ScanMetadata::for_each_scan_file`].
+pub type ScanFileCallback<T> = fn(context: &mut T, scan_file: ScanFile);
+
 // add some visitor magic for engines
-struct ScanFileVisitor<'a, T> {
-    callback: ScanCallback<T>,
+struct ScanFileVisitor<'a, T, F> {
+    callback: F,
     selection_vector: &'a [bool],
     transforms: &'a [Option<ExpressionRef>],
     context: T,
 }
-impl<T> RowVisitor for ScanFileVisitor<'_, T> {
+impl<T, F: FnMut(&mut T, ScanFile)> RowVisitor for ScanFileVisitor<'_, T, F> {
     fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
         static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> =
             LazyLock::new(|| SCAN_ROW_SCHEMA.leaves(None));
@@ -179,7 +289,7 @@ impl<T> RowVisitor for ScanFileVisitor<'_, T> {
     }
     fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
         require!(
-            getters.len() == 10,
+            getters.len() == 13,
             Error::InternalError(format!(
                 "Wrong number of ScanFileVisitor getters: {}",
                 getters.len()
@@ -210,15 +320,22 @@ impl<T> RowVisitor for ScanFileVisitor<'_, T> {
                 let dv_info = DvInfo { deletion_vector };
                 let partition_values =
                     getters[9].get(row_index, "scanFile.fileConstantValues.partitionValues")?;
-                (self.callback)(
-                    &mut self.context,
+                let tags: Option<_> = getters[10].get_opt(row_index, "scanFile.tags")?;
+                let base_row_id = getters[11].get_opt(row_index, "scanFile.baseRowId")?;
+                let default_row_commit_version =
+                    getters[12].get_opt(row_index, "scanFile.defaultRowCommitVersion")?;
+                let scan_file = ScanFile {
                     path,
                     size,
                     stats,
                     dv_info,
-                    get_transform_for_row(row_index, self.transforms),
+                    transform: get_transform_for_row(row_index, self.transforms),
                     partition_values,
-                )
+                    tags: tags.unwrap_or_default(),
+                    base_row_id,
+                    default_row_commit_version,
+                };
+                (self.callback)(&mut self.context, scan_file)
             }
         }
         Ok(())
@@ -227,41 +344,51 @@ impl<T> RowVisitor for ScanFileVisitor<'_, T> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
     use crate::actions::get_log_schema;
     use crate::scan::test_utils::{add_batch_simple, run_with_validate_callback};
-    use crate::ExpressionRef;
 
-    use super::{DvInfo, Stats};
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use url::Url;
+
+    use crate::actions::deletion_vector::DeletionVectorDescriptor;
+    use crate::engine::sync::SyncEngine;
+    use crate::{
+        DeltaResult, Engine, EvaluationHandler, FileMeta, FileSlice, JsonHandler, ParquetHandler,
+        StorageHandler,
+    };
+
+    use super::{DvInfo, ScanFile};
 
     #[derive(Clone)]
     struct TestContext {
         id: usize,
     }
 
-    fn validate_visit(
-        context: &mut TestContext,
-        path: &str,
-        size: i64,
-        stats: Option<Stats>,
-        dv_info: DvInfo,
-        transform: Option<ExpressionRef>,
-        part_vals: HashMap<String, String>,
-    ) {
+    fn validate_visit(context: &mut TestContext, scan_file: ScanFile) {
         assert_eq!(
-            path,
+            scan_file.path,
             "part-00000-fae5310a-a37d-4e51-827b-c3d5516560ca-c000.snappy.parquet"
         );
-        assert_eq!(size, 635);
-        assert!(stats.is_some());
-        assert_eq!(stats.as_ref().unwrap().num_records, 10);
+        assert_eq!(scan_file.size, 635);
+        assert!(scan_file.stats.is_some());
+        assert_eq!(scan_file.stats.as_ref().unwrap().num_records, 10);
+        let part_vals = &scan_file.partition_values;
         assert_eq!(part_vals.get("date"), Some(&"2017-12-10".to_string()));
         assert_eq!(part_vals.get("non-existent"), None);
-        assert!(dv_info.deletion_vector.is_some());
-        let dv = dv_info.deletion_vector.unwrap();
+        assert_eq!(scan_file.tags.len(), 4);
+        assert_eq!(
+            scan_file.tags.get("OPTIMIZE_TARGET_SIZE"),
+            Some(&"268435456".to_string())
+        );
+        // the add action has no row tracking fields
+        assert_eq!(scan_file.base_row_id, None);
+        assert_eq!(scan_file.default_row_commit_version, None);
+        assert!(scan_file.dv_info.deletion_vector.is_some());
+        let dv = scan_file.dv_info.deletion_vector.unwrap();
         assert_eq!(dv.unique_id(), "uvBn[lx{q8@P<9BNH/isA@1");
-        assert!(transform.is_none());
+        assert!(scan_file.transform.is_none());
         assert_eq!(context.id, 2);
     }
 
@@ -277,4 +404,72 @@ mod tests {
             validate_visit,
         );
     }
+
+    // A storage handler that fails any attempt to access storage
+    struct NoStorageHandler;
+
+    impl StorageHandler for NoStorageHandler {
+        fn list_from(
+            &self,
+            _path: &Url,
+        ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<FileMeta>>>> {
+            panic!("unexpected list_from");
+        }
+
+        fn read_files(
+            &self,
+            _files: Vec<FileSlice>,
+        ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<Bytes>>>> {
+            panic!("unexpected read_files");
+        }
+
+        fn write_file(&self, _path: &Url, _data: Bytes, _overwrite: bool) -> DeltaResult<()> {
+            panic!("unexpected write_file");
+        }
+    }
+
+    struct NoStorageEngine(SyncEngine);
+
+    impl Engine for NoStorageEngine {
+        fn evaluation_handler(&self) -> Arc<dyn EvaluationHandler> {
+            self.0.evaluation_handler()
+        }
+
+        fn storage_handler(&self) -> Arc<dyn StorageHandler> {
+            Arc::new(NoStorageHandler)
+        }
+
+        fn json_handler(&self) -> Arc<dyn JsonHandler> {
+            self.0.json_handler()
+        }
+
+        fn parquet_handler(&self) -> Arc<dyn ParquetHandler> {
+            self.0.parquet_handler()
+        }
+    }
+
+    #[test]
+    fn test_inline_dv_selection_vector() {
+        let dv_info = DvInfo::from(DeletionVectorDescriptor {
+            storage_type: "i".to_string(),
+            path_or_inline_dv: "^Bg9^0rr910000000000iXQKl0rr91000f55c8Xg0@@D72lkbi5=-{L"
+                .to_string(),
+            offset: None,
+            size_in_bytes: 44,
+            cardinality: 6,
+        });
+        let engine = NoStorageEngine(SyncEngine::new());
+        let table_root = Url::parse("memory:///not/read/").unwrap();
+
+        let selection_vector = dv_info
+            .get_selection_vector(&engine, &table_root)
+            .unwrap()
+            .unwrap();
+        let deleted = [3, 4, 7, 11, 18, 29];
+        let expected: Vec<_> = (0..30).map(|i| !deleted.contains(&i)).collect();
+        assert_eq!(selection_vector, expected);
+
+        let row_indexes = dv_info.get_row_indexes(&engine, &table_root).unwrap();
+        assert_eq!(row_indexes, Some(deleted.to_vec()));
+    }
 }
@@ -10,7 +10,9 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 // re-export because many call sites that use schemas do not necessarily use expressions
+use self::compare::SchemaComparison as _;
 pub(crate) use crate::expressions::{column_name, ColumnName};
+use crate::table_features::ColumnMappingMode;
 use crate::utils::{require, CowExt as _};
 use crate::{DeltaResult, Error};
 use delta_kernel_derive::internal_api;
@@ -91,6 +93,8 @@ pub enum ColumnMetadataKey {
     IdentityHighWaterMark,
     IdentityAllowExplicitInsert,
     Invariants,
+    ParquetFieldId,
+    RowIndex,
 }
 
 impl AsRef<str> for ColumnMetadataKey {
@@ -104,6 +108,8 @@ impl AsRef<str> for ColumnMetadataKey {
             Self::IdentityStart => "delta.identity.start",
             Self::IdentityStep => "delta.identity.step",
             Self::Invariants => "delta.invariants",
+            Self::ParquetFieldId => "parquet.field.id",
+            Self::RowIndex => "delta.kernel.rowIndex",
         }
     }
 }
@@ -142,6 +148,22 @@ impl StructField {
         Self::new(name, data_type, false)
     }
 
+    /// Creates a new non-nullable `LONG` field which is not read from the data files, but instead
+    /// filled in by the parquet reader with the index of each row within its file (see
+    /// [`ColumnMetadataKey::RowIndex`]).
+    pub fn row_index(name: impl Into<String>) -> Self {
+        Self::not_null(name, DataType::LONG)
+            .with_metadata([(ColumnMetadataKey::RowIndex.as_ref(), true)])
+    }
+
+    /// Returns true if this is a row index field, see [`StructField::row_index`].
+    pub fn is_row_index(&self) -> bool {
+        matches!(
+            self.get_config_value(&ColumnMetadataKey::RowIndex),
+            Some(MetadataValue::Boolean(true))
+        )
+    }
+
     pub fn with_metadata(
         mut self,
         metadata: impl IntoIterator<Item = (impl Into<String>, impl Into<MetadataValue>)>,
@@ -218,21 +240,41 @@ impl StructField {
     /// [`crate::table_features::validate_schema_column_mapping`], to ensure that annotations are
     /// always and only present when column mapping mode is enabled.
     pub fn make_physical(&self) -> Self {
-        struct MakePhysical;
+        self.make_physical_with_mode(ColumnMappingMode::Name)
+    }
+
+    /// Like [`StructField::make_physical`], but in [`ColumnMappingMode::Id`] mode each field is
+    /// also annotated with its `parquet.field.id` (see [`ColumnMetadataKey::ParquetFieldId`]), so
+    /// that readers can resolve parquet columns by field id rather than by name.
+    ///
+    /// NOTE: The same validation requirement as [`StructField::make_physical`] applies.
+    pub fn make_physical_with_mode(&self, column_mapping_mode: ColumnMappingMode) -> Self {
+        struct MakePhysical {
+            column_mapping_mode: ColumnMappingMode,
+        }
         impl<'a> SchemaTransform<'a> for MakePhysical {
             fn transform_struct_field(
                 &mut self,
                 field: &'a StructField,
             ) -> Option<Cow<'a, StructField>> {
                 let field = self.recurse_into_struct_field(field)?;
-                Some(Cow::Owned(field.with_name(field.physical_name())))
+                let mut physical_field = field.with_name(field.physical_name());
+                if self.column_mapping_mode == ColumnMappingMode::Id {
+                    if let Some(id) = field.get_config_value(&ColumnMetadataKey::ColumnMappingId) {
+                        let key = ColumnMetadataKey::ParquetFieldId.as_ref().to_string();
+                        physical_field.metadata.insert(key, id.clone());
+                    }
+                }
+                Some(Cow::Owned(physical_field))
             }
         }
         // NOTE: unwrap is safe because the transformer is incapable of returning None
-        MakePhysical
-            .transform_struct_field(self)
-            .unwrap()
-            .into_owned()
+        MakePhysical {
+            column_mapping_mode,
+        }
+        .transform_struct_field(self)
+        .unwrap()
+        .into_owned()
     }
 
     fn has_invariants(&self) -> bool {
@@ -309,6 +351,54 @@ impl StructType {
         self.fields.contains_key(name.as_ref())
     }
 
+    /// Merge this schema with `other`, e.g. to read data written before and after an additive
+    /// schema change with a single schema. Follows Delta's schema merge rules:
+    /// - The merged schema has the fields of this schema, in order, followed by any fields only
+    ///   present in `other`. Fields added by `other` are always nullable, since data written
+    ///   with this schema has no values for them.
+    /// - A field present in both schemas is nullable if it is nullable in either of them, and
+    ///   keeps the metadata of this schema. Struct, array and map types are merged recursively.
+    /// - Fields are matched by exact name. Fields whose names only differ by case, and fields
+    ///   whose (primitive) types differ, are incompatible and produce an error.
+    pub fn merge(&self, other: &StructType) -> DeltaResult<StructType> {
+        let mut fields = self.fields.clone();
+        for other_field in other.fields() {
+            match fields.get_mut(other_field.name()) {
+                Some(field) => {
+                    field.data_type =
+                        merge_data_types(field.name(), field.data_type(), other_field.data_type())?;
+                    field.nullable |= other_field.nullable;
+                }
+                None => {
+                    if let Some(existing) = fields
+                        .keys()
+                        .find(|name| name.eq_ignore_ascii_case(other_field.name()))
+                    {
+                        return Err(Error::schema(format!(
+                            "Cannot merge fields {existing} and {} which differ only by case",
+                            other_field.name()
+                        )));
+                    }
+                    let mut field = other_field.clone();
+                    field.nullable = true;
+                    fields.insert(field.name.clone(), field);
+                }
+            }
+        }
+        Ok(Self {
+            type_name: self.type_name.clone(),
+            fields,
+        })
+    }
+
+    /// Returns true if data written with this schema can be read using `other` as the read
+    /// schema, e.g. before unioning results from two tables or table versions. Follows Delta's
+    /// read-compatibility rules: `other` may reorder fields, add nullable fields and widen the
+    /// nullability of existing fields, but must not drop fields or change their types.
+    pub fn is_read_compatible_with(&self, other: &StructType) -> bool {
+        self.can_read_as(other).is_ok()
+    }
+
     /// Extracts the name and type of all leaf columns, in schema order. Caller should pass Some
     /// `own_name` if this schema is embedded in a larger struct (e.g. `add.*`) and None if the
     /// schema is a top-level result (e.g. `*`).
@@ -323,6 +413,27 @@ impl StructType {
     }
 }
 
+/// Merge the data types of field `name` for [`StructType::merge`].
+fn merge_data_types(name: &str, left: &DataType, right: &DataType) -> DeltaResult<DataType> {
+    match (left, right) {
+        (DataType::Struct(left), DataType::Struct(right)) => Ok(left.merge(right)?.into()),
+        (DataType::Array(left), DataType::Array(right)) => {
+            let element_type = merge_data_types(name, left.element_type(), right.element_type())?;
+            Ok(ArrayType::new(element_type, left.contains_null || right.contains_null).into())
+        }
+        (DataType::Map(left), DataType::Map(right)) => {
+            let key_type = merge_data_types(name, left.key_type(), right.key_type())?;
+            let value_type = merge_data_types(name, left.value_type(), right.value_type())?;
+            let value_contains_null = left.value_contains_null || right.value_contains_null;
+            Ok(MapType::new(key_type, value_type, value_contains_null).into())
+        }
+        (left, right) if left == right => Ok(left.clone()),
+        (left, right) => Err(Error::schema(format!(
+            "Cannot merge incompatible types {left} and {right} of field {name}"
+        ))),
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct InvariantChecker {
     has_invariants: bool,
@@ -506,12 +617,29 @@ pub struct DecimalType {
 }
 
 impl DecimalType {
+    /// The maximum precision of a decimal type in a table schema, as defined by the Delta protocol.
+    pub const MAX_PRECISION: u8 = 38;
+    /// The maximum precision of a decimal value produced by intermediate computations, e.g. a
+    /// literal that does not fit in [`Self::MAX_PRECISION`] digits. Such values need 256-bit
+    /// storage, see [`Self::requires_256_bits`].
+    pub const MAX_WIDE_PRECISION: u8 = 76;
+
     /// Check if the given precision and scale are valid for a decimal type.
     pub fn try_new(precision: u8, scale: u8) -> DeltaResult<Self> {
+        Self::try_new_bounded(precision, scale, Self::MAX_PRECISION)
+    }
+
+    /// Same as [`Self::try_new`], but allows precisions up to [`Self::MAX_WIDE_PRECISION`]. Such
+    /// types are not valid in a table schema, and should only be used for intermediate values.
+    pub fn try_new_wide(precision: u8, scale: u8) -> DeltaResult<Self> {
+        Self::try_new_bounded(precision, scale, Self::MAX_WIDE_PRECISION)
+    }
+
+    fn try_new_bounded(precision: u8, scale: u8, max_precision: u8) -> DeltaResult<Self> {
         require!(
-            0 < precision && precision <= 38,
+            0 < precision && precision <= max_precision,
             Error::invalid_decimal(format!(
-                "precision must be in range 1..38 inclusive, found: {precision}."
+                "precision must be in range 1..{max_precision} inclusive, found: {precision}."
             ))
         );
         require!(
@@ -530,6 +658,12 @@ impl DecimalType {
     pub fn scale(&self) -> u8 {
         self.scale
     }
+
+    /// Whether values of this type need 256-bit storage, i.e. the precision exceeds
+    /// [`Self::MAX_PRECISION`].
+    pub fn requires_256_bits(&self) -> bool {
+        self.precision > Self::MAX_PRECISION
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
@@ -578,6 +712,22 @@ fn serialize_decimal<S: serde::Serializer>(
     serializer.serialize_str(&format!("decimal({},{})", dtype.precision(), dtype.scale()))
 }
 
+fn serialize_void<S: serde::Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str("void")
+}
+
+fn deserialize_void<'de, D>(deserializer: D) -> Result<(), D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let str_value = String::deserialize(deserializer)?;
+    require!(
+        str_value == "void",
+        serde::de::Error::custom(format!("Invalid void type: {str_value}"))
+    );
+    Ok(())
+}
+
 fn deserialize_decimal<'de, D>(deserializer: D) -> Result<DecimalType, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -639,6 +789,13 @@ pub enum DataType {
     /// A map stores an arbitrary length collection of key-value pairs
     /// with a single keyType and a single valueType
     Map(Box<MapType>),
+    /// The type of an untyped null (`void`). It is only a placeholder: a value of this type is
+    /// always null, and takes on the type of whatever field or element it is bound to.
+    #[serde(
+        serialize_with = "serialize_void",
+        deserialize_with = "deserialize_void"
+    )]
+    Null,
 }
 
 impl From<DecimalType> for PrimitiveType {
@@ -738,6 +895,7 @@ impl Display for DataType {
                 write!(f, ">")
             }
             DataType::Map(m) => write!(f, "map<{}, {}>", m.key_type, m.value_type),
+            DataType::Null => write!(f, "void"),
         }
     }
 }
@@ -825,6 +983,7 @@ pub trait SchemaTransform<'a> {
             Map(mtype) => self
                 .transform_map(mtype)?
                 .map_owned_or_else(data_type, DataType::from),
+            Null => Cow::Borrowed(data_type),
         };
         Some(result)
     }
@@ -1092,6 +1251,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_schemas() {
+        let schema = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable(
+                "info",
+                DataType::struct_type([StructField::not_null("name", DataType::STRING)]),
+            ),
+        ]);
+        let evolved = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::not_null("added", DataType::STRING),
+            StructField::nullable(
+                "info",
+                DataType::struct_type([
+                    StructField::not_null("name", DataType::STRING),
+                    StructField::not_null("age", DataType::INTEGER),
+                ]),
+            ),
+        ]);
+        let expected = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable(
+                "info",
+                DataType::struct_type([
+                    StructField::not_null("name", DataType::STRING),
+                    StructField::nullable("age", DataType::INTEGER),
+                ]),
+            ),
+            StructField::nullable("added", DataType::STRING),
+        ]);
+        assert_eq!(schema.merge(&evolved).unwrap(), expected);
+        assert_eq!(schema.merge(&schema).unwrap(), schema);
+    }
+
+    #[test]
+    fn test_merge_incompatible_schemas() {
+        let schema = StructType::new([StructField::nullable("id", DataType::LONG)]);
+        let conflicting = StructType::new([StructField::nullable("id", DataType::STRING)]);
+        assert!(matches!(schema.merge(&conflicting), Err(Error::Schema(_))));
+
+        let nested = |data_type| {
+            StructType::new([StructField::nullable(
+                "arr",
+                ArrayType::new(data_type, false),
+            )])
+        };
+        let result = nested(DataType::LONG).merge(&nested(DataType::INTEGER));
+        assert!(matches!(result, Err(Error::Schema(_))));
+
+        let different_case = StructType::new([StructField::nullable("ID", DataType::LONG)]);
+        assert!(matches!(
+            schema.merge(&different_case),
+            Err(Error::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_read_compatible_with() {
+        let schema = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable("value", DataType::STRING),
+        ]);
+        let evolved = StructType::new([
+            StructField::nullable("value", DataType::STRING),
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable("added", DataType::INTEGER),
+        ]);
+        assert!(schema.is_read_compatible_with(&schema));
+        assert!(schema.is_read_compatible_with(&evolved));
+        // `evolved` widened the nullability of `id` and has a column `schema` lacks
+        assert!(!evolved.is_read_compatible_with(&schema));
+
+        let type_change = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable("value", DataType::INTEGER),
+        ]);
+        assert!(!schema.is_read_compatible_with(&type_change));
+    }
+
+    #[test]
+    fn test_roundtrip_void() {
+        let data = r#"{"name":"a","type":"void","nullable":true,"metadata":{}}"#;
+        let field: StructField = serde_json::from_str(data).unwrap();
+        assert_eq!(field.data_type, DataType::Null);
+        assert_eq!(field.data_type.to_string(), "void");
+        assert_eq!(serde_json::to_string(&field).unwrap(), data);
+    }
+
     #[test]
     fn test_field_metadata() {
         let data = r#"
@@ -1138,20 +1386,40 @@ mod tests {
             field.physical_name(),
             "col-5f422f40-de70-45b2-88ab-1d5c90e94db1"
         );
+        let field_id_key = ColumnMetadataKey::ParquetFieldId;
         let physical_field = field.make_physical();
         assert_eq!(
             physical_field.name,
             "col-5f422f40-de70-45b2-88ab-1d5c90e94db1"
         );
+        assert!(physical_field.get_config_value(&field_id_key).is_none());
+        let DataType::Array(atype) = physical_field.data_type else {
+            panic!("Expected an Array");
+        };
+        let DataType::Struct(stype) = atype.element_type else {
+            panic!("Expected a Struct");
+        };
+        let inner_field = stype.fields.get_index(0).unwrap().1;
+        assert_eq!(inner_field.name, "col-a7f4159c-53be-4cb0-b81a-f7e5240cfc49");
+        assert!(inner_field.get_config_value(&field_id_key).is_none());
+
+        // id mode additionally annotates every field with its parquet field id
+        let physical_field = field.make_physical_with_mode(ColumnMappingMode::Id);
+        assert_eq!(
+            physical_field.get_config_value(&field_id_key),
+            Some(&MetadataValue::Number(4))
+        );
         let DataType::Array(atype) = physical_field.data_type else {
             panic!("Expected an Array");
         };
         let DataType::Struct(stype) = atype.element_type else {
             panic!("Expected a Struct");
         };
+        let inner_field = stype.fields.get_index(0).unwrap().1;
+        assert_eq!(inner_field.name, "col-a7f4159c-53be-4cb0-b81a-f7e5240cfc49");
         assert_eq!(
-            stype.fields.get_index(0).unwrap().1.name,
-            "col-a7f4159c-53be-4cb0-b81a-f7e5240cfc49"
+            inner_field.get_config_value(&field_id_key),
+            Some(&MetadataValue::Number(5))
         );
     }
 
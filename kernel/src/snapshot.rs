@@ -1,27 +1,36 @@
 //! In-memory representation of snapshots of tables (snapshot is a table at given point in time, it
 //! has schema etc.)
 
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::num::NonZero;
 use std::sync::Arc;
 
-use crate::actions::domain_metadata::domain_metadata_configuration;
+use crate::actions::domain_metadata::{clustering_columns, domain_metadata_configuration};
 use crate::actions::set_transaction::SetTransactionScanner;
-use crate::actions::{Metadata, Protocol, INTERNAL_DOMAIN_PREFIX};
-use crate::checkpoint::CheckpointWriter;
+use crate::actions::{get_log_schema, Format, Metadata, Protocol, INTERNAL_DOMAIN_PREFIX};
+use crate::checkpoint::{CheckpointWriter, DEFAULT_CHECKPOINT_INTERVAL};
+use crate::expressions::{ColumnName, Scalar};
 use crate::log_segment::{self, ListedLogFiles, LogSegment};
-use crate::scan::ScanBuilder;
-use crate::schema::{Schema, SchemaRef};
+use crate::scan::{parse_partition_value, ScanBuilder};
+use crate::schema::{DataType, Schema, SchemaRef, StructType};
 use crate::table_configuration::TableConfiguration;
 use crate::table_features::ColumnMappingMode;
 use crate::table_properties::TableProperties;
-use crate::transaction::Transaction;
+use crate::transaction::{Clock, Transaction};
 use crate::utils::{calculate_transaction_expiration_timestamp, try_parse_uri};
-use crate::{DeltaResult, Engine, Error, StorageHandler, Version};
+use crate::{DeltaResult, Engine, Error, FileDataReadResultIterator, StorageHandler, Version};
 use delta_kernel_derive::internal_api;
 
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 use url::Url;
 
+mod diff;
+
+pub use diff::SnapshotDiff;
+
 /// Name of the _last_checkpoint file that provides metadata about the last checkpoint
 /// created for the table. This file is used as a hint for the engine to quickly locate
 /// the latest checkpoint without a full directory listing.
@@ -289,6 +298,30 @@ impl Snapshot {
         CheckpointWriter::try_new(self)
     }
 
+    /// Creates a [`CheckpointWriter`] for this snapshot if a checkpoint is due, or returns `None`
+    /// otherwise. This is typically called with the snapshot produced by a successful commit, to
+    /// keep the log from growing unbounded.
+    ///
+    /// A checkpoint is due if the snapshot's version is a (nonzero) multiple of the table's
+    /// `delta.checkpointInterval` property (default: [`DEFAULT_CHECKPOINT_INTERVAL`]), and the
+    /// snapshot is not already backed by a checkpoint at that version.
+    ///
+    /// [`DEFAULT_CHECKPOINT_INTERVAL`]: crate::checkpoint::DEFAULT_CHECKPOINT_INTERVAL
+    pub fn maybe_checkpoint(self: Arc<Self>) -> DeltaResult<Option<CheckpointWriter>> {
+        let interval = self
+            .table_properties()
+            .checkpoint_interval
+            .map_or(DEFAULT_CHECKPOINT_INTERVAL, NonZero::get);
+        let version = self.version();
+        let is_due = version > 0
+            && version % interval == 0
+            && self.log_segment.checkpoint_version != Some(version);
+        if !is_due {
+            return Ok(None);
+        }
+        self.checkpoint().map(Some)
+    }
+
     /// Log segment this snapshot uses
     #[internal_api]
     pub(crate) fn log_segment(&self) -> &LogSegment {
@@ -309,6 +342,24 @@ impl Snapshot {
         self.table_configuration.schema()
     }
 
+    /// The time this table was created, in milliseconds since the Unix epoch, if recorded in the
+    /// table's `metaData` action.
+    pub fn created_time(&self) -> Option<i64> {
+        self.metadata().created_time()
+    }
+
+    /// The table's raw properties (the `configuration` of its `metaData` action) at this
+    /// `Snapshot`s version. See [`Self::table_properties`] for the parsed, typed properties.
+    pub fn properties(&self) -> &HashMap<String, String> {
+        self.metadata().configuration()
+    }
+
+    /// The encoding of the table's data files (the `format` of its `metaData` action) at this
+    /// `Snapshot`s version. Kernel can only scan tables whose provider is `parquet`.
+    pub fn format(&self) -> &Format {
+        &self.metadata().format
+    }
+
     /// Table [`Metadata`] at this `Snapshot`s version.
     #[internal_api]
     pub(crate) fn metadata(&self) -> &Metadata {
@@ -355,6 +406,12 @@ impl Snapshot {
         Transaction::try_new(self)
     }
 
+    /// Create a [`Transaction`] for this `Arc<Snapshot>`, whose commit timestamp is read from
+    /// `clock` rather than the system clock.
+    pub fn transaction_with_clock(self: Arc<Self>, clock: &dyn Clock) -> DeltaResult<Transaction> {
+        Transaction::try_new_with_clock(self, clock)
+    }
+
     /// Fetch the latest version of the provided `application_id` for this snapshot. Filters the txn based on the SetTransactionRetentionDuration property and lastUpdated
     ///
     /// Note that this method performs log replay (fetches and processes metadata from storage).
@@ -392,8 +449,161 @@ impl Snapshot {
 
         domain_metadata_configuration(self.log_segment(), domain, engine)
     }
+
+    /// Fetch the clustering columns of this snapshot, as recorded in the `delta.clustering` domain
+    /// metadata by liquid clustering. The columns are returned by their logical names, in
+    /// clustering order. Returns `None` if the table is not clustered.
+    ///
+    /// Note that this method performs log replay (fetches and processes metadata from storage).
+    pub fn clustering_columns(&self, engine: &dyn Engine) -> DeltaResult<Option<Vec<ColumnName>>> {
+        clustering_columns(self.log_segment(), &self.schema(), engine)
+    }
+
+    /// Enumerates the distinct combinations of partition values among the data files of this
+    /// snapshot, e.g. for engines building a partition catalog. Each combination maps the name of
+    /// every partition column (as listed in the table metadata) to its value, parsed to the
+    /// column's type. A partition value that is missing from a file's `add` action is null.
+    /// Combinations are returned in the order they are first encountered during log replay. An
+    /// unpartitioned table with any data files has a single, empty combination.
+    ///
+    /// Note that this method performs log replay (fetches and processes metadata from storage), but
+    /// does not read any data files.
+    pub fn distinct_partition_values(
+        &self,
+        engine: &dyn Engine,
+    ) -> DeltaResult<Vec<HashMap<String, Scalar>>> {
+        let schema = self.schema();
+        let partition_columns: Vec<_> = self
+            .metadata()
+            .partition_columns
+            .iter()
+            .map(|column| {
+                let (key, data_type) = resolve_partition_column(&schema, column)?;
+                Ok((column, key, data_type))
+            })
+            .collect::<DeltaResult<_>>()?;
+
+        let snapshot = Snapshot::new(self.log_segment.clone(), self.table_configuration.clone());
+        let scan = ScanBuilder::new(snapshot).build()?;
+        // The distinct combinations seen so far. The interior mutability clippy finds in `Scalar`
+        // is a lazily decoded cache of array elements, which does not change how values compare.
+        #[allow(clippy::mutable_key_type)]
+        let mut seen = BTreeSet::new();
+        let mut combinations = vec![];
+        for scan_metadata in scan.scan_metadata(engine)? {
+            let raw_values = scan_metadata?.for_each_scan_file(
+                vec![],
+                |raw_values: &mut Vec<HashMap<String, String>>, scan_file| {
+                    raw_values.push(scan_file.partition_values)
+                },
+            )?;
+            for raw_values in raw_values {
+                let values: Vec<_> = partition_columns
+                    .iter()
+                    .map(|(_, key, data_type)| {
+                        parse_partition_value(raw_values.get(key), data_type)
+                    })
+                    .try_collect()?;
+                // Typed values are compared, so distinct raw strings of the same value (e.g. `1` and
+                // `01` for an integer column) are deduplicated as well.
+                if seen.insert(TotalOrdValues(values.clone())) {
+                    let names = partition_columns
+                        .iter()
+                        .map(|(name, _, _)| name.to_string());
+                    combinations.push(names.zip(values).collect());
+                }
+            }
+        }
+        Ok(combinations)
+    }
+
+    /// Computes the data files added and removed going from this snapshot to `other`, a snapshot
+    /// of the same table. This is lighter than a [`TableChanges`] scan when only file-level changes
+    /// are needed, since no data files are read. If `other` is older than this snapshot, the
+    /// returned files describe going back in time, i.e. files removed since `other` are reported
+    /// as added.
+    ///
+    /// Note that this method replays only the commits between the two versions. A file added by
+    /// one of those commits is considered new, even if it was re-added to a snapshot which already
+    /// had it (e.g. to update its statistics). If some of those commits are no longer in the log,
+    /// both snapshots are replayed in full instead.
+    ///
+    /// [`TableChanges`]: crate::table_changes::TableChanges
+    pub fn diff(&self, other: &Snapshot, engine: &dyn Engine) -> DeltaResult<SnapshotDiff> {
+        SnapshotDiff::try_new(self, other, engine)
+    }
+
+    /// Reads the actions of the commit at `version`, parsed into the schema of the Delta log's
+    /// actions: each row is one action, with a (nullable) column per action type. The actions are
+    /// returned as they appear in the commit file, without any log replay, which is useful for
+    /// tooling and debugging.
+    ///
+    /// Returns an error if `version` is newer than this snapshot, or if the commit file does not
+    /// exist (e.g. because it was removed by log cleanup).
+    pub fn commit_actions(
+        &self,
+        engine: &dyn Engine,
+        version: Version,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        if version > self.version() {
+            return Err(Error::generic(format!(
+                "Requested commit version {version} is newer than snapshot version {}",
+                self.version()
+            )));
+        }
+        let log_segment = LogSegment::for_table_changes(
+            engine.storage_handler().as_ref(),
+            self.log_segment.log_root.clone(),
+            version,
+            version,
+        )?;
+        let commit_files: Vec<_> = log_segment
+            .ascending_commit_files
+            .into_iter()
+            .map(|commit| commit.location)
+            .collect();
+        engine
+            .json_handler()
+            .read_json_files(&commit_files, get_log_schema().clone(), None)
+    }
+}
+
+// Resolves a (top-level) partition column of the table, given by its logical name, to its data
+// type and the key of its values in the `partitionValues` of `add` actions, i.e. its physical name.
+fn resolve_partition_column(schema: &StructType, column: &str) -> DeltaResult<(String, DataType)> {
+    let field = schema
+        .field(column)
+        .ok_or_else(|| Error::missing_column(column))?;
+    Ok((field.physical_name().to_string(), field.data_type().clone()))
+}
+
+// A list of partition values, ordered lexicographically by [`Scalar::total_cmp`] so that lists
+// can be deduplicated in a sorted set.
+struct TotalOrdValues(Vec<Scalar>);
+
+impl Ord for TotalOrdValues {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let mut orderings = self.0.iter().zip(&other.0).map(|(a, b)| a.total_cmp(b));
+        orderings
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| self.0.len().cmp(&other.0.len()))
+    }
+}
+
+impl PartialOrd for TotalOrdValues {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
+impl PartialEq for TotalOrdValues {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for TotalOrdValues {}
+
 // Note: Schema can not be derived because the checkpoint schema is only known at runtime.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -455,7 +665,7 @@ mod tests {
     use crate::object_store::ObjectStore;
     use serde_json::json;
 
-    use crate::arrow::array::StringArray;
+    use crate::arrow::array::{Array as _, StringArray};
     use crate::arrow::record_batch::RecordBatch;
     use crate::parquet::arrow::ArrowWriter;
 
@@ -466,7 +676,7 @@ mod tests {
     use crate::engine::sync::SyncEngine;
     use crate::path::ParsedLogPath;
     use crate::utils::test_utils::string_array_to_engine_data;
-    use test_utils::{add_commit, delta_path_for_version};
+    use test_utils::{actions_to_string, add_commit, delta_path_for_version, TestAction};
 
     #[test]
     fn test_snapshot_read_metadata() {
@@ -486,6 +696,76 @@ mod tests {
         assert_eq!(snapshot.schema(), expected);
     }
 
+    #[test]
+    fn test_snapshot_created_time_and_properties() {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url, &engine, None).unwrap();
+
+        assert_eq!(snapshot.created_time(), Some(1677811175819));
+        let expected = HashMap::from([
+            (
+                "delta.enableDeletionVectors".to_string(),
+                "true".to_string(),
+            ),
+            ("delta.columnMapping.mode".to_string(), "none".to_string()),
+        ]);
+        assert_eq!(snapshot.properties(), &expected);
+        assert_eq!(
+            snapshot.table_properties().enable_deletion_vectors,
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_format() -> DeltaResult<()> {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(snapshot.format().provider(), "parquet");
+        assert!(snapshot.format().options().is_empty());
+
+        // a table whose data files are not parquet can't be scanned
+        let store = Arc::new(InMemory::new());
+        let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+        let commit = [
+            json!({
+                "protocol": {
+                    "minReaderVersion": 1,
+                    "minWriterVersion": 1
+                }
+            }),
+            json!({
+                "metaData": {
+                    "id":"5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                    "format": { "provider": "orc", "options": { "compression": "zlib" } },
+                    "schemaString": "{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}",
+                    "partitionColumns": [],
+                    "configuration": {},
+                    "createdTime": 1587968585495i64
+                }
+            }),
+        ]
+        .map(|json| json.to_string())
+        .join("\n");
+        add_commit(store.as_ref(), 0, commit).await.unwrap();
+
+        let snapshot = Snapshot::try_new(Url::parse("memory:///")?, &engine, None)?;
+        assert_eq!(snapshot.format().provider(), "orc");
+        assert_eq!(
+            snapshot.format().options(),
+            &HashMap::from([("compression".to_string(), "zlib".to_string())])
+        );
+        let err = snapshot.into_scan_builder().build().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(msg) if msg.contains("'orc'")));
+        Ok(())
+    }
+
     #[test]
     fn test_new_snapshot() {
         let path =
@@ -504,6 +784,266 @@ mod tests {
         assert_eq!(snapshot.schema(), expected);
     }
 
+    #[test]
+    fn test_snapshot_table_not_found() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let url = Url::from_directory_path(tmp_dir.path().join("no_such_table")).unwrap();
+        let engine = SyncEngine::new();
+        let result = Snapshot::try_new(url, &engine, None);
+        assert!(matches!(result, Err(Error::TableNotFound(_))));
+
+        // A directory without a `_delta_log` is not a table either
+        let url = Url::from_directory_path(tmp_dir.path()).unwrap();
+        let result = Snapshot::try_new(url, &engine, None);
+        assert!(matches!(result, Err(Error::TableNotFound(_))));
+
+        // Object stores have no directories, so a table is simply absent
+        let store = Arc::new(InMemory::new());
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let url = Url::parse("memory:///no_such_table/").unwrap();
+        let result = Snapshot::try_new(url, &engine, None);
+        assert!(matches!(result, Err(Error::TableNotFound(_))));
+    }
+
+    #[test]
+    fn test_snapshot_empty_table() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp_dir.path().join("_delta_log")).unwrap();
+        let url = Url::from_directory_path(tmp_dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let result = Snapshot::try_new(url.clone(), &engine, None);
+        assert!(matches!(result, Err(Error::EmptyTable(_))));
+
+        // A log with files, but no commits or checkpoints, is also empty
+        std::fs::write(tmp_dir.path().join("_delta_log/_commit.tmp"), "").unwrap();
+        let result = Snapshot::try_new(url, &engine, None);
+        assert!(matches!(result, Err(Error::EmptyTable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_truncated_commit() {
+        let protocol = json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}});
+        let metadata = json!({
+            "metaData": {
+                "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": "{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}",
+                "partitionColumns": [],
+                "configuration": {}
+            }
+        });
+        let commit0 = format!("{protocol}\n{metadata}");
+        // A partially written commit: the metadata action is cut off mid-object
+        let commit1 = metadata.to_string();
+        let commit1 = &commit1[..commit1.len() / 2];
+
+        let store = Arc::new(InMemory::new());
+        add_commit(store.as_ref(), 0, commit0.clone())
+            .await
+            .unwrap();
+        add_commit(store.as_ref(), 1, commit1.to_string())
+            .await
+            .unwrap();
+        let url = Url::parse("memory:///").unwrap();
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let result = Snapshot::try_new(url.clone(), &engine, None);
+        assert!(matches!(result, Err(Error::CorruptLog(1))), "{result:?}");
+
+        // The preceding (complete) version is still readable
+        let snapshot = Snapshot::try_new(url, &engine, Some(0)).unwrap();
+        assert_eq!(snapshot.version(), 0);
+
+        // The same log on the local filesystem, read with the sync engine
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let log_dir = tmp_dir.path().join("_delta_log");
+        std::fs::create_dir(&log_dir).unwrap();
+        std::fs::write(log_dir.join(format!("{:020}.json", 0)), commit0).unwrap();
+        std::fs::write(log_dir.join(format!("{:020}.json", 1)), commit1).unwrap();
+        let url = Url::from_directory_path(tmp_dir.path()).unwrap();
+        let result = Snapshot::try_new(url, &SyncEngine::new(), None);
+        assert!(matches!(result, Err(Error::CorruptLog(1))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_commit_without_actions() {
+        let protocol = json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}});
+        let metadata = json!({
+            "metaData": {
+                "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": "{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}",
+                "partitionColumns": [],
+                "configuration": {}
+            }
+        });
+        let commit0 = format!("{protocol}\n{metadata}");
+        let commit_info = json!({"commitInfo": {"timestamp": 1, "operation": "WRITE"}});
+
+        // An empty commit (e.g. one cut off before its first line), and a commit which parses but
+        // has no recognizable action, are both corrupt
+        for commit1 in ["", "{}\n{\"unknown\": {\"a\": 1}}"] {
+            let store = Arc::new(InMemory::new());
+            add_commit(store.as_ref(), 0, commit0.clone())
+                .await
+                .unwrap();
+            add_commit(store.as_ref(), 1, commit1.to_string())
+                .await
+                .unwrap();
+            let url = Url::parse("memory:///").unwrap();
+            let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+            let result = Snapshot::try_new(url, &engine, None);
+            assert!(
+                matches!(result, Err(Error::CorruptLog(1))),
+                "{commit1:?}: {result:?}"
+            );
+        }
+
+        // A commit with only actions outside the read schema (e.g. commitInfo, when reading
+        // protocol and metadata) is not
+        let store = Arc::new(InMemory::new());
+        add_commit(store.as_ref(), 0, commit0).await.unwrap();
+        add_commit(store.as_ref(), 1, commit_info.to_string())
+            .await
+            .unwrap();
+        let url = Url::parse("memory:///").unwrap();
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let snapshot = Snapshot::try_new(url, &engine, None).unwrap();
+        assert_eq!(snapshot.version(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_diff() {
+        let store = Arc::new(InMemory::new());
+        let commits = [
+            vec![TestAction::Metadata, TestAction::Add("a".into())],
+            vec![TestAction::Add("b".into())],
+            vec![TestAction::Remove("a".into()), TestAction::Add("c".into())],
+            vec![TestAction::Add("d".into()), TestAction::Remove("b".into())],
+            vec![TestAction::Remove("d".into())],
+        ];
+        for (version, actions) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as u64, actions_to_string(actions))
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///").unwrap();
+        let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+        let snapshot = |version| Snapshot::try_new(url.clone(), &engine, Some(version)).unwrap();
+
+        let diff = snapshot(1).diff(&snapshot(2), &engine).unwrap();
+        assert_eq!(diff.added_files(), ["c"]);
+        assert_eq!(diff.removed_files(), ["a"]);
+
+        // going back in time swaps the added and removed files
+        let diff = snapshot(2).diff(&snapshot(1), &engine).unwrap();
+        assert_eq!(diff.added_files(), ["a"]);
+        assert_eq!(diff.removed_files(), ["c"]);
+
+        // `d` is added and removed within the range, so it is not part of the diff
+        let diff = snapshot(1).diff(&snapshot(4), &engine).unwrap();
+        assert_eq!(diff.added_files(), ["c"]);
+        assert_eq!(diff.removed_files(), ["b", "a"]);
+
+        let diff = snapshot(2).diff(&snapshot(2), &engine).unwrap();
+        assert_eq!(diff, SnapshotDiff::default());
+
+        // only the commits in the range are replayed, so re-adding `c`, which already existed at
+        // version 4, reports it as added
+        let commit = actions_to_string(vec![TestAction::Add("c".into())]);
+        add_commit(store.as_ref(), 5, commit).await.unwrap();
+        let diff = snapshot(4).diff(&snapshot(5), &engine).unwrap();
+        assert_eq!(diff.added_files(), ["c"]);
+        assert!(diff.removed_files().is_empty());
+
+        // snapshots of different tables can't be diffed
+        let other_commit = Path::from(format!("other/{}", delta_path_for_version(0, "json")));
+        let commit = actions_to_string(vec![TestAction::Metadata]);
+        store.put(&other_commit, commit.into()).await.unwrap();
+        let other_url = Url::parse("memory:///other/").unwrap();
+        let other = Snapshot::try_new(other_url, &engine, None).unwrap();
+        assert!(snapshot(1).diff(&other, &engine).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_diff_without_commits() {
+        // A copy of the table without the commit of version 2, which is covered by a checkpoint
+        let source = std::fs::canonicalize(PathBuf::from(
+            "./tests/data/with_checkpoint_no_last_checkpoint/_delta_log/",
+        ))
+        .unwrap();
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let log_dir = tmp_dir.path().join("_delta_log");
+        std::fs::create_dir(&log_dir).unwrap();
+        for entry in std::fs::read_dir(source).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap();
+            if name != "00000000000000000002.json" {
+                std::fs::copy(&path, log_dir.join(name)).unwrap();
+            }
+        }
+        let url = Url::from_directory_path(tmp_dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = |version| Snapshot::try_new(url.clone(), &engine, Some(version)).unwrap();
+
+        // The commits between the versions can't be replayed, so both snapshots are replayed in
+        // full instead
+        let diff = snapshot(1).diff(&snapshot(3), &engine).unwrap();
+        assert_eq!(
+            diff.added_files(),
+            ["part-00000-70b1dcdf-0236-4f63-a072-124cdbafd8a0-c000.snappy.parquet"]
+        );
+        assert_eq!(
+            diff.removed_files(),
+            ["part-00000-ad1a4bb7-07e8-4f40-b50b-49910d209e0c-c000.snappy.parquet"]
+        );
+        let diff = snapshot(3).diff(&snapshot(1), &engine).unwrap();
+        assert_eq!(
+            diff.added_files(),
+            ["part-00000-ad1a4bb7-07e8-4f40-b50b-49910d209e0c-c000.snappy.parquet"]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_commit_actions() {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url, &engine, Some(1)).unwrap();
+
+        // Returns the action type of each row of the given commit's actions
+        let action_types = |version| -> Vec<String> {
+            let batches = snapshot.commit_actions(&engine, version).unwrap();
+            let batches: Vec<_> = batches
+                .map(|data| ArrowEngineData::try_from_engine_data(data.unwrap()).unwrap())
+                .collect();
+            batches
+                .iter()
+                .flat_map(|data| {
+                    let batch = data.record_batch();
+                    (0..batch.num_rows()).map(move |row| {
+                        // exactly one action column is set in each row
+                        let column = batch
+                            .columns()
+                            .iter()
+                            .positions(|column| column.is_valid(row))
+                            .exactly_one()
+                            .unwrap();
+                        batch.schema().field(column).name().clone()
+                    })
+                })
+                .collect()
+        };
+        assert_eq!(
+            action_types(0),
+            ["commitInfo", "protocol", "metaData", "add"]
+        );
+        assert_eq!(action_types(1), ["commitInfo", "remove", "add"]);
+
+        // versions newer than the snapshot can't be read
+        assert!(snapshot.commit_actions(&engine, 2).is_err());
+    }
+
     // TODO: unify this and lots of stuff in LogSegment tests and test_utils
     async fn commit(store: &InMemory, version: Version, commit: Vec<serde_json::Value>) {
         let commit_data = commit
@@ -1045,4 +1585,190 @@ mod tests {
                 msg == "User DomainMetadata are not allowed to use system-controlled 'delta.*' domain"));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_clustering_columns() -> DeltaResult<()> {
+        let url = Url::parse("memory:///")?;
+        let store = Arc::new(InMemory::new());
+        let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+
+        // `id` is mapped to physical name `col-1`, and `s.x` to `col-3`
+        let schema_string = json!({
+            "type": "struct",
+            "fields": [
+                {"name": "id", "type": "integer", "nullable": true, "metadata": {
+                    "delta.columnMapping.id": 1,
+                    "delta.columnMapping.physicalName": "col-1"
+                }},
+                {"name": "s", "type": {"type": "struct", "fields": [
+                    {"name": "x", "type": "string", "nullable": true, "metadata": {
+                        "delta.columnMapping.id": 3,
+                        "delta.columnMapping.physicalName": "col-3"
+                    }}
+                ]}, "nullable": true, "metadata": {
+                    "delta.columnMapping.id": 2,
+                    "delta.columnMapping.physicalName": "col-2"
+                }}
+            ]
+        })
+        .to_string();
+        let commit = [
+            json!({
+                "protocol": {
+                    "minReaderVersion": 2,
+                    "minWriterVersion": 5
+                }
+            }),
+            json!({
+                "metaData": {
+                    "id":"5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                    "format": { "provider": "parquet", "options": {} },
+                    "schemaString": schema_string,
+                    "partitionColumns": [],
+                    "configuration": {
+                        "delta.columnMapping.mode": "name",
+                        "delta.columnMapping.maxColumnId": "3"
+                    },
+                    "createdTime": 1587968585495i64
+                }
+            }),
+        ]
+        .map(|json| json.to_string())
+        .join("\n");
+        add_commit(store.as_ref(), 0, commit).await.unwrap();
+
+        // not clustered yet
+        let snapshot = Snapshot::try_new(url.clone(), &engine, None)?;
+        assert_eq!(snapshot.clustering_columns(&engine)?, None);
+
+        let commit = json!({
+            "domainMetadata": {
+                "domain": "delta.clustering",
+                "configuration": r#"{"clusteringColumns":[["col-2","col-3"],["col-1"]]}"#,
+                "removed": false
+            }
+        })
+        .to_string();
+        add_commit(store.as_ref(), 1, commit).await.unwrap();
+
+        let snapshot = Snapshot::try_new(url.clone(), &engine, None)?;
+        assert_eq!(
+            snapshot.clustering_columns(&engine)?,
+            Some(vec![ColumnName::new(["s", "x"]), ColumnName::new(["id"])])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_distinct_partition_values() -> DeltaResult<()> {
+        let url = Url::parse("memory:///")?;
+        let store = Arc::new(InMemory::new());
+        let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+
+        let schema_string = json!({
+            "type": "struct",
+            "fields": [
+                {"name": "id", "type": "long", "nullable": true, "metadata": {}},
+                {"name": "letter", "type": "string", "nullable": true, "metadata": {}},
+                {"name": "number", "type": "integer", "nullable": true, "metadata": {}},
+                {"name": "day", "type": "date", "nullable": true, "metadata": {}}
+            ]
+        })
+        .to_string();
+        let add = |path: &str, partition_values: serde_json::Value| {
+            json!({
+                "add": {
+                    "path": path,
+                    "partitionValues": partition_values,
+                    "size": 100,
+                    "modificationTime": 1587968586000i64,
+                    "dataChange": true
+                }
+            })
+        };
+        let commit0 = [
+            json!({ "protocol": { "minReaderVersion": 1, "minWriterVersion": 2 } }),
+            json!({
+                "metaData": {
+                    "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                    "format": { "provider": "parquet", "options": {} },
+                    "schemaString": schema_string,
+                    "partitionColumns": ["letter", "number", "day"],
+                    "configuration": {},
+                    "createdTime": 1587968585495i64
+                }
+            }),
+            add(
+                "a",
+                json!({"letter": "a", "number": "1", "day": "2024-01-01"}),
+            ),
+            add(
+                "b",
+                json!({"letter": "a", "number": "2", "day": "2024-01-01"}),
+            ),
+            // the same partition as `a`, with a different string for the same number
+            add(
+                "c",
+                json!({"letter": "a", "number": "01", "day": "2024-01-01"}),
+            ),
+            add(
+                "d",
+                json!({"letter": "b", "number": null, "day": "2024-01-02"}),
+            ),
+        ];
+        add_commit(store.as_ref(), 0, commit0.map(|j| j.to_string()).join("\n"))
+            .await
+            .unwrap();
+        let commit1 = [
+            json!({ "remove": { "path": "b", "deletionTimestamp": 1587968587000i64, "dataChange": true } }),
+            add("e", json!({"letter": "c", "number": "3"})),
+        ];
+        add_commit(store.as_ref(), 1, commit1.map(|j| j.to_string()).join("\n"))
+            .await
+            .unwrap();
+
+        let snapshot = Snapshot::try_new(url.clone(), &engine, None)?;
+        let mut actual: Vec<_> = snapshot
+            .distinct_partition_values(&engine)?
+            .into_iter()
+            .map(|values| {
+                let get = |name: &str| values[name].clone();
+                (get("letter"), get("number"), get("day"))
+            })
+            .collect();
+        actual.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let expected = vec![
+            (Scalar::from("a"), Scalar::Integer(1), Scalar::Date(19723)),
+            (
+                Scalar::from("b"),
+                Scalar::Null(DataType::INTEGER),
+                Scalar::Date(19724),
+            ),
+            (
+                Scalar::from("c"),
+                Scalar::Integer(3),
+                Scalar::Null(DataType::DATE),
+            ),
+        ];
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert!(actual.0.null_eq_any_type(&expected.0), "{actual:?}");
+            assert!(actual.1.null_eq_any_type(&expected.1), "{actual:?}");
+            assert!(actual.2.null_eq_any_type(&expected.2), "{actual:?}");
+            assert_eq!(actual.1.data_type(), DataType::INTEGER);
+            assert_eq!(actual.2.data_type(), DataType::DATE);
+        }
+
+        // the partition values of `b` are present before it was removed, and gone after
+        let has_b = |version| -> DeltaResult<bool> {
+            let snapshot = Snapshot::try_new(url.clone(), &engine, Some(version))?;
+            let values = snapshot.distinct_partition_values(&engine)?;
+            Ok(values
+                .iter()
+                .any(|values| values["number"].null_eq_any_type(&Scalar::Integer(2))))
+        };
+        assert!(has_b(0)?);
+        assert!(!has_b(1)?);
+        Ok(())
+    }
 }
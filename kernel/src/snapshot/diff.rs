@@ -0,0 +1,265 @@
+//! File-level differences between two [`Snapshot`]s of the same table, see [`Snapshot::diff`].
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use crate::actions::{get_log_schema, ADD_NAME, REMOVE_NAME};
+use crate::engine_data::{GetData, RowVisitor};
+use crate::log_replay::{FileActionDeduplicator, FileActionKey};
+use crate::log_segment::LogSegment;
+use crate::scan::ScanBuilder;
+use crate::schema::{column_name, ColumnName, ColumnNamesAndTypes, DataType, SchemaRef};
+use crate::utils::require;
+use crate::{DeltaResult, Engine, Error};
+
+use super::Snapshot;
+
+/// Schema for reading the file actions of commits
+static FILE_ACTIONS_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    get_log_schema()
+        .project(&[ADD_NAME, REMOVE_NAME])
+        .expect("log schema has add and remove actions")
+});
+
+/// The data files added and removed between two versions of a table, as returned by
+/// [`Snapshot::diff`].
+///
+/// Files are identified by their path, as it appears in the table's `add` and `remove` actions. A
+/// file whose deletion vector changed between the two versions appears in both lists, since the
+/// new (file, deletion vector) pair is added and the old pair is removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    added_files: Vec<String>,
+    removed_files: Vec<String>,
+}
+
+impl SnapshotDiff {
+    /// Paths of the files present in the newer snapshot but not in the older one, ordered from the
+    /// most recently added file.
+    pub fn added_files(&self) -> &[String] {
+        &self.added_files
+    }
+
+    /// Paths of the files present in the older snapshot but not in the newer one, ordered from the
+    /// most recently removed file.
+    pub fn removed_files(&self) -> &[String] {
+        &self.removed_files
+    }
+
+    /// Computes the files added and removed going from the `start` snapshot to the `end` snapshot.
+    /// If `end` is older than `start`, the added and removed files are swapped accordingly.
+    pub(crate) fn try_new(
+        start: &Snapshot,
+        end: &Snapshot,
+        engine: &dyn Engine,
+    ) -> DeltaResult<Self> {
+        require!(
+            start.table_root() == end.table_root(),
+            Error::generic(format!(
+                "Cannot diff snapshots of different tables: {} and {}",
+                start.table_root(),
+                end.table_root()
+            ))
+        );
+        if start.version() == end.version() {
+            return Ok(Self::default());
+        }
+
+        let (older, newer) = if start.version() < end.version() {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        // The commits after the older snapshot (up to and including the newer snapshot) are
+        // replayed to find the files they add and remove. If some of those commits are no longer
+        // in the log (e.g. they were cleaned up after a checkpoint), both snapshots are replayed in
+        // full and their files compared instead.
+        let (added, removed) = match LogSegment::for_table_changes(
+            engine.storage_handler().as_ref(),
+            newer.log_segment().log_root.clone(),
+            older.version() + 1,
+            newer.version(),
+        ) {
+            Ok(log_segment) => diff_commits(log_segment, engine)?,
+            Err(_) => diff_files(older, newer, engine)?,
+        };
+
+        let (added_files, removed_files) = if start.version() < end.version() {
+            (added, removed)
+        } else {
+            (removed, added)
+        };
+        Ok(Self {
+            added_files: added_files.into_iter().map(|key| key.path).collect(),
+            removed_files: removed_files.into_iter().map(|key| key.path).collect(),
+        })
+    }
+}
+
+/// Returns the files added and removed by the commits of `log_segment`. The newest action of each
+/// file decides whether it was added or removed.
+fn diff_commits(
+    log_segment: LogSegment,
+    engine: &dyn Engine,
+) -> DeltaResult<(Vec<FileActionKey>, Vec<FileActionKey>)> {
+    // Commits are replayed from newest to oldest
+    let commit_files: Vec<_> = log_segment
+        .ascending_commit_files
+        .into_iter()
+        .rev()
+        .map(|commit| commit.location)
+        .collect();
+    let actions =
+        engine
+            .json_handler()
+            .read_json_files(&commit_files, FILE_ACTIONS_SCHEMA.clone(), None)?;
+
+    let mut visitor = FileDiffVisitor::default();
+    for actions in actions {
+        visitor.visit_rows_of(actions?.as_ref())?;
+    }
+    // A file whose oldest action in the range is a remove was part of the older snapshot, so it
+    // did not change if a newer action adds it back. Like in [`TableChanges`], a file whose oldest
+    // action is an add is new, so it did not change if a newer action removes it.
+    //
+    // [`TableChanges`]: crate::table_changes::TableChanges
+    let FileDiffVisitor {
+        oldest_is_add,
+        added,
+        removed,
+        ..
+    } = visitor;
+    let added = added
+        .into_iter()
+        .filter(|key| oldest_is_add.get(key) == Some(&true))
+        .collect();
+    let removed = removed
+        .into_iter()
+        .filter(|key| oldest_is_add.get(key) == Some(&false))
+        .collect();
+    Ok((added, removed))
+}
+
+/// Returns the files of `newer` that are not files of `older`, and vice versa. This replays the
+/// whole log of both snapshots.
+fn diff_files(
+    older: &Snapshot,
+    newer: &Snapshot,
+    engine: &dyn Engine,
+) -> DeltaResult<(Vec<FileActionKey>, Vec<FileActionKey>)> {
+    let older_files = file_keys(older, engine)?;
+    let newer_files = file_keys(newer, engine)?;
+    let older_set: HashSet<_> = older_files.iter().collect();
+    let newer_set: HashSet<_> = newer_files.iter().collect();
+    let added = newer_files
+        .iter()
+        .filter(|key| !older_set.contains(key))
+        .cloned()
+        .collect();
+    let removed = older_files
+        .iter()
+        .filter(|key| !newer_set.contains(key))
+        .cloned()
+        .collect();
+    Ok((added, removed))
+}
+
+/// Returns the files of `snapshot`, in log replay order.
+fn file_keys(snapshot: &Snapshot, engine: &dyn Engine) -> DeltaResult<Vec<FileActionKey>> {
+    let snapshot = Snapshot::new(
+        snapshot.log_segment.clone(),
+        snapshot.table_configuration.clone(),
+    );
+    let scan = ScanBuilder::new(snapshot).build()?;
+    let mut keys = vec![];
+    for scan_metadata in scan.scan_metadata(engine)? {
+        keys = scan_metadata?.for_each_scan_file(
+            keys,
+            |keys: &mut Vec<FileActionKey>, scan_file| {
+                let dv = scan_file.dv_info.deletion_vector.as_ref();
+                let dv_unique_id = dv.map(|dv| dv.unique_id());
+                keys.push(FileActionKey::new(scan_file.path, dv_unique_id));
+            },
+        )?;
+    }
+    Ok(keys)
+}
+
+/// Replays the file actions of a range of commits, in newest-to-oldest order, and tracks the
+/// newest action seen for each unique (path, dvId) pair.
+#[derive(Default)]
+struct FileDiffVisitor {
+    /// All file keys seen so far
+    seen_file_keys: HashSet<FileActionKey>,
+    /// Whether the oldest action seen so far for each file key is an add
+    oldest_is_add: HashMap<FileActionKey, bool>,
+    /// File keys whose newest action is an add
+    added: Vec<FileActionKey>,
+    /// File keys whose newest action is a remove
+    removed: Vec<FileActionKey>,
+}
+
+impl FileDiffVisitor {
+    // These index positions correspond to the order of columns defined in
+    // `selected_column_names_and_types()`
+    const ADD_PATH_INDEX: usize = 0; // Position of "add.path" in getters
+    const ADD_DV_START_INDEX: usize = 1; // Start position of add deletion vector columns
+    const REMOVE_PATH_INDEX: usize = 4; // Position of "remove.path" in getters
+    const REMOVE_DV_START_INDEX: usize = 5; // Start position of remove deletion vector columns
+}
+
+impl RowVisitor for FileDiffVisitor {
+    fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+        static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
+            const STRING: DataType = DataType::STRING;
+            const INTEGER: DataType = DataType::INTEGER;
+            let types_and_names = vec![
+                (STRING, column_name!("add.path")),
+                (STRING, column_name!("add.deletionVector.storageType")),
+                (STRING, column_name!("add.deletionVector.pathOrInlineDv")),
+                (INTEGER, column_name!("add.deletionVector.offset")),
+                (STRING, column_name!("remove.path")),
+                (STRING, column_name!("remove.deletionVector.storageType")),
+                (STRING, column_name!("remove.deletionVector.pathOrInlineDv")),
+                (INTEGER, column_name!("remove.deletionVector.offset")),
+            ];
+            let (types, names) = types_and_names.into_iter().unzip();
+            (names, types).into()
+        });
+        NAMES_AND_TYPES.as_ref()
+    }
+
+    fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
+        require!(
+            getters.len() == 8,
+            Error::InternalError(format!(
+                "Wrong number of FileDiffVisitor getters: {}",
+                getters.len()
+            ))
+        );
+
+        let mut deduplicator = FileActionDeduplicator::new(
+            &mut self.seen_file_keys,
+            true, // all batches come from commit files
+            Self::ADD_PATH_INDEX,
+            Self::REMOVE_PATH_INDEX,
+            Self::ADD_DV_START_INDEX,
+            Self::REMOVE_DV_START_INDEX,
+        );
+        for i in 0..row_count {
+            let Some((file_key, is_add)) = deduplicator.extract_file_action(i, getters, false)?
+            else {
+                continue;
+            };
+            self.oldest_is_add.insert(file_key.clone(), is_add);
+            if deduplicator.check_and_record_seen(file_key.clone()) {
+                continue; // A newer action for this file was already seen
+            }
+            if is_add {
+                self.added.push(file_key);
+            } else {
+                self.removed.push(file_key);
+            }
+        }
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use crate::schema::{
     ArrayType, ColumnNamesAndTypes, DataType, MapType, SchemaRef, StructField, StructType,
     ToSchema as _,
 };
+use crate::table_changes::scan::CdfSource;
 use crate::table_changes::scan_file::{cdf_scan_row_expression, cdf_scan_row_schema};
 use crate::table_changes::{check_cdf_table_properties, ensure_cdf_read_supported};
 use crate::table_properties::TableProperties;
@@ -54,12 +55,14 @@ pub(crate) fn table_changes_action_iter(
     commit_files: impl IntoIterator<Item = ParsedLogPath>,
     table_schema: SchemaRef,
     physical_predicate: Option<(PredicateRef, SchemaRef)>,
+    cdf_source: CdfSource,
 ) -> DeltaResult<impl Iterator<Item = DeltaResult<TableChangesScanMetadata>>> {
     let filter = DataSkippingFilter::new(engine.as_ref(), physical_predicate).map(Arc::new);
     let result = commit_files
         .into_iter()
         .map(move |commit_file| -> DeltaResult<_> {
-            let scanner = LogReplayScanner::try_new(engine.as_ref(), commit_file, &table_schema)?;
+            let scanner =
+                LogReplayScanner::try_new(engine.as_ref(), commit_file, &table_schema, cdf_source)?;
             scanner.into_scan_batches(engine.clone(), filter.clone())
         }) //Iterator-Result-Iterator-Result
         .flatten_ok() // Iterator-Result-Result
@@ -140,11 +143,15 @@ impl LogReplayScanner {
     ///    as an add action.
     /// 3. Perform validation on each protocol and metadata action in the commit.
     ///
+    /// With [`CdfSource::AddRemove`], the `cdc` actions of a commit that only adds or only removes
+    /// files are ignored, and the commit is processed as if it had none.
+    ///
     /// For more details, see the documentation for [`LogReplayScanner`].
     fn try_new(
         engine: &dyn Engine,
         commit_file: ParsedLogPath,
         table_schema: &SchemaRef,
+        cdf_source: CdfSource,
     ) -> DeltaResult<Self> {
         let visitor_schema = PreparePhaseVisitor::schema();
 
@@ -192,10 +199,15 @@ impl LogReplayScanner {
                     Error::change_data_feed_incompatible_schema(table_schema, &schema)
                 );
                 let table_properties = TableProperties::from(configuration);
-                check_cdf_table_properties(&table_properties)
-                    .map_err(|_| Error::change_data_feed_unsupported(commit_file.version))?;
+                check_cdf_table_properties(&table_properties, commit_file.version)?;
             }
         }
+        // The changes of a commit can only be reconstructed from its add and remove actions if it
+        // adds or removes whole files. A commit that does both may have rewritten files, whose
+        // unchanged rows would be reported as deleted and re-inserted, so its cdc files are read
+        // even with `CdfSource::AddRemove`.
+        let rewrites_files = !add_paths.is_empty() && !remove_dvs.is_empty();
+        let has_cdc_action = has_cdc_action && (cdf_source == CdfSource::Cdc || rewrites_files);
         // We resolve the remove deletion vector map after visiting the entire commit.
         if has_cdc_action {
             remove_dvs.clear();
@@ -334,12 +346,12 @@ impl RowVisitor for PreparePhaseVisitor<'_> {
         for i in 0..row_count {
             if let Some(path) = getters[0].get_str(i, "add.path")? {
                 // If no data was changed, we must ignore that action
-                if !*self.has_cdc_action && getters[1].get(i, "add.dataChange")? {
+                if getters[1].get(i, "add.dataChange")? {
                     self.add_paths.insert(path.to_string());
                 }
             } else if let Some(path) = getters[2].get_str(i, "remove.path")? {
                 // If no data was changed, we must ignore that action
-                if !*self.has_cdc_action && getters[3].get(i, "remove.dataChange")? {
+                if getters[3].get(i, "remove.dataChange")? {
                     let deletion_vector = visit_deletion_vector_at(i, &getters[4..=8])?;
                     self.remove_dvs
                         .insert(path.to_string(), DvInfo { deletion_vector });
@@ -10,7 +10,8 @@ use crate::scan::state::DvInfo;
 use crate::scan::PhysicalPredicate;
 use crate::schema::{DataType, StructField, StructType};
 use crate::table_changes::log_replay::LogReplayScanner;
-use crate::table_features::ReaderFeature;
+use crate::table_changes::scan::CdfSource;
+use crate::table_features::{ReaderFeature, WriterFeature};
 use crate::utils::test_utils::{Action, LocalMockTable};
 use crate::Predicate;
 use crate::{DeltaResult, Engine, Error, Version};
@@ -86,8 +87,14 @@ async fn metadata_protocol() {
         .unwrap()
         .into_iter();
 
-    let scan_batches =
-        table_changes_action_iter(engine, commits, get_schema().into(), None).unwrap();
+    let scan_batches = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap();
     let sv = result_to_sv(scan_batches);
     assert_eq!(sv, &[false, false]);
 }
@@ -111,12 +118,17 @@ async fn cdf_not_enabled() {
         .unwrap()
         .into_iter();
 
-    let res: DeltaResult<Vec<_>> =
-        table_changes_action_iter(engine, commits, get_schema().into(), None)
-            .unwrap()
-            .try_collect();
+    let res: DeltaResult<Vec<_>> = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .try_collect();
 
-    assert!(matches!(res, Err(Error::ChangeDataFeedUnsupported(_))));
+    assert!(matches!(res, Err(Error::ChangeDataFeedNotEnabled(0))));
 }
 
 #[tokio::test]
@@ -139,13 +151,106 @@ async fn unsupported_reader_feature() {
         .unwrap()
         .into_iter();
 
-    let res: DeltaResult<Vec<_>> =
-        table_changes_action_iter(engine, commits, get_schema().into(), None)
-            .unwrap()
-            .try_collect();
+    let res: DeltaResult<Vec<_>> = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .try_collect();
 
     assert!(matches!(res, Err(Error::ChangeDataFeedUnsupported(_))));
 }
+
+#[tokio::test]
+async fn protocol_upgrade_mid_range() {
+    let engine = Arc::new(SyncEngine::new());
+    let mut mock_table = LocalMockTable::new();
+    let schema_string = serde_json::to_string(&get_schema()).unwrap();
+    let add = |path: &str| {
+        Action::Add(Add {
+            path: path.into(),
+            data_change: true,
+            ..Default::default()
+        })
+    };
+    mock_table
+        .commit([
+            Action::Metadata(Metadata {
+                schema_string,
+                configuration: HashMap::from([(
+                    "delta.enableChangeDataFeed".to_string(),
+                    "true".to_string(),
+                )]),
+                ..Default::default()
+            }),
+            Action::Protocol(
+                Protocol::try_new(1, 1, None::<Vec<String>>, None::<Vec<String>>).unwrap(),
+            ),
+        ])
+        .await;
+    mock_table.commit([add("fake_path_1")]).await;
+    // A compatible upgrade that CDF can read through
+    mock_table
+        .commit([Action::Protocol(
+            Protocol::try_new(
+                3,
+                7,
+                Some([ReaderFeature::DeletionVectors]),
+                Some([WriterFeature::DeletionVectors]),
+            )
+            .unwrap(),
+        )])
+        .await;
+    mock_table.commit([add("fake_path_2")]).await;
+    // An incompatible upgrade: CDF does not support column mapping
+    mock_table
+        .commit([Action::Protocol(
+            Protocol::try_new(
+                3,
+                7,
+                Some([ReaderFeature::DeletionVectors, ReaderFeature::ColumnMapping]),
+                Some([WriterFeature::DeletionVectors, WriterFeature::ColumnMapping]),
+            )
+            .unwrap(),
+        )])
+        .await;
+
+    // Reading up to (but excluding) the incompatible upgrade succeeds
+    let commits = get_segment(engine.as_ref(), mock_table.table_root(), 0, 3)
+        .unwrap()
+        .into_iter();
+    let scan_batches = table_changes_action_iter(
+        engine.clone(),
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap();
+    let sv = result_to_sv(scan_batches);
+    assert_eq!(sv, &[false, false, true, false, true]);
+
+    // Reading across the incompatible upgrade fails at the version of the upgrade
+    let commits = get_segment(engine.as_ref(), mock_table.table_root(), 0, None)
+        .unwrap()
+        .into_iter();
+    let mut results = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .collect_vec();
+    let last = results.pop().unwrap();
+    assert!(results.iter().all(Result::is_ok));
+    assert!(matches!(last, Err(Error::ChangeDataFeedUnsupported(4))));
+}
+
 #[tokio::test]
 async fn column_mapping_should_fail() {
     let engine = Arc::new(SyncEngine::new());
@@ -170,10 +275,15 @@ async fn column_mapping_should_fail() {
         .unwrap()
         .into_iter();
 
-    let res: DeltaResult<Vec<_>> =
-        table_changes_action_iter(engine, commits, get_schema().into(), None)
-            .unwrap()
-            .try_collect();
+    let res: DeltaResult<Vec<_>> = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .try_collect();
 
     assert!(matches!(res, Err(Error::ChangeDataFeedUnsupported(_))));
 }
@@ -201,10 +311,15 @@ async fn incompatible_schemas_fail() {
             .unwrap()
             .into_iter();
 
-        let res: DeltaResult<Vec<_>> =
-            table_changes_action_iter(engine, commits, cdf_schema.into(), None)
-                .unwrap()
-                .try_collect();
+        let res: DeltaResult<Vec<_>> = table_changes_action_iter(
+            engine,
+            commits,
+            cdf_schema.into(),
+            None,
+            CdfSource::default(),
+        )
+        .unwrap()
+        .try_collect();
 
         assert!(matches!(
             res,
@@ -291,14 +406,20 @@ async fn add_remove() {
         .unwrap()
         .into_iter();
 
-    let sv = table_changes_action_iter(engine, commits, get_schema().into(), None)
-        .unwrap()
-        .flat_map(|scan_metadata| {
-            let scan_metadata = scan_metadata.unwrap();
-            assert_eq!(scan_metadata.remove_dvs, HashMap::new().into());
-            scan_metadata.selection_vector
-        })
-        .collect_vec();
+    let sv = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .flat_map(|scan_metadata| {
+        let scan_metadata = scan_metadata.unwrap();
+        assert_eq!(scan_metadata.remove_dvs, HashMap::new().into());
+        scan_metadata.selection_vector
+    })
+    .collect_vec();
 
     assert_eq!(sv, &[true, true]);
 }
@@ -341,14 +462,20 @@ async fn filter_data_change() {
         .unwrap()
         .into_iter();
 
-    let sv = table_changes_action_iter(engine, commits, get_schema().into(), None)
-        .unwrap()
-        .flat_map(|scan_metadata| {
-            let scan_metadata = scan_metadata.unwrap();
-            assert_eq!(scan_metadata.remove_dvs, HashMap::new().into());
-            scan_metadata.selection_vector
-        })
-        .collect_vec();
+    let sv = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .flat_map(|scan_metadata| {
+        let scan_metadata = scan_metadata.unwrap();
+        assert_eq!(scan_metadata.remove_dvs, HashMap::new().into());
+        scan_metadata.selection_vector
+    })
+    .collect_vec();
 
     assert_eq!(sv, &[false; 5]);
 }
@@ -382,21 +509,56 @@ async fn cdc_selection() {
             }),
         ])
         .await;
+    // Rewrites a file, so its changes are always read from the cdc file
+    mock_table
+        .commit([
+            Action::Add(Add {
+                path: "fake_path_5".into(),
+                data_change: true,
+                ..Default::default()
+            }),
+            Action::Remove(Remove {
+                path: "fake_path_2".into(),
+                data_change: true,
+                ..Default::default()
+            }),
+            Action::Cdc(Cdc {
+                path: "fake_path_6".into(),
+                ..Default::default()
+            }),
+        ])
+        .await;
 
-    let commits = get_segment(engine.as_ref(), mock_table.table_root(), 0, None)
-        .unwrap()
-        .into_iter();
-
-    let sv = table_changes_action_iter(engine, commits, get_schema().into(), None)
+    let select = |cdf_source| {
+        let commits = get_segment(engine.as_ref(), mock_table.table_root(), 0, None)
+            .unwrap()
+            .into_iter();
+        table_changes_action_iter(
+            engine.clone(),
+            commits,
+            get_schema().into(),
+            None,
+            cdf_source,
+        )
         .unwrap()
         .flat_map(|scan_metadata| {
             let scan_metadata = scan_metadata.unwrap();
             assert_eq!(scan_metadata.remove_dvs, HashMap::new().into());
             scan_metadata.selection_vector
         })
-        .collect_vec();
+        .collect_vec()
+    };
 
-    assert_eq!(sv, &[true, false, true, true]);
+    assert_eq!(
+        select(CdfSource::Cdc),
+        &[true, false, true, true, false, false, true]
+    );
+    // Reconstructing the changes selects the remove action instead of the cdc files, except for
+    // the commit that rewrites a file
+    assert_eq!(
+        select(CdfSource::AddRemove),
+        &[true, true, false, false, false, false, true]
+    );
 }
 
 #[tokio::test]
@@ -453,14 +615,20 @@ async fn dv() {
         },
     )])
     .into();
-    let sv = table_changes_action_iter(engine, commits, get_schema().into(), None)
-        .unwrap()
-        .flat_map(|scan_metadata| {
-            let scan_metadata = scan_metadata.unwrap();
-            assert_eq!(scan_metadata.remove_dvs, expected_remove_dvs);
-            scan_metadata.selection_vector
-        })
-        .collect_vec();
+    let sv = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .flat_map(|scan_metadata| {
+        let scan_metadata = scan_metadata.unwrap();
+        assert_eq!(scan_metadata.remove_dvs, expected_remove_dvs);
+        scan_metadata.selection_vector
+    })
+    .collect_vec();
 
     assert_eq!(sv, &[false, true, true]);
 }
@@ -530,13 +698,19 @@ async fn data_skipping_filter() {
         .unwrap()
         .into_iter();
 
-    let sv = table_changes_action_iter(engine, commits, logical_schema.into(), predicate)
-        .unwrap()
-        .flat_map(|scan_metadata| {
-            let scan_metadata = scan_metadata.unwrap();
-            scan_metadata.selection_vector
-        })
-        .collect_vec();
+    let sv = table_changes_action_iter(
+        engine,
+        commits,
+        logical_schema.into(),
+        predicate,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .flat_map(|scan_metadata| {
+        let scan_metadata = scan_metadata.unwrap();
+        scan_metadata.selection_vector
+    })
+    .collect_vec();
 
     // Note: since the first pair is a dv operation, remove action will always be filtered
     assert_eq!(sv, &[false, true, false, false, true]);
@@ -575,10 +749,15 @@ async fn failing_protocol() {
         .unwrap()
         .into_iter();
 
-    let res: DeltaResult<Vec<_>> =
-        table_changes_action_iter(engine, commits, get_schema().into(), None)
-            .unwrap()
-            .try_collect();
+    let res: DeltaResult<Vec<_>> = table_changes_action_iter(
+        engine,
+        commits,
+        get_schema().into(),
+        None,
+        CdfSource::default(),
+    )
+    .unwrap()
+    .try_collect();
 
     assert!(res.is_err());
 }
@@ -602,6 +781,12 @@ async fn file_meta_timestamp() {
 
     let commit = commits.next().unwrap();
     let file_meta_ts = commit.location.last_modified;
-    let scanner = LogReplayScanner::try_new(engine.as_ref(), commit, &get_schema().into()).unwrap();
+    let scanner = LogReplayScanner::try_new(
+        engine.as_ref(),
+        commit,
+        &get_schema().into(),
+        CdfSource::default(),
+    )
+    .unwrap();
     assert_eq!(scanner.timestamp, file_meta_ts);
 }
@@ -68,7 +68,16 @@ static CDF_FIELDS: LazyLock<[StructField; 3]> = LazyLock::new(|| {
 /// Represents a call to read the Change Data Feed (CDF) between two versions of a table. The schema of
 /// `TableChanges` will be the schema of the table at the end version with three additional columns:
 /// - `_change_type`: String representing the type of change that for that commit. This may be one
-///   of `delete`, `insert`, `update_preimage`, or `update_postimage`.
+///   of `delete`, `insert`, `update_preimage`, or `update_postimage`. Updated rows are read from the
+///   commit's `cdc` files, which hold an `update_preimage` row with the values before the update and
+///   an `update_postimage` row with the values after it, so an update is never reported as a
+///   `delete` and an `insert`. Which of the `cdc` files and the `add`/`remove` actions of a commit
+///   that has both are read depends on the [`CdfSource`] of the scan: by default the `add` and
+///   `remove` actions are ignored, while [`CdfSource::AddRemove`] reads them instead of the `cdc`
+///   files for commits that only add or only remove files.
+///
+///   [`CdfSource`]: scan::CdfSource
+///   [`CdfSource::AddRemove`]: scan::CdfSource::AddRemove
 /// - `_commit_version`: Long representing the commit the change occurred in.
 /// - `_commit_timestamp`: Time at which the commit occurred. The timestamp is retrieved from the
 ///   file modification time of the log file. No timezone is associated with the timestamp.
@@ -120,7 +129,8 @@ pub struct TableChanges {
 impl TableChanges {
     /// Creates a new [`TableChanges`] instance for the given version range. This function checks
     /// these properties:
-    /// - The change data feed table feature must be enabled in both the start or end versions.
+    /// - The change data feed table feature must be enabled in both the start or end versions,
+    ///   otherwise this returns [`Error::ChangeDataFeedNotEnabled`].
     /// - Other than the deletion vector reader feature, no other reader features are enabled for the table.
     /// - The schemas at the start and end versions are the same.
     ///
@@ -166,6 +176,7 @@ impl TableChanges {
         //
         // Note: We must still check each metadata and protocol action in the CDF range.
         let check_table_config = |snapshot: &Snapshot| {
+            check_cdf_table_properties(snapshot.table_properties(), snapshot.version())?;
             if snapshot.table_configuration().is_cdf_read_supported() {
                 Ok(())
             } else {
@@ -236,19 +247,22 @@ impl TableChanges {
     }
 }
 
-/// Ensures that change data feed is enabled in `table_properties`. See the documentation
-/// of [`TableChanges`] for more details.
-fn check_cdf_table_properties(table_properties: &TableProperties) -> DeltaResult<()> {
+/// Ensures that change data feed is enabled in the `table_properties` of the table at `version`.
+/// See the documentation of [`TableChanges`] for more details.
+fn check_cdf_table_properties(
+    table_properties: &TableProperties,
+    version: Version,
+) -> DeltaResult<()> {
     require!(
         table_properties.enable_change_data_feed.unwrap_or(false),
-        Error::unsupported("Change data feed is not enabled")
+        Error::change_data_feed_not_enabled(version)
     );
     require!(
         matches!(
             table_properties.column_mapping_mode,
             None | Some(ColumnMappingMode::None)
         ),
-        Error::unsupported("Change data feed not supported when column mapping is enabled")
+        Error::change_data_feed_unsupported(version)
     );
     Ok(())
 }
@@ -310,9 +324,20 @@ mod tests {
                 start_version,
                 end_version.into(),
             );
-            assert!(matches!(res, Err(Error::ChangeDataFeedUnsupported(_))))
+            assert!(matches!(res, Err(Error::ChangeDataFeedNotEnabled(2))))
         }
     }
+
+    #[test]
+    fn table_changes_fails_on_non_cdf_table() {
+        let path = "./tests/data/table-without-dv-small";
+        let engine = Box::new(SyncEngine::new());
+        let url = delta_kernel::try_parse_uri(path).unwrap();
+
+        let res = TableChanges::try_new(url, engine.as_ref(), 0, None);
+        assert!(matches!(res, Err(Error::ChangeDataFeedNotEnabled(0))));
+    }
+
     #[test]
     fn schema_evolution_fails() {
         let path = "./tests/data/table-with-cdf";
@@ -31,7 +31,11 @@ fn get_cdf_columns(scan_file: &CdfScanFile) -> DeltaResult<HashMap<&str, Express
 }
 
 /// Generates the expression used to convert physical data from the `scan_file` path into logical
-/// data matching the `logical_schema`
+/// data matching the `logical_schema`.
+///
+/// Generated columns (fields carrying `delta.generationExpression` metadata) need no special
+/// handling: writers materialize their values in the data files, so they are read like any other
+/// physical column rather than re-evaluated.
 pub(crate) fn physical_to_logical_expr(
     scan_file: &CdfScanFile,
     logical_schema: &StructType,
@@ -40,29 +44,82 @@ pub(crate) fn physical_to_logical_expr(
     let mut cdf_columns = get_cdf_columns(scan_file)?;
     let all_fields = all_fields
         .iter()
-        .map(|field| match field {
-            ColumnType::Partition(field_idx) => {
-                let field = logical_schema.fields.get_index(*field_idx);
-                let Some((_, field)) = field else {
-                    return Err(Error::generic(
-                        "logical schema did not contain expected field, can't transform data",
-                    ));
-                };
-                let name = field.physical_name();
-                let value_expression =
-                    parse_partition_value(scan_file.partition_values.get(name), field.data_type())?;
-                Ok(value_expression.into())
-            }
+        .enumerate()
+        .map(|(index, field)| match field {
             ColumnType::Selected(field_name) => {
                 // Remove to take ownership
                 let generated_column = cdf_columns.remove(field_name.as_str());
                 Ok(generated_column.unwrap_or_else(|| ColumnName::new([field_name]).into()))
             }
+            _ => column_expr(scan_file, logical_schema, field, index, &[], ""),
         })
         .try_collect()?;
     Ok(Expression::Struct(all_fields))
 }
 
+/// Generates the expression for the field at `index` of the (possibly nested) struct `fields`,
+/// given the physical column path of that struct and the dotted physical path under which the
+/// values of its nested partition columns are stored.
+fn column_expr(
+    scan_file: &CdfScanFile,
+    fields: &StructType,
+    field: &ColumnType,
+    index: usize,
+    column_path: &[&str],
+    partition_prefix: &str,
+) -> DeltaResult<Expression> {
+    let get_field = |field_idx: usize| match fields.fields.get_index(field_idx) {
+        Some((_, field)) => Ok(field),
+        None => Err(Error::generic(
+            "logical schema did not contain expected field, can't transform data",
+        )),
+    };
+    match field {
+        ColumnType::Partition(field_idx) => {
+            let field = get_field(*field_idx)?;
+            let name = format!("{partition_prefix}{}", field.physical_name());
+            let value_expression =
+                parse_partition_value(scan_file.partition_values.get(&name), field.data_type())?;
+            Ok(value_expression.into())
+        }
+        ColumnType::Selected(field_name) => {
+            let path = column_path.iter().copied().chain([field_name.as_str()]);
+            Ok(ColumnName::new(path).into())
+        }
+        ColumnType::Nested(field_name, nested_fields) => {
+            let field = get_field(index)?;
+            let DataType::Struct(struct_type) = field.data_type() else {
+                return Err(Error::generic(
+                    "nested partition column parent is not a struct, can't transform data",
+                ));
+            };
+            let column_path = [column_path, &[field_name.as_str()]].concat();
+            let partition_prefix = format!("{partition_prefix}{}.", field.physical_name());
+            let nested_fields = nested_fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    column_expr(
+                        scan_file,
+                        struct_type,
+                        field,
+                        index,
+                        &column_path,
+                        &partition_prefix,
+                    )
+                })
+                .try_collect()?;
+            Ok(Expression::Struct(nested_fields))
+        }
+        ColumnType::RowTracking(_) => Err(Error::unsupported(
+            "Row tracking metadata is not supported in change data feed scans",
+        )),
+        ColumnType::RowIndex => Err(Error::unsupported(
+            "Row indexes are not supported in change data feed scans",
+        )),
+    }
+}
+
 /// Gets the physical schema that will be used to read data in the `scan_file` path.
 pub(crate) fn scan_file_physical_schema(
     scan_file: &CdfScanFile,
@@ -83,7 +140,7 @@ mod tests {
 
     use crate::expressions::{column_expr, Expression as Expr, Scalar};
     use crate::scan::ColumnType;
-    use crate::schema::{DataType, StructField, StructType};
+    use crate::schema::{ColumnMetadataKey, DataType, StructField, StructType};
     use crate::table_changes::physical_to_logical::physical_to_logical_expr;
     use crate::table_changes::scan_file::{CdfScanFile, CdfScanFileType};
     use crate::table_changes::{
@@ -135,4 +192,77 @@ mod tests {
         test(CdfScanFileType::Remove, Expr::literal(REMOVE_CHANGE_TYPE));
         test(CdfScanFileType::Cdc, cdc_change_type);
     }
+
+    #[test]
+    fn verify_nested_partition_expression() {
+        let scan_file = CdfScanFile {
+            scan_type: CdfScanFileType::Add,
+            path: "fake_path".to_string(),
+            dv_info: Default::default(),
+            remove_dv: None,
+            partition_values: HashMap::from([("info.age".to_string(), "20".to_string())]),
+            commit_version: 42,
+            commit_timestamp: 1234,
+        };
+        let logical_schema = StructType::new([
+            StructField::nullable("id", DataType::STRING),
+            StructField::nullable(
+                "info",
+                DataType::struct_type([
+                    StructField::nullable("name", DataType::STRING),
+                    StructField::not_null("age", DataType::LONG),
+                ]),
+            ),
+        ]);
+        let all_fields = vec![
+            ColumnType::Selected("id".to_string()),
+            ColumnType::Nested(
+                "info".to_string(),
+                vec![
+                    ColumnType::Selected("name".to_string()),
+                    ColumnType::Partition(1),
+                ],
+            ),
+        ];
+        let phys_to_logical_expr =
+            physical_to_logical_expr(&scan_file, &logical_schema, &all_fields).unwrap();
+        let expected_expr = Expr::struct_from([
+            column_expr!("id"),
+            Expr::struct_from([column_expr!("info.name"), Scalar::Long(20).into()]),
+        ]);
+        assert_eq!(phys_to_logical_expr, expected_expr)
+    }
+
+    #[test]
+    fn verify_generated_column_expression() {
+        let scan_file = CdfScanFile {
+            scan_type: CdfScanFileType::Add,
+            path: "fake_path".to_string(),
+            dv_info: Default::default(),
+            remove_dv: None,
+            partition_values: HashMap::new(),
+            commit_version: 42,
+            commit_timestamp: 1234,
+        };
+        let logical_schema = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable("id_plus_one", DataType::LONG)
+                .with_metadata([(ColumnMetadataKey::GenerationExpression.as_ref(), "id + 1")]),
+            StructField::not_null(CHANGE_TYPE_COL_NAME, DataType::STRING),
+        ]);
+        let all_fields = vec![
+            ColumnType::Selected("id".to_string()),
+            ColumnType::Selected("id_plus_one".to_string()),
+            ColumnType::Selected(CHANGE_TYPE_COL_NAME.to_string()),
+        ];
+        let phys_to_logical_expr =
+            physical_to_logical_expr(&scan_file, &logical_schema, &all_fields).unwrap();
+        // The generated column is read from the data file, not re-evaluated
+        let expected_expr = Expr::struct_from([
+            column_expr!("id"),
+            column_expr!("id_plus_one"),
+            Expr::literal(ADD_CHANGE_TYPE),
+        ]);
+        assert_eq!(phys_to_logical_expr, expected_expr)
+    }
 }
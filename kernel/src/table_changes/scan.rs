@@ -7,8 +7,9 @@ use tracing::debug;
 use url::Url;
 
 use crate::actions::deletion_vector::split_vector;
-use crate::scan::{ColumnType, PhysicalPredicate, ScanResult};
-use crate::schema::{SchemaRef, StructType};
+use crate::path::resolve_file_path;
+use crate::scan::{get_nested_partition_column, ColumnType, PhysicalPredicate, ScanResult};
+use crate::schema::{SchemaRef, StructField, StructType};
 use crate::{DeltaResult, Engine, FileMeta, PredicateRef};
 
 use super::log_replay::{table_changes_action_iter, TableChangesScanMetadata};
@@ -17,6 +18,25 @@ use super::resolve_dvs::{resolve_scan_file_dv, ResolvedCdfScanFile};
 use super::scan_file::scan_metadata_to_scan_file;
 use super::{TableChanges, CDF_FIELDS};
 
+/// Where a [`TableChangesScan`] reads the changes of a commit from, when the commit has both `cdc`
+/// files and `add`/`remove` actions. Commits without `cdc` files are always read from their `add`
+/// and `remove` actions. Both sources produce the same changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CdfSource {
+    /// Read the commit's `cdc` files, as the Delta protocol requires, and ignore its `add` and
+    /// `remove` actions. This is the default. The `cdc` files hold exactly the changed rows,
+    /// including the `update_preimage` and `update_postimage` rows of updates.
+    #[default]
+    Cdc,
+    /// Reconstruct the changes of a commit that only adds or only removes files from its `add` and
+    /// `remove` actions, where each row of an added file is an `insert` and each row of a removed
+    /// file is a `delete`, and ignore its `cdc` files. This avoids reading the `cdc` files of such
+    /// commits. The changes of a commit that both adds and removes files, which may rewrite
+    /// files (e.g. to update or delete some of their rows), can't be reconstructed this way, so
+    /// they are still read from its `cdc` files.
+    AddRemove,
+}
+
 /// The result of building a [`TableChanges`] scan over a table. This can be used to get the change
 /// data feed from the table.
 #[derive(Debug)]
@@ -34,6 +54,8 @@ pub struct TableChangesScan {
     physical_predicate: PhysicalPredicate,
     // The [`ColumnType`] of all the fields in the `logical_schema`
     all_fields: Arc<Vec<ColumnType>>,
+    // Where to read the changes of commits with `cdc` files from
+    cdf_source: CdfSource,
 }
 
 /// This builder constructs a [`TableChangesScan`] that can be used to read the [`TableChanges`]
@@ -75,6 +97,8 @@ pub struct TableChangesScanBuilder {
     table_changes: Arc<TableChanges>,
     schema: Option<SchemaRef>,
     predicate: Option<PredicateRef>,
+    include_cdf_columns: bool,
+    cdf_source: CdfSource,
 }
 
 impl TableChangesScanBuilder {
@@ -84,6 +108,8 @@ impl TableChangesScanBuilder {
             table_changes: table_changes.into(),
             schema: None,
             predicate: None,
+            include_cdf_columns: true,
+            cdf_source: CdfSource::default(),
         }
     }
 
@@ -109,6 +135,24 @@ impl TableChangesScanBuilder {
         self
     }
 
+    /// Whether to include the columns generated for Change Data Feed (`_change_type`,
+    /// `_commit_version`, and `_commit_timestamp`) in the scan's output. Defaults to `true`. When
+    /// `false`, these columns are dropped from the logical schema (even if the schema passed to
+    /// [`Self::with_schema`] selects them), e.g. to append the changed rows to a table with the
+    /// original schema. The predicate is still resolved against the schema before they are dropped.
+    pub fn with_cdf_columns(mut self, include_cdf_columns: bool) -> Self {
+        self.include_cdf_columns = include_cdf_columns;
+        self
+    }
+
+    /// Where to read the changes of commits that have both `cdc` files and `add`/`remove` actions
+    /// from. Defaults to [`CdfSource::Cdc`]. See [`CdfSource`] for which commits
+    /// [`CdfSource::AddRemove`] reads from their `add`/`remove` actions.
+    pub fn with_cdf_source(mut self, cdf_source: CdfSource) -> Self {
+        self.cdf_source = cdf_source;
+        self
+    }
+
     /// Build the [`TableChangesScan`].
     ///
     /// This does not scan the table at this point, but does do some work to ensure that the
@@ -120,6 +164,18 @@ impl TableChangesScanBuilder {
         let logical_schema = self
             .schema
             .unwrap_or_else(|| self.table_changes.schema.clone().into());
+        let physical_predicate = match self.predicate {
+            Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?,
+            None => PhysicalPredicate::None,
+        };
+        let logical_schema = if self.include_cdf_columns {
+            logical_schema
+        } else {
+            let is_cdf_field =
+                |field: &StructField| CDF_FIELDS.iter().any(|f| f.name() == field.name());
+            let fields = logical_schema.fields().filter(|field| !is_cdf_field(field));
+            Arc::new(StructType::new(fields.cloned()))
+        };
         let mut read_fields = Vec::with_capacity(logical_schema.fields.len());
 
         // Loop over all selected fields. We produce the following:
@@ -127,22 +183,29 @@ impl TableChangesScanBuilder {
         // - If the field is a column generated by CDF, it is also  ([`ColumnType::Selected`]).
         //   These fields will be handled separately from the other ([`ColumnType::Selected`]).
         // - If the field is a partition column, it is ([`ColumnType::Partition`]).
+        // - If the field is a struct containing partition columns, it is ([`ColumnType::Nested`]).
+        //   Only its non-partition fields are read from the parquet file.
         //
         //   Both the partition columns and CDF generated columns will be filled in by evaluating an
         //   expression when transforming physical data to the logical representation.
+        let partition_columns = self.table_changes.partition_columns();
+        let column_mapping_mode = self.table_changes.end_snapshot.column_mapping_mode();
         let all_fields = logical_schema
             .fields()
             .enumerate()
             .map(|(index, logical_field)| -> DeltaResult<_> {
-                if self
-                    .table_changes
-                    .partition_columns()
-                    .contains(logical_field.name())
-                {
+                if partition_columns.contains(logical_field.name()) {
                     // Store the index into the schema for this field. When we turn it into an
                     // expression in the inner loop, we will index into the schema and get the name and
                     // data type, which we need to properly materialize the column.
                     Ok(ColumnType::Partition(index))
+                } else if let Some((column_type, read_field)) = get_nested_partition_column(
+                    logical_field,
+                    logical_field.name(),
+                    partition_columns,
+                ) {
+                    read_fields.push(read_field.make_physical_with_mode(column_mapping_mode));
+                    Ok(column_type)
                 } else if CDF_FIELDS
                     .iter()
                     .any(|field| field.name() == logical_field.name())
@@ -154,7 +217,7 @@ impl TableChangesScanBuilder {
                 } else {
                     // Add to read schema, store field so we can build a `Column` expression later
                     // if needed (i.e. if we have partition columns)
-                    let physical_field = logical_field.make_physical();
+                    let physical_field = logical_field.make_physical_with_mode(column_mapping_mode);
                     debug!("\n\n{logical_field:#?}\nAfter mapping: {physical_field:#?}\n\n");
                     let physical_name = physical_field.name.clone();
                     read_fields.push(physical_field);
@@ -162,10 +225,6 @@ impl TableChangesScanBuilder {
                 }
             })
             .try_collect()?;
-        let physical_predicate = match self.predicate {
-            Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?,
-            None => PhysicalPredicate::None,
-        };
 
         Ok(TableChangesScan {
             table_changes: self.table_changes,
@@ -173,6 +232,7 @@ impl TableChangesScanBuilder {
             physical_predicate,
             all_fields: Arc::new(all_fields),
             physical_schema: StructType::new(read_fields).into(),
+            cdf_source: self.cdf_source,
         })
     }
 }
@@ -200,7 +260,13 @@ impl TableChangesScan {
             PhysicalPredicate::None => None,
         };
         let schema = self.table_changes.end_snapshot.schema();
-        let it = table_changes_action_iter(engine, commits, schema, physical_predicate)?;
+        let it = table_changes_action_iter(
+            engine,
+            commits,
+            schema,
+            physical_predicate,
+            self.cdf_source,
+        )?;
         Ok(Some(it).into_iter().flatten())
     }
 
@@ -222,6 +288,11 @@ impl TableChangesScan {
         self.table_changes.table_root()
     }
 
+    /// Where the scan reads the changes of commits with `cdc` files from.
+    pub fn cdf_source(&self) -> CdfSource {
+        self.cdf_source
+    }
+
     /// Get the predicate [`PredicateRef`] of the scan.
     fn physical_predicate(&self) -> Option<PredicateRef> {
         if let PhysicalPredicate::Some(ref predicate, _) = self.physical_predicate {
@@ -297,7 +368,7 @@ fn read_scan_file(
     // Determine if the scan file was derived from a deletion vector pair
     let is_dv_resolved_pair = scan_file.remove_dv.is_some();
 
-    let location = table_root.join(&scan_file.path)?;
+    let location = resolve_file_path(table_root, &scan_file.path)?;
     let file = FileMeta {
         last_modified: 0,
         size: 0,
@@ -435,4 +506,20 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn table_changes_scan_builder_rejects_unknown_predicate_column() {
+        let path = "./tests/data/table-with-cdf";
+        let engine = Box::new(SyncEngine::new());
+        let url = delta_kernel::try_parse_uri(path).unwrap();
+
+        let table_changes = TableChanges::try_new(url, engine.as_ref(), 0, Some(1)).unwrap();
+        let predicate = Arc::new(Predicate::gt(column_expr!("no_such_col"), Scalar::from(10)));
+        let result = table_changes
+            .into_scan_builder()
+            .with_predicate(predicate)
+            .build();
+        let err = result.expect_err("unknown column");
+        assert!(err.to_string().contains("no_such_col"));
+    }
 }
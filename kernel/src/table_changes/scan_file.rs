@@ -250,6 +250,7 @@ mod tests {
     use crate::scan::state::DvInfo;
     use crate::schema::{DataType, StructField, StructType};
     use crate::table_changes::log_replay::table_changes_action_iter;
+    use crate::table_changes::scan::CdfSource;
     use crate::utils::test_utils::{Action, LocalMockTable};
     use crate::Engine as _;
 
@@ -338,6 +339,7 @@ mod tests {
             log_segment.ascending_commit_files.clone(),
             table_schema.into(),
             None,
+            CdfSource::default(),
         )
         .unwrap();
         let scan_files: Vec<_> = scan_metadata_to_scan_file(scan_metadata)
@@ -282,6 +282,30 @@ impl TableConfiguration {
         read_supported && write_supported
     }
 
+    /// Returns `true` if the table supports row tracking. To support this feature the table must
+    /// have a min_writer_version of 7 and the [`WriterFeature::RowTracking`] writer feature. Once
+    /// supported, every file added to the table is assigned a `baseRowId`.
+    ///
+    /// See: <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#row-tracking>
+    pub(crate) fn is_row_tracking_supported(&self) -> bool {
+        self.protocol().min_writer_version() == 7
+            && self
+                .protocol()
+                .has_writer_feature(&WriterFeature::RowTracking)
+    }
+
+    /// Returns `true` if the table supports domain metadata. To support this feature the table
+    /// must have a min_writer_version of 7 and the [`WriterFeature::DomainMetadata`] writer
+    /// feature.
+    ///
+    /// See: <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#domain-metadata>
+    pub(crate) fn is_domain_metadata_supported(&self) -> bool {
+        self.protocol().min_writer_version() == 7
+            && self
+                .protocol()
+                .has_writer_feature(&WriterFeature::DomainMetadata)
+    }
+
     /// Returns `true` if the table supports writing in-commit timestamps.
     ///
     /// To support this feature the table must:
@@ -137,6 +137,14 @@ pub struct TableProperties {
     /// whether to enable row tracking during writes.
     pub enable_row_tracking: Option<bool>,
 
+    /// The name of the physical column in which the row ids of rows whose row id is no longer
+    /// their file's `baseRowId` plus their row index (e.g. after an update) are materialized.
+    pub materialized_row_id_column_name: Option<String>,
+
+    /// The name of the physical column in which the row commit versions of rows whose row commit
+    /// version is no longer their file's `defaultRowCommitVersion` are materialized.
+    pub materialized_row_commit_version_column_name: Option<String>,
+
     /// Whether to enable [In-Commit Timestamps]. The in-commit timestamps writer feature strongly
     /// associates a monotonically increasing timestamp with each commit by storing it in the
     /// commit's metadata.
@@ -204,6 +212,20 @@ pub enum IsolationLevel {
     SnapshotIsolation,
 }
 
+impl IsolationLevel {
+    /// The name of the isolation level as written to the `isolationLevel` field of a commit info,
+    /// e.g. `"Serializable"`. See [`Transaction::with_isolation_level`].
+    ///
+    /// [`Transaction::with_isolation_level`]: crate::transaction::Transaction::with_isolation_level
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Serializable => "Serializable",
+            Self::WriteSerializable => "WriteSerializable",
+            Self::SnapshotIsolation => "SnapshotIsolation",
+        }
+    }
+}
+
 /// The checkpoint policy applied when writing checkpoints
 #[derive(Debug, EnumString, Default, Clone, PartialEq, Eq)]
 #[strum(serialize_all = "camelCase")]
@@ -282,6 +304,14 @@ mod tests {
             ("delta.tuneFileSizesForRewrites", "true"),
             ("delta.checkpointPolicy", "v2"),
             ("delta.enableRowTracking", "true"),
+            (
+                "delta.rowTracking.materializedRowIdColumnName",
+                "_row-id-col",
+            ),
+            (
+                "delta.rowTracking.materializedRowCommitVersionColumnName",
+                "_row-commit-version-col",
+            ),
             ("delta.enableInCommitTimestamps", "true"),
             ("delta.inCommitTimestampEnablementVersion", "15"),
             ("delta.inCommitTimestampEnablementTimestamp", "1612345678"),
@@ -310,6 +340,10 @@ mod tests {
             tune_file_sizes_for_rewrites: Some(true),
             checkpoint_policy: Some(CheckpointPolicy::V2),
             enable_row_tracking: Some(true),
+            materialized_row_id_column_name: Some("_row-id-col".to_string()),
+            materialized_row_commit_version_column_name: Some(
+                "_row-commit-version-col".to_string(),
+            ),
             enable_in_commit_timestamps: Some(true),
             in_commit_timestamp_enablement_version: Some(15),
             in_commit_timestamp_enablement_timestamp: Some(1_612_345_678),
@@ -1,18 +1,27 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter;
 use std::sync::{Arc, LazyLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::actions::SetTransaction;
 use crate::actions::COMMIT_INFO_NAME;
-use crate::actions::{get_log_add_schema, get_log_commit_info_schema, get_log_txn_schema};
+use crate::actions::{
+    get_log_add_schema, get_log_commit_info_schema, get_log_metadata_schema, get_log_txn_schema,
+};
+use crate::actions::{Metadata, SetTransaction};
+use crate::engine_data::{GetData, RowVisitor};
 use crate::error::Error;
-use crate::expressions::{column_expr, Scalar, StructData};
+use crate::expressions::{column_expr, column_name, ColumnName, MapData, Scalar, StructData};
 use crate::path::ParsedLogPath;
-use crate::schema::{MapType, SchemaRef, StructField, StructType};
+use crate::schema::compare::SchemaComparison as _;
+use crate::schema::{ColumnNamesAndTypes, MapType, SchemaRef, StructField, StructType};
 use crate::snapshot::Snapshot;
+use crate::table_configuration::TableConfiguration;
+use crate::table_features::{validate_schema_column_mapping, ColumnMappingMode};
+use crate::table_properties::IsolationLevel;
+use crate::utils::require;
 use crate::{DataType, DeltaResult, Engine, EngineData, Expression, IntoEngineData, Version};
 
+use itertools::Itertools;
 use url::Url;
 
 const KERNEL_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -58,6 +67,9 @@ pub fn add_files_schema() -> &'static SchemaRef {
 pub struct Transaction {
     read_snapshot: Arc<Snapshot>,
     operation: Option<String>,
+    operation_metrics: Option<HashMap<String, String>>,
+    isolation_level: Option<IsolationLevel>,
+    user_metadata: Option<String>,
     commit_info: Option<Arc<dyn EngineData>>,
     add_files_metadata: Vec<Box<dyn EngineData>>,
     // NB: hashmap would require either duplicating the appid or splitting SetTransaction
@@ -66,6 +78,10 @@ pub struct Transaction {
     // would make error messaging unnecessarily difficult. Thus, we keep Vec here and deduplicate in
     // the commit method.
     set_transactions: Vec<SetTransaction>,
+    // A staged `metaData` action, if this transaction changes the table schema or properties.
+    metadata_update: Option<Box<Metadata>>,
+    // The table configuration as of this transaction, including any staged metadata update.
+    table_configuration: Box<TableConfiguration>,
     // commit-wide timestamp (in milliseconds since epoch) - used in ICT, `txn` action, etc. to
     // keep all timestamps within the same commit consistent.
     commit_timestamp: i64,
@@ -89,6 +105,16 @@ impl Transaction {
     /// [Snapshot::transaction](crate::snapshot::Snapshot::transaction) to create a transaction from
     /// a snapshot.
     pub(crate) fn try_new(snapshot: impl Into<Arc<Snapshot>>) -> DeltaResult<Self> {
+        Self::try_new_with_clock(snapshot, &SystemClock)
+    }
+
+    /// Create a new transaction from a snapshot, taking its commit timestamp from `clock` instead
+    /// of the system clock. See also
+    /// [Snapshot::transaction_with_clock](crate::snapshot::Snapshot::transaction_with_clock).
+    pub(crate) fn try_new_with_clock(
+        snapshot: impl Into<Arc<Snapshot>>,
+        clock: &dyn Clock,
+    ) -> DeltaResult<Self> {
         let read_snapshot = snapshot.into();
 
         // important! before a read/write to the table we must check it is supported
@@ -96,19 +122,26 @@ impl Transaction {
             .table_configuration()
             .ensure_write_supported()?;
 
-        // TODO: unify all these into a (safer) `fn current_time_ms()`
-        let commit_timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .ok()
-            .and_then(|d| i64::try_from(d.as_millis()).ok())
-            .ok_or_else(|| Error::generic("Failed to get current time for commit_timestamp"))?;
+        let commit_timestamp = clock.now_ms();
+        require!(
+            commit_timestamp >= 0,
+            Error::generic(format!(
+                "Invalid commit timestamp {commit_timestamp}: must not be before the unix epoch"
+            ))
+        );
 
+        let table_configuration = Box::new(read_snapshot.table_configuration().clone());
         Ok(Transaction {
             read_snapshot,
             operation: None,
+            operation_metrics: None,
+            isolation_level: None,
+            user_metadata: None,
             commit_info: None,
             add_files_metadata: vec![],
             set_transactions: vec![],
+            metadata_update: None,
+            table_configuration,
             commit_timestamp,
         })
     }
@@ -137,6 +170,12 @@ impl Transaction {
             .into_iter()
             .map(|txn| txn.into_engine_data(get_log_txn_schema().clone(), engine));
 
+        // data files can only be added to a table that kernel can write to, which a staged metadata
+        // update may have changed
+        if !self.add_files_metadata.is_empty() {
+            self.table_configuration.ensure_write_supported()?;
+        }
+
         // step one: construct the iterator of commit info + file actions we want to commit
         let engine_commit_info = self
             .commit_info
@@ -145,12 +184,19 @@ impl Transaction {
         let commit_info_actions = generate_commit_info(
             engine,
             self.operation.as_deref(),
+            self.operation_metrics.as_ref(),
+            self.isolation_level,
+            self.user_metadata.as_deref(),
             self.commit_timestamp,
             engine_commit_info.as_ref(),
         );
+        let metadata_action = self.metadata_update.clone().map(|metadata| {
+            (*metadata).into_engine_data(get_log_metadata_schema().clone(), engine)
+        });
         let add_actions = generate_adds(engine, self.add_files_metadata.iter().map(|a| a.as_ref()));
 
         let actions = iter::once(commit_info_actions)
+            .chain(metadata_action)
             .chain(add_actions)
             .chain(set_transaction_actions);
 
@@ -175,17 +221,52 @@ impl Transaction {
         self
     }
 
+    /// Add metrics about the operation this transaction is performing (e.g. `numFiles` or
+    /// `numOutputRows`). These are persisted in the commit as the `operationMetrics` map of the
+    /// commit info. Calling this multiple times adds to the set of metrics, overwriting any
+    /// metrics that were previously set with the same name.
+    pub fn with_operation_metrics(mut self, metrics: HashMap<String, String>) -> Self {
+        self.operation_metrics
+            .get_or_insert_with(HashMap::new)
+            .extend(metrics);
+        self
+    }
+
+    /// Set the isolation level this transaction's operation runs at, for engines that implement a
+    /// specific isolation level. It is persisted in the commit as the `isolationLevel` of the
+    /// commit info. Note that kernel does not itself enforce the isolation level.
+    pub fn with_isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Attach an arbitrary string to this transaction's commit, e.g. to describe the change in the
+    /// table's history. It is persisted in the commit as the `userMetadata` of the commit info.
+    pub fn with_user_metadata(mut self, user_metadata: String) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
     /// Include a SetTransaction (app_id and version) action for this transaction (with an optional
     /// `last_updated` timestamp).
     /// Note that each app_id can only appear once per transaction. That is, multiple app_ids with
     /// different versions are disallowed in a single transaction. If a duplicate app_id is
     /// included, the `commit` will fail (that is, we don't eagerly check app_id validity here).
+    /// Use [`Transaction::set_transaction_id`] to replace the version of an already included app_id.
     pub fn with_transaction_id(mut self, app_id: String, version: i64) -> Self {
         let set_transaction = SetTransaction::new(app_id, version, Some(self.commit_timestamp));
         self.set_transactions.push(set_transaction);
         self
     }
 
+    /// Like [`Transaction::with_transaction_id`], but replaces any SetTransaction action already
+    /// included for `app_id` instead of adding a duplicate. That is, if this is called multiple
+    /// times for the same app_id, only the last version is committed.
+    pub fn set_transaction_id(mut self, app_id: String, version: i64) -> Self {
+        self.set_transactions.retain(|t| t.app_id != app_id);
+        self.with_transaction_id(app_id, version)
+    }
+
     /// WARNING: This is an unstable API and will likely change in the future.
     ///
     /// Add commit info to the transaction. This is commit-wide metadata that is written as the
@@ -204,30 +285,59 @@ impl Transaction {
         self
     }
 
-    // Generate the logical-to-physical transform expression which must be evaluated on every data
-    // chunk before writing. At the moment, this is a transaction-wide expression.
-    fn generate_logical_to_physical(&self) -> Expression {
-        // for now, we just pass through all the columns except partition columns.
-        // note this is _incorrect_ if table config deems we need partition columns.
-        let partition_columns = &self.read_snapshot.metadata().partition_columns;
-        let schema = self.read_snapshot.schema();
-        let fields = schema
-            .fields()
-            .filter(|f| !partition_columns.contains(f.name()))
-            .map(|f| Expression::column([f.name()]));
-        Expression::struct_from(fields)
+    /// Get the write context for this transaction. At the moment, this is constant for the whole
+    /// transaction, except that it reflects a staged metadata update (see
+    /// [`Transaction::update_metadata`]).
+    ///
+    /// Returns an [`Error::Unsupported`] if kernel can't write data to the table, e.g. because a
+    /// staged metadata update adds a column with invariants.
+    pub fn get_write_context(&self) -> DeltaResult<WriteContext> {
+        self.table_configuration.ensure_write_supported()?;
+        WriteContext::try_new_from(&self.table_configuration)
     }
 
-    /// Get the write context for this transaction. At the moment, this is constant for the whole
-    /// transaction.
-    // Note: after we introduce metadata updates (modify table schema, etc.), we need to make sure
-    // that engines cannot call this method after a metadata change, since the write context could
-    // have invalid metadata.
-    pub fn get_write_context(&self) -> WriteContext {
-        let target_dir = self.read_snapshot.table_root();
-        let snapshot_schema = self.read_snapshot.schema();
-        let logical_to_physical = self.generate_logical_to_physical();
-        WriteContext::new(target_dir.clone(), snapshot_schema, logical_to_physical)
+    /// Stage a `metaData` action that changes the table schema and/or table properties. If
+    /// `new_schema` is `None`, the schema is left unchanged. Each entry of `property_updates` is
+    /// set in the table configuration, replacing any existing value for the same key.
+    ///
+    /// The new schema must be readable by readers of the current schema: columns can be added (if
+    /// nullable) and nullability can be relaxed, but columns cannot be removed, renamed, or change
+    /// type. Calling this multiple times applies each update on top of the previous one.
+    ///
+    /// Once a metadata update is staged, [`Transaction::get_write_context`] returns a write context
+    /// for the updated metadata, or an error if kernel can't write data to the updated table. Since
+    /// the update is additive, data written with a write context obtained before the update remains
+    /// readable with the new schema.
+    pub fn update_metadata(
+        mut self,
+        new_schema: Option<SchemaRef>,
+        property_updates: HashMap<String, String>,
+    ) -> DeltaResult<Self> {
+        let mut metadata = self
+            .metadata_update
+            .take()
+            .unwrap_or_else(|| Box::new(self.read_snapshot.metadata().clone()));
+        if let Some(new_schema) = new_schema {
+            let current_schema = metadata.parse_schema()?;
+            current_schema
+                .can_read_as(&new_schema)
+                .map_err(|e| Error::schema(format!("Incompatible schema update: {e}")))?;
+            metadata.schema_string = serde_json::to_string(new_schema.as_ref())?;
+        }
+        metadata.configuration.extend(property_updates);
+
+        // make sure the updated table is still valid. Whether data can be written to it is checked
+        // when data is written, see `get_write_context`.
+        let table_configuration = TableConfiguration::try_new_from(
+            self.read_snapshot.table_configuration(),
+            Some(metadata.as_ref().clone()),
+            None,
+            self.read_snapshot.version() + 1,
+        )?;
+
+        self.table_configuration = Box::new(table_configuration);
+        self.metadata_update = Some(metadata);
+        Ok(self)
     }
 
     /// Add files to include in this transaction. This API generally enables the engine to
@@ -269,21 +379,46 @@ fn generate_adds<'a>(
 /// write table data.
 ///
 /// [`Transaction`]: struct.Transaction.html
+#[derive(Clone)]
 pub struct WriteContext {
     target_dir: Url,
     schema: SchemaRef,
+    partition_columns: Vec<String>,
     logical_to_physical: Expression,
 }
 
 impl WriteContext {
-    fn new(target_dir: Url, schema: SchemaRef, logical_to_physical: Expression) -> Self {
+    fn new(
+        target_dir: Url,
+        schema: SchemaRef,
+        partition_columns: Vec<String>,
+        logical_to_physical: Expression,
+    ) -> Self {
         WriteContext {
             target_dir,
             schema,
+            partition_columns,
             logical_to_physical,
         }
     }
 
+    // Creates the write context for a table with the given configuration
+    fn try_new_from(table_configuration: &TableConfiguration) -> DeltaResult<Self> {
+        let schema = table_configuration.schema();
+        let partition_columns = table_configuration.metadata().partition_columns.clone();
+        let logical_to_physical = Self::logical_to_physical_transform(
+            &schema,
+            &partition_columns,
+            table_configuration.column_mapping_mode(),
+        )?;
+        Ok(Self::new(
+            table_configuration.table_root().clone(),
+            schema,
+            partition_columns,
+            logical_to_physical,
+        ))
+    }
+
     pub fn target_dir(&self) -> &Url {
         &self.target_dir
     }
@@ -292,9 +427,83 @@ impl WriteContext {
         &self.schema
     }
 
+    /// The names of the table's partition columns, in the order declared by the table metadata.
+    pub fn partition_columns(&self) -> &[String] {
+        &self.partition_columns
+    }
+
+    /// The names and data types of the table's partition columns, in the order declared by the
+    /// table metadata. Engines need the types to serialize partition values, e.g. into the
+    /// partition directory names of written files.
+    pub fn partition_column_types(&self) -> Vec<(String, DataType)> {
+        // partition columns are validated against the schema when the write context is created
+        self.partition_columns
+            .iter()
+            .filter_map(|name| {
+                let field = self.schema.field(name)?;
+                Some((name.clone(), field.data_type().clone()))
+            })
+            .collect()
+    }
+
     pub fn logical_to_physical(&self) -> &Expression {
         &self.logical_to_physical
     }
+
+    /// Computes the expression that transforms write data with the given `logical_schema` into the
+    /// physical data to be written to parquet files. This is the same transform exposed by
+    /// [`WriteContext::logical_to_physical`], for engines that want to apply it independently.
+    ///
+    /// Partition columns are dropped, since their values are stored in the `add` action instead of
+    /// the data files. The expression references columns by logical name; column mapping renames
+    /// take effect by evaluating it into the physical schema (see [`StructField::make_physical`]).
+    ///
+    /// Returns an error if `logical_schema` is not annotated consistently with
+    /// `column_mapping_mode`, or if a partition column is not a top-level column of the schema.
+    pub fn logical_to_physical_transform(
+        logical_schema: &StructType,
+        partition_columns: &[String],
+        column_mapping_mode: ColumnMappingMode,
+    ) -> DeltaResult<Expression> {
+        validate_schema_column_mapping(logical_schema, column_mapping_mode)?;
+        if let Some(missing) = partition_columns
+            .iter()
+            .find(|col| logical_schema.field(col).is_none())
+        {
+            return Err(Error::schema(format!(
+                "Partition column {missing} not found in schema"
+            )));
+        }
+        let fields = logical_schema
+            .fields()
+            .filter(|f| !partition_columns.contains(f.name()))
+            .map(|f| Expression::column([f.name()]));
+        Ok(Expression::struct_from(fields))
+    }
+}
+
+/// A source of commit timestamps for a [`Transaction`]. The clock is read once, when the
+/// transaction is created, so that all timestamps within a commit (e.g. in `commitInfo` and `txn`
+/// actions) are consistent. The default is [`SystemClock`]; a custom clock can be supplied with
+/// [`Snapshot::transaction_with_clock`], e.g. to make commit timestamps deterministic in tests.
+pub trait Clock: Send + Sync {
+    /// The current time, in milliseconds since the unix epoch.
+    fn now_ms(&self) -> i64;
+}
+
+/// A [`Clock`] that reads the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        // NOTE: A system time before the unix epoch is reported as a negative timestamp, which
+        // `Transaction` rejects.
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => i64::try_from(d.as_millis()).unwrap_or(i64::MAX),
+            Err(e) => i64::try_from(e.duration().as_millis()).map_or(i64::MIN, |ms| -ms),
+        }
+    }
 }
 
 /// Result after committing a transaction. If 'committed', the version is the new version written
@@ -310,10 +519,25 @@ pub enum CommitResult {
     Conflict(Transaction, Version),
 }
 
+impl CommitResult {
+    /// Convert this result into the committed version, for callers which do not want to retry on
+    /// conflict. A conflict is returned as an [`Error::CommitConflict`] with the conflicting
+    /// version, and the returned transaction is dropped.
+    pub fn into_version(self) -> DeltaResult<Version> {
+        match self {
+            CommitResult::Committed(version) => Ok(version),
+            CommitResult::Conflict(_, version) => Err(Error::CommitConflict(version)),
+        }
+    }
+}
+
 // given the engine's commit info we want to create commitInfo action to commit (and append more actions to)
 fn generate_commit_info(
     engine: &dyn Engine,
     operation: Option<&str>,
+    operation_metrics: Option<&HashMap<String, String>>,
+    isolation_level: Option<IsolationLevel>,
+    user_metadata: Option<&str>,
     timestamp: i64,
     engine_commit_info: &dyn EngineData,
 ) -> DeltaResult<Box<dyn EngineData>> {
@@ -324,6 +548,31 @@ fn generate_commit_info(
         )));
     }
 
+    // Check the type of the `engineCommitInfo` column up front, so a mismatch surfaces as a clear
+    // error rather than an engine-specific failure while evaluating the expression below. A missing
+    // column is fine: the evaluator substitutes a null map for it.
+    match EngineCommitInfoTypeChecker.visit_rows_of(engine_commit_info) {
+        Ok(()) => {}
+        Err(Error::MissingColumn(_)) => {}
+        Err(Error::Backtraced { source, .. }) if matches!(*source, Error::MissingColumn(_)) => {}
+        Err(err) => {
+            return Err(Error::InvalidCommitInfo(format!(
+                "engineCommitInfo must be a map<string, string>: {err}"
+            )))
+        }
+    }
+
+    // sort the metrics so that they are written in a deterministic order
+    let operation_metrics_type = MapType::new(DataType::STRING, DataType::STRING, false);
+    let operation_metrics = match operation_metrics {
+        Some(metrics) => {
+            let metrics = metrics.iter().map(|(k, v)| (k.as_str(), v.as_str()));
+            let map_data = MapData::try_new(operation_metrics_type, metrics.sorted())?;
+            Scalar::Map(map_data)
+        }
+        None => Scalar::Null(operation_metrics_type.into()),
+    };
+
     let commit_info_exprs = [
         Expression::literal(timestamp),
         Expression::literal(operation.unwrap_or(UNKNOWN_OPERATION)),
@@ -336,6 +585,15 @@ fn generate_commit_info(
             )],
             vec![Scalar::Null(DataType::INTEGER)],
         )?)),
+        Expression::literal(operation_metrics),
+        Expression::literal(match isolation_level {
+            Some(level) => Scalar::from(level.as_str()),
+            None => Scalar::Null(DataType::STRING),
+        }),
+        Expression::literal(match user_metadata {
+            Some(user_metadata) => Scalar::from(user_metadata),
+            None => Scalar::Null(DataType::STRING),
+        }),
         Expression::literal(format!("v{KERNEL_VERSION}")),
         column_expr!("engineCommitInfo"),
     ];
@@ -383,13 +641,42 @@ fn generate_commit_info(
     commit_info_evaluator.evaluate(engine_commit_info)
 }
 
+/// A visitor that reads nothing, used only to validate that the engine commit info's
+/// `engineCommitInfo` column (if present) is a `map<string, string>`.
+struct EngineCommitInfoTypeChecker;
+
+impl RowVisitor for EngineCommitInfoTypeChecker {
+    fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+        static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
+            let map_type = MapType::new(DataType::STRING, DataType::STRING, true);
+            (
+                vec![column_name!("engineCommitInfo")],
+                vec![map_type.into()],
+            )
+                .into()
+        });
+        NAMES_AND_TYPES.as_ref()
+    }
+
+    fn visit<'a>(
+        &mut self,
+        _row_count: usize,
+        _getters: &[&'a dyn GetData<'a>],
+    ) -> DeltaResult<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::path::PathBuf;
+
     use crate::engine::arrow_data::ArrowEngineData;
     use crate::engine::arrow_expression::ArrowEvaluationHandler;
-    use crate::schema::MapType;
+    use crate::engine::sync::SyncEngine;
+    use crate::schema::{MapType, MetadataValue};
     use crate::{EvaluationHandler, JsonHandler, ParquetHandler, StorageHandler};
 
     use crate::arrow::array::{MapArray, MapBuilder, MapFieldNames, StringArray, StringBuilder};
@@ -487,6 +774,9 @@ mod tests {
         let actions = generate_commit_info(
             &engine,
             Some("test operation"),
+            None,
+            None,
+            None,
             123456789,
             &ArrowEngineData::new(commit_info_batch),
         )?;
@@ -548,6 +838,9 @@ mod tests {
         let actions = generate_commit_info(
             &engine,
             Some("test operation"),
+            None,
+            None,
+            None,
             123456789,
             &ArrowEngineData::new(commit_info_batch),
         )?;
@@ -587,6 +880,9 @@ mod tests {
         let _ = generate_commit_info(
             &engine,
             Some("test operation"),
+            None,
+            None,
+            None,
             123456789,
             &ArrowEngineData::new(commit_info_batch),
         )
@@ -613,18 +909,20 @@ mod tests {
             vec![Arc::new(StringArray::new_null(1))],
         )?;
 
-        let _ = generate_commit_info(
+        let result = generate_commit_info(
             &engine,
             Some("test operation"),
+            None,
+            None,
+            None,
             123456789,
             &ArrowEngineData::new(commit_info_batch),
-        )
-        .map_err(|e| match e {
-            Error::Arrow(ArrowError::InvalidArgumentError(_)) => (),
-            Error::Backtraced { source, .. }
-                if matches!(&*source, Error::Arrow(ArrowError::InvalidArgumentError(_))) => {}
-            _ => panic!("expected arrow invalid arg error, got {e:?}"),
-        });
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidCommitInfo(msg))
+                if msg.starts_with("engineCommitInfo must be a map<string, string>")
+        ));
 
         Ok(())
     }
@@ -709,6 +1007,9 @@ mod tests {
             let actions = generate_commit_info(
                 &engine,
                 Some("test operation"),
+                None,
+                None,
+                None,
                 timestamp,
                 &ArrowEngineData::new(commit_info_batch),
             )?;
@@ -718,6 +1019,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_logical_to_physical_transform() {
+        let mapped_field = |name: &str, id: i64| {
+            StructField::nullable(name, DataType::STRING).with_metadata([
+                ("delta.columnMapping.id", MetadataValue::Number(id)),
+                (
+                    "delta.columnMapping.physicalName",
+                    MetadataValue::String(format!("col-{id}")),
+                ),
+            ])
+        };
+        let schema = StructType::new([
+            mapped_field("id", 1),
+            mapped_field("part", 2),
+            mapped_field("value", 3),
+        ]);
+        let partition_columns = vec!["part".to_string()];
+
+        let transform = WriteContext::logical_to_physical_transform(
+            &schema,
+            &partition_columns,
+            ColumnMappingMode::Name,
+        )
+        .unwrap();
+        let expected = Expression::struct_from([column_expr!("id"), column_expr!("value")]);
+        assert_eq!(transform, expected);
+
+        // the schema annotations must agree with the column mapping mode
+        let result = WriteContext::logical_to_physical_transform(
+            &schema,
+            &partition_columns,
+            ColumnMappingMode::None,
+        );
+        assert!(matches!(result, Err(Error::InvalidColumnMappingMode(_))));
+
+        // partition columns must exist in the schema
+        let result = WriteContext::logical_to_physical_transform(
+            &schema,
+            &["missing".to_string()],
+            ColumnMappingMode::Name,
+        );
+        assert!(matches!(result, Err(Error::Schema(_))));
+    }
+
     #[test]
     fn test_add_files_schema() {
         let schema = add_files_schema();
@@ -733,4 +1078,19 @@ mod tests {
         ]);
         assert_eq!(*schema, expected.into());
     }
+
+    #[test]
+    fn test_commit_result_into_version() {
+        assert_eq!(CommitResult::Committed(5).into_version().unwrap(), 5);
+
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-without-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let snapshot = Snapshot::try_new(url, &SyncEngine::new(), None).unwrap();
+        let txn = Transaction::try_new(snapshot).unwrap();
+        assert!(matches!(
+            CommitResult::Conflict(txn, 5).into_version(),
+            Err(Error::CommitConflict(5))
+        ));
+    }
 }
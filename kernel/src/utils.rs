@@ -158,8 +158,12 @@ pub(crate) mod test_utils {
     use crate::arrow::datatypes::{DataType, Field, Schema as ArrowSchema};
     use crate::engine::arrow_data::ArrowEngineData;
     use crate::engine::sync::SyncEngine;
+    use crate::engine_data::{GetData, RowVisitor};
+    use crate::expressions::{ColumnName, Scalar};
+    use crate::schema::DataType as KernelDataType;
     use crate::Engine;
     use crate::EngineData;
+    use crate::{DeltaResult, Error};
 
     use crate::object_store::local::LocalFileSystem;
     use crate::object_store::ObjectStore;
@@ -247,6 +251,74 @@ pub(crate) mod test_utils {
         Box::new(ArrowEngineData::new(batch))
     }
 
+    /// Reads the values of the (possibly nested) column `column_name` of `data` as [`Scalar`]s of
+    /// type `data_type`, with nulls as [`Scalar::Null`]. Only the types that [`GetData`] can
+    /// extract (boolean, integer, long and string) are supported.
+    pub(crate) fn engine_data_column_as_scalars(
+        data: &dyn EngineData,
+        column_name: ColumnName,
+        data_type: KernelDataType,
+    ) -> DeltaResult<Vec<Scalar>> {
+        // The column is selected by name when calling `visit_rows` below, so the visitor only needs
+        // to report its type. Each supported type has a static slice to report.
+        let types: &'static [KernelDataType] = match data_type {
+            KernelDataType::BOOLEAN => &[KernelDataType::BOOLEAN],
+            KernelDataType::INTEGER => &[KernelDataType::INTEGER],
+            KernelDataType::LONG => &[KernelDataType::LONG],
+            KernelDataType::STRING => &[KernelDataType::STRING],
+            _ => {
+                return Err(Error::unsupported(format!(
+                    "Can't read column {column_name} of type {data_type} as scalars"
+                )))
+            }
+        };
+        struct ColumnVisitor {
+            field_name: String,
+            types: &'static [KernelDataType],
+            values: Vec<Scalar>,
+        }
+        impl RowVisitor for ColumnVisitor {
+            fn selected_column_names_and_types(
+                &self,
+            ) -> (&'static [ColumnName], &'static [KernelDataType]) {
+                (&[], self.types)
+            }
+            fn visit<'a>(
+                &mut self,
+                row_count: usize,
+                getters: &[&'a dyn GetData<'a>],
+            ) -> DeltaResult<()> {
+                let field_name = &self.field_name;
+                let data_type = &self.types[0];
+                for row_index in 0..row_count {
+                    let value = match *data_type {
+                        KernelDataType::BOOLEAN => getters[0]
+                            .get_bool(row_index, field_name)?
+                            .map(Scalar::from),
+                        KernelDataType::INTEGER => {
+                            getters[0].get_int(row_index, field_name)?.map(Scalar::from)
+                        }
+                        KernelDataType::LONG => getters[0]
+                            .get_long(row_index, field_name)?
+                            .map(Scalar::from),
+                        _ => getters[0].get_str(row_index, field_name)?.map(Scalar::from),
+                    };
+                    self.values
+                        .push(value.unwrap_or_else(|| Scalar::Null(data_type.clone())));
+                }
+                Ok(())
+            }
+        }
+
+        let mut visitor = ColumnVisitor {
+            field_name: column_name.to_string(),
+            types,
+            values: vec![],
+        };
+        data.visit_rows(&[column_name], &mut visitor)?;
+        Ok(visitor.values)
+    }
+
     pub(crate) fn parse_json_batch(json_strings: StringArray) -> Box<dyn EngineData> {
         let engine = SyncEngine::new();
         let json_handler = engine.json_handler();
@@ -318,6 +390,41 @@ mod tests {
         resolve_uri_type("file://foo/bar").expect_err("file://foo/bar should not have parsed");
     }
 
+    #[test]
+    fn test_engine_data_column_as_scalars() {
+        use crate::expressions::{column_name, Scalar};
+        use crate::schema::DataType;
+        use crate::utils::test_utils::{action_batch, engine_data_column_as_scalars};
+
+        let batch = action_batch();
+        let sizes =
+            engine_data_column_as_scalars(batch.as_ref(), column_name!("add.size"), DataType::LONG)
+                .unwrap();
+        assert_eq!(sizes.len(), 9);
+        assert_eq!(sizes[0], Scalar::Long(635));
+        assert!(sizes[1..].iter().all(|size| size.is_null()));
+
+        let versions = engine_data_column_as_scalars(
+            batch.as_ref(),
+            column_name!("protocol.minReaderVersion"),
+            DataType::INTEGER,
+        )
+        .unwrap();
+        assert_eq!(versions[3], Scalar::Integer(3));
+        assert!(matches!(versions[0], Scalar::Null(DataType::INTEGER)));
+
+        let app_ids = engine_data_column_as_scalars(
+            batch.as_ref(),
+            column_name!("txn.appId"),
+            DataType::STRING,
+        )
+        .unwrap();
+        assert_eq!(app_ids[7], Scalar::from("myApp"));
+
+        engine_data_column_as_scalars(batch.as_ref(), column_name!("add.path"), DataType::DATE)
+            .expect_err("dates are not supported");
+    }
+
     #[test]
     fn try_from_uri_without_trailing_slash() {
         let location = "s3://foo/__unitystorage/catalogs/cid/tables/tid";
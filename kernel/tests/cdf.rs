@@ -1,16 +1,24 @@
+use std::collections::HashMap;
 use std::error;
+use std::sync::Arc;
 
-use delta_kernel::arrow::array::RecordBatch;
+use delta_kernel::arrow::array::{AsArray as _, RecordBatch};
 use delta_kernel::arrow::compute::filter_record_batch;
 use delta_kernel::arrow::datatypes::Schema as ArrowSchema;
+use delta_kernel::arrow::datatypes::{Int32Type, Int64Type};
 use itertools::Itertools;
 
 use delta_kernel::engine::arrow_conversion::TryFromKernel as _;
+use delta_kernel::engine::default::executor::tokio::TokioBackgroundExecutor;
 use delta_kernel::engine::default::DefaultEngine;
+use delta_kernel::object_store::{memory::InMemory, path::Path, ObjectStore as _};
+use delta_kernel::table_changes::scan::{CdfSource, TableChangesScan};
 use delta_kernel::table_changes::TableChanges;
-use delta_kernel::{DeltaResult, Error, PredicateRef, Version};
+use delta_kernel::{DeltaResult, Engine, Error, PredicateRef, Version};
 
-use test_utils::DefaultEngineExtension;
+use test_utils::{
+    add_commit, generate_batch, record_batch_to_bytes, DefaultEngineExtension, IntoArray as _,
+};
 
 mod common;
 use common::load_test_data;
@@ -48,6 +56,13 @@ fn read_cdf_for_table(
         .with_schema(schema)
         .with_predicate(predicate)
         .build()?;
+    read_cdf_scan(&scan, engine)
+}
+
+fn read_cdf_scan(
+    scan: &TableChangesScan,
+    engine: Arc<dyn Engine>,
+) -> DeltaResult<Vec<RecordBatch>> {
     let scan_schema_as_arrow =
         ArrowSchema::try_from_kernel(scan.logical_schema().as_ref()).unwrap();
     let batches: Vec<RecordBatch> = scan
@@ -151,6 +166,254 @@ fn basic_cdf() -> Result<(), Box<dyn error::Error>> {
     Ok(())
 }
 
+#[test]
+fn update_pre_and_post_images_are_paired() -> Result<(), Box<dyn error::Error>> {
+    let batches = read_cdf_for_table("cdf-table", 0, None, None)?;
+    // (_commit_version, _change_type) -> ids of the changed rows
+    let mut changes: HashMap<(i64, String), Vec<i32>> = HashMap::new();
+    for batch in &batches {
+        let column = |name| batch.column_by_name(name).unwrap();
+        let ids = column("id").as_primitive::<Int32Type>();
+        let change_types = column("_change_type").as_string::<i32>();
+        let versions = column("_commit_version").as_primitive::<Int64Type>();
+        for row in 0..batch.num_rows() {
+            let key = (versions.value(row), change_types.value(row).to_string());
+            changes.entry(key).or_default().push(ids.value(row));
+        }
+    }
+    for ids in changes.values_mut() {
+        ids.sort();
+    }
+    let ids = |version, change_type: &str| changes.get(&(version, change_type.to_string()));
+
+    // Both update commits report each updated row once before and once after the update, and never
+    // as a plain delete or insert.
+    for (version, expected) in [(1, vec![2, 3, 4]), (2, vec![5, 6, 7])] {
+        assert_eq!(ids(version, "update_preimage"), Some(&expected));
+        assert_eq!(ids(version, "update_postimage"), Some(&expected));
+        assert_eq!(ids(version, "delete"), None);
+        assert_eq!(ids(version, "insert"), None);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn cdf_sources_agree() -> Result<(), Box<dyn error::Error>> {
+    let storage = Arc::new(InMemory::new());
+    let schema = r#"{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}"#;
+    let commit0 = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":4}}"#.to_string(),
+        format!(
+            r#"{{"metaData":{{"id":"test","format":{{"provider":"parquet","options":{{}}}},"schemaString":"{schema}","partitionColumns":[],"configuration":{{"delta.enableChangeDataFeed":"true"}},"createdTime":1677811175819}}}}"#
+        ),
+    ];
+    add_commit(storage.as_ref(), 0, commit0.join("\n")).await?;
+
+    // Commit 1 inserts two rows, which are recorded by both an add action and a cdc file
+    let data = generate_batch(vec![("id", vec![1, 2].into_array())])?;
+    let data = record_batch_to_bytes(&data);
+    let cdc = generate_batch(vec![
+        ("id", vec![1, 2].into_array()),
+        ("_change_type", vec!["insert", "insert"].into_array()),
+    ])?;
+    let cdc = record_batch_to_bytes(&cdc);
+    let commit1 = [
+        format!(
+            r#"{{"add":{{"path":"part-1.parquet","partitionValues":{{}},"size":{},"modificationTime":1677811178336,"dataChange":true}}}}"#,
+            data.len()
+        ),
+        format!(
+            r#"{{"cdc":{{"path":"_change_data/cdc-1.parquet","partitionValues":{{}},"size":{},"dataChange":false}}}}"#,
+            cdc.len()
+        ),
+    ];
+    storage
+        .put(&Path::from("part-1.parquet"), data.into())
+        .await?;
+    let cdc_path = Path::from("_change_data/cdc-1.parquet");
+    storage.put(&cdc_path, cdc.into()).await?;
+    add_commit(storage.as_ref(), 1, commit1.join("\n")).await?;
+
+    let engine: Arc<dyn Engine> = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let read = |cdf_source, version| -> DeltaResult<Vec<RecordBatch>> {
+        let url = url::Url::parse("memory:///")?;
+        let table_changes = TableChanges::try_new(url, engine.as_ref(), version, Some(version))?;
+        let schema = table_changes
+            .schema()
+            .project(&["id", "_change_type", "_commit_version"])?;
+        let scan = table_changes
+            .into_scan_builder()
+            .with_schema(schema)
+            .with_cdf_source(cdf_source)
+            .build()?;
+        assert_eq!(scan.cdf_source(), cdf_source);
+        read_cdf_scan(&scan, engine.clone())
+    };
+
+    let mut expected = vec![
+        "+----+--------------+-----------------+",
+        "| id | _change_type | _commit_version |",
+        "+----+--------------+-----------------+",
+        "| 1  | insert       | 1               |",
+        "| 2  | insert       | 1               |",
+        "+----+--------------+-----------------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &read(CdfSource::Cdc, 1)?);
+    assert_batches_sorted_eq!(expected, &read(CdfSource::AddRemove, 1)?);
+
+    // Commit 2 updates row 2 to 20 by rewriting the file. Its remove and add actions would report
+    // the unchanged row 1 as deleted and re-inserted, so both sources read the cdc file, which has
+    // the pre- and post-image of the updated row.
+    let data = generate_batch(vec![("id", vec![1, 20].into_array())])?;
+    let data = record_batch_to_bytes(&data);
+    let cdc = generate_batch(vec![
+        ("id", vec![2, 20].into_array()),
+        (
+            "_change_type",
+            vec!["update_preimage", "update_postimage"].into_array(),
+        ),
+    ])?;
+    let cdc = record_batch_to_bytes(&cdc);
+    let commit2 = [
+        r#"{"remove":{"path":"part-1.parquet","partitionValues":{},"deletionTimestamp":1677811179336,"dataChange":true}}"#.to_string(),
+        format!(
+            r#"{{"add":{{"path":"part-2.parquet","partitionValues":{{}},"size":{},"modificationTime":1677811179336,"dataChange":true}}}}"#,
+            data.len()
+        ),
+        format!(
+            r#"{{"cdc":{{"path":"_change_data/cdc-2.parquet","partitionValues":{{}},"size":{},"dataChange":false}}}}"#,
+            cdc.len()
+        ),
+    ];
+    storage
+        .put(&Path::from("part-2.parquet"), data.into())
+        .await?;
+    storage
+        .put(&Path::from("_change_data/cdc-2.parquet"), cdc.into())
+        .await?;
+    add_commit(storage.as_ref(), 2, commit2.join("\n")).await?;
+
+    let mut expected_cdc = vec![
+        "+----+------------------+-----------------+",
+        "| id | _change_type     | _commit_version |",
+        "+----+------------------+-----------------+",
+        "| 2  | update_preimage  | 2               |",
+        "| 20 | update_postimage | 2               |",
+        "+----+------------------+-----------------+",
+    ];
+    sort_lines!(expected_cdc);
+    assert_batches_sorted_eq!(expected_cdc, &read(CdfSource::Cdc, 2)?);
+    assert_batches_sorted_eq!(expected_cdc, &read(CdfSource::AddRemove, 2)?);
+
+    // Only the default source reads the cdc file
+    storage.delete(&cdc_path).await?;
+    assert!(read(CdfSource::Cdc, 1).is_err());
+    assert_batches_sorted_eq!(expected, &read(CdfSource::AddRemove, 1)?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn cdf_with_generated_column() -> Result<(), Box<dyn error::Error>> {
+    let storage = Arc::new(InMemory::new());
+    let schema = r#"{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}},{\"name\":\"id_plus_one\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{\"delta.generationExpression\":\"id + 1\"}}]}"#;
+    let commit0 = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":4}}"#.to_string(),
+        format!(
+            r#"{{"metaData":{{"id":"test","format":{{"provider":"parquet","options":{{}}}},"schemaString":"{schema}","partitionColumns":[],"configuration":{{"delta.enableChangeDataFeed":"true"}},"createdTime":1677811175819}}}}"#
+        ),
+    ];
+    add_commit(storage.as_ref(), 0, commit0.join("\n")).await?;
+
+    // Commit 1 inserts two rows, whose changes are read from the add action
+    let data = generate_batch(vec![
+        ("id", vec![1, 2].into_array()),
+        ("id_plus_one", vec![2, 3].into_array()),
+    ])?;
+    let data = record_batch_to_bytes(&data);
+    let commit1 = [format!(
+        r#"{{"add":{{"path":"part-1.parquet","partitionValues":{{}},"size":{},"modificationTime":1677811178336,"dataChange":true}}}}"#,
+        data.len()
+    )];
+    storage
+        .put(&Path::from("part-1.parquet"), data.into())
+        .await?;
+    add_commit(storage.as_ref(), 1, commit1.join("\n")).await?;
+
+    // Commit 2 updates row 2 to 5, whose changes are read from the cdc file
+    let data = generate_batch(vec![
+        ("id", vec![1, 5].into_array()),
+        ("id_plus_one", vec![2, 6].into_array()),
+    ])?;
+    let data = record_batch_to_bytes(&data);
+    let cdc = generate_batch(vec![
+        ("id", vec![2, 5].into_array()),
+        ("id_plus_one", vec![3, 6].into_array()),
+        (
+            "_change_type",
+            vec!["update_preimage", "update_postimage"].into_array(),
+        ),
+    ])?;
+    let cdc = record_batch_to_bytes(&cdc);
+    let commit2 = [
+        r#"{"remove":{"path":"part-1.parquet","partitionValues":{},"deletionTimestamp":1677811179336,"dataChange":true}}"#.to_string(),
+        format!(
+            r#"{{"add":{{"path":"part-2.parquet","partitionValues":{{}},"size":{},"modificationTime":1677811179336,"dataChange":true}}}}"#,
+            data.len()
+        ),
+        format!(
+            r#"{{"cdc":{{"path":"_change_data/cdc-2.parquet","partitionValues":{{}},"size":{},"dataChange":false}}}}"#,
+            cdc.len()
+        ),
+    ];
+    storage
+        .put(&Path::from("part-2.parquet"), data.into())
+        .await?;
+    storage
+        .put(&Path::from("_change_data/cdc-2.parquet"), cdc.into())
+        .await?;
+    add_commit(storage.as_ref(), 2, commit2.join("\n")).await?;
+
+    let engine: Arc<dyn Engine> = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let url = url::Url::parse("memory:///")?;
+    let table_changes = TableChanges::try_new(url, engine.as_ref(), 1, Some(2))?;
+    let generated_field = table_changes.schema().field("id_plus_one").unwrap();
+    assert!(generated_field
+        .metadata()
+        .contains_key("delta.generationExpression"));
+    let schema = table_changes.schema().project(&[
+        "id",
+        "id_plus_one",
+        "_change_type",
+        "_commit_version",
+    ])?;
+    let scan = table_changes
+        .into_scan_builder()
+        .with_schema(schema)
+        .build()?;
+
+    // The generated column's values are read from the data and cdc files
+    let mut expected = vec![
+        "+----+-------------+------------------+-----------------+",
+        "| id | id_plus_one | _change_type     | _commit_version |",
+        "+----+-------------+------------------+-----------------+",
+        "| 1  | 2           | insert           | 1               |",
+        "| 2  | 3           | insert           | 1               |",
+        "| 2  | 3           | update_preimage  | 2               |",
+        "| 5  | 6           | update_postimage | 2               |",
+        "+----+-------------+------------------+-----------------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &read_cdf_scan(&scan, engine)?);
+    Ok(())
+}
+
 #[test]
 fn cdf_non_partitioned() -> Result<(), Box<dyn error::Error>> {
     let batches = read_cdf_for_table("cdf-table-non-partitioned", 0, None, None)?;
@@ -555,3 +818,47 @@ fn conditional_delete_two_rows() -> DeltaResult<()> {
     assert_batches_sorted_eq!(expected, &batches);
     Ok(())
 }
+
+#[test]
+fn cdf_without_cdf_columns() -> DeltaResult<()> {
+    let test_name = "cdf-table-delete-conditional-two-rows";
+    let test_dir = load_test_data("tests/data", test_name).unwrap();
+    let test_path = test_dir.path().join(test_name);
+    let test_path = delta_kernel::try_parse_uri(test_path.to_str().expect("table path to string"))?;
+    let engine = DefaultEngine::new_local();
+    let table_changes = TableChanges::try_new(test_path, engine.as_ref(), 0, None)?;
+    let scan = table_changes
+        .into_scan_builder()
+        .with_cdf_columns(false)
+        .build()?;
+    let names = scan
+        .logical_schema()
+        .fields()
+        .map(|f| f.name())
+        .collect_vec();
+    assert_eq!(names, ["id"]);
+
+    let scan_schema_as_arrow =
+        ArrowSchema::try_from_kernel(scan.logical_schema().as_ref()).unwrap();
+    let batches: Vec<RecordBatch> = scan
+        .execute(engine)?
+        .map(|scan_result| -> DeltaResult<_> {
+            let scan_result = scan_result?;
+            let mask = scan_result.full_mask();
+            let record_batch = to_arrow(scan_result.raw_data?)?;
+            assert_eq!(record_batch.schema().as_ref(), &scan_schema_as_arrow);
+            match mask {
+                Some(mask) => Ok(filter_record_batch(&record_batch, &mask.into())?),
+                None => Ok(record_batch),
+            }
+        })
+        .try_collect()?;
+    // the inserted rows of version 0 and the deleted rows of version 1
+    let mut expected = vec![
+        "+----+", "| id |", "+----+", "| 0  |", "| 1  |", "| 2  |", "| 3  |", "| 4  |", "| 5  |",
+        "| 6  |", "| 7  |", "| 8  |", "| 9  |", "| 2  |", "| 8  |", "+----+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &batches);
+    Ok(())
+}
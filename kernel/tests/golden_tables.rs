@@ -7,8 +7,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use delta_kernel::arrow::array::{Array, AsArray, StructArray};
-use delta_kernel::arrow::compute::{concat_batches, take};
-use delta_kernel::arrow::compute::{lexsort_to_indices, SortColumn};
+use delta_kernel::arrow::compute::concat_batches;
 use delta_kernel::arrow::datatypes::{DataType, FieldRef, Schema};
 use delta_kernel::arrow::{compute::filter_record_batch, record_batch::RecordBatch};
 use delta_kernel::object_store::{local::LocalFileSystem, ObjectStore};
@@ -29,6 +28,7 @@ use url::Url;
 mod common;
 use common::load_test_data;
 
+use test_utils::sort::sort_record_batch;
 use test_utils::to_arrow;
 
 // NB adapted from DAT: read all parquet files in the directory and concatenate them
@@ -56,44 +56,6 @@ async fn read_expected(path: &Path) -> DeltaResult<RecordBatch> {
     Ok(all_data)
 }
 
-// copied from DAT
-fn sort_record_batch(batch: RecordBatch) -> DeltaResult<RecordBatch> {
-    if batch.num_rows() < 2 {
-        // 0 or 1 rows doesn't need sorting
-        return Ok(batch);
-    }
-    // Sort by as many columns as possible
-    let mut sort_columns = vec![];
-    for col in batch.columns() {
-        match col.data_type() {
-            DataType::Struct(_) | DataType::Map(_, _) => {
-                // can't sort by structs or maps
-            }
-            DataType::List(list_field) => {
-                let list_dt = list_field.data_type();
-                if list_dt.is_primitive() {
-                    // we can sort lists of primitives
-                    sort_columns.push(SortColumn {
-                        values: col.clone(),
-                        options: None,
-                    })
-                }
-            }
-            _ => sort_columns.push(SortColumn {
-                values: col.clone(),
-                options: None,
-            }),
-        }
-    }
-    let indices = lexsort_to_indices(&sort_columns, None)?;
-    let columns = batch
-        .columns()
-        .iter()
-        .map(|c| take(c, &indices, None).unwrap())
-        .collect();
-    Ok(RecordBatch::try_new(batch.schema(), columns)?)
-}
-
 // Ensure that two sets of  fields have the same names, and dict_is_ordered
 // We ignore:
 //  - data type: This is checked already in `assert_columns_match`
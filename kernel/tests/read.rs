@@ -3,23 +3,31 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use delta_kernel::actions::deletion_vector::split_vector;
+use delta_kernel::arrow::array::{
+    Array as _, ArrayRef, AsArray as _, FixedSizeBinaryArray, Int32Array, Int64Array, RecordBatch,
+    StructArray,
+};
 use delta_kernel::arrow::compute::{concat_batches, filter_record_batch};
-use delta_kernel::arrow::datatypes::Schema as ArrowSchema;
+use delta_kernel::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+use delta_kernel::arrow::datatypes::{Int32Type, Int64Type};
 use delta_kernel::engine::default::executor::tokio::TokioBackgroundExecutor;
 use delta_kernel::engine::default::DefaultEngine;
 use delta_kernel::expressions::{
-    column_expr, column_pred, Expression as Expr, ExpressionRef, Predicate as Pred,
+    column_expr, column_pred, Expression as Expr, ExpressionRef, Predicate as Pred, Scalar,
 };
 use delta_kernel::object_store::{memory::InMemory, path::Path, ObjectStore};
+use delta_kernel::parquet::arrow::PARQUET_FIELD_ID_META_KEY;
 use delta_kernel::parquet::file::properties::{EnabledStatistics, WriterProperties};
 use delta_kernel::scan::state::{transform_to_logical, DvInfo, Stats};
-use delta_kernel::scan::Scan;
-use delta_kernel::schema::{DataType, Schema};
+use delta_kernel::scan::{Scan, UnknownStatsColumns, ROW_INDEX_FIELD_NAME};
+use delta_kernel::schema::{DataType, Schema, StructField};
 use delta_kernel::{Engine, FileMeta, Snapshot};
 use itertools::Itertools;
+use serde_json::json;
 use test_utils::{
-    actions_to_string, add_commit, generate_batch, generate_simple_batch, into_record_batch,
-    record_batch_to_bytes, record_batch_to_bytes_with_props, IntoArray, TestAction, METADATA,
+    actions_to_string, add_commit, engine_for_in_memory_table, generate_batch,
+    generate_simple_batch, into_record_batch, record_batch_to_bytes,
+    record_batch_to_bytes_with_props, IntoArray, TestAction, METADATA,
 };
 use url::Url;
 
@@ -81,6 +89,46 @@ async fn single_commit_two_add_files() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[tokio::test]
+async fn read_files_with_unencoded_query_characters_in_paths(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let batch = generate_simple_batch()?;
+    let storage = Arc::new(InMemory::new());
+    // `add.path` should be percent-encoded, but some writers leave `?` and `#` as-is, which must
+    // not be taken to start a query or fragment
+    let paths = ["part=a?b/file%201.parquet", "file#2.parquet"];
+    add_commit(
+        storage.as_ref(),
+        0,
+        actions_to_string(
+            [TestAction::Metadata]
+                .into_iter()
+                .chain(paths.map(|path| TestAction::Add(path.to_string())))
+                .collect(),
+        ),
+    )
+    .await?;
+    for path in paths {
+        let encoded = path.replace('?', "%3F").replace('#', "%23");
+        let data = record_batch_to_bytes(&batch);
+        storage
+            .put(&Path::from_url_path(encoded)?, data.into())
+            .await?;
+    }
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Snapshot::try_new(location, engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches, vec![batch.clone(), batch]);
+    Ok(())
+}
+
 #[tokio::test]
 async fn two_commits() -> Result<(), Box<dyn std::error::Error>> {
     let batch = generate_simple_batch()?;
@@ -134,6 +182,207 @@ async fn two_commits() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn two_commits_in_memory_table() -> Result<(), Box<dyn std::error::Error>> {
+    let engine = engine_for_in_memory_table(vec![
+        vec![
+            TestAction::Metadata,
+            TestAction::Add(PARQUET_FILE1.to_string()),
+            TestAction::Add(PARQUET_FILE2.to_string()),
+        ],
+        vec![TestAction::Remove(PARQUET_FILE1.to_string())],
+    ])
+    .await?;
+
+    let location = Url::parse("memory:///")?;
+    let snapshot = Snapshot::try_new(location, engine.as_ref(), None)?;
+    assert_eq!(snapshot.version(), 1);
+
+    let scan = snapshot.into_scan_builder().build()?;
+    let mut scan_files = vec![];
+    for res in scan.scan_metadata(engine.as_ref())? {
+        scan_files = res?.visit_scan_files(scan_files, scan_metadata_callback)?;
+    }
+    let paths: Vec<_> = scan_files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(paths, vec![PARQUET_FILE2]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn strict_nullability_rejects_nulls_in_non_nullable_column(
+) -> Result<(), Box<dyn std::error::Error>> {
+    // The table schema declares `id` non-nullable, but the parquet file has a NULL `id`
+    let metadata = METADATA.replace(
+        r#"{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true"#,
+        r#"{\"name\":\"id\",\"type\":\"integer\",\"nullable\":false"#,
+    );
+    let add = actions_to_string(vec![TestAction::Add(PARQUET_FILE1.to_string())]);
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, format!("{metadata}\n{add}")).await?;
+    let batch = generate_batch(vec![
+        (
+            "id",
+            Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+        ),
+        ("val", vec!["a", "b", "c"].into_array()),
+    ])?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE1),
+            record_batch_to_bytes(&batch).into(),
+        )
+        .await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+    let id_field = snapshot.schema().field("id").cloned().unwrap();
+    assert!(!id_field.is_nullable());
+
+    // By default, the data is read as-is
+    let scan = snapshot.clone().scan_builder().build()?;
+    let batches = read_scan(&scan, engine.clone())?;
+    assert_eq!(
+        batches
+            .iter()
+            .map(|b| b.column(0).null_count())
+            .sum::<usize>(),
+        1
+    );
+
+    // With strict nullability, the NULL is an error
+    let scan = snapshot
+        .scan_builder()
+        .with_strict_nullability(true)
+        .build()?;
+    let err = read_scan(&scan, engine).unwrap_err();
+    assert!(
+        matches!(&err, delta_kernel::Error::InvalidStructData(msg) if msg.contains("id")),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn strict_nullability_ignores_nulls_in_deleted_rows() -> Result<(), Box<dyn std::error::Error>>
+{
+    // The table schema declares `id` non-nullable, and the parquet file's NULL `id`s are in rows 3
+    // and 4, which the file's (inline) deletion vector deletes along with rows 7, 11, 18 and 29
+    let metadata = METADATA.replace(
+        r#"{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true"#,
+        r#"{\"name\":\"id\",\"type\":\"integer\",\"nullable\":false"#,
+    );
+    let dv = r#""deletionVector":{"storageType":"i","pathOrInlineDv":"^Bg9^0rr910000000000iXQKl0rr91000f55c8Xg0@@D72lkbi5=-{L","sizeInBytes":44,"cardinality":6},"#;
+    let add = actions_to_string(vec![TestAction::Add(PARQUET_FILE1.to_string())]).replace(
+        r#""dataChange":true,"#,
+        &format!(r#""dataChange":true, {dv}"#),
+    );
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, format!("{metadata}\n{add}")).await?;
+    let batch = generate_batch(vec![
+        (
+            "id",
+            Arc::new(Int32Array::from(vec![
+                Some(0),
+                Some(1),
+                Some(2),
+                None,
+                None,
+            ])) as ArrayRef,
+        ),
+        ("val", vec!["a", "b", "c", "d", "e"].into_array()),
+    ])?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE1),
+            record_batch_to_bytes(&batch).into(),
+        )
+        .await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+
+    // With strict nullability, the deleted NULLs are not an error
+    let scan = snapshot
+        .clone()
+        .scan_builder()
+        .with_strict_nullability(true)
+        .build()?;
+    let batches = read_scan(&scan, engine.clone())?;
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+    assert!(batches.iter().all(|b| b.column(0).null_count() == 0));
+
+    // ... but they are when deletion vectors are not applied
+    let scan = snapshot
+        .scan_builder()
+        .with_strict_nullability(true)
+        .with_apply_deletion_vectors(false)
+        .build()?;
+    let err = read_scan(&scan, engine).unwrap_err();
+    assert!(
+        matches!(&err, delta_kernel::Error::InvalidStructData(msg) if msg.contains("id")),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn fixed_length_binary_column() -> Result<(), Box<dyn std::error::Error>> {
+    // The table schema declares `val` as binary, but the parquet file stores it as a parquet
+    // FIXED_LEN_BYTE_ARRAY column
+    let metadata = METADATA.replace(
+        r#"{\"name\":\"val\",\"type\":\"string\""#,
+        r#"{\"name\":\"val\",\"type\":\"binary\""#,
+    );
+    let add = actions_to_string(vec![TestAction::Add(PARQUET_FILE1.to_string())]);
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, format!("{metadata}\n{add}")).await?;
+    let values = [Some(b"abcd"), None, Some(b"\x00\x01\x02\x03")];
+    let batch = generate_batch(vec![
+        ("id", vec![1, 2, 3].into_array()),
+        (
+            "val",
+            Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                values.into_iter(),
+                4,
+            )?) as ArrayRef,
+        ),
+    ])?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE1),
+            record_batch_to_bytes(&batch).into(),
+        )
+        .await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+    let scan = snapshot.scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+    let batch = concat_batches(&batches[0].schema(), &batches)?;
+
+    let val = batch.column(1).as_binary::<i32>();
+    let actual: Vec<_> = val.iter().collect();
+    let expected: Vec<Option<&[u8]>> = values
+        .iter()
+        .map(|value| value.map(|v| v.as_slice()))
+        .collect();
+    assert_eq!(actual, expected);
+    assert!(val.iter().flatten().all(|v| v.len() == 4));
+    Ok(())
+}
+
 #[tokio::test]
 async fn remove_action() -> Result<(), Box<dyn std::error::Error>> {
     let batch = generate_simple_batch()?;
@@ -1141,6 +1390,649 @@ async fn predicate_on_non_nullable_column_missing_stats() -> Result<(), Box<dyn
     Ok(())
 }
 
+#[tokio::test]
+async fn column_mapping_id_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let id_physical = "col-2f4e1d4a-7f36-4c5e-9c0b-5b8d6a2e1f90";
+    let name_physical = "col-8a1c3e5b-0d2f-4b7a-a6e9-3c4d5e6f7a8b";
+    let schema_string = format!(
+        r#"{{"type":"struct","fields":[{{"name":"id","type":"integer","nullable":true,"metadata":{{"delta.columnMapping.id":1,"delta.columnMapping.physicalName":"{id_physical}"}}}},{{"name":"name","type":"string","nullable":true,"metadata":{{"delta.columnMapping.id":2,"delta.columnMapping.physicalName":"{name_physical}"}}}}]}}"#
+    );
+    let metadata = json!({
+        "metaData": {
+            "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+            "format": { "provider": "parquet", "options": {} },
+            "schemaString": schema_string,
+            "partitionColumns": [],
+            "configuration": { "delta.columnMapping.mode": "id" },
+            "createdTime": 1587968585495i64,
+        }
+    });
+    let actions = [
+        r#"{"protocol":{"minReaderVersion":2,"minWriterVersion":5}}"#.to_string(),
+        metadata.to_string(),
+        format!(
+            r#"{{"add":{{"path":"{PARQUET_FILE1}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true}}}}"#
+        ),
+    ];
+
+    // The parquet columns are named after the *other* column's physical name, so they can only be
+    // read correctly if they are resolved by field id.
+    let with_field_id = |name: &str, data_type, id: i32| {
+        let metadata = HashMap::from([(PARQUET_FIELD_ID_META_KEY.to_string(), id.to_string())]);
+        Field::new(name, data_type, true).with_metadata(metadata)
+    };
+    let parquet_schema = ArrowSchema::new(vec![
+        with_field_id(name_physical, ArrowDataType::Int32, 1),
+        with_field_id(id_physical, ArrowDataType::Utf8, 2),
+    ]);
+    let parquet_batch = RecordBatch::try_new(
+        Arc::new(parquet_schema),
+        vec![vec![1, 2, 3].into_array(), vec!["a", "b", "c"].into_array()],
+    )?;
+
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, actions.iter().join("\n")).await?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE1),
+            record_batch_to_bytes(&parquet_batch).into(),
+        )
+        .await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+
+    let scan = snapshot.clone().scan_builder().build()?;
+    let batches = read_scan(&scan, engine.clone())?;
+    let expected = generate_batch(vec![
+        ("id", vec![1, 2, 3].into_array()),
+        ("name", vec!["a", "b", "c"].into_array()),
+    ])?;
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].columns(), expected.columns());
+    let column_names: Vec<_> = batches[0]
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    assert_eq!(column_names, ["id", "name"]);
+
+    // projecting out a single column also resolves it by field id
+    let scan_schema = snapshot.schema().project(&["name"])?;
+    let scan = snapshot.scan_builder().with_schema(scan_schema).build()?;
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].columns(), &[vec!["a", "b", "c"].into_array()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn nested_partition_column() -> Result<(), Box<dyn std::error::Error>> {
+    let schema_string = r#"{"type":"struct","fields":[{"name":"id","type":"integer","nullable":true,"metadata":{}},{"name":"info","type":{"type":"struct","fields":[{"name":"name","type":"string","nullable":true,"metadata":{}},{"name":"part","type":"integer","nullable":true,"metadata":{}}]},"nullable":true,"metadata":{}}]}"#;
+    let metadata = json!({
+        "metaData": {
+            "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+            "format": { "provider": "parquet", "options": {} },
+            "schemaString": schema_string,
+            "partitionColumns": ["info.part"],
+            "configuration": {},
+            "createdTime": 1587968585495i64,
+        }
+    });
+    let actions = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#.to_string(),
+        metadata.to_string(),
+        format!(
+            r#"{{"add":{{"path":"info.part=7/{PARQUET_FILE1}","partitionValues":{{"info.part":"7"}},"size":0,"modificationTime":1587968586000,"dataChange":true}}}}"#
+        ),
+    ];
+
+    // The parquet file only holds the non-partition field of the `info` struct
+    let name_field = Arc::new(Field::new("name", ArrowDataType::Utf8, true));
+    let info = StructArray::new(
+        vec![name_field].into(),
+        vec![vec!["a", "b", "c"].into_array()],
+        None,
+    );
+    let parquet_batch = generate_batch(vec![
+        ("id", vec![1, 2, 3].into_array()),
+        ("info", Arc::new(info)),
+    ])?;
+
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, actions.iter().join("\n")).await?;
+    storage
+        .put(
+            &Path::from("info.part=7").child(PARQUET_FILE1),
+            record_batch_to_bytes(&parquet_batch).into(),
+        )
+        .await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+
+    let scan = snapshot.scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.column(0), &vec![1, 2, 3].into_array());
+
+    // the partition value is materialized inside the `info` struct, after the `name` field
+    let info = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .expect("info should be a struct");
+    let field_names: Vec<_> = info.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(field_names, ["name", "part"]);
+    assert_eq!(info.column(0), &vec!["a", "b", "c"].into_array());
+    assert_eq!(info.column(1), &vec![7, 7, 7].into_array());
+    Ok(())
+}
+
+#[tokio::test]
+async fn row_tracking_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = json!({
+        "metaData": {
+            "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+            "format": { "provider": "parquet", "options": {} },
+            "schemaString": r#"{"type":"struct","fields":[{"name":"id","type":"integer","nullable":true,"metadata":{}}]}"#,
+            "partitionColumns": [],
+            "configuration": { "delta.enableRowTracking": "true" },
+            "createdTime": 1587968585495i64,
+        }
+    });
+    let add = |path: &str, base_row_id: i64, default_row_commit_version: i64| {
+        json!({
+            "add": {
+                "path": path,
+                "partitionValues": {},
+                "size": 0,
+                "modificationTime": 1587968586000i64,
+                "dataChange": true,
+                "baseRowId": base_row_id,
+                "defaultRowCommitVersion": default_row_commit_version,
+            }
+        })
+        .to_string()
+    };
+    let commit0 = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":7,"writerFeatures":["rowTracking","domainMetadata"]}}"#.to_string(),
+        metadata.to_string(),
+        add(PARQUET_FILE1, 0, 0),
+    ];
+
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, commit0.iter().join("\n")).await?;
+    add_commit(storage.as_ref(), 1, add(PARQUET_FILE2, 5, 1)).await?;
+
+    // The first file is split into several row groups, so that row indexes span row groups
+    let batch1 = generate_batch(vec![("id", vec![0, 1, 2, 3, 4].into_array())])?;
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(2)
+        .build();
+    storage
+        .put(
+            &Path::from(PARQUET_FILE1),
+            record_batch_to_bytes_with_props(&batch1, props).into(),
+        )
+        .await?;
+    let batch2 = generate_batch(vec![("id", vec![10, 11, 12].into_array())])?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE2),
+            record_batch_to_bytes(&batch2).into(),
+        )
+        .await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+
+    let scan = snapshot
+        .clone()
+        .scan_builder()
+        .with_row_tracking(true)
+        .build()?;
+    let field_names: Vec<_> = scan.logical_schema().fields().map(|f| f.name()).collect();
+    assert_eq!(field_names, ["id", "_metadata"]);
+
+    let mut rows = vec![];
+    for batch in read_scan(&scan, engine)? {
+        let ids = batch.column(0).as_primitive::<Int32Type>();
+        let metadata = batch.column(1).as_struct();
+        let row_ids = metadata.column(0).as_primitive::<Int64Type>();
+        let row_commit_versions = metadata.column(1).as_primitive::<Int64Type>();
+        for i in 0..batch.num_rows() {
+            rows.push((ids.value(i), row_ids.value(i), row_commit_versions.value(i)));
+        }
+    }
+    rows.sort();
+    // Row ids are contiguous within each file, starting at the file's baseRowId
+    let expected = vec![
+        (0, 0, 0),
+        (1, 1, 0),
+        (2, 2, 0),
+        (3, 3, 0),
+        (4, 4, 0),
+        (10, 5, 1),
+        (11, 6, 1),
+        (12, 7, 1),
+    ];
+    assert_eq!(rows, expected);
+
+    // Tables without row tracking can't materialize the row tracking metadata
+    let storage = Arc::new(InMemory::new());
+    add_commit(
+        storage.as_ref(),
+        0,
+        actions_to_string(vec![TestAction::Metadata]),
+    )
+    .await?;
+    let engine = DefaultEngine::new(storage, Arc::new(TokioBackgroundExecutor::new()));
+    let snapshot = Snapshot::try_new(Url::parse("memory:///")?, &engine, None)?;
+    let result = snapshot.into_scan_builder().with_row_tracking(true).build();
+    assert!(matches!(result, Err(delta_kernel::Error::Unsupported(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn row_tracking_materialized_columns() -> Result<(), Box<dyn std::error::Error>> {
+    const ROW_ID_COLUMN: &str = "_row-id-col";
+    const ROW_COMMIT_VERSION_COLUMN: &str = "_row-commit-version-col";
+    let metadata = json!({
+        "metaData": {
+            "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+            "format": { "provider": "parquet", "options": {} },
+            "schemaString": r#"{"type":"struct","fields":[{"name":"id","type":"integer","nullable":true,"metadata":{}}]}"#,
+            "partitionColumns": [],
+            "configuration": {
+                "delta.enableRowTracking": "true",
+                "delta.rowTracking.materializedRowIdColumnName": ROW_ID_COLUMN,
+                "delta.rowTracking.materializedRowCommitVersionColumnName": ROW_COMMIT_VERSION_COLUMN,
+            },
+            "createdTime": 1587968585495i64,
+        }
+    });
+    let add = |path: &str, base_row_id: i64, default_row_commit_version: i64| {
+        json!({
+            "add": {
+                "path": path,
+                "partitionValues": {},
+                "size": 0,
+                "modificationTime": 1587968586000i64,
+                "dataChange": true,
+                "baseRowId": base_row_id,
+                "defaultRowCommitVersion": default_row_commit_version,
+            }
+        })
+        .to_string()
+    };
+    let commit0 = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":7,"writerFeatures":["rowTracking","domainMetadata"]}}"#.to_string(),
+        metadata.to_string(),
+        add(PARQUET_FILE1, 0, 0),
+        add(PARQUET_FILE2, 3, 0),
+    ];
+
+    let storage = Arc::new(InMemory::new());
+    add_commit(storage.as_ref(), 0, commit0.iter().join("\n")).await?;
+
+    // The first file was rewritten by an update of its second row, which kept the row ids and row
+    // commit versions of the other rows materialized
+    let batch1 = generate_batch(vec![
+        ("id", vec![0, 1, 2].into_array()),
+        (
+            ROW_ID_COLUMN,
+            Arc::new(Int64Array::from(vec![Some(7), None, Some(9)])) as ArrayRef,
+        ),
+        (
+            ROW_COMMIT_VERSION_COLUMN,
+            Arc::new(Int64Array::from(vec![Some(2), None, Some(2)])) as ArrayRef,
+        ),
+    ])?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE1),
+            record_batch_to_bytes(&batch1).into(),
+        )
+        .await?;
+    // The second file has no materialized columns at all
+    let batch2 = generate_batch(vec![("id", vec![10, 11].into_array())])?;
+    storage
+        .put(
+            &Path::from(PARQUET_FILE2),
+            record_batch_to_bytes(&batch2).into(),
+        )
+        .await?;
+
+    let engine = Arc::new(DefaultEngine::new(
+        storage.clone(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(
+        Url::parse("memory:///")?,
+        engine.as_ref(),
+        None,
+    )?);
+    let scan = snapshot.scan_builder().with_row_tracking(true).build()?;
+    let field_names: Vec<_> = scan.logical_schema().fields().map(|f| f.name()).collect();
+    assert_eq!(field_names, ["id", "_metadata"]);
+
+    let mut rows = vec![];
+    for batch in read_scan(&scan, engine)? {
+        assert_eq!(batch.num_columns(), 2);
+        let ids = batch.column(0).as_primitive::<Int32Type>();
+        let metadata = batch.column(1).as_struct();
+        let row_ids = metadata.column(0).as_primitive::<Int64Type>();
+        let row_commit_versions = metadata.column(1).as_primitive::<Int64Type>();
+        assert_eq!(row_ids.null_count(), 0);
+        assert_eq!(row_commit_versions.null_count(), 0);
+        for i in 0..batch.num_rows() {
+            rows.push((ids.value(i), row_ids.value(i), row_commit_versions.value(i)));
+        }
+    }
+    rows.sort();
+    // Materialized values win; the others are derived from the file's metadata
+    let expected = vec![(0, 7, 2), (1, 1, 0), (2, 9, 2), (10, 3, 0), (11, 4, 0)];
+    assert_eq!(rows, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn scan_files_row_tracking_fields() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::scan::state::ScanFile;
+
+    const PARQUET_FILE3: &str =
+        "part-00002-3f1c8e8a-5d4b-4b9e-9a57-0c2d7e4f6b1a-c000.snappy.parquet";
+    type RowTrackingFields = (String, Option<i64>, Option<i64>);
+    fn collect_row_tracking_fields(files: &mut Vec<RowTrackingFields>, scan_file: ScanFile) {
+        let (base_row_id, default_row_commit_version) =
+            (scan_file.base_row_id, scan_file.default_row_commit_version);
+        files.push((scan_file.path, base_row_id, default_row_commit_version));
+    }
+
+    let storage = Arc::new(InMemory::new());
+    let actions = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":7,"writerFeatures":["rowTracking","domainMetadata"]}}"#.to_string(),
+        r#"{"metaData":{"id":"5fba94ed-9794-4965-ba6e-6ee3c0d22af9","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":[],"configuration":{"delta.enableRowTracking":"true"},"createdTime":1587968585495}}"#.to_string(),
+        format!(r#"{{"add":{{"path":"{PARQUET_FILE1}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true,"baseRowId":0,"defaultRowCommitVersion":0}}}}"#),
+    ];
+    add_commit(storage.as_ref(), 0, actions.iter().join("\n")).await?;
+    let add = format!(
+        r#"{{"add":{{"path":"{PARQUET_FILE2}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true,"baseRowId":5,"defaultRowCommitVersion":1}}}}"#
+    );
+    add_commit(storage.as_ref(), 1, add).await?;
+    // a file added without row tracking fields
+    let add = format!(
+        r#"{{"add":{{"path":"{PARQUET_FILE3}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true}}}}"#
+    );
+    add_commit(storage.as_ref(), 2, add).await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = DefaultEngine::new(storage, Arc::new(TokioBackgroundExecutor::new()));
+    let snapshot = Arc::new(Snapshot::try_new(location, &engine, None)?);
+    let scan = snapshot.scan_builder().build()?;
+    let mut files = vec![];
+    for scan_metadata in scan.scan_metadata(&engine)? {
+        files = scan_metadata?.for_each_scan_file(files, collect_row_tracking_fields)?;
+    }
+    files.sort();
+
+    let expected = [
+        (PARQUET_FILE1.to_string(), Some(0), Some(0)),
+        (PARQUET_FILE2.to_string(), Some(5), Some(1)),
+        (PARQUET_FILE3.to_string(), None, None),
+    ];
+    assert_eq!(files, expected);
+    Ok(())
+}
+
+#[test]
+fn scan_output_schema_with_row_index() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let engine = Arc::new(DefaultEngine::try_new(
+        &url,
+        std::iter::empty::<(&str, &str)>(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    )?);
+    let snapshot = Arc::new(Snapshot::try_new(url, engine.as_ref(), None)?);
+
+    let scan = snapshot.clone().scan_builder().build()?;
+    assert_eq!(scan.output_schema(), snapshot.schema());
+
+    let scan = snapshot
+        .clone()
+        .scan_builder()
+        .with_row_index(true)
+        .build()?;
+    let expected_schema = Schema::new([
+        StructField::nullable("value", DataType::INTEGER),
+        StructField::not_null(ROW_INDEX_FIELD_NAME, DataType::LONG),
+    ]);
+    assert_eq!(scan.output_schema().as_ref(), &expected_schema);
+    assert!(!scan.is_identity_transform());
+
+    // The file's values are its row indexes; rows 0 and 9 are deleted by its deletion vector
+    let mut rows = vec![];
+    for batch in read_scan(&scan, engine)? {
+        assert_eq!(batch.schema().field(1).name(), ROW_INDEX_FIELD_NAME);
+        let values = batch.column(0).as_primitive::<Int32Type>();
+        let row_indexes = batch.column(1).as_primitive::<Int64Type>();
+        for i in 0..batch.num_rows() {
+            rows.push((values.value(i), row_indexes.value(i)));
+        }
+    }
+    let expected: Vec<_> = (1..9).map(|i| (i, i as i64)).collect();
+    assert_eq!(rows, expected);
+
+    // The row index column can't collide with a column of the scan schema
+    let schema = Arc::new(Schema::new([StructField::nullable(
+        ROW_INDEX_FIELD_NAME,
+        DataType::LONG,
+    )]));
+    let result = snapshot
+        .scan_builder()
+        .with_schema(schema)
+        .with_row_index(true)
+        .build();
+    assert!(matches!(result, Err(delta_kernel::Error::Schema(_))));
+    Ok(())
+}
+
+// Creates a table partitioned on `id` with one file in each of two partitions: the file in
+// partition `id=1` has stats, the file in partition `id=2` does not.
+async fn table_with_statsless_file(
+) -> Result<(Arc<DefaultEngine<TokioBackgroundExecutor>>, Arc<Snapshot>), Box<dyn std::error::Error>>
+{
+    let batch = generate_batch(vec![("val", vec!["a", "b", "c"].into_array())])?;
+
+    let storage = Arc::new(InMemory::new());
+    let actions = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#.to_string(),
+        r#"{"commitInfo":{"timestamp":1587968586154,"operation":"WRITE","operationParameters":{"mode":"ErrorIfExists","partitionBy":"[\"id\"]"},"isBlindAppend":true}}"#.to_string(),
+        r#"{"metaData":{"id":"5fba94ed-9794-4965-ba6e-6ee3c0d22af9","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}},{\"name\":\"val\",\"type\":\"string\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":["id"],"configuration":{},"createdTime":1587968585495}}"#.to_string(),
+        format!(r#"{{"add":{{"path":"id=1/{PARQUET_FILE1}","partitionValues":{{"id":"1"}},"size":0,"modificationTime":1587968586000,"dataChange":true, "stats":"{{\"numRecords\":3,\"nullCount\":{{\"val\":0}},\"minValues\":{{\"val\":\"a\"}},\"maxValues\":{{\"val\":\"c\"}}}}"}}}}"#),
+        format!(r#"{{"add":{{"path":"id=2/{PARQUET_FILE2}","partitionValues":{{"id":"2"}},"size":0,"modificationTime":1587968586000,"dataChange":true}}}}"#),
+    ];
+
+    add_commit(storage.as_ref(), 0, actions.iter().join("\n")).await?;
+    for path in [
+        Path::from("id=1").child(PARQUET_FILE1),
+        Path::from("id=2").child(PARQUET_FILE2),
+    ] {
+        storage
+            .put(&path, record_batch_to_bytes(&batch).into())
+            .await?;
+    }
+
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage,
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+    Ok((engine, snapshot))
+}
+
+#[tokio::test]
+async fn scan_require_stats() -> Result<(), Box<dyn std::error::Error>> {
+    let (engine, snapshot) = table_with_statsless_file().await?;
+
+    // by default, files without stats are simply read
+    let scan = snapshot.clone().scan_builder().build()?;
+    assert!(!scan.require_stats());
+    assert_eq!(read_scan(&scan, engine.clone())?.len(), 2);
+
+    let scan = snapshot
+        .clone()
+        .scan_builder()
+        .with_require_stats(true)
+        .build()?;
+    assert!(scan.require_stats());
+    match read_scan(&scan, engine.clone()) {
+        Err(delta_kernel::Error::MissingStats(path)) => {
+            assert_eq!(path, format!("id=2/{PARQUET_FILE2}"))
+        }
+        other => panic!("expected a missing stats error, got {other:?}"),
+    }
+
+    // a file without stats is fine if it isn't selected by the scan
+    let predicate = Pred::eq(column_expr!("id"), Expr::literal(1));
+    let scan = snapshot
+        .scan_builder()
+        .with_predicate(Arc::new(predicate))
+        .with_require_stats(true)
+        .build()?;
+    assert_eq!(read_scan(&scan, engine)?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn scan_unknown_stats_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let batch = generate_simple_batch()?;
+    let storage = Arc::new(InMemory::new());
+    // the stats of the second file reference a column `dropped` which is not in the schema
+    let actions = [
+        METADATA.to_string(),
+        format!(
+            r#"{{"add":{{"path":"{PARQUET_FILE1}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true, "stats":"{{\"numRecords\":3,\"nullCount\":{{\"id\":0}},\"minValues\":{{\"id\":1}},\"maxValues\":{{\"id\":3}}}}"}}}}"#
+        ),
+        format!(
+            r#"{{"add":{{"path":"{PARQUET_FILE2}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true, "stats":"{{\"numRecords\":3,\"nullCount\":{{\"id\":0,\"dropped\":0}},\"minValues\":{{\"id\":1,\"dropped\":5}},\"maxValues\":{{\"id\":3,\"dropped\":7}}}}"}}}}"#
+        ),
+    ];
+    add_commit(storage.as_ref(), 0, actions.iter().join("\n")).await?;
+    for file in [PARQUET_FILE1, PARQUET_FILE2] {
+        let data = record_batch_to_bytes(&batch);
+        storage.put(&Path::from(file), data.into()).await?;
+    }
+    let location = Url::parse("memory:///")?;
+    let engine = Arc::new(DefaultEngine::new(
+        storage,
+        Arc::new(TokioBackgroundExecutor::new()),
+    ));
+    let snapshot = Arc::new(Snapshot::try_new(location, engine.as_ref(), None)?);
+
+    // by default, stats of unknown columns are ignored
+    let scan = snapshot.clone().scan_builder().build()?;
+    assert_eq!(scan.unknown_stats_columns(), UnknownStatsColumns::Ignore);
+    assert_eq!(read_scan(&scan, engine.clone())?.len(), 2);
+    let scan = snapshot
+        .clone()
+        .scan_builder()
+        .with_unknown_stats_columns(UnknownStatsColumns::Warn)
+        .build()?;
+    assert_eq!(read_scan(&scan, engine.clone())?.len(), 2);
+
+    let scan = snapshot
+        .scan_builder()
+        .with_unknown_stats_columns(UnknownStatsColumns::Error)
+        .build()?;
+    match read_scan(&scan, engine) {
+        Err(delta_kernel::Error::Schema(msg)) => {
+            assert!(msg.contains(PARQUET_FILE2), "{msg}");
+            assert!(msg.contains("dropped"), "{msg}");
+        }
+        other => panic!("expected a schema error, got {other:?}"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn scan_files_tags() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::scan::state::ScanFile;
+
+    fn collect_tags(tags: &mut Vec<(String, HashMap<String, String>)>, scan_file: ScanFile) {
+        tags.push((scan_file.path, scan_file.tags));
+    }
+
+    let storage = Arc::new(InMemory::new());
+    let actions = [
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#.to_string(),
+        r#"{"metaData":{"id":"5fba94ed-9794-4965-ba6e-6ee3c0d22af9","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"val\",\"type\":\"string\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":[],"configuration":{},"createdTime":1587968585495}}"#.to_string(),
+        format!(r#"{{"add":{{"path":"{PARQUET_FILE1}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true,"tags":{{"INSERTION_TIME":"1587968586000000"}}}}}}"#),
+        format!(r#"{{"add":{{"path":"{PARQUET_FILE2}","partitionValues":{{}},"size":0,"modificationTime":1587968586000,"dataChange":true}}}}"#),
+    ];
+    add_commit(storage.as_ref(), 0, actions.iter().join("\n")).await?;
+
+    let location = Url::parse("memory:///")?;
+    let engine = DefaultEngine::new(storage, Arc::new(TokioBackgroundExecutor::new()));
+    let snapshot = Arc::new(Snapshot::try_new(location, &engine, None)?);
+    let scan = snapshot.scan_builder().build()?;
+    let mut tags = vec![];
+    for scan_metadata in scan.scan_metadata(&engine)? {
+        tags = scan_metadata?.for_each_scan_file(tags, collect_tags)?;
+    }
+    tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let expected_tags =
+        HashMap::from([("INSERTION_TIME".to_string(), "1587968586000000".to_string())]);
+    // a file without tags gets an empty map
+    let expected = [
+        (PARQUET_FILE1.to_string(), expected_tags),
+        (PARQUET_FILE2.to_string(), HashMap::new()),
+    ];
+    assert_eq!(tags, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn estimated_row_count_without_stats() -> Result<(), Box<dyn std::error::Error>> {
+    let (engine, snapshot) = table_with_statsless_file().await?;
+
+    // files without stats don't contribute to the estimate, so it is a lower bound
+    let scan = snapshot.clone().scan_builder().build()?;
+    assert_eq!(scan.estimated_row_count(engine.as_ref())?, 3);
+    let num_rows: usize = read_scan(&scan, engine.clone())?
+        .iter()
+        .map(|batch| batch.num_rows())
+        .sum();
+    assert_eq!(num_rows, 6);
+
+    // if only the file without stats is selected, the estimate is zero
+    let predicate = Pred::eq(column_expr!("id"), Expr::literal(2));
+    let scan = snapshot
+        .scan_builder()
+        .with_predicate(Arc::new(predicate))
+        .build()?;
+    assert_eq!(scan.estimated_row_count(engine.as_ref())?, 0);
+    Ok(())
+}
+
 #[test]
 fn short_dv() -> Result<(), Box<dyn std::error::Error>> {
     let expected = vec![
@@ -1202,6 +2094,29 @@ fn timestamp_ntz() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn timestamp_ntz_partition_predicate() -> Result<(), Box<dyn std::error::Error>> {
+    // timestampNtz values are not adjusted to UTC, so 2013-07-05 17:01:00.123456 is exactly this
+    // many microseconds since the epoch and must match the partition value as written.
+    let predicate =
+        column_expr!("tsNtzPartition").eq(Expr::literal(Scalar::TimestampNtz(1373043660123456)));
+    let expected = vec![
+        "+----+----------------------------+----------------------------+",
+        "| id | tsNtz                      | tsNtzPartition             |",
+        "+----+----------------------------+----------------------------+",
+        "| 3  | 2021-11-18T02:30:00.123456 | 2013-07-05T17:01:00.123456 |",
+        "| 4  | 2013-07-05T17:01:00.123456 | 2013-07-05T17:01:00.123456 |",
+        "| 5  |                            | 2013-07-05T17:01:00.123456 |",
+        "+----+----------------------------+----------------------------+",
+    ];
+    read_table_data_str(
+        "./tests/data/data-reader-timestamp_ntz/",
+        None,
+        Some(predicate),
+        expected,
+    )
+}
+
 #[test]
 fn type_widening_basic() -> Result<(), Box<dyn std::error::Error>> {
     let expected = vec![
@@ -1341,3 +2256,82 @@ fn compacted_log_files_table() -> Result<(), Box<dyn std::error::Error>> {
     let test_path = test_dir.path().join(test_name);
     read_table_data_str(test_path.to_str().unwrap(), None, None, expected)
 }
+
+#[test]
+fn scan_deleted_rows_match_selection_vectors() -> Result<(), Box<dyn std::error::Error>> {
+    // Copy `with-short-dv` and add a commit that attaches the same deletion vector to its second
+    // file as well, so that the table has deletions in more than one file.
+    let source = PathBuf::from("./tests/data/with-short-dv/");
+    let test_dir = tempfile::tempdir()?;
+    std::fs::create_dir(test_dir.path().join("_delta_log"))?;
+    for entry in std::fs::read_dir(&source)?.chain(std::fs::read_dir(source.join("_delta_log"))?) {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            let relative = entry.path().strip_prefix(&source)?.to_path_buf();
+            std::fs::copy(entry.path(), test_dir.path().join(relative))?;
+        }
+    }
+    let second_file = "part-00001-24db34ab-bdfe-4814-aba8-c1f34d6d8923-c000.snappy.parquet";
+    let commit = [
+        json!({"remove": {"path": second_file, "deletionTimestamp": 1685559515000i64, "dataChange": true}}),
+        json!({"add": {
+            "path": second_file,
+            "partitionValues": {},
+            "size": 1428,
+            "modificationTime": 1685559514000i64,
+            "dataChange": true,
+            "deletionVector": {
+                "storageType": "u",
+                "pathOrInlineDv": "U5OWRz5k%CFT.Td}yCPW",
+                "offset": 1,
+                "sizeInBytes": 38,
+                "cardinality": 3
+            }
+        }}),
+    ]
+    .iter()
+    .join("\n");
+    std::fs::write(
+        test_dir.path().join("_delta_log/00000000000000000001.json"),
+        commit,
+    )?;
+
+    let url = url::Url::from_directory_path(test_dir.path()).unwrap();
+    let engine = DefaultEngine::try_new(
+        &url,
+        std::iter::empty::<(&str, &str)>(),
+        Arc::new(TokioBackgroundExecutor::new()),
+    )?;
+    let snapshot = Snapshot::try_new(url.clone(), &engine, None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+
+    // compute the expected deletions from each file's own selection vector
+    fn scan_metadata_callback(
+        files: &mut Vec<(String, DvInfo)>,
+        path: &str,
+        _: i64,
+        _: Option<Stats>,
+        dv_info: DvInfo,
+        _: Option<ExpressionRef>,
+        _: HashMap<String, String>,
+    ) {
+        files.push((path.to_string(), dv_info));
+    }
+    let mut expected = HashMap::new();
+    for scan_metadata in scan.scan_metadata(&engine)? {
+        let files = scan_metadata?.visit_scan_files(vec![], scan_metadata_callback)?;
+        for (path, dv_info) in files {
+            let Some(selection_vector) = dv_info.get_selection_vector(&engine, &url)? else {
+                continue;
+            };
+            let row_indexes = selection_vector.iter().positions(|selected| !selected);
+            expected.insert(path, row_indexes.map(|i| i as u64).collect::<Vec<_>>());
+        }
+    }
+    assert_eq!(expected.len(), 2);
+    assert!(expected.values().all(|row_indexes| row_indexes.len() == 3));
+
+    let deleted = scan.deleted_rows(&engine)?;
+    assert_eq!(deleted, expected);
+    Ok(())
+}
@@ -1,5 +1,8 @@
+use std::path::Path;
+
 use delta_kernel::arrow::array::RecordBatch;
 use delta_kernel::engine::default::DefaultEngine;
+use delta_kernel::scan::state::ScanFile;
 
 use delta_kernel::{DeltaResult, Snapshot};
 
@@ -227,3 +230,54 @@ fn v2_checkpoints_parquet_with_last_checkpoint() -> DeltaResult<()> {
         get_simple_id_table(),
     )
 }
+
+/// Collect the (sorted) paths of the files a full scan of the table at `table_path` would read.
+fn scan_file_paths(table_path: &Path) -> DeltaResult<Vec<String>> {
+    fn callback(paths: &mut Vec<String>, scan_file: ScanFile) {
+        paths.push(scan_file.path);
+    }
+
+    let engine = DefaultEngine::new_local();
+    let snapshot = Snapshot::try_from_uri(
+        table_path.to_str().expect("table path to string"),
+        engine.as_ref(),
+        None,
+    )?;
+    let scan = snapshot.into_scan_builder().build()?;
+    let mut paths = vec![];
+    for scan_metadata in scan.scan_metadata(engine.as_ref())? {
+        paths = scan_metadata?.for_each_scan_file(paths, callback)?;
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Replaying the table from its v2 checkpoint (and sidecars) must produce the same set of files as
+/// replaying only its JSON commits.
+#[test]
+fn v2_checkpoint_with_sidecars_matches_commit_replay() -> DeltaResult<()> {
+    for table_name in [
+        "v2-checkpoints-json-with-sidecars",
+        "v2-checkpoints-parquet-with-sidecars",
+    ] {
+        let test_dir = load_test_data("tests/data", table_name).unwrap();
+        let table_path = test_dir.path().join(table_name);
+        let from_checkpoint = scan_file_paths(&table_path)?;
+
+        // Remove the checkpoints (and the sidecars they reference), leaving only the commits
+        let log_path = table_path.join("_delta_log");
+        std::fs::remove_dir_all(log_path.join("_sidecars")).unwrap();
+        for entry in std::fs::read_dir(&log_path).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_str().unwrap();
+            if name.contains(".checkpoint.") || name == "_last_checkpoint" {
+                std::fs::remove_file(&path).unwrap();
+            }
+        }
+        let from_commits = scan_file_paths(&table_path)?;
+
+        assert!(!from_checkpoint.is_empty());
+        assert_eq!(from_checkpoint, from_commits, "{table_name}");
+    }
+    Ok(())
+}
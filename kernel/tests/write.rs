@@ -2,7 +2,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use delta_kernel::arrow::array::{
-    Int32Array, MapBuilder, MapFieldNames, StringArray, StringBuilder, TimestampMicrosecondArray,
+    Array as _, AsArray as _, Int32Array, MapBuilder, MapFieldNames, StringArray, StringBuilder,
+    TimestampMicrosecondArray,
 };
 use delta_kernel::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
 use delta_kernel::arrow::error::ArrowError;
@@ -17,6 +18,8 @@ use serde_json::Deserializer;
 use delta_kernel::engine::arrow_conversion::TryIntoArrow as _;
 use delta_kernel::engine::arrow_data::ArrowEngineData;
 use delta_kernel::schema::{DataType, StructField, StructType};
+use delta_kernel::table_properties::IsolationLevel;
+use delta_kernel::transaction::Clock;
 use delta_kernel::DeltaResult;
 use delta_kernel::Error as KernelError;
 use delta_kernel::Snapshot;
@@ -117,6 +120,176 @@ async fn test_commit_info() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_commit_info_with_operation_metrics() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table_url, engine, store, table_name) in setup_test_tables(schema, &[]).await? {
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        let metrics = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        // later metrics are added to (and overwrite) earlier ones
+        let txn = snapshot
+            .transaction()?
+            .with_operation("WRITE".to_string())
+            .with_operation_metrics(metrics(&[("numFiles", "0"), ("numOutputRows", "1")]))
+            .with_operation_metrics(metrics(&[("numOutputRows", "0")]))
+            .with_commit_info(new_commit_info()?);
+
+        // commit!
+        txn.commit(&engine)?;
+
+        let commit1 = store
+            .get(&Path::from(format!(
+                "/{table_name}/_delta_log/00000000000000000001.json"
+            )))
+            .await?;
+
+        let mut parsed_commit: serde_json::Value = serde_json::from_slice(&commit1.bytes().await?)?;
+        *parsed_commit
+            .get_mut("commitInfo")
+            .unwrap()
+            .get_mut("timestamp")
+            .unwrap() = serde_json::Value::Number(0.into());
+
+        let expected_commit = json!({
+            "commitInfo": {
+                "timestamp": 0,
+                "operation": "WRITE",
+                "kernelVersion": format!("v{}", env!("CARGO_PKG_VERSION")),
+                "operationParameters": {},
+                "operationMetrics": {
+                    "numFiles": "0",
+                    "numOutputRows": "0"
+                },
+                "engineCommitInfo": {
+                    "engineInfo": "default engine"
+                }
+            }
+        });
+
+        assert_eq!(parsed_commit, expected_commit);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_commit_info_with_isolation_level() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table_url, engine, store, table_name) in setup_test_tables(schema, &[]).await? {
+        let levels = [
+            (IsolationLevel::Serializable, "Serializable"),
+            (IsolationLevel::WriteSerializable, "WriteSerializable"),
+            (IsolationLevel::SnapshotIsolation, "SnapshotIsolation"),
+        ];
+        for (version, (level, expected)) in (1..).zip(levels) {
+            let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+            let txn = snapshot
+                .transaction()?
+                .with_isolation_level(level)
+                .with_commit_info(new_commit_info()?);
+            txn.commit(&engine)?;
+
+            let commit = store
+                .get(&Path::from(format!(
+                    "/{table_name}/_delta_log/{version:020}.json"
+                )))
+                .await?;
+            let parsed_commit: serde_json::Value = serde_json::from_slice(&commit.bytes().await?)?;
+            assert_eq!(
+                parsed_commit["commitInfo"]["isolationLevel"],
+                json!(expected)
+            );
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_commit_info_with_user_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table_url, engine, store, table_name) in setup_test_tables(schema, &[]).await? {
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        let txn = snapshot
+            .transaction()?
+            .with_user_metadata("nightly backfill".to_string())
+            .with_commit_info(new_commit_info()?);
+        txn.commit(&engine)?;
+
+        let commit1 = store
+            .get(&Path::from(format!(
+                "/{table_name}/_delta_log/00000000000000000001.json"
+            )))
+            .await?;
+        let parsed_commit: serde_json::Value = serde_json::from_slice(&commit1.bytes().await?)?;
+        assert_eq!(
+            parsed_commit["commitInfo"]["userMetadata"],
+            json!("nightly backfill")
+        );
+
+        // read the user metadata back from the table's history
+        let snapshot = Snapshot::try_new(table_url.clone(), &engine, None)?;
+        let user_metadata: Vec<_> = snapshot
+            .commit_actions(&engine, 1)?
+            .map_ok(|data| -> Vec<Option<String>> {
+                let data = ArrowEngineData::try_from_engine_data(data).unwrap();
+                let batch = data.record_batch();
+                let commit_info = batch.column_by_name("commitInfo").unwrap().as_struct();
+                let user_metadata = commit_info.column_by_name("userMetadata").unwrap();
+                (0..batch.num_rows())
+                    .filter(|&row| commit_info.is_valid(row))
+                    .map(|row| {
+                        let values = user_metadata.as_string::<i32>();
+                        values.is_valid(row).then(|| values.value(row).to_string())
+                    })
+                    .collect()
+            })
+            .flatten_ok()
+            .try_collect()?;
+        assert_eq!(user_metadata, [Some("nightly backfill".to_string())]);
+
+        // the commit that created the table has no user metadata
+        let snapshot = Snapshot::try_new(table_url.clone(), &engine, None)?;
+        let batches: Vec<_> = snapshot.commit_actions(&engine, 0)?.try_collect()?;
+        let batch = ArrowEngineData::try_from_engine_data(batches.into_iter().next().unwrap())?;
+        let commit_info = batch
+            .record_batch()
+            .column_by_name("commitInfo")
+            .unwrap()
+            .as_struct();
+        let user_metadata = commit_info.column_by_name("userMetadata").unwrap();
+        assert!((0..commit_info.len()).all(|row| user_metadata.is_null(row)));
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_empty_commit() -> Result<(), Box<dyn std::error::Error>> {
     // setup tracing
@@ -191,6 +364,119 @@ async fn test_invalid_commit_info() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_update_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+    // the evolved schema adds a nullable column
+    let new_schema = Arc::new(StructType::new(vec![
+        StructField::nullable("number", DataType::INTEGER),
+        StructField::nullable("name", DataType::STRING),
+    ]));
+    let properties = HashMap::from([
+        ("delta.appendOnly".to_string(), "true".to_string()),
+        ("my.custom.property".to_string(), "hello".to_string()),
+    ]);
+
+    for (table_url, engine, _store, _table_name) in setup_test_tables(schema, &[]).await? {
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        let txn = snapshot
+            .clone()
+            .transaction()?
+            .with_commit_info(new_commit_info()?)
+            .update_metadata(Some(new_schema.clone()), properties.clone())?;
+
+        // the write context reflects the metadata update
+        assert_eq!(txn.get_write_context()?.schema(), &new_schema);
+        assert_eq!(txn.commit(&engine)?.into_version()?, 1);
+
+        let new_snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        assert_eq!(new_snapshot.version(), 1);
+        assert_eq!(new_snapshot.schema(), new_schema);
+        assert_eq!(new_snapshot.properties(), &properties);
+        assert_eq!(new_snapshot.table_properties().append_only, Some(true));
+        // the rest of the metadata is unchanged
+        assert_eq!(new_snapshot.created_time(), snapshot.created_time());
+
+        // removing a column or adding a non-nullable one is not allowed
+        let incompatible_schemas = [
+            StructType::new(vec![StructField::nullable("name", DataType::STRING)]),
+            StructType::new(vec![
+                StructField::nullable("number", DataType::INTEGER),
+                StructField::nullable("name", DataType::STRING),
+                StructField::not_null("id", DataType::LONG),
+            ]),
+        ];
+        for incompatible_schema in incompatible_schemas {
+            let result = new_snapshot
+                .clone()
+                .transaction()?
+                .update_metadata(Some(Arc::new(incompatible_schema)), HashMap::new());
+            assert!(matches!(result, Err(KernelError::Schema(_))));
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_context_unsupported() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // create a table with writer version 2, which supports column invariants
+    let schema = StructType::new(vec![StructField::nullable("number", DataType::INTEGER)]);
+    let (store, engine, table_url) = engine_store_setup("test_table_invariants", true);
+    let actions = [
+        json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}}),
+        json!({
+            "metaData": {
+                "id": "test_id",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": serde_json::to_string(&schema)?,
+                "partitionColumns": [],
+                "configuration": {},
+                "createdTime": 1677811175819u64
+            }
+        }),
+    ];
+    let commit = actions.iter().map(|action| action.to_string()).join("\n");
+    let commit_path = table_url.join("_delta_log/00000000000000000000.json")?;
+    store
+        .put(&Path::from_url_path(commit_path.path())?, commit.into())
+        .await?;
+
+    let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+    let txn = snapshot.transaction()?.with_commit_info(new_commit_info()?);
+    assert!(txn.get_write_context().is_ok());
+
+    // adding a column with an invariant is a valid metadata update, but kernel can't write data to
+    // a table with invariants
+    let new_schema = Arc::new(StructType::new(vec![
+        StructField::nullable("number", DataType::INTEGER),
+        StructField::nullable("name", DataType::STRING).with_metadata([(
+            "delta.invariants",
+            r#"{"expression": {"expression": "name IS NOT NULL"}}"#,
+        )]),
+    ]));
+    let txn = txn.update_metadata(Some(new_schema.clone()), HashMap::new())?;
+    assert!(matches!(
+        txn.get_write_context(),
+        Err(KernelError::Unsupported(_))
+    ));
+
+    // the metadata update itself can still be committed
+    assert_eq!(txn.commit(&engine)?.into_version()?, 1);
+    let new_snapshot = Snapshot::try_new(table_url, &engine, None)?;
+    assert_eq!(new_snapshot.schema(), new_schema);
+    Ok(())
+}
+
 // check that the timestamps in commit_info and add actions are within 10s of SystemTime::now()
 fn check_action_timestamps<'a>(
     parsed_commits: impl Iterator<Item = &'a serde_json::Value>,
@@ -275,7 +561,7 @@ async fn test_append() -> Result<(), Box<dyn std::error::Error>> {
 
         // write data out by spawning async tasks to simulate executors
         let engine = Arc::new(engine);
-        let write_context = Arc::new(txn.get_write_context());
+        let write_context = Arc::new(txn.get_write_context()?);
         let tasks = append_data.into_iter().map(|data| {
             // arc clones
             let engine = engine.clone();
@@ -371,6 +657,33 @@ async fn test_append() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_write_context_partition_column_types() -> Result<(), Box<dyn std::error::Error>> {
+    // partition columns are declared in a different order than they appear in the schema
+    let table_schema = Arc::new(StructType::new(vec![
+        StructField::nullable("number", DataType::INTEGER),
+        StructField::nullable("letter", DataType::STRING),
+        StructField::nullable("date", DataType::DATE),
+    ]));
+
+    for (table_url, engine, _store, _table_name) in
+        setup_test_tables(table_schema, &["date", "letter"]).await?
+    {
+        let snapshot = Arc::new(Snapshot::try_new(table_url, &engine, None)?);
+        let txn = snapshot.transaction()?;
+        let write_context = txn.get_write_context()?;
+        assert_eq!(write_context.partition_columns(), ["date", "letter"]);
+        assert_eq!(
+            write_context.partition_column_types(),
+            [
+                ("date".to_string(), DataType::DATE),
+                ("letter".to_string(), DataType::STRING),
+            ]
+        );
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_append_partitioned() -> Result<(), Box<dyn std::error::Error>> {
     // setup tracing
@@ -409,7 +722,7 @@ async fn test_append_partitioned() -> Result<(), Box<dyn std::error::Error>> {
 
         // write data out by spawning async tasks to simulate executors
         let engine = Arc::new(engine);
-        let write_context = Arc::new(txn.get_write_context());
+        let write_context = Arc::new(txn.get_write_context()?);
         let tasks = append_data
             .into_iter()
             .zip(partition_vals)
@@ -547,7 +860,7 @@ async fn test_append_invalid_schema() -> Result<(), Box<dyn std::error::Error>>
 
         // write data out by spawning async tasks to simulate executors
         let engine = Arc::new(engine);
-        let write_context = Arc::new(txn.get_write_context());
+        let write_context = Arc::new(txn.get_write_context()?);
         let tasks = append_data.into_iter().map(|data| {
             // arc clones
             let engine = engine.clone();
@@ -576,6 +889,56 @@ async fn test_append_invalid_schema() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_commit_timestamp_from_clock() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    struct MockClock(i64);
+    impl Clock for MockClock {
+        fn now_ms(&self) -> i64 {
+            self.0
+        }
+    }
+
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table_url, engine, store, table_name) in setup_test_tables(schema, &[]).await? {
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        let txn = snapshot
+            .transaction_with_clock(&MockClock(1234))?
+            .with_commit_info(new_commit_info()?)
+            .with_transaction_id("app_id1".to_string(), 1);
+
+        // commit!
+        txn.commit(&engine)?;
+
+        let commit1 = store
+            .get(&Path::from(format!(
+                "/{table_name}/_delta_log/00000000000000000001.json"
+            )))
+            .await?;
+        let parsed_commits: Vec<serde_json::Value> =
+            Deserializer::from_slice(&commit1.bytes().await?)
+                .into_iter()
+                .try_collect()?;
+        assert_eq!(parsed_commits[0]["commitInfo"]["timestamp"], json!(1234));
+        assert_eq!(parsed_commits[1]["txn"]["lastUpdated"], json!(1234));
+
+        // a clock before the unix epoch is rejected
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        assert!(matches!(
+            snapshot.transaction_with_clock(&MockClock(-1)),
+            Err(KernelError::Generic(_))
+        ));
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_write_txn_actions() -> Result<(), Box<dyn std::error::Error>> {
     // setup tracing
@@ -708,6 +1071,58 @@ async fn test_write_txn_actions() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_set_transaction_id_replaces_version() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table_url, engine, store, table_name) in setup_test_tables(schema, &[]).await? {
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+        snapshot
+            .transaction()?
+            .with_commit_info(new_commit_info()?)
+            .with_transaction_id("app_id1".to_string(), 1)
+            .set_transaction_id("app_id2".to_string(), 2)
+            .set_transaction_id("app_id1".to_string(), 3)
+            .set_transaction_id("app_id1".to_string(), 4)
+            .commit(&engine)?;
+
+        let snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, Some(1))?);
+        assert_eq!(
+            snapshot.clone().get_app_id_version("app_id1", &engine)?,
+            Some(4)
+        );
+        assert_eq!(snapshot.get_app_id_version("app_id2", &engine)?, Some(2));
+
+        // only the latest version of each app_id is written to the commit
+        let commit1 = store
+            .get(&Path::from(format!(
+                "/{table_name}/_delta_log/00000000000000000001.json"
+            )))
+            .await?;
+        let parsed_commits: Vec<serde_json::Value> =
+            Deserializer::from_slice(&commit1.bytes().await?)
+                .into_iter()
+                .try_collect()?;
+        let txns: Vec<_> = parsed_commits
+            .iter()
+            .filter_map(|action| action.get("txn"))
+            .map(|txn| (txn["appId"].clone(), txn["version"].clone()))
+            .collect();
+        assert_eq!(
+            txns,
+            [(json!("app_id2"), json!(2)), (json!("app_id1"), json!(4))]
+        );
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_append_timestamp_ntz() -> Result<(), Box<dyn std::error::Error>> {
     // setup tracing
@@ -753,7 +1168,7 @@ async fn test_append_timestamp_ntz() -> Result<(), Box<dyn std::error::Error>> {
 
     // Write data
     let engine = Arc::new(engine);
-    let write_context = Arc::new(txn.get_write_context());
+    let write_context = Arc::new(txn.get_write_context()?);
 
     let add_files_metadata = engine
         .write_parquet(
@@ -791,3 +1206,90 @@ async fn test_append_timestamp_ntz() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_checkpoint_after_commit() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::BooleanArray;
+    use delta_kernel::arrow::compute::filter_record_batch;
+    use delta_kernel::checkpoint::DEFAULT_CHECKPOINT_INTERVAL;
+    use delta_kernel::parquet::arrow::arrow_writer::ArrowWriter;
+    use delta_kernel::FileMeta;
+
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+    let (store, engine, table_location) = engine_store_setup("test_table", true);
+    let table_url = create_table(store.clone(), table_location, schema, &[], false, false).await?;
+
+    // commit until a checkpoint is due, which only happens at the checkpoint interval
+    let mut snapshot = Arc::new(Snapshot::try_new(table_url.clone(), &engine, None)?);
+    let writer = loop {
+        let version = snapshot
+            .clone()
+            .transaction()?
+            .with_commit_info(new_commit_info()?)
+            .commit(&engine)?
+            .into_version()?;
+        snapshot = Snapshot::try_new_from(snapshot, &engine, Some(version))?;
+        match snapshot.clone().maybe_checkpoint()? {
+            Some(writer) => break writer,
+            None => assert!(version < DEFAULT_CHECKPOINT_INTERVAL),
+        }
+    };
+    assert_eq!(snapshot.version(), DEFAULT_CHECKPOINT_INTERVAL);
+
+    // write the checkpoint data as parquet, as an engine would
+    let checkpoint_path = writer.checkpoint_path()?;
+    let mut checkpoint_data = writer.checkpoint_data(&engine)?;
+    let batches: Vec<_> = checkpoint_data
+        .by_ref()
+        .map(|data| -> DeltaResult<_> {
+            let data = data?;
+            let selection_vector = BooleanArray::from(data.selection_vector);
+            let batch = ArrowEngineData::try_from_engine_data(data.data)?;
+            Ok(filter_record_batch(
+                batch.record_batch(),
+                &selection_vector,
+            )?)
+        })
+        .try_collect()?;
+    let mut buffer = vec![];
+    let mut parquet_writer = ArrowWriter::try_new(&mut buffer, batches[0].schema(), None)?;
+    for batch in &batches {
+        parquet_writer.write(batch)?;
+    }
+    parquet_writer.close()?;
+    let size = buffer.len() as u64;
+    store
+        .put(&Path::from_url_path(checkpoint_path.path())?, buffer.into())
+        .await?;
+    let metadata = FileMeta::new(checkpoint_path.clone(), 0, size);
+    writer.finalize(&engine, &metadata, checkpoint_data)?;
+
+    // both the checkpoint and the `_last_checkpoint` hint now exist
+    assert!(checkpoint_path
+        .path()
+        .ends_with("_delta_log/00000000000000000010.checkpoint.parquet"));
+    store
+        .head(&Path::from_url_path(checkpoint_path.path())?)
+        .await?;
+    let last_checkpoint_path = table_url.join("_delta_log/_last_checkpoint")?;
+    let last_checkpoint = store
+        .get(&Path::from_url_path(last_checkpoint_path.path())?)
+        .await?
+        .bytes()
+        .await?;
+    let last_checkpoint: serde_json::Value = serde_json::from_slice(&last_checkpoint)?;
+    assert_eq!(last_checkpoint["version"], DEFAULT_CHECKPOINT_INTERVAL);
+    assert_eq!(last_checkpoint["sizeInBytes"], size);
+
+    // a new snapshot is backed by the checkpoint, so no further checkpoint is due
+    let snapshot = Arc::new(Snapshot::try_new(table_url, &engine, None)?);
+    assert_eq!(snapshot.version(), DEFAULT_CHECKPOINT_INTERVAL);
+    assert!(snapshot.maybe_checkpoint()?.is_none());
+    Ok(())
+}
@@ -26,6 +26,8 @@ use itertools::Itertools;
 use serde_json::{json, to_vec};
 use url::Url;
 
+pub mod sort;
+
 /// A common useful initial metadata and protocol. Also includes a single commitInfo
 pub const METADATA: &str = r#"{"commitInfo":{"timestamp":1587968586154,"operation":"WRITE","operationParameters":{"mode":"ErrorIfExists","partitionBy":"[]"},"isBlindAppend":true}}
 {"protocol":{"minReaderVersion":1,"minWriterVersion":2}}
@@ -156,6 +158,21 @@ pub async fn add_commit(
     Ok(())
 }
 
+/// Build a [`DefaultEngine`] over an [`InMemory`] object store that contains a table at
+/// `memory:///`. Each element of `commits` is written as the next commit (starting at version 0),
+/// so the first commit should usually include [`TestAction::Metadata`]. Note that only the log is
+/// written: any data files must be added to the store separately.
+pub async fn engine_for_in_memory_table(
+    commits: Vec<Vec<TestAction>>,
+) -> Result<Arc<dyn Engine>, Box<dyn std::error::Error>> {
+    let store = Arc::new(InMemory::new());
+    for (version, actions) in commits.into_iter().enumerate() {
+        add_commit(store.as_ref(), version as u64, actions_to_string(actions)).await?;
+    }
+    let executor = Arc::new(TokioBackgroundExecutor::new());
+    Ok(Arc::new(DefaultEngine::new(store, executor)))
+}
+
 /// Try to convert an `EngineData` into a `RecordBatch`. Panics if not using `ArrowEngineData` from
 /// the default module
 pub fn into_record_batch(engine_data: Box<dyn EngineData>) -> RecordBatch {
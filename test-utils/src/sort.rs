@@ -0,0 +1,136 @@
+//! Helpers to deterministically sort the rows of a [`RecordBatch`], e.g. to compare scan results
+//! against expected (golden) data regardless of the order in which rows were read.
+
+use std::sync::Arc;
+
+use delta_kernel::arrow::array::{Array, ArrayRef, RecordBatch, StringArray, UInt32Array};
+use delta_kernel::arrow::compute::{
+    lexsort_to_indices, take_record_batch, SortColumn, SortOptions,
+};
+use delta_kernel::arrow::datatypes::DataType;
+use delta_kernel::arrow::util::display::{ArrayFormatter, FormatOptions};
+use delta_kernel::DeltaResult;
+use itertools::Itertools;
+
+/// Where [`sort_record_batch_with_null_ordering`] places null values relative to non-null values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullOrdering {
+    /// Nulls sort before all other values. This is the arrow default.
+    #[default]
+    NullsFirst,
+    /// Nulls sort after all other values.
+    NullsLast,
+}
+
+/// Sorts the rows of `batch` by all of its columns, with nulls first. See
+/// [`sort_record_batch_with_null_ordering`].
+pub fn sort_record_batch(batch: RecordBatch) -> DeltaResult<RecordBatch> {
+    sort_record_batch_with_null_ordering(batch, NullOrdering::default())
+}
+
+/// Sorts the rows of `batch` in ascending order by all of its columns, in column order, placing
+/// nulls according to `null_ordering`.
+///
+/// Arrow can't sort struct, list or map columns directly, so these are sorted by a string
+/// serialization of their values instead. The sort is stable: rows that are equal in every column
+/// keep their original relative order.
+pub fn sort_record_batch_with_null_ordering(
+    batch: RecordBatch,
+    null_ordering: NullOrdering,
+) -> DeltaResult<RecordBatch> {
+    if batch.num_rows() < 2 {
+        // 0 or 1 rows doesn't need sorting
+        return Ok(batch);
+    }
+    let options = SortOptions {
+        descending: false,
+        nulls_first: null_ordering == NullOrdering::NullsFirst,
+    };
+    let mut sort_columns: Vec<_> = batch
+        .columns()
+        .iter()
+        .map(|column| {
+            Ok(SortColumn {
+                values: sortable_values(column)?,
+                options: Some(options),
+            })
+        })
+        .try_collect::<_, _, delta_kernel::Error>()?;
+    // break ties by the original row order, to make the sort stable
+    let num_rows = u32::try_from(batch.num_rows()).expect("batch has too many rows to sort");
+    sort_columns.push(SortColumn {
+        values: Arc::new(UInt32Array::from_iter_values(0..num_rows)),
+        options: None,
+    });
+    let indices = lexsort_to_indices(&sort_columns, None)?;
+    Ok(take_record_batch(&batch, &indices)?)
+}
+
+// Returns the values to sort `column` by: the column itself if arrow can sort it, or else a string
+// serialization of its (non-null) values.
+fn sortable_values(column: &ArrayRef) -> DeltaResult<ArrayRef> {
+    match column.data_type() {
+        DataType::Struct(_)
+        | DataType::List(_)
+        | DataType::LargeList(_)
+        | DataType::FixedSizeList(_, _)
+        | DataType::Map(_, _) => {
+            let formatter = ArrayFormatter::try_new(column.as_ref(), &FormatOptions::default())?;
+            let values: StringArray = (0..column.len())
+                .map(|i| column.is_valid(i).then(|| formatter.value(i).to_string()))
+                .collect();
+            Ok(Arc::new(values))
+        }
+        _ => Ok(column.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use delta_kernel::arrow::array::{AsArray as _, Int32Array, ListArray};
+    use delta_kernel::arrow::datatypes::Int32Type;
+
+    fn test_batch() -> RecordBatch {
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(2),
+            None,
+            Some(1),
+            None,
+            Some(1),
+        ]));
+        let lists: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1)]),
+            Some(vec![Some(3)]),
+            Some(vec![Some(2), Some(1)]),
+            Some(vec![Some(3)]),
+            None,
+        ]));
+        let tags: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c", "d", "e"]));
+        RecordBatch::try_from_iter([("value", values), ("list", lists), ("tag", tags)]).unwrap()
+    }
+
+    fn tags(batch: &RecordBatch) -> Vec<&str> {
+        let tags = batch.column(2).as_string::<i32>();
+        tags.iter().map(Option::unwrap).collect()
+    }
+
+    #[test]
+    fn test_sort_record_batch_null_ordering() {
+        // rows `b` and `d` tie on both `value` and `list`, so they are ordered by `tag`
+        let sorted = sort_record_batch(test_batch()).unwrap();
+        assert_eq!(tags(&sorted), ["b", "d", "e", "c", "a"]);
+
+        let sorted =
+            sort_record_batch_with_null_ordering(test_batch(), NullOrdering::NullsLast).unwrap();
+        assert_eq!(tags(&sorted), ["c", "e", "a", "b", "d"]);
+
+        // sorting is stable, so sorted output doesn't change when sorted again
+        for null_ordering in [NullOrdering::NullsFirst, NullOrdering::NullsLast] {
+            let sorted = sort_record_batch_with_null_ordering(test_batch(), null_ordering).unwrap();
+            let resorted = sort_record_batch_with_null_ordering(sorted.clone(), null_ordering);
+            assert_eq!(resorted.unwrap(), sorted);
+        }
+    }
+}